@@ -359,6 +359,7 @@ fn gen_corelib(
         "slint_image_load_from_path",
         "slint_image_load_from_embedded_data",
         "slint_image_from_embedded_textures",
+        "slint_image_from_embedded_animation",
         "slint_image_compare_equal",
         "slint_image_set_nine_slice_edges",
         "slint_timer_start",
@@ -398,7 +399,9 @@ fn gen_corelib(
         .with_config(string_config)
         .with_src(crate_dir.join("string.rs"))
         .with_src(crate_dir.join("slice.rs"))
-        .with_after_include("namespace slint { struct SharedString; }")
+        .with_after_include(
+            "namespace slint { struct SharedString; template<typename T> struct SharedVector; }",
+        )
         .generate()
         .context("Unable to generate bindings for slint_string_internal.h")?
         .write_to_file(include_dir.join("slint_string_internal.h"));
@@ -469,16 +472,18 @@ fn gen_corelib(
                 "slint_image_load_from_path",
                 "slint_image_load_from_embedded_data",
                 "slint_image_from_embedded_textures",
+                "slint_image_from_embedded_animation",
                 "slint_image_compare_equal",
                 "slint_image_set_nine_slice_edges",
                 "SharedPixelBuffer",
                 "SharedImageBuffer",
                 "StaticTextures",
+                "StaticAnimationFrame",
                 "BorrowedOpenGLTextureOrigin"
             ],
             vec!["Color"],
             "slint_image_internal.h",
-            "namespace slint::cbindgen_private { struct ParsedSVG{}; struct HTMLImage{}; using namespace vtable; namespace types{ struct NineSliceImage{}; } }",
+            "namespace slint::cbindgen_private { struct ParsedSVG{}; struct HTMLImage{}; using namespace vtable; namespace types{ struct NineSliceImage{}; struct AnimatedImage{}; struct PendingImage{}; } }",
         ),
         (
             vec!["Color", "slint_color_brighter", "slint_color_darker",
@@ -498,7 +503,15 @@ fn gen_corelib(
             "",
         ),
         (
-            vec!["Brush", "LinearGradient", "GradientStop", "RadialGradient"],
+            vec![
+                "Brush",
+                "LinearGradient",
+                "GradientStop",
+                "RadialGradient",
+                "RadialGradientShape",
+                "RadialGradientExtent",
+                "ConicGradient",
+            ],
             vec!["Color"],
             "slint_brush_internal.h",
             "",
@@ -557,6 +570,7 @@ fn gen_corelib(
             "slint_image_load_from_embedded_data",
             "slint_image_set_nine_slice_edges",
             "slint_image_from_embedded_textures",
+            "slint_image_from_embedded_animation",
             "slint_image_compare_equal",
         ]
         .iter()
@@ -60,25 +60,41 @@ private:
     }
 };
 
+using cbindgen_private::types::RadialGradientExtent;
+using cbindgen_private::types::RadialGradientShape;
+
 /// \private
-/// RadialGradientBrush represents a circular gradient centered in the middle
+/// RadialGradientBrush represents a gradient where the colors are interpolated along circles or
+/// ellipses around a center point.
 class RadialGradientBrush
 {
 public:
-    /// Constructs an empty linear gradient with no color stops.
+    /// Constructs an empty radial gradient with no color stops.
     RadialGradientBrush() = default;
-    /// Constructs a new circular radial gradient . The color stops will be
+    /// Constructs a new circular radial gradient, centered in the middle, with a radius that is the
+    /// average of half the width and half the height of the shape. The color stops will be
     /// constructed from the stops array pointed to be \a firstStop, with the length \a stopCount.
     RadialGradientBrush(const GradientStop *firstStop, int stopCount)
-        : inner(make_circle_gradient(firstStop, stopCount))
+        : inner(make_radial_gradient(RadialGradientShape::Circle,
+                                     RadialGradientExtent::AverageSide, 0.5, 0.5, firstStop,
+                                     stopCount))
+    {
+    }
+    /// Constructs a new radial gradient with the specified \a shape and \a extent, around the
+    /// center (\a center_x, \a center_y), relative to the size of the shape. The color stops will
+    /// be constructed from the stops array pointed to be \a firstStop, with the length
+    /// \a stopCount.
+    RadialGradientBrush(RadialGradientShape shape, RadialGradientExtent extent, float center_x,
+                        float center_y, const GradientStop *firstStop, int stopCount)
+        : inner(make_radial_gradient(shape, extent, center_x, center_y, firstStop, stopCount))
     {
     }
 
     /// Returns the number of gradient stops.
-    int stopCount() const { return int(inner.size()); }
+    int stopCount() const { return int(inner.size()) - 4; }
 
     /// Returns a pointer to the first gradient stop; undefined if the gradient has not stops.
-    const GradientStop *stopsBegin() const { return inner.begin(); }
+    const GradientStop *stopsBegin() const { return inner.begin() + 4; }
     /// Returns a pointer past the last gradient stop. The returned pointer cannot be dereferenced,
     /// it can only be used for comparison.
     const GradientStop *stopsEnd() const { return inner.end(); }
@@ -89,9 +105,68 @@ private:
     friend class slint::Brush;
 
     static SharedVector<private_api::GradientStop>
-    make_circle_gradient(const GradientStop *firstStop, int stopCount)
+    make_radial_gradient(RadialGradientShape shape, RadialGradientExtent extent, float center_x,
+                         float center_y, const GradientStop *firstStop, int stopCount)
     {
         SharedVector<private_api::GradientStop> gradient;
+        // The gradient's first four stops are fake stops to store the center, shape, and extent
+        gradient.push_back({ Color::from_argb_encoded(0).inner, center_x });
+        gradient.push_back({ Color::from_argb_encoded(0).inner, center_y });
+        gradient.push_back({ Color::from_argb_encoded(0).inner, float(shape) });
+        gradient.push_back({ Color::from_argb_encoded(0).inner, float(extent) });
+        for (int i = 0; i < stopCount; ++i, ++firstStop)
+            gradient.push_back(*firstStop);
+        return gradient;
+    }
+};
+
+/// \private
+/// ConicGradientBrush represents a gradient for a brush where the color stops are placed
+/// clockwise around a center point.
+class ConicGradientBrush
+{
+public:
+    /// Constructs an empty conic gradient with no color stops.
+    ConicGradientBrush() = default;
+    /// Constructs a new conic gradient starting at the specified \a angle and turning around
+    /// the center (\a center_x, \a center_y), relative to the size of the shape. The color stops
+    /// will be constructed from the stops array pointed to be \a firstStop, with the length
+    /// \a stopCount.
+    ConicGradientBrush(float angle, float center_x, float center_y, const GradientStop *firstStop,
+                       int stopCount)
+        : inner(make_conic_gradient(angle, center_x, center_y, firstStop, stopCount))
+    {
+    }
+
+    /// Returns the angle of the first stop in degrees.
+    float angle() const
+    {
+        // The gradient's first three stops are fake stops to store the angle and the center
+        return inner[0].position;
+    }
+
+    /// Returns the number of gradient stops.
+    int stopCount() const { return int(inner.size()) - 3; }
+
+    /// Returns a pointer to the first gradient stop; undefined if the gradient has not stops.
+    const GradientStop *stopsBegin() const { return inner.begin() + 3; }
+    /// Returns a pointer past the last gradient stop. The returned pointer cannot be dereferenced,
+    /// it can only be used for comparison.
+    const GradientStop *stopsEnd() const { return inner.end(); }
+
+private:
+    cbindgen_private::types::ConicGradientBrush inner;
+
+    friend class slint::Brush;
+
+    static SharedVector<private_api::GradientStop>
+    make_conic_gradient(float angle, float center_x, float center_y, const GradientStop *firstStop,
+                        int stopCount)
+    {
+        SharedVector<private_api::GradientStop> gradient;
+        gradient.push_back({ Color::from_argb_encoded(0).inner, angle });
+        gradient.push_back({ Color::from_argb_encoded(0).inner, center_x });
+        gradient.push_back({ Color::from_argb_encoded(0).inner, center_y });
         for (int i = 0; i < stopCount; ++i, ++firstStop)
             gradient.push_back(*firstStop);
         return gradient;
@@ -123,6 +198,13 @@ public:
     {
     }
 
+    /// \private
+    /// Constructs a new brush that is the gradient \a gradient.
+    Brush(const private_api::ConicGradientBrush &gradient)
+        : data(Inner::ConicGradient(gradient.inner))
+    {
+    }
+
     /// Returns the color of the brush. If the brush is a gradient, this function returns the color
     /// of the first stop.
     inline Color color() const;
@@ -173,8 +255,13 @@ Color Brush::color() const
         }
         break;
     case Tag::RadialGradient:
-        if (data.radial_gradient._0.size() > 0) {
-            result.inner = data.radial_gradient._0[0].color;
+        if (data.radial_gradient._0.size() > 4) {
+            result.inner = data.radial_gradient._0[4].color;
+        }
+        break;
+    case Tag::ConicGradient:
+        if (data.conic_gradient._0.size() > 3) {
+            result.inner = data.conic_gradient._0[3].color;
         }
         break;
     }
@@ -196,11 +283,17 @@ inline Brush Brush::brighter(float factor) const
         }
         break;
     case Tag::RadialGradient:
-        for (std::size_t i = 0; i < data.linear_gradient._0.size(); ++i) {
+        for (std::size_t i = 4; i < data.radial_gradient._0.size(); ++i) {
             cbindgen_private::types::slint_color_brighter(&data.radial_gradient._0[i].color, factor,
                                                           &result.data.radial_gradient._0[i].color);
         }
         break;
+    case Tag::ConicGradient:
+        for (std::size_t i = 3; i < data.conic_gradient._0.size(); ++i) {
+            cbindgen_private::types::slint_color_brighter(&data.conic_gradient._0[i].color, factor,
+                                                          &result.data.conic_gradient._0[i].color);
+        }
+        break;
     }
     return result;
 }
@@ -220,11 +313,17 @@ inline Brush Brush::darker(float factor) const
         }
         break;
     case Tag::RadialGradient:
-        for (std::size_t i = 0; i < data.linear_gradient._0.size(); ++i) {
+        for (std::size_t i = 4; i < data.radial_gradient._0.size(); ++i) {
             cbindgen_private::types::slint_color_darker(&data.radial_gradient._0[i].color, factor,
                                                         &result.data.radial_gradient._0[i].color);
         }
         break;
+    case Tag::ConicGradient:
+        for (std::size_t i = 3; i < data.conic_gradient._0.size(); ++i) {
+            cbindgen_private::types::slint_color_darker(&data.conic_gradient._0[i].color, factor,
+                                                        &result.data.conic_gradient._0[i].color);
+        }
+        break;
     }
     return result;
 }
@@ -245,12 +344,19 @@ inline Brush Brush::transparentize(float factor) const
         }
         break;
     case Tag::RadialGradient:
-        for (std::size_t i = 0; i < data.linear_gradient._0.size(); ++i) {
+        for (std::size_t i = 4; i < data.radial_gradient._0.size(); ++i) {
             cbindgen_private::types::slint_color_transparentize(
                     &data.radial_gradient._0[i].color, factor,
                     &result.data.radial_gradient._0[i].color);
         }
         break;
+    case Tag::ConicGradient:
+        for (std::size_t i = 3; i < data.conic_gradient._0.size(); ++i) {
+            cbindgen_private::types::slint_color_transparentize(
+                    &data.conic_gradient._0[i].color, factor,
+                    &result.data.conic_gradient._0[i].color);
+        }
+        break;
     }
     return result;
 }
@@ -271,12 +377,19 @@ inline Brush Brush::with_alpha(float alpha) const
         }
         break;
     case Tag::RadialGradient:
-        for (std::size_t i = 0; i < data.linear_gradient._0.size(); ++i) {
+        for (std::size_t i = 4; i < data.radial_gradient._0.size(); ++i) {
             cbindgen_private::types::slint_color_with_alpha(
                     &data.radial_gradient._0[i].color, alpha,
                     &result.data.radial_gradient._0[i].color);
         }
         break;
+    case Tag::ConicGradient:
+        for (std::size_t i = 3; i < data.conic_gradient._0.size(); ++i) {
+            cbindgen_private::types::slint_color_with_alpha(
+                    &data.conic_gradient._0[i].color, alpha,
+                    &result.data.conic_gradient._0[i].color);
+        }
+        break;
     }
     return result;
 }
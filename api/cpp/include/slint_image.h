@@ -262,6 +262,14 @@ inline Image image_from_embedded_textures(const cbindgen_private::types::StaticT
     cbindgen_private::types::slint_image_from_embedded_textures(textures, &img);
     return Image(img);
 }
+
+inline Image image_from_embedded_animation(
+        cbindgen_private::Slice<cbindgen_private::types::StaticAnimationFrame> frames)
+{
+    cbindgen_private::types::Image img(cbindgen_private::types::Image::ImageInner_None());
+    cbindgen_private::types::slint_image_from_embedded_animation(frames, &img);
+    return Image(img);
+}
 }
 
 }
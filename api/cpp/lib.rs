@@ -152,6 +152,17 @@ pub extern "C" fn slint_string_to_float(string: &SharedString, value: &mut f32)
     }
 }
 
+#[no_mangle]
+pub extern "C" fn slint_string_to_int(string: &SharedString, value: &mut i32) -> bool {
+    match string.as_str().parse::<i32>() {
+        Ok(v) => {
+            *value = v;
+            true
+        }
+        Err(_) => false,
+    }
+}
+
 #[cfg(not(feature = "std"))]
 mod allocator {
     use core::alloc::Layout;
@@ -124,6 +124,18 @@ test('loadSource', (t) => {
     ]);
 })
 
+test('loadSource multiple components', (t) => {
+    const source = `export component Dialog {
+        out property <string> check: "Dialog";
+    }
+    export component Main {
+        out property <string> check: "Main";
+    }`
+    let demo = loadSource(source, 'api.spec.ts') as any;
+    t.is(new demo.Dialog().check, "Dialog");
+    t.is(new demo.Main().check, "Main");
+})
+
 test('loadSource constructor parameters', (t) => {
     const source = `export component Test {
         callback say_hello();
@@ -114,6 +114,40 @@ test('get/set bool properties', (t) => {
 
 })
 
+test('get/set optional properties', (t) => {
+  let compiler = new private_api.ComponentCompiler;
+  let definition = compiler.buildFromSource(`
+    export component App {
+        in-out property <int?> count;
+        in-out property <string?> name: "Initial";
+    }`, "");
+  t.not(definition, null);
+
+  let instance = definition!.create();
+  t.not(instance, null);
+
+  t.true(instance!.getProperty("count") === undefined);
+  t.true(instance!.getProperty("name") === "Initial");
+
+  instance!.setProperty("count", 42);
+  t.true(instance!.getProperty("count") === 42);
+
+  instance!.setProperty("count", null);
+  t.true(instance!.getProperty("count") === undefined);
+
+  instance!.setProperty("name", undefined);
+  t.true(instance!.getProperty("name") === undefined);
+
+  t.throws(() => {
+    instance!.setProperty("count", "Hello")
+  },
+    {
+      code: "InvalidArg",
+      message: "expect Number, got: String"
+    }
+  );
+})
+
 test('set struct properties', (t) => {
   let compiler = new private_api.ComponentCompiler;
   let definition = compiler.buildFromSource(`
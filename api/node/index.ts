@@ -697,9 +697,9 @@ function loadSlint(loadData: LoadData): Object {
         }
     }
 
-    let definition = loadData.from === 'file' ? compiler.buildFromPath(filePath) : compiler.buildFromSource(loadData.fileData.source, filePath);
+    let result = loadData.from === 'file' ? compiler.buildAllFromPath(filePath) : compiler.buildAllFromSource(loadData.fileData.source, filePath);
 
-    let diagnostics = compiler.diagnostics;
+    let diagnostics = result.diagnostics;
 
     if (diagnostics.length > 0) {
         let warnings = diagnostics.filter(
@@ -721,121 +721,125 @@ function loadSlint(loadData: LoadData): Object {
 
     let slint_module = Object.create({});
 
-    Object.defineProperty(slint_module, definition!.name.replace(/-/g, "_"), {
-        value: function (properties: any) {
-            let instance = definition!.create();
+    result.componentNames.forEach((componentName) => {
+        let definition = result.component(componentName);
 
-            if (instance == null) {
-                throw Error(
-                    "Could not create a component handle for" + filePath
-                );
-            }
-
-            for (var key in properties) {
-                let value = properties[key];
-
-                if (value instanceof Function) {
-                    instance.setCallback(key, value);
-                } else {
-                    instance.setProperty(key, properties[key]);
-                }
-            }
+        Object.defineProperty(slint_module, componentName.replace(/-/g, "_"), {
+            value: function (properties: any) {
+                let instance = definition!.create();
 
-            let componentHandle = new Component(instance!);
-            instance!.definition().properties.forEach((prop) => {
-                let propName = prop.name.replace(/-/g, "_");
-
-                if (componentHandle[propName] !== undefined) {
-                    console.warn("Duplicated property name " + propName);
-                } else {
-                    Object.defineProperty(componentHandle, propName, {
-                        get() {
-                            return instance!.getProperty(prop.name);
-                        },
-                        set(value) {
-                            instance!.setProperty(prop.name, value);
-                        },
-                        enumerable: true,
-                    });
+                if (instance == null) {
+                    throw Error(
+                        "Could not create a component handle for" + filePath
+                    );
                 }
-            });
 
-            instance!.definition().callbacks.forEach((cb) => {
-                let callbackName = cb.replace(/-/g, "_");
-
-                if (componentHandle[callbackName] !== undefined) {
-                    console.warn("Duplicated callback name " + callbackName);
-                } else {
-                    Object.defineProperty(componentHandle, cb.replace(/-/g, "_"), {
-                        get() {
-                            return function () {
-                                return instance!.invoke(cb, Array.from(arguments));
-                            };
-                        },
-                        set(callback) {
-                            instance!.setCallback(cb, callback);
-                        },
-                        enumerable: true,
-                    });
-                }
-            });
+                for (var key in properties) {
+                    let value = properties[key];
 
-            // globals
-            instance!.definition().globals.forEach((globalName) => {
-                if (componentHandle[globalName] !== undefined) {
-                    console.warn("Duplicated property name " + globalName);
-                } else {
-                    let globalObject = Object.create({});
-
-                    instance!.definition().globalProperties(globalName).forEach((prop) => {
-                        let propName = prop.name.replace(/-/g, "_");
-
-                        if (globalObject[propName] !== undefined) {
-                            console.warn("Duplicated property name " + propName + " on global " + global);
-                        } else {
-                            Object.defineProperty(globalObject, propName, {
-                                get() {
-                                    return instance!.getGlobalProperty(globalName, prop.name);
-                                },
-                                set(value) {
-                                    instance!.setGlobalProperty(globalName, prop.name, value);
-                                },
-                                enumerable: true,
-                            });
-                        }
-                    });
-
-                    instance!.definition().globalCallbacks(globalName).forEach((cb) => {
-                        let callbackName = cb.replace(/-/g, "_");
-
-                        if (globalObject[callbackName] !== undefined) {
-                            console.warn("Duplicated property name " + cb + " on global " + global);
-                        } else {
-                            Object.defineProperty(globalObject, cb.replace(/-/g, "_"), {
-                                get() {
-                                    return function () {
-                                        return instance!.invokeGlobal(globalName, cb, Array.from(arguments));
-                                    };
-                                },
-                                set(callback) {
-                                    instance!.setGlobalCallback(globalName, cb, callback);
-                                },
-                                enumerable: true,
-                            });
-                        }
-                    });
-
-                    Object.defineProperty(componentHandle, globalName, {
-                        get() {
-                            return globalObject;
-                        },
-                        enumerable: true,
-                    });
+                    if (value instanceof Function) {
+                        instance.setCallback(key, value);
+                    } else {
+                        instance.setProperty(key, properties[key]);
+                    }
                 }
-            });
 
-            return Object.seal(componentHandle);
-        },
+                let componentHandle = new Component(instance!);
+                instance!.definition().properties.forEach((prop) => {
+                    let propName = prop.name.replace(/-/g, "_");
+
+                    if (componentHandle[propName] !== undefined) {
+                        console.warn("Duplicated property name " + propName);
+                    } else {
+                        Object.defineProperty(componentHandle, propName, {
+                            get() {
+                                return instance!.getProperty(prop.name);
+                            },
+                            set(value) {
+                                instance!.setProperty(prop.name, value);
+                            },
+                            enumerable: true,
+                        });
+                    }
+                });
+
+                instance!.definition().callbacks.forEach((cb) => {
+                    let callbackName = cb.replace(/-/g, "_");
+
+                    if (componentHandle[callbackName] !== undefined) {
+                        console.warn("Duplicated callback name " + callbackName);
+                    } else {
+                        Object.defineProperty(componentHandle, cb.replace(/-/g, "_"), {
+                            get() {
+                                return function () {
+                                    return instance!.invoke(cb, Array.from(arguments));
+                                };
+                            },
+                            set(callback) {
+                                instance!.setCallback(cb, callback);
+                            },
+                            enumerable: true,
+                        });
+                    }
+                });
+
+                // globals
+                instance!.definition().globals.forEach((globalName) => {
+                    if (componentHandle[globalName] !== undefined) {
+                        console.warn("Duplicated property name " + globalName);
+                    } else {
+                        let globalObject = Object.create({});
+
+                        instance!.definition().globalProperties(globalName).forEach((prop) => {
+                            let propName = prop.name.replace(/-/g, "_");
+
+                            if (globalObject[propName] !== undefined) {
+                                console.warn("Duplicated property name " + propName + " on global " + global);
+                            } else {
+                                Object.defineProperty(globalObject, propName, {
+                                    get() {
+                                        return instance!.getGlobalProperty(globalName, prop.name);
+                                    },
+                                    set(value) {
+                                        instance!.setGlobalProperty(globalName, prop.name, value);
+                                    },
+                                    enumerable: true,
+                                });
+                            }
+                        });
+
+                        instance!.definition().globalCallbacks(globalName).forEach((cb) => {
+                            let callbackName = cb.replace(/-/g, "_");
+
+                            if (globalObject[callbackName] !== undefined) {
+                                console.warn("Duplicated property name " + cb + " on global " + global);
+                            } else {
+                                Object.defineProperty(globalObject, cb.replace(/-/g, "_"), {
+                                    get() {
+                                        return function () {
+                                            return instance!.invokeGlobal(globalName, cb, Array.from(arguments));
+                                        };
+                                    },
+                                    set(callback) {
+                                        instance!.setGlobalCallback(globalName, cb, callback);
+                                    },
+                                    enumerable: true,
+                                });
+                            }
+                        });
+
+                        Object.defineProperty(componentHandle, globalName, {
+                            get() {
+                                return globalObject;
+                            },
+                            enumerable: true,
+                        });
+                    }
+                });
+
+                return Object.seal(componentHandle);
+            },
+        });
     });
 
     return Object.seal(slint_module);
@@ -867,7 +871,7 @@ function loadSlint(loadData: LoadData): Object {
  *                 against the process' working directory.
  * @param options Use {@link LoadFileOptions} to configure additional Slint compilation aspects,
  *                such as include search paths, library imports, or the widget style.
- * @returns The returned object is sealed and provides a property by the name of the component exported
+ * @returns The returned object is sealed and provides a property by the name of each component exported
  *          in the `.slint` file. In the above example the name of the property is `Main`. The property
  *          is a constructor function. Use it with the new operator to instantiate the component.
  *          The instantiated object exposes properties and callbacks, and implements the {@link ComponentHandle} interface.
@@ -903,7 +907,7 @@ export function loadFile(filePath: string, options?: LoadFileOptions): Object {
  *                 against the process' working directory.
  * @param options Use {@link LoadFileOptions} to configure additional Slint compilation aspects,
  *                such as include search paths, library imports, or the widget style.
- * @returns The returned object is sealed and provides a property by the name of the component exported
+ * @returns The returned object is sealed and provides a property by the name of each component exported
  *          in the `.slint` file. In the above example the name of the property is `Main`. The property
  *          is a constructor function. Use it with the new operator to instantiate the component.
  *          The instantiated object exposes properties and callbacks, and implements the {@link ComponentHandle} interface.
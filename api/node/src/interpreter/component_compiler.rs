@@ -6,8 +6,9 @@
 
 use super::JsComponentDefinition;
 use super::JsDiagnostic;
+use super::JsProperty;
 use itertools::Itertools;
-use slint_interpreter::ComponentCompiler;
+use slint_interpreter::{CompilationResult, ComponentCompiler};
 
 /// ComponentCompiler is the entry point to the Slint interpreter that can be used
 /// to load .slint files or compile them on-the-fly from a string.
@@ -117,4 +118,82 @@ pub fn build_from_source(
         spin_on::spin_on(self.internal.build_from_source(source_code, PathBuf::from(path)))
             .map(|d| d.into())
     }
+
+    /// Compile a .slint file and all the components it exports
+    #[napi]
+    pub fn build_all_from_path(&mut self, path: String) -> JsCompilationResult {
+        spin_on::spin_on(self.internal.build_all_from_path(PathBuf::from(path))).into()
+    }
+
+    /// Compile some .slint code and all the components it exports
+    #[napi]
+    pub fn build_all_from_source(
+        &mut self,
+        source_code: String,
+        path: String,
+    ) -> JsCompilationResult {
+        spin_on::spin_on(self.internal.build_all_from_source(source_code, PathBuf::from(path)))
+            .into()
+    }
+}
+
+/// CompilationResult gives access to all the components, structs and enums exported
+/// by a compiled .slint file.
+#[napi(js_name = "CompilationResult")]
+pub struct JsCompilationResult {
+    internal: CompilationResult,
+}
+
+impl From<CompilationResult> for JsCompilationResult {
+    fn from(result: CompilationResult) -> Self {
+        Self { internal: result }
+    }
+}
+
+#[napi]
+impl JsCompilationResult {
+    #[napi(getter)]
+    pub fn component_names(&self) -> Vec<String> {
+        self.internal.component_names().map(ToString::to_string).collect()
+    }
+
+    #[napi]
+    pub fn component(&self, name: String) -> Option<JsComponentDefinition> {
+        self.internal.component(&name).map(|d| d.into())
+    }
+
+    #[napi(getter)]
+    pub fn structs(&self) -> HashMap<String, Vec<JsProperty>> {
+        self.internal
+            .struct_names()
+            .map(|name| {
+                let fields = self.internal.struct_fields(name).into_iter().flatten();
+                (
+                    name.to_string(),
+                    fields
+                        .map(|(field, value_type)| JsProperty {
+                            name: field.to_string(),
+                            value_type: value_type.into(),
+                        })
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[napi(getter)]
+    pub fn enums(&self) -> HashMap<String, Vec<String>> {
+        self.internal
+            .enum_names()
+            .map(|name| {
+                let values = self.internal.enum_values(name).into_iter().flatten();
+                (name.to_string(), values.map(ToString::to_string).collect())
+            })
+            .collect()
+    }
+
+    #[napi(getter)]
+    pub fn diagnostics(&self) -> Vec<JsDiagnostic> {
+        self.internal.diagnostics().map(JsDiagnostic::from).collect()
+    }
 }
@@ -49,7 +49,8 @@ pub struct JsProperty {
 
 pub fn to_js_unknown(env: &Env, value: &Value) -> Result<JsUnknown> {
     match value {
-        Value::Void => env.get_null().map(|v| v.into_unknown()),
+        // `void` and `null` optionals
+        Value::Void => env.get_undefined().map(|v| v.into_unknown()),
         Value::Number(number) => env.create_double(*number).map(|v| v.into_unknown()),
         Value::String(string) => env.create_string(string).map(|v| v.into_unknown()),
         Value::Bool(value) => env.get_boolean(*value).map(|v| v.into_unknown()),
@@ -252,6 +253,10 @@ fn try_convert_image<BufferType: AsRef<[u8]> + FromNapiValue>(
                 Ok(Value::Model(rust_model))
             }
         }
+        Type::Optional(inner) => match unknown.get_type()? {
+            napi::ValueType::Null | napi::ValueType::Undefined => Ok(Value::Void),
+            _ => to_value(env, unknown, inner),
+        },
         Type::Enumeration(_) => todo!(),
         Type::Invalid
         | Type::Model
@@ -261,6 +266,8 @@ fn try_convert_image<BufferType: AsRef<[u8]> + FromNapiValue>(
         | Type::Function { .. }
         | Type::Callback { .. }
         | Type::ComponentFactory { .. }
+        | Type::Interface(_)
+        | Type::TypeParameter(_)
         | Type::Easing
         | Type::PathData
         | Type::LayoutCache
@@ -1,7 +1,10 @@
 // Copyright © SixtyFPS GmbH <info@slint.dev>
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.2 OR LicenseRef-Slint-commercial
 
-use i_slint_core::{graphics::GradientStop, Brush, Color};
+use i_slint_core::{
+    graphics::{GradientStop, RadialGradientExtent, RadialGradientShape},
+    Brush, Color,
+};
 use napi::{bindgen_prelude::External, Error, Result};
 
 /// RgbaColor represents a color in the Slint run-time, represented using 8-bit channels for red, green, blue and the alpha (opacity).
@@ -296,12 +299,45 @@ pub fn to_string(&self) -> String {
                     gradient_stops_to_string(gradient.stops())
                 );
             }
-            Brush::RadialGradient(gradient) => {
+            Brush::RadialGradient(gradient)
+                if gradient.extent() == RadialGradientExtent::AverageSide =>
+            {
                 return format!(
                     "radial-gradient(circle, {})",
                     gradient_stops_to_string(gradient.stops())
                 );
             }
+            Brush::RadialGradient(gradient) => {
+                let shape = match gradient.shape() {
+                    RadialGradientShape::Circle => "circle",
+                    RadialGradientShape::Ellipse => "ellipse",
+                };
+                let extent = match gradient.extent() {
+                    RadialGradientExtent::ClosestSide => "closest-side",
+                    RadialGradientExtent::FarthestSide => "farthest-side",
+                    RadialGradientExtent::ClosestCorner => "closest-corner",
+                    RadialGradientExtent::FarthestCorner | RadialGradientExtent::AverageSide => {
+                        "farthest-corner"
+                    }
+                };
+                return format!(
+                    "radial-gradient({} {} at {}% {}%, {})",
+                    shape,
+                    extent,
+                    gradient.center().x * 100.,
+                    gradient.center().y * 100.,
+                    gradient_stops_to_string(gradient.stops())
+                );
+            }
+            Brush::ConicGradient(gradient) => {
+                return format!(
+                    "conic-gradient(from {}deg at {}% {}%, {})",
+                    gradient.angle(),
+                    gradient.center().x * 100.,
+                    gradient.center().y * 100.,
+                    gradient_stops_to_string(gradient.stops())
+                );
+            }
             _ => String::default(),
         }
     }
@@ -84,6 +84,63 @@ fn build_from_source(
         spin_on::spin_on(self.compiler.build_from_source(source_code, path))
             .map(|definition| ComponentDefinition { definition })
     }
+
+    fn build_all_from_path(&mut self, path: PathBuf) -> CompilationResult {
+        CompilationResult { result: spin_on::spin_on(self.compiler.build_all_from_path(path)) }
+    }
+
+    fn build_all_from_source(&mut self, source_code: String, path: PathBuf) -> CompilationResult {
+        CompilationResult {
+            result: spin_on::spin_on(self.compiler.build_all_from_source(source_code, path)),
+        }
+    }
+}
+
+#[pyclass(unsendable)]
+pub struct CompilationResult {
+    result: slint_interpreter::CompilationResult,
+}
+
+#[pymethods]
+impl CompilationResult {
+    #[getter]
+    fn component_names(&self) -> Vec<String> {
+        self.result.component_names().map(ToString::to_string).collect()
+    }
+
+    fn component(&self, name: &str) -> Option<ComponentDefinition> {
+        self.result.component(name).map(|definition| ComponentDefinition { definition })
+    }
+
+    #[getter]
+    fn structs(&self) -> IndexMap<String, IndexMap<String, PyValueType>> {
+        self.result
+            .struct_names()
+            .map(|name| {
+                let fields = self.result.struct_fields(name).into_iter().flatten();
+                (
+                    name.to_string(),
+                    fields.map(|(field, ty)| (field.to_string(), ty.into())).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[getter]
+    fn enums(&self) -> IndexMap<String, Vec<String>> {
+        self.result
+            .enum_names()
+            .map(|name| {
+                let values = self.result.enum_values(name).into_iter().flatten();
+                (name.to_string(), values.map(ToString::to_string).collect())
+            })
+            .collect()
+    }
+
+    #[getter]
+    fn diagnostics(&self) -> Vec<PyDiagnostic> {
+        self.result.diagnostics().map(PyDiagnostic).collect()
+    }
 }
 
 #[derive(Debug, Clone)]
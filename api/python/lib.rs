@@ -3,7 +3,9 @@
 
 mod image;
 mod interpreter;
-use interpreter::{ComponentCompiler, PyDiagnostic, PyDiagnosticLevel, PyValueType};
+use interpreter::{
+    CompilationResult, ComponentCompiler, PyDiagnostic, PyDiagnosticLevel, PyValueType,
+};
 mod brush;
 mod errors;
 mod models;
@@ -31,6 +33,7 @@ fn slint(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     .map_err(|e| errors::PyPlatformError(e))?;
 
     m.add_class::<ComponentCompiler>()?;
+    m.add_class::<CompilationResult>()?;
     m.add_class::<image::PyImage>()?;
     m.add_class::<PyValueType>()?;
     m.add_class::<PyDiagnosticLevel>()?;
@@ -167,9 +167,9 @@ def load_file(path, quiet=False, style=None, include_paths=None, library_paths=N
     if translation_domain is not None:
         compiler.translation_domain = translation_domain
 
-    compdef = compiler.build_from_path(path)
+    result = compiler.build_all_from_path(path)
 
-    diagnostics = compiler.diagnostics
+    diagnostics = result.diagnostics
     if diagnostics:
         if not quiet:
             for diag in diagnostics:
@@ -181,10 +181,10 @@ def load_file(path, quiet=False, style=None, include_paths=None, library_paths=N
             if errors:
                 raise CompileError(f"Could not compile {path}", diagnostics)
 
-    wrapper_class = _build_class(compdef)
-
     module = types.SimpleNamespace()
-    setattr(module, compdef.name, wrapper_class)
+    for name in result.component_names:
+        wrapper_class = _build_class(result.component(name))
+        setattr(module, name, wrapper_class)
 
     return module
 
@@ -64,3 +64,25 @@ def test_compiler_build_from_path():
 
     assert diags[0].level == native.DiagnosticLevel.Error
     assert diags[0].message.startswith("Could not load Nonexistent.slint:")
+
+def test_compiler_build_all():
+    compiler = native.ComponentCompiler()
+
+    result = compiler.build_all_from_source("""
+        export struct Person { name: string, age: int }
+        export enum Mode { light, dark }
+        export component Dialog {
+            in property <string> title;
+        }
+        export component Main {
+            in property <Mode> mode;
+        }
+    """, "")
+    assert result.diagnostics == []
+    assert result.component_names == ["Dialog", "Main"]
+    assert result.structs == {"Person": {"age": ValueType.Number, "name": ValueType.String}}
+    assert result.enums == {"Mode": ["light", "dark"]}
+
+    assert result.component("Garbage") == None
+    assert result.component("Dialog").name == "Dialog"
+    assert result.component("Main").create() != None
@@ -120,6 +120,34 @@ def test_property_access():
     assert instance.get_global_property("TestGlobal", "theglobalprop") == "Ok"
 
 
+def test_optional_property_access():
+    compiler = native.ComponentCompiler()
+
+    compdef = compiler.build_from_source("""
+        export component Test {
+            in-out property <int?> count;
+            in-out property <string?> name: "Initial";
+        }
+    """, "")
+    assert compdef != None
+
+    instance = compdef.create()
+    assert instance != None
+
+    assert instance.get_property("count") is None
+    assert instance.get_property("name") == "Initial"
+
+    instance.set_property("count", 42)
+    assert instance.get_property("count") == 42
+    instance.set_property("count", None)
+    assert instance.get_property("count") is None
+
+    instance.set_property("name", None)
+    assert instance.get_property("name") is None
+    with pytest.raises(ValueError, match="wrong type"):
+        instance.set_property("count", "Hello")
+
+
 def test_callbacks():
     compiler = native.ComponentCompiler()
 
@@ -127,7 +127,22 @@ fn fill_token_vec(stream: impl Iterator<Item = TokenTree>, vec: &mut Vec<parser:
                     }
                     ';' => SyntaxKind::Semicolon,
                     '!' => SyntaxKind::Bang,
-                    '.' => SyntaxKind::Dot,
+                    '.' => {
+                        if let Some(last) = vec.last_mut() {
+                            if last.kind == SyntaxKind::Dot && prev_spacing == Spacing::Joint {
+                                last.kind = SyntaxKind::DoubleDot;
+                                last.text = "..".into();
+                                continue;
+                            } else if last.kind == SyntaxKind::Question
+                                && prev_spacing == Spacing::Joint
+                            {
+                                last.kind = SyntaxKind::QuestionDot;
+                                last.text = "?.".into();
+                                continue;
+                            }
+                        }
+                        SyntaxKind::Dot
+                    }
                     '+' => SyntaxKind::Plus,
                     '-' => {
                         if let Some(last) = vec.last_mut() {
@@ -168,7 +183,16 @@ fn fill_token_vec(stream: impl Iterator<Item = TokenTree>, vec: &mut Vec<parser:
                         SyntaxKind::RAngle
                     }
                     '#' => SyntaxKind::ColorLiteral,
-                    '?' => SyntaxKind::Question,
+                    '?' => {
+                        if let Some(last) = vec.last_mut() {
+                            if last.kind == SyntaxKind::Question && prev_spacing == Spacing::Joint {
+                                last.kind = SyntaxKind::QuestionQuestion;
+                                last.text = "??".into();
+                                continue;
+                            }
+                        }
+                        SyntaxKind::Question
+                    }
                     ',' => SyntaxKind::Comma,
                     '&' => {
                         // Since the '&' alone does not exist or cannot be part of any other token that &&
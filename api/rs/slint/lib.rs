@@ -213,6 +213,7 @@ struct MyComponent{ /*...*/ }
 pub use i_slint_core::graphics::{
     Brush, Color, Image, LoadImageError, Rgb8Pixel, Rgba8Pixel, RgbaColor, SharedPixelBuffer,
 };
+pub use i_slint_core::items::ImageLoadingStatus;
 pub use i_slint_core::model::{
     FilterModel, MapModel, Model, ModelExt, ModelNotify, ModelPeer, ModelRc, ModelTracker,
     ReverseModel, SortModel, StandardListViewItem, TableColumn, VecModel,
@@ -207,6 +207,8 @@ pub mod re_exports {
         set_state_binding, ChangeTracker, Property, PropertyTracker, StateInfo,
     };
     pub use i_slint_core::slice::Slice;
+    pub use i_slint_core::string::functions as string_functions;
+    pub use i_slint_core::translations::{format_number, format_padded_number};
     pub use i_slint_core::window::{
         InputMethodRequest, WindowAdapter, WindowAdapterRc, WindowInner,
     };
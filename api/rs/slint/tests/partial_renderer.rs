@@ -262,3 +262,155 @@ fn list_view() {
     }));
     assert!(!window.draw_if_needed(|_| { unreachable!() }));
 }
+
+#[test]
+fn dirty_region_api() {
+    slint::slint! {
+        export component Ui inherits Window {
+            in property <color> c: yellow;
+            background: black;
+            Rectangle {
+                x: 10phx;
+                y: 10phx;
+                width: 20phx;
+                height: 20phx;
+                background: c;
+            }
+            Rectangle {
+                x: 100phx;
+                y: 150phx;
+                width: 30phx;
+                height: 10phx;
+                background: c;
+            }
+        }
+    }
+
+    slint::platform::set_platform(Box::new(TestPlatform)).ok();
+    let ui = Ui::new().unwrap();
+    let window = WINDOW.with(|x| x.clone());
+    window.set_size(slint::PhysicalSize::new(180, 260));
+    ui.show().unwrap();
+    assert!(window.draw_if_needed(|renderer| {
+        do_test_render_region(renderer, 0, 0, 180, 260);
+    }));
+
+    ui.set_c(slint::Color::from_rgb_u8(45, 12, 13));
+    assert!(window.draw_if_needed(|renderer| {
+        let region = renderer.dirty_region();
+        let rects = region.iter().collect::<Vec<_>>();
+        assert_eq!(rects.len(), 2);
+        assert!(rects.contains(&(
+            PhysicalPosition { x: 10, y: 10 },
+            PhysicalSize { width: 20, height: 20 }
+        )));
+        assert!(rects.contains(&(
+            PhysicalPosition { x: 100, y: 150 },
+            PhysicalSize { width: 30, height: 10 }
+        )));
+        assert_eq!(region.iter_disjoint().count(), 2);
+
+        let mut buffer = vec![TestPixel(false); 500 * 500];
+        let rendered = renderer.render(buffer.as_mut_slice(), 500);
+        assert_eq!(rendered.iter().collect::<Vec<_>>(), rects);
+    }));
+
+    ui.set_c(slint::Color::from_rgb_u8(12, 45, 13));
+    assert!(window.draw_if_needed(|renderer| {
+        renderer.set_max_dirty_rect_count(1);
+        renderer.mark_dirty_rect(
+            PhysicalPosition { x: 0, y: 200 },
+            PhysicalSize { width: 5, height: 5 },
+        );
+        let region = renderer.dirty_region();
+        assert_eq!(region.iter().count(), 1);
+        assert_eq!(region.bounding_box_origin(), PhysicalPosition { x: 0, y: 10 });
+        assert_eq!(region.bounding_box_size(), PhysicalSize { width: 130, height: 195 });
+        do_test_render_region(renderer, 0, 10, 130, 205);
+    }));
+}
+
+#[test]
+fn box_shadow_offset() {
+    slint::slint! {
+        export component Ui inherits Window {
+            in property <length> offset: 30phx;
+            background: black;
+            Rectangle {
+                x: 10phx;
+                y: 10phx;
+                width: 20phx;
+                height: 20phx;
+                background: red;
+                drop-shadow-color: blue;
+                drop-shadow-offset-x: offset;
+            }
+        }
+    }
+
+    slint::platform::set_platform(Box::new(TestPlatform)).ok();
+    let ui = Ui::new().unwrap();
+    let window = WINDOW.with(|x| x.clone());
+    window.set_size(slint::PhysicalSize::new(180, 260));
+    ui.show().unwrap();
+    assert!(window.draw_if_needed(|renderer| {
+        do_test_render_region(renderer, 0, 0, 180, 260);
+    }));
+    assert!(!window.draw_if_needed(|_| { unreachable!() }));
+    // The area of the shadow at its previous offset must be redrawn as well
+    ui.set_offset(10.);
+    assert!(window.draw_if_needed(|renderer| {
+        do_test_render_region(renderer, 10, 10, 10 + 30 + 20, 10 + 20);
+    }));
+    assert!(!window.draw_if_needed(|_| { unreachable!() }));
+}
+
+#[test]
+fn cached_layer_invalidation() {
+    slint::slint! {
+        export component Ui inherits Window {
+            in property <color> c: red;
+            background: black;
+            Rectangle {
+                x: 10phx;
+                y: 10phx;
+                width: 20phx;
+                height: 20phx;
+                cache-rendering-hint: true;
+                Rectangle {
+                    x: 5phx;
+                    y: 5phx;
+                    width: 10phx;
+                    height: 10phx;
+                    background: c;
+                }
+            }
+        }
+    }
+
+    slint::platform::set_platform(Box::new(TestPlatform)).ok();
+    let ui = Ui::new().unwrap();
+    let window = WINDOW.with(|x| x.clone());
+    window.set_size(slint::PhysicalSize::new(180, 260));
+    ui.show().unwrap();
+    let mut buffer = vec![PremultipliedRgbaColor::default(); 180 * 260];
+    let pixel = |buffer: &[PremultipliedRgbaColor]| {
+        let p = buffer[20 * 180 + 20];
+        (p.red, p.green, p.blue)
+    };
+    assert!(window.draw_if_needed(|renderer| {
+        renderer.render(buffer.as_mut_slice(), 180);
+    }));
+    assert_eq!(pixel(&buffer), (255, 0, 0));
+    assert!(!window.draw_if_needed(|_| { unreachable!() }));
+
+    // Changing a property of a child must render the whole layer again
+    ui.set_c(slint::Color::from_rgb_u8(0, 0, 255));
+    assert!(window.draw_if_needed(|renderer| {
+        let r = renderer.render(buffer.as_mut_slice(), 180);
+        assert_eq!(r.bounding_box_origin(), PhysicalPosition { x: 10, y: 10 });
+        assert_eq!(r.bounding_box_size(), PhysicalSize { width: 20, height: 20 });
+    }));
+    assert_eq!(pixel(&buffer), (0, 0, 255));
+    assert!(!window.draw_if_needed(|_| { unreachable!() }));
+}
@@ -563,11 +563,16 @@ fn mangle_position(position: f32, idx: usize, count: usize) -> f32 {
             }}
         }
         i_slint_core::Brush::RadialGradient(g) => {
+            let center_x = g.center().x as qttypes::qreal * width;
+            let center_y = g.center().y as qttypes::qreal * height;
+            let radii = g.radii([width as f32, height as f32].into());
+            let (radius_x, radius_y) =
+                (radii.width as qttypes::qreal, radii.height as qttypes::qreal);
             cpp_class!(unsafe struct QRadialGradient as "QRadialGradient");
+            // The gradient is a unit circle scaled to the radii of the ellipse by the brush transform
             let mut qrg = cpp! {
-                unsafe [width as "qreal", height as "qreal"] -> QRadialGradient as "QRadialGradient" {
-                    QRadialGradient qrg(width / 2, height / 2, (width + height) / 4);
-                    return qrg;
+                unsafe [] -> QRadialGradient as "QRadialGradient" {
+                    return QRadialGradient(0, 0, 1);
                 }
             };
             let count = g.stops().count();
@@ -578,8 +583,38 @@ fn mangle_position(position: f32, idx: usize, count: usize) -> f32 {
                     qrg.setColorAt(pos, QColor::fromRgba(color));
                 }};
             }
-            cpp! {unsafe [qrg as "QRadialGradient"] -> qttypes::QBrush as "QBrush" {
-                return QBrush(qrg);
+            cpp! {unsafe [qrg as "QRadialGradient", center_x as "qreal", center_y as "qreal", radius_x as "qreal", radius_y as "qreal"] -> qttypes::QBrush as "QBrush" {
+                QBrush brush(qrg);
+                brush.setTransform(QTransform(radius_x, 0, 0, radius_y, center_x, center_y));
+                return brush;
+            }}
+        }
+        i_slint_core::Brush::ConicGradient(g) => {
+            let center = qttypes::QPointF {
+                x: g.center().x as qttypes::qreal * width,
+                y: g.center().y as qttypes::qreal * height,
+            };
+            // QConicalGradient starts at 3 o'clock and turns counter-clockwise, while conic
+            // gradients start at the top and turn clockwise, so the stops are reversed.
+            let start_angle = (90. - g.angle()) as qttypes::qreal;
+            cpp_class!(unsafe struct QConicalGradient as "QConicalGradient");
+            let mut qcg = cpp! {
+                unsafe [center as "QPointF", start_angle as "qreal"] -> QConicalGradient as "QConicalGradient" {
+                    QConicalGradient qcg(center, start_angle);
+                    return qcg;
+                }
+            };
+            let stops = g.stops().collect::<Vec<_>>();
+            let count = stops.len();
+            for (idx, s) in stops.iter().rev().enumerate() {
+                let pos: f32 = mangle_position(1. - s.position, idx, count);
+                let color: u32 = s.color.as_argb_encoded();
+                cpp! {unsafe [mut qcg as "QConicalGradient", pos as "float", color as "QRgb"] {
+                    qcg.setColorAt(pos, QColor::fromRgba(color));
+                }};
+            }
+            cpp! {unsafe [qcg as "QConicalGradient"] -> qttypes::QBrush as "QBrush" {
+                return QBrush(qcg);
             }}
         }
         _ => qttypes::QBrush::default(),
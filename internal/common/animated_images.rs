@@ -0,0 +1,119 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.2 OR LicenseRef-Slint-commercial
+
+//! Decoding of animated GIF, PNG (APNG) and WebP images, shared between the run-time
+//! and the compiler when it embeds images.
+
+use std::io::{BufRead, Read, Seek, SeekFrom};
+
+pub use image;
+
+/// A decoded frame of an animated image
+pub struct AnimationFrame {
+    /// The content of the whole frame, already composed with the previous frames
+    pub buffer: image::RgbaImage,
+    /// How long the frame is shown, in milliseconds
+    pub duration: u32,
+}
+
+/// Decode all the frames of an animated GIF, PNG (APNG) or WebP image.
+///
+/// Returns None if the format doesn't support animations, or if the image has less than two frames,
+/// in which case it should be decoded as a still image.
+pub fn load_animation(
+    mut reader: impl BufRead + Seek,
+    format: image::ImageFormat,
+) -> Option<Vec<AnimationFrame>> {
+    use image::AnimationDecoder;
+    let frames = match format {
+        image::ImageFormat::Gif => image::codecs::gif::GifDecoder::new(reader).ok()?.into_frames(),
+        image::ImageFormat::Png => {
+            if !is_apng(&mut reader)? {
+                return None;
+            }
+            image::codecs::png::PngDecoder::new(reader).ok()?.apng().into_frames()
+        }
+        image::ImageFormat::WebP => {
+            let decoder = image::codecs::webp::WebPDecoder::new(reader).ok()?;
+            if !decoder.has_animation() {
+                return None;
+            }
+            decoder.into_frames()
+        }
+        _ => return None,
+    };
+    let frames = frames.collect_frames().map_or_else(
+        |decode_err| {
+            eprintln!("Error decoding animated image: {}", decode_err);
+            None
+        },
+        Some,
+    )?;
+    if frames.len() < 2 {
+        return None;
+    }
+    Some(
+        frames
+            .into_iter()
+            .map(|frame| {
+                let (numerator, denominator) = frame.delay().numer_denom_ms();
+                // Like web browsers, treat very short delays as the default delay of 100ms
+                let duration = match numerator / denominator.max(1) {
+                    0..=10 => 100,
+                    duration => duration,
+                };
+                AnimationFrame { buffer: frame.into_buffer(), duration }
+            })
+            .collect(),
+    )
+}
+
+/// Returns true if the PNG image contains an `acTL` chunk before its image data, which marks it as an APNG.
+///
+/// Only the chunk headers are read, and the reader is rewound to where it was.
+fn is_apng(reader: &mut (impl Read + Seek)) -> Option<bool> {
+    let start = reader.stream_position().ok()?;
+    let result = (|| {
+        let mut signature = [0u8; 8];
+        reader.read_exact(&mut signature).ok()?;
+        loop {
+            let mut header = [0u8; 8];
+            reader.read_exact(&mut header).ok()?;
+            let (length, chunk_type) = header.split_at(4);
+            match chunk_type {
+                b"acTL" => return Some(true),
+                b"IDAT" | b"IEND" => return Some(false),
+                _ => {
+                    let length = u32::from_be_bytes(length.try_into().unwrap());
+                    // skip the chunk data and its CRC
+                    reader.seek(SeekFrom::Current(length as i64 + 4)).ok()?;
+                }
+            }
+        }
+    })();
+    reader.seek(SeekFrom::Start(start)).ok()?;
+    result
+}
+
+#[test]
+fn test_is_apng() {
+    fn chunk(chunk_type: &[u8; 4], data: &[u8]) -> Vec<u8> {
+        let mut chunk = (data.len() as u32).to_be_bytes().to_vec();
+        chunk.extend_from_slice(chunk_type);
+        chunk.extend_from_slice(data);
+        chunk.extend_from_slice(&[0; 4]);
+        chunk
+    }
+    let signature = b"\x89PNG\r\n\x1a\n".to_vec();
+    let ihdr = chunk(b"IHDR", &[0; 13]);
+
+    let apng = [signature.clone(), ihdr.clone(), chunk(b"acTL", &[0; 8]), chunk(b"IDAT", &[])];
+    let mut reader = std::io::Cursor::new(apng.concat());
+    assert_eq!(is_apng(&mut reader), Some(true));
+    assert_eq!(reader.position(), 0);
+
+    let png = [signature, ihdr, chunk(b"IDAT", &[]), chunk(b"acTL", &[0; 8])];
+    let mut reader = std::io::Cursor::new(png.concat());
+    assert_eq!(is_apng(&mut reader), Some(false));
+    assert_eq!(reader.position(), 0);
+}
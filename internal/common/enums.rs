@@ -279,6 +279,16 @@ enum ImageTiling {
                 Round,
             }
 
+            /// This enum describes whether the source of an [`Image`](elements.md#image) element is loaded.
+            enum ImageLoadingStatus {
+                /// The image is loaded, or the source is empty.
+                Ready,
+                /// The image is being loaded in the background. The image is empty until then.
+                Loading,
+                /// The image couldn't be loaded.
+                Error,
+            }
+
             /// This enum is used to define the type of the input field.
             #[non_exhaustive]
             enum InputType {
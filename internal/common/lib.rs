@@ -3,7 +3,7 @@
 
 #![doc = include_str!("README.md")]
 #![doc(html_logo_url = "https://slint.dev/logo/slint-logo-square-light.svg")]
-#![cfg_attr(not(feature = "shared-fontdb"), no_std)]
+#![cfg_attr(not(any(feature = "shared-fontdb", feature = "animated-images")), no_std)]
 
 pub mod builtin_structs;
 pub mod enums;
@@ -12,6 +12,9 @@
 #[cfg(feature = "shared-fontdb")]
 pub mod sharedfontdb;
 
+#[cfg(feature = "animated-images")]
+pub mod animated_images;
+
 /// Detect the native style depending on the platform
 pub fn get_native_style(has_qt: bool, target: &str) -> &'static str {
     // NOTE: duplicated in api/cpp/CMakeLists.txt
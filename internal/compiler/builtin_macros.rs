@@ -64,6 +64,12 @@ pub fn lower_macro(
         }
         BuiltinMacroFunction::Rgb => rgb_macro(n, sub_expr.collect(), diag),
         BuiltinMacroFunction::Hsv => hsv_macro(n, sub_expr.collect(), diag),
+        BuiltinMacroFunction::ArrayFilter
+        | BuiltinMacroFunction::ArrayMap
+        | BuiltinMacroFunction::ArraySortBy
+        | BuiltinMacroFunction::ArraySlice => {
+            unreachable!("model operations are resolved with their lambda")
+        }
     }
 }
 
@@ -298,7 +304,9 @@ fn to_debug_string(
         | Type::InferredCallback
         | Type::InferredProperty
         | Type::Callback { .. }
-        | Type::ComponentFactory
+        | Type::ComponentFactory(_)
+        | Type::Interface(_)
+        | Type::TypeParameter(_)
         | Type::Function { .. }
         | Type::ElementReference
         | Type::LayoutCache
@@ -383,6 +391,29 @@ fn to_debug_string(
                 ]),
             }
         }
+        Type::Optional(inner) if **inner == Type::Void => Expression::StringLiteral("null".into()),
+        Type::Optional(inner) => {
+            let variable = format!(
+                "debug_optional{}",
+                COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+            );
+            let value = to_debug_string(
+                Expression::ReadLocalVariable { name: variable.clone(), ty: (**inner).clone() },
+                node,
+                diag,
+            );
+            Expression::NullCoalescing {
+                lhs: Box::new(Expression::OptionalChaining {
+                    optional: Box::new(expr),
+                    variable,
+                    body: Box::new(Expression::Cast {
+                        from: Box::new(value),
+                        to: Type::Optional(Box::new(Type::String)),
+                    }),
+                }),
+                rhs: Box::new(Expression::StringLiteral("null".into())),
+            }
+        }
         Type::Enumeration(enu) => {
             let local_object = "debug_enum";
             let mut v = vec![Expression::StoreLocalVariable {
@@ -0,0 +1,69 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.2 OR LicenseRef-Slint-commercial
+
+//! Rewrites of deprecated syntax and names to the current ones.
+//!
+//! The `slint-updater` applies them to whole files, and the LSP offers them as quick fixes for
+//! the deprecation warnings.
+
+use crate::parser::{normalize_identifier, NodeOrToken, SyntaxKind, SyntaxNode, TextRange};
+
+/// A change to the source: the text in `range` is replaced by `text`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub range: TextRange,
+    pub text: String,
+}
+
+fn is_global(declaration: &SyntaxNode) -> bool {
+    declaration.child_token(SyntaxKind::Identifier).map_or(false, |t| t.text() == "global")
+}
+
+/// Remove the `:=` of a struct or a global declared with the deprecated `struct Foo := { }` or
+/// `global Foo := { }` syntax, along with the whitespace before it
+pub fn remove_colon_equal(declaration: &SyntaxNode) -> Option<TextEdit> {
+    match declaration.kind() {
+        SyntaxKind::StructDeclaration => {}
+        SyntaxKind::Component if is_global(declaration) => {}
+        _ => return None,
+    }
+    let colon_equal = declaration.child_token(SyntaxKind::ColonEqual)?;
+    let mut range = colon_equal.text_range();
+    if let Some(ws) = colon_equal.prev_token().filter(|t| t.kind() == SyntaxKind::Whitespace) {
+        range = ws.text_range().cover(range);
+    }
+    Some(TextEdit { range, text: String::new() })
+}
+
+/// Turn a component declared with the deprecated `Foo := Base { }` syntax into
+/// `component Foo inherits Base { }`
+pub fn new_component_declaration(declaration: &SyntaxNode) -> Option<Vec<TextEdit>> {
+    if declaration.kind() != SyntaxKind::Component || is_global(declaration) {
+        return None;
+    }
+    let colon_equal = declaration.child_token(SyntaxKind::ColonEqual)?;
+    let space =
+        |kind: Option<SyntaxKind>| if kind == Some(SyntaxKind::Whitespace) { "" } else { " " };
+    let inherits = format!(
+        "{}inherits{}",
+        space(colon_equal.prev_token().map(|t| t.kind())),
+        space(colon_equal.next_token().map(|t| t.kind())),
+    );
+    Some(vec![
+        TextEdit {
+            range: TextRange::empty(declaration.text_range().start()),
+            text: "component ".into(),
+        },
+        TextEdit { range: colon_equal.text_range(), text: inherits },
+    ])
+}
+
+/// Rename the identifier `old` to `new` in `node`, the binding or the qualified name that uses
+/// a deprecated property or enumeration value
+pub fn rename_identifier(node: &SyntaxNode, old: &str, new: &str) -> Option<TextEdit> {
+    let token = node
+        .children_with_tokens()
+        .filter_map(NodeOrToken::into_token)
+        .find(|t| t.kind() == SyntaxKind::Identifier && normalize_identifier(t.text()) == old)?;
+    Some(TextEdit { range: token.text_range(), text: new.into() })
+}
@@ -45,6 +45,14 @@ pub fn new_empty() -> Self {
     }
 }
 
+#[cfg(feature = "software-renderer")]
+#[derive(Debug, Clone)]
+pub struct AnimationFrame {
+    pub texture: Texture,
+    /// How long the frame is shown, in milliseconds
+    pub duration: u32,
+}
+
 #[cfg(feature = "software-renderer")]
 #[derive(Debug, Clone, Default)]
 pub struct BitmapGlyph {
@@ -90,6 +98,9 @@ pub enum EmbeddedResourcesKind {
     /// The data has been processed in a texture
     #[cfg(feature = "software-renderer")]
     TextureData(Texture),
+    /// The frames of an animated image, each processed in a texture
+    #[cfg(feature = "software-renderer")]
+    AnimatedTextureData(Vec<AnimationFrame>),
     /// A set of pre-rendered glyphs of a TrueType font
     #[cfg(feature = "software-renderer")]
     BitmapFontData(BitmapFont),
@@ -48,6 +48,28 @@ pub enum BuiltinFunction {
     StringToFloat,
     /// the "42".is_float()
     StringIsFloat,
+    /// the "42".to-int()
+    StringToInt,
+    /// the "abc".length
+    StringLength,
+    /// the "abc".is-empty
+    StringIsEmpty,
+    StringToUppercase,
+    StringToLowercase,
+    StringTrim,
+    StringContains,
+    StringStartsWith,
+    StringEndsWith,
+    StringReplace,
+    StringSplit,
+    StringSubstring,
+    StringCharAt,
+    /// the 42.5.to-fixed(2)
+    NumberToFixed,
+    /// the 1234.5.format-number(2)
+    NumberFormat,
+    /// the 5.to-padded(2)
+    NumberToPadded,
     ColorRgbaStruct,
     ColorHsvaStruct,
     ColorBrighter,
@@ -57,6 +79,11 @@ pub enum BuiltinFunction {
     ColorWithAlpha,
     ImageSize,
     ArrayLength,
+    ArrayIndexOf,
+    ArrayContains,
+    ArraySum,
+    ArrayMin,
+    ArrayMax,
     Rgb,
     Hsv,
     ColorScheme,
@@ -92,6 +119,13 @@ pub enum BuiltinMacroFunction {
     Hsv,
     /// transform `debug(a, b, c)` into debug `a + " " + b + " " + c`
     Debug,
+    /// `model.filter(x => ...)`, `model.map(x => ...)`, `model.sort-by(x => ...)`, and
+    /// `model.slice(start, end)` are transformed into an [`Expression::ModelOperation`].
+    /// The lambda is resolved with the call, since its parameter has the type of the model's rows.
+    ArrayFilter,
+    ArrayMap,
+    ArraySortBy,
+    ArraySlice,
 }
 
 impl BuiltinFunction {
@@ -158,6 +192,45 @@ pub fn ty(&self) -> Type {
             BuiltinFunction::StringIsFloat => {
                 Type::Function { return_type: Box::new(Type::Bool), args: vec![Type::String] }
             }
+            BuiltinFunction::StringToInt | BuiltinFunction::StringLength => {
+                Type::Function { return_type: Box::new(Type::Int32), args: vec![Type::String] }
+            }
+            BuiltinFunction::StringIsEmpty => {
+                Type::Function { return_type: Box::new(Type::Bool), args: vec![Type::String] }
+            }
+            BuiltinFunction::StringToUppercase
+            | BuiltinFunction::StringToLowercase
+            | BuiltinFunction::StringTrim => {
+                Type::Function { return_type: Box::new(Type::String), args: vec![Type::String] }
+            }
+            BuiltinFunction::StringContains
+            | BuiltinFunction::StringStartsWith
+            | BuiltinFunction::StringEndsWith => Type::Function {
+                return_type: Box::new(Type::Bool),
+                args: vec![Type::String, Type::String],
+            },
+            BuiltinFunction::StringReplace => Type::Function {
+                return_type: Box::new(Type::String),
+                args: vec![Type::String, Type::String, Type::String],
+            },
+            BuiltinFunction::StringSplit => Type::Function {
+                return_type: Box::new(Type::Array(Box::new(Type::String))),
+                args: vec![Type::String, Type::String],
+            },
+            BuiltinFunction::StringSubstring => Type::Function {
+                return_type: Box::new(Type::String),
+                args: vec![Type::String, Type::Int32, Type::Int32],
+            },
+            BuiltinFunction::StringCharAt => Type::Function {
+                return_type: Box::new(Type::String),
+                args: vec![Type::String, Type::Int32],
+            },
+            BuiltinFunction::NumberToFixed
+            | BuiltinFunction::NumberFormat
+            | BuiltinFunction::NumberToPadded => Type::Function {
+                return_type: Box::new(Type::String),
+                args: vec![Type::Float32, Type::Int32],
+            },
             BuiltinFunction::ImplicitLayoutInfo(_) => Type::Function {
                 return_type: Box::new(crate::layout::layout_info_type()),
                 args: vec![Type::ElementReference],
@@ -228,6 +301,19 @@ pub fn ty(&self) -> Type {
             BuiltinFunction::ArrayLength => {
                 Type::Function { return_type: Box::new(Type::Int32), args: vec![Type::Model] }
             }
+            // The type of the second argument is the type of the elements of the array,
+            // it is resolved when the function is called
+            BuiltinFunction::ArrayIndexOf => Type::Function {
+                return_type: Box::new(Type::Int32),
+                args: vec![Type::Model, Type::Invalid],
+            },
+            BuiltinFunction::ArrayContains => Type::Function {
+                return_type: Box::new(Type::Bool),
+                args: vec![Type::Model, Type::Invalid],
+            },
+            BuiltinFunction::ArraySum | BuiltinFunction::ArrayMin | BuiltinFunction::ArrayMax => {
+                Type::Function { return_type: Box::new(Type::Float32), args: vec![Type::Model] }
+            }
             BuiltinFunction::Rgb => Type::Function {
                 return_type: Box::new(Type::Color),
                 args: vec![Type::Int32, Type::Int32, Type::Int32, Type::Float32],
@@ -302,6 +388,22 @@ fn is_const(&self) -> bool {
             BuiltinFunction::SetSelectionOffsets => false,
             BuiltinFunction::ItemMemberFunction(..) => false,
             BuiltinFunction::StringToFloat | BuiltinFunction::StringIsFloat => true,
+            BuiltinFunction::StringToInt
+            | BuiltinFunction::StringLength
+            | BuiltinFunction::StringIsEmpty
+            | BuiltinFunction::StringToUppercase
+            | BuiltinFunction::StringToLowercase
+            | BuiltinFunction::StringTrim
+            | BuiltinFunction::StringContains
+            | BuiltinFunction::StringStartsWith
+            | BuiltinFunction::StringEndsWith
+            | BuiltinFunction::StringReplace
+            | BuiltinFunction::StringSplit
+            | BuiltinFunction::StringSubstring
+            | BuiltinFunction::StringCharAt => true,
+            // The formatting depends on the locale, which can change at run-time
+            BuiltinFunction::NumberToFixed | BuiltinFunction::NumberFormat => false,
+            BuiltinFunction::NumberToPadded => true,
             BuiltinFunction::ColorRgbaStruct
             | BuiltinFunction::ColorHsvaStruct
             | BuiltinFunction::ColorBrighter
@@ -318,6 +420,11 @@ fn is_const(&self) -> bool {
             #[cfg(target_arch = "wasm32")]
             BuiltinFunction::ImageSize => false,
             BuiltinFunction::ArrayLength => true,
+            BuiltinFunction::ArrayIndexOf
+            | BuiltinFunction::ArrayContains
+            | BuiltinFunction::ArraySum
+            | BuiltinFunction::ArrayMin
+            | BuiltinFunction::ArrayMax => true,
             BuiltinFunction::Rgb => true,
             BuiltinFunction::Hsv => true,
             BuiltinFunction::SetTextInputFocused => false,
@@ -359,6 +466,22 @@ pub fn is_pure(&self) -> bool {
             BuiltinFunction::SetSelectionOffsets => false,
             BuiltinFunction::ItemMemberFunction(..) => false,
             BuiltinFunction::StringToFloat | BuiltinFunction::StringIsFloat => true,
+            BuiltinFunction::StringToInt
+            | BuiltinFunction::StringLength
+            | BuiltinFunction::StringIsEmpty
+            | BuiltinFunction::StringToUppercase
+            | BuiltinFunction::StringToLowercase
+            | BuiltinFunction::StringTrim
+            | BuiltinFunction::StringContains
+            | BuiltinFunction::StringStartsWith
+            | BuiltinFunction::StringEndsWith
+            | BuiltinFunction::StringReplace
+            | BuiltinFunction::StringSplit
+            | BuiltinFunction::StringSubstring
+            | BuiltinFunction::StringCharAt => true,
+            BuiltinFunction::NumberToFixed
+            | BuiltinFunction::NumberFormat
+            | BuiltinFunction::NumberToPadded => true,
             BuiltinFunction::ColorRgbaStruct
             | BuiltinFunction::ColorHsvaStruct
             | BuiltinFunction::ColorBrighter
@@ -368,6 +491,11 @@ pub fn is_pure(&self) -> bool {
             | BuiltinFunction::ColorWithAlpha => true,
             BuiltinFunction::ImageSize => true,
             BuiltinFunction::ArrayLength => true,
+            BuiltinFunction::ArrayIndexOf
+            | BuiltinFunction::ArrayContains
+            | BuiltinFunction::ArraySum
+            | BuiltinFunction::ArrayMin
+            | BuiltinFunction::ArrayMax => true,
             BuiltinFunction::Rgb => true,
             BuiltinFunction::Hsv => true,
             BuiltinFunction::ImplicitLayoutInfo(_) => true,
@@ -496,6 +624,23 @@ pub enum MinMaxOp {
     Max,
 }
 
+/// The operation of an [`Expression::ModelOperation`]
+///
+/// The `variable` is the name of the local variable that holds the row when evaluating the
+/// `body`. The body of these lambdas only depend on local variables: the resolving pass stores
+/// everything else in local variables before the model operation.
+#[derive(Debug, Clone)]
+pub enum ModelOperation {
+    /// Only keep the rows for which the body evaluates to true
+    Filter { variable: String, body: Box<Expression> },
+    /// Each row is the result of the body
+    Map { variable: String, body: Box<Expression> },
+    /// Sort the rows by the value of the body, which is a number or a string
+    SortBy { variable: String, body: Box<Expression> },
+    /// The rows from `start` to `end` (excluded)
+    Slice { start: Box<Expression>, end: Box<Expression> },
+}
+
 /// The Expression is hold by properties, so it should not hold any strong references to node from the object_tree
 #[derive(Debug, Clone, Default)]
 pub enum Expression {
@@ -511,6 +656,8 @@ pub enum Expression {
     NumberLiteral(f64, Unit),
     /// Bool
     BoolLiteral(bool),
+    /// `null`. The type is the optional type it was converted to.
+    NullLiteral(Type),
 
     /// Reference to the callback `<name>` in the `<element>`
     ///
@@ -641,6 +788,22 @@ pub enum Expression {
         false_expr: Box<Expression>,
     },
 
+    /// `lhs ?? rhs`: the value of the optional `lhs`, or `rhs` if `lhs` is null.
+    /// `rhs` is only evaluated if `lhs` is null.
+    NullCoalescing {
+        lhs: Box<Expression>,
+        rhs: Box<Expression>,
+    },
+
+    /// `optional?.member`: null if `optional` is null. Otherwise the value of `optional` is
+    /// stored in the local variable `variable`, and this evaluates to the `body`, which is
+    /// itself optional.
+    OptionalChaining {
+        optional: Box<Expression>,
+        variable: String,
+        body: Box<Expression>,
+    },
+
     Array {
         element_ty: Type,
         values: Vec<Expression>,
@@ -661,6 +824,21 @@ pub enum Expression {
     },
 
     RadialGradient {
+        shape: RadialGradientShape,
+        extent: RadialGradientExtent,
+        /// The center, as a ratio of the width and height of the shape
+        center_x: Box<Expression>,
+        center_y: Box<Expression>,
+        /// First expression in the tuple is a color, second expression is the stop position
+        stops: Vec<(Expression, Expression)>,
+    },
+
+    ConicGradient {
+        /// The angle of the first stop
+        from_angle: Box<Expression>,
+        /// The center, as a ratio of the width and height of the shape
+        center_x: Box<Expression>,
+        center_y: Box<Expression>,
         /// First expression in the tuple is a color, second expression is the stop position
         stops: Vec<(Expression, Expression)>,
     },
@@ -669,6 +847,32 @@ pub enum Expression {
 
     ReturnStatement(Option<Box<Expression>>),
 
+    /// A `for` loop. The `model` is either an array, or an integer for the range `0..model`.
+    /// The current row is stored in the local variable `variable` before evaluating the body.
+    ///
+    /// The body may contain [`Self::Break`], [`Self::Continue`] and [`Self::ReturnStatement`]
+    /// until the remove_return pass, which lowers them so that the body evaluates to a boolean:
+    /// `false` means to exit the loop. If the body contained a `return`, it evaluates instead to a
+    /// struct with such a boolean in its `condition` field, and the loop evaluates to the struct
+    /// that exited it, or to the default value of the struct if it ran to completion.
+    ForLoop {
+        variable: String,
+        model: Box<Expression>,
+        body: Box<Expression>,
+    },
+
+    /// `break` within the body of a [`Self::ForLoop`]
+    Break,
+
+    /// `continue` within the body of a [`Self::ForLoop`]
+    Continue,
+
+    /// A new model computed from `model`, which follows the changes of `model`.
+    ModelOperation {
+        model: Box<Expression>,
+        operation: ModelOperation,
+    },
+
     LayoutCacheAccess {
         layout_cache_prop: NamedReference,
         index: usize,
@@ -698,6 +902,7 @@ pub fn ty(&self) -> Type {
             Expression::StringLiteral(_) => Type::String,
             Expression::NumberLiteral(_, unit) => unit.ty(),
             Expression::BoolLiteral(_) => Type::Bool,
+            Expression::NullLiteral(ty) => ty.clone(),
             Expression::CallbackReference(nr, _) => nr.ty(),
             Expression::FunctionReference(nr, _) => nr.ty(),
             Expression::PropertyReference(nr) => nr.ty(),
@@ -746,6 +951,8 @@ pub fn ty(&self) -> Type {
                     Type::Void
                 }
             }
+            Expression::NullCoalescing { rhs, .. } => rhs.ty(),
+            Expression::OptionalChaining { body, .. } => body.ty(),
             Expression::BinaryExpression { op, lhs, rhs } => {
                 if operator_class(*op) != OperatorClass::ArithmeticOp {
                     Type::Bool
@@ -807,9 +1014,20 @@ pub fn ty(&self) -> Type {
             Expression::EasingCurve(_) => Type::Easing,
             Expression::LinearGradient { .. } => Type::Brush,
             Expression::RadialGradient { .. } => Type::Brush,
+            Expression::ConicGradient { .. } => Type::Brush,
             Expression::EnumerationValue(value) => Type::Enumeration(value.enumeration.clone()),
             // invalid because the expression is unreachable
             Expression::ReturnStatement(_) => Type::Invalid,
+            Expression::ForLoop { body, .. } => match body.ty() {
+                ty @ Type::Struct { .. } => ty,
+                _ => Type::Void,
+            },
+            Expression::ModelOperation { model, operation } => match operation {
+                ModelOperation::Map { body, .. } => Type::Array(Box::new(body.ty())),
+                _ => model.ty(),
+            },
+            // invalid because the rest of the block is unreachable
+            Expression::Break | Expression::Continue => Type::Invalid,
             Expression::LayoutCacheAccess { .. } => Type::LogicalLength,
             Expression::ComputeLayoutInfo(..) => crate::layout::layout_info_type(),
             Expression::SolveLayout(..) => Type::LayoutCache,
@@ -825,6 +1043,7 @@ pub fn visit(&self, mut visitor: impl FnMut(&Self)) {
             Expression::StringLiteral(_) => {}
             Expression::NumberLiteral(_, _) => {}
             Expression::BoolLiteral(_) => {}
+            Expression::NullLiteral(_) => {}
             Expression::CallbackReference { .. } => {}
             Expression::PropertyReference { .. } => {}
             Expression::FunctionReference { .. } => {}
@@ -861,6 +1080,14 @@ pub fn visit(&self, mut visitor: impl FnMut(&Self)) {
                 visitor(true_expr);
                 visitor(false_expr);
             }
+            Expression::NullCoalescing { lhs, rhs } => {
+                visitor(lhs);
+                visitor(rhs);
+            }
+            Expression::OptionalChaining { optional, body, .. } => {
+                visitor(optional);
+                visitor(body);
+            }
             Expression::BinaryExpression { lhs, rhs, .. } => {
                 visitor(lhs);
                 visitor(rhs);
@@ -897,7 +1124,18 @@ pub fn visit(&self, mut visitor: impl FnMut(&Self)) {
                     visitor(s);
                 }
             }
-            Expression::RadialGradient { stops } => {
+            Expression::RadialGradient { center_x, center_y, stops, .. } => {
+                visitor(center_x);
+                visitor(center_y);
+                for (c, s) in stops {
+                    visitor(c);
+                    visitor(s);
+                }
+            }
+            Expression::ConicGradient { from_angle, center_x, center_y, stops } => {
+                visitor(from_angle);
+                visitor(center_x);
+                visitor(center_y);
                 for (c, s) in stops {
                     visitor(c);
                     visitor(s);
@@ -907,6 +1145,23 @@ pub fn visit(&self, mut visitor: impl FnMut(&Self)) {
             Expression::ReturnStatement(expr) => {
                 expr.as_deref().map(visitor);
             }
+            Expression::ForLoop { model, body, .. } => {
+                visitor(model);
+                visitor(body);
+            }
+            Expression::Break | Expression::Continue => {}
+            Expression::ModelOperation { model, operation } => {
+                visitor(model);
+                match operation {
+                    ModelOperation::Filter { body, .. }
+                    | ModelOperation::Map { body, .. }
+                    | ModelOperation::SortBy { body, .. } => visitor(body),
+                    ModelOperation::Slice { start, end } => {
+                        visitor(start);
+                        visitor(end);
+                    }
+                }
+            }
             Expression::LayoutCacheAccess { repeater_index, .. } => {
                 repeater_index.as_deref().map(visitor);
             }
@@ -926,6 +1181,7 @@ pub fn visit_mut(&mut self, mut visitor: impl FnMut(&mut Self)) {
             Expression::StringLiteral(_) => {}
             Expression::NumberLiteral(_, _) => {}
             Expression::BoolLiteral(_) => {}
+            Expression::NullLiteral(_) => {}
             Expression::CallbackReference { .. } => {}
             Expression::PropertyReference { .. } => {}
             Expression::FunctionReference { .. } => {}
@@ -962,6 +1218,14 @@ pub fn visit_mut(&mut self, mut visitor: impl FnMut(&mut Self)) {
                 visitor(true_expr);
                 visitor(false_expr);
             }
+            Expression::NullCoalescing { lhs, rhs } => {
+                visitor(lhs);
+                visitor(rhs);
+            }
+            Expression::OptionalChaining { optional, body, .. } => {
+                visitor(optional);
+                visitor(body);
+            }
             Expression::BinaryExpression { lhs, rhs, .. } => {
                 visitor(lhs);
                 visitor(rhs);
@@ -1001,7 +1265,18 @@ pub fn visit_mut(&mut self, mut visitor: impl FnMut(&mut Self)) {
                     visitor(s);
                 }
             }
-            Expression::RadialGradient { stops } => {
+            Expression::RadialGradient { center_x, center_y, stops, .. } => {
+                visitor(center_x);
+                visitor(center_y);
+                for (c, s) in stops {
+                    visitor(c);
+                    visitor(s);
+                }
+            }
+            Expression::ConicGradient { from_angle, center_x, center_y, stops } => {
+                visitor(from_angle);
+                visitor(center_x);
+                visitor(center_y);
                 for (c, s) in stops {
                     visitor(c);
                     visitor(s);
@@ -1011,6 +1286,23 @@ pub fn visit_mut(&mut self, mut visitor: impl FnMut(&mut Self)) {
             Expression::ReturnStatement(expr) => {
                 expr.as_deref_mut().map(visitor);
             }
+            Expression::ForLoop { model, body, .. } => {
+                visitor(model);
+                visitor(body);
+            }
+            Expression::Break | Expression::Continue => {}
+            Expression::ModelOperation { model, operation } => {
+                visitor(model);
+                match operation {
+                    ModelOperation::Filter { body, .. }
+                    | ModelOperation::Map { body, .. }
+                    | ModelOperation::SortBy { body, .. } => visitor(body),
+                    ModelOperation::Slice { start, end } => {
+                        visitor(start);
+                        visitor(end);
+                    }
+                }
+            }
             Expression::LayoutCacheAccess { repeater_index, .. } => {
                 repeater_index.as_deref_mut().map(visitor);
             }
@@ -1042,6 +1334,7 @@ pub fn is_constant(&self) -> bool {
             Expression::StringLiteral(_) => true,
             Expression::NumberLiteral(_, _) => true,
             Expression::BoolLiteral(_) => true,
+            Expression::NullLiteral(_) => true,
             Expression::CallbackReference { .. } => false,
             Expression::FunctionReference(nr, _) => nr.is_constant(),
             Expression::PropertyReference(nr) => nr.is_constant(),
@@ -1065,6 +1358,10 @@ pub fn is_constant(&self) -> bool {
             Expression::Condition { condition, false_expr, true_expr } => {
                 condition.is_constant() && false_expr.is_constant() && true_expr.is_constant()
             }
+            Expression::NullCoalescing { lhs, rhs } => lhs.is_constant() && rhs.is_constant(),
+            Expression::OptionalChaining { optional, body, .. } => {
+                optional.is_constant() && body.is_constant()
+            }
             Expression::BinaryExpression { lhs, rhs, .. } => lhs.is_constant() && rhs.is_constant(),
             Expression::UnaryOp { sub, .. } => sub.is_constant(),
             Expression::Array { values, .. } => values.iter().all(Expression::is_constant),
@@ -1083,13 +1380,24 @@ pub fn is_constant(&self) -> bool {
             Expression::LinearGradient { angle, stops } => {
                 angle.is_constant() && stops.iter().all(|(c, s)| c.is_constant() && s.is_constant())
             }
-            Expression::RadialGradient { stops } => {
-                stops.iter().all(|(c, s)| c.is_constant() && s.is_constant())
+            Expression::RadialGradient { center_x, center_y, stops, .. } => {
+                center_x.is_constant()
+                    && center_y.is_constant()
+                    && stops.iter().all(|(c, s)| c.is_constant() && s.is_constant())
+            }
+            Expression::ConicGradient { from_angle, center_x, center_y, stops } => {
+                from_angle.is_constant()
+                    && center_x.is_constant()
+                    && center_y.is_constant()
+                    && stops.iter().all(|(c, s)| c.is_constant() && s.is_constant())
             }
             Expression::EnumerationValue(_) => true,
             Expression::ReturnStatement(expr) => {
                 expr.as_ref().map_or(true, |expr| expr.is_constant())
             }
+            Expression::ForLoop { .. } => false,
+            Expression::ModelOperation { .. } => false,
+            Expression::Break | Expression::Continue => true,
             // TODO:  detect constant property within layouts
             Expression::LayoutCacheAccess { .. } => false,
             Expression::ComputeLayoutInfo(..) => false,
@@ -1113,6 +1421,19 @@ pub fn maybe_convert_to(
             || ty == Type::Invalid
         {
             self
+        } else if let (Type::Optional(inner), true) = (&target_type, ty.can_convert(&target_type)) {
+            if matches!(ty, Type::Optional(_)) {
+                // The `null` literal
+                return Expression::NullLiteral(target_type);
+            }
+            let value = self.maybe_convert_to((**inner).clone(), node, diag);
+            Expression::Cast { from: Box::new(value), to: target_type }
+        } else if let (Type::ComponentFactory(_), Type::ComponentFactory(_), true) =
+            (&ty, &target_type, ty.can_convert(&target_type))
+        {
+            // All component factories have the same representation, and the interpreter checks
+            // the interface again when the factory creates a component
+            self
         } else if ty.can_convert(&target_type) {
             let from = match (ty, &target_type) {
                 (Type::Percent, Type::Float32) => Expression::BinaryExpression {
@@ -1281,7 +1602,9 @@ pub fn default_value_for_type(ty: &Type) -> Expression {
         match ty {
             Type::Invalid
             | Type::Callback { .. }
-            | Type::ComponentFactory
+            | Type::ComponentFactory(_)
+            | Type::Interface(_)
+            | Type::TypeParameter(_)
             | Type::Function { .. }
             | Type::InferredProperty
             | Type::InferredCallback
@@ -1311,6 +1634,7 @@ pub fn default_value_for_type(ty: &Type) -> Expression {
             Type::Array(element_ty) => {
                 Expression::Array { element_ty: (**element_ty).clone(), values: vec![] }
             }
+            Type::Optional(_) => Expression::NullLiteral(ty.clone()),
             Type::Struct { fields, .. } => Expression::Struct {
                 ty: ty.clone(),
                 values: fields
@@ -1556,6 +1880,27 @@ pub enum EasingCurve {
     // Custom(Box<dyn Fn(f32)->f32>),
 }
 
+/// The shape of a `@radial-gradient`
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum RadialGradientShape {
+    Circle,
+    #[default]
+    Ellipse,
+}
+
+/// The size of the ending shape of a `@radial-gradient`, relative to the box of the element
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum RadialGradientExtent {
+    ClosestSide,
+    FarthestSide,
+    ClosestCorner,
+    #[default]
+    FarthestCorner,
+    /// `@radial-gradient(circle, ...)` without size or position: the radius is the average of
+    /// half the width and half the height
+    AverageSide,
+}
+
 // The compiler generates ResourceReference::AbsolutePath for all references like @image-url("foo.png")
 // and the resource lowering path may change this to EmbeddedData if configured.
 #[derive(Clone, Debug)]
@@ -1564,6 +1909,7 @@ pub enum ImageReference {
     AbsolutePath(String),
     EmbeddedData { resource_id: usize, extension: String },
     EmbeddedTexture { resource_id: usize },
+    EmbeddedAnimation { resource_id: usize },
 }
 
 /// Print the expression as a .slint code (not necessarily valid .slint)
@@ -1574,6 +1920,7 @@ pub fn pretty_print(f: &mut dyn std::fmt::Write, expression: &Expression) -> std
         Expression::StringLiteral(s) => write!(f, "{:?}", s),
         Expression::NumberLiteral(vl, unit) => write!(f, "{}{}", vl, unit),
         Expression::BoolLiteral(b) => write!(f, "{:?}", b),
+        Expression::NullLiteral(_) => write!(f, "null"),
         Expression::CallbackReference(a, _) => write!(f, "{:?}", a),
         Expression::PropertyReference(a) => write!(f, "{:?}", a),
         Expression::FunctionReference(a, _) => write!(f, "{:?}", a),
@@ -1659,6 +2006,17 @@ pub fn pretty_print(f: &mut dyn std::fmt::Write, expression: &Expression) -> std
             pretty_print(f, false_expr)?;
             write!(f, " }}")
         }
+        Expression::NullCoalescing { lhs, rhs } => {
+            pretty_print(f, lhs)?;
+            write!(f, " ?? ")?;
+            pretty_print(f, rhs)
+        }
+        Expression::OptionalChaining { optional, variable, body } => {
+            pretty_print(f, optional)?;
+            write!(f, "?.({variable} => ")?;
+            pretty_print(f, body)?;
+            write!(f, ")")
+        }
         Expression::Array { element_ty: _, values } => {
             write!(f, "[")?;
             for e in values {
@@ -1689,8 +2047,26 @@ pub fn pretty_print(f: &mut dyn std::fmt::Write, expression: &Expression) -> std
             }
             write!(f, ")")
         }
-        Expression::RadialGradient { stops } => {
-            write!(f, "@radial-gradient(circle")?;
+        Expression::RadialGradient { shape, extent, center_x, center_y, stops } => {
+            write!(f, "@radial-gradient({shape:?} {extent:?} at ")?;
+            pretty_print(f, center_x)?;
+            write!(f, " ")?;
+            pretty_print(f, center_y)?;
+            for (c, s) in stops {
+                write!(f, ", ")?;
+                pretty_print(f, c)?;
+                write!(f, "  ")?;
+                pretty_print(f, s)?;
+            }
+            write!(f, ")")
+        }
+        Expression::ConicGradient { from_angle, center_x, center_y, stops } => {
+            write!(f, "@conic-gradient(from ")?;
+            pretty_print(f, from_angle)?;
+            write!(f, " at ")?;
+            pretty_print(f, center_x)?;
+            write!(f, " ")?;
+            pretty_print(f, center_y)?;
             for (c, s) in stops {
                 write!(f, ", ")?;
                 pretty_print(f, c)?;
@@ -1707,6 +2083,38 @@ pub fn pretty_print(f: &mut dyn std::fmt::Write, expression: &Expression) -> std
             write!(f, "return ")?;
             e.as_ref().map(|e| pretty_print(f, e)).unwrap_or(Ok(()))
         }
+        Expression::ForLoop { variable, model, body } => {
+            write!(f, "for {variable} in ")?;
+            pretty_print(f, model)?;
+            write!(f, " ")?;
+            pretty_print(f, body)
+        }
+        Expression::Break => write!(f, "break"),
+        Expression::Continue => write!(f, "continue"),
+        Expression::ModelOperation { model, operation } => {
+            pretty_print(f, model)?;
+            match operation {
+                ModelOperation::Filter { variable, body } => {
+                    write!(f, ".filter({variable} => ")?;
+                    pretty_print(f, body)?;
+                }
+                ModelOperation::Map { variable, body } => {
+                    write!(f, ".map({variable} => ")?;
+                    pretty_print(f, body)?;
+                }
+                ModelOperation::SortBy { variable, body } => {
+                    write!(f, ".sort-by({variable} => ")?;
+                    pretty_print(f, body)?;
+                }
+                ModelOperation::Slice { start, end } => {
+                    write!(f, ".slice(")?;
+                    pretty_print(f, start)?;
+                    write!(f, ", ")?;
+                    pretty_print(f, end)?;
+                }
+            }
+            write!(f, ")")
+        }
         Expression::LayoutCacheAccess { layout_cache_prop, index, repeater_index } => {
             write!(
                 f,
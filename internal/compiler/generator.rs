@@ -542,8 +542,44 @@ pub fn to_kebab_case(str: &str) -> String {
     String::from_utf8(result).unwrap()
 }
 
+/// Convert the name of an instance of a generic component or struct, such as `Pair<int, [string]>`,
+/// to a name that can be used as an identifier, such as `Pair_int_array_string`.
+/// Other names are returned unchanged.
+pub fn mangle_generic_name(name: &str) -> std::borrow::Cow<'_, str> {
+    if !name.contains('<') {
+        return name.into();
+    }
+    fn push_separator(result: &mut String) {
+        if !result.is_empty() && !result.ends_with('_') {
+            result.push('_');
+        }
+    }
+    let mut result = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_alphanumeric() || c == '-' {
+            result.push(c);
+        } else {
+            if c == '[' {
+                push_separator(&mut result);
+                result.push_str("array");
+            }
+            push_separator(&mut result);
+        }
+    }
+    result.truncate(result.trim_end_matches('_').len());
+    result.into()
+}
+
 #[test]
 fn case_conversions() {
     assert_eq!(to_kebab_case("HelloWorld"), "hello-world");
     assert_eq!(to_pascal_case("hello-world"), "HelloWorld");
 }
+
+#[test]
+fn generic_name_mangling() {
+    assert_eq!(mangle_generic_name("Foo"), "Foo");
+    assert_eq!(mangle_generic_name("Pair<int, string>"), "Pair_int_string");
+    assert_eq!(mangle_generic_name("Pair<int, [string]>"), "Pair_int_array_string");
+    assert_eq!(mangle_generic_name("Picker<Pair<int, int>>_root-1"), "Picker_Pair_int_int_root-1");
+}
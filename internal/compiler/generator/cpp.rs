@@ -15,10 +15,11 @@ pub struct Config {
 }
 
 fn ident(ident: &str) -> String {
+    let ident = crate::generator::mangle_generic_name(ident);
     if ident.contains('-') {
         ident.replace('-', "_")
     } else {
-        ident.into()
+        ident.into_owned()
     }
 }
 
@@ -386,6 +387,7 @@ fn cpp_type(&self) -> Option<String> {
             }
 
             Type::Array(i) => Some(format!("std::shared_ptr<slint::Model<{}>>", i.cpp_type()?)),
+            Type::Optional(i) => Some(format!("std::optional<{}>", i.cpp_type()?)),
             Type::Image => Some("slint::Image".to_owned()),
             Type::Enumeration(enumeration) => {
                 if enumeration.node.is_some() {
@@ -570,60 +572,29 @@ pub fn generate(doc: &Document, config: Config) -> impl std::fmt::Display {
                 }));
             }
             #[cfg(feature = "software-renderer")]
-            crate::embedded_resources::EmbeddedResourcesKind::TextureData(
-                crate::embedded_resources::Texture {
-                    data,
-                    format,
-                    rect,
-                    total_size: crate::embedded_resources::Size { width, height },
-                    original_size:
-                        crate::embedded_resources::Size {
-                            width: unscaled_width,
-                            height: unscaled_height,
-                        },
-                },
-            ) => {
-                let (r_x, r_y, r_w, r_h) = (rect.x(), rect.y(), rect.width(), rect.height());
-                let color =
-                    if let crate::embedded_resources::PixelFormat::AlphaMap([r, g, b]) = format {
-                        format!("slint::Color::from_rgb_uint8({r}, {g}, {b})")
-                    } else {
-                        "slint::Color{}".to_string()
-                    };
-                let count = data.len();
-                let data = data.iter().map(ToString::to_string).join(", ");
-                let data_name = format!("slint_embedded_resource_{}_data", er.id);
-                file.declarations.push(Declaration::Var(Var {
-                    ty: "inline const uint8_t".into(),
-                    name: data_name.clone(),
-                    array_size: Some(count),
-                    init: Some(format!("{{ {data} }}")),
-                }));
-                let texture_name = format!("slint_embedded_resource_{}_texture", er.id);
-                file.declarations.push(Declaration::Var(Var {
-                    ty: "inline const slint::cbindgen_private::types::StaticTexture".into(),
-                    name: texture_name.clone(),
-                    array_size: None,
-                    init: Some(format!(
-                        "{{
-                            .rect = {{ {r_x}, {r_y}, {r_w}, {r_h} }},
-                            .format = slint::cbindgen_private::types::PixelFormat::{format},
-                            .color = {color},
-                            .index = 0,
-                            }}"
-                    )),
-                }));
-                let init = format!("slint::cbindgen_private::types::StaticTextures {{
-                        .size = {{ {width}, {height} }},
-                        .original_size = {{ {unscaled_width}, {unscaled_height} }},
-                        .data = slint::cbindgen_private::Slice<uint8_t>{{  {data_name} , {count} }},
-                        .textures = slint::cbindgen_private::Slice<slint::cbindgen_private::types::StaticTexture>{{ &{texture_name}, 1 }}
-                    }}");
+            crate::embedded_resources::EmbeddedResourcesKind::TextureData(texture) => {
+                generate_static_textures(
+                    &mut file,
+                    &format!("slint_embedded_resource_{}", er.id),
+                    texture,
+                );
+            }
+            #[cfg(feature = "software-renderer")]
+            crate::embedded_resources::EmbeddedResourcesKind::AnimatedTextureData(frames) => {
+                let frames = frames
+                    .iter()
+                    .enumerate()
+                    .map(|(i, frame)| {
+                        let frame_name = format!("slint_embedded_resource_{}_frame_{i}", er.id);
+                        generate_static_textures(&mut file, &frame_name, &frame.texture);
+                        format!("{{ &{frame_name}, {} }}", frame.duration)
+                    })
+                    .collect::<Vec<_>>();
                 file.declarations.push(Declaration::Var(Var {
-                    ty: "inline const slint::cbindgen_private::types::StaticTextures".into(),
+                    ty: "inline const slint::cbindgen_private::types::StaticAnimationFrame".into(),
                     name: format!("slint_embedded_resource_{}", er.id),
-                    array_size: None,
-                    init: Some(init),
+                    array_size: Some(frames.len()),
+                    init: Some(format!("{{ {} }}", frames.join(", "))),
                 }))
             }
             #[cfg(feature = "software-renderer")]
@@ -825,6 +796,63 @@ pub fn generate(doc: &Document, config: Config) -> impl std::fmt::Display {
     file
 }
 
+#[cfg(feature = "software-renderer")]
+fn generate_static_textures(
+    file: &mut File,
+    name: &str,
+    texture: &crate::embedded_resources::Texture,
+) {
+    let crate::embedded_resources::Texture {
+        data,
+        format,
+        rect,
+        total_size: crate::embedded_resources::Size { width, height },
+        original_size:
+            crate::embedded_resources::Size { width: unscaled_width, height: unscaled_height },
+    } = texture;
+    let (r_x, r_y, r_w, r_h) = (rect.x(), rect.y(), rect.width(), rect.height());
+    let color = if let crate::embedded_resources::PixelFormat::AlphaMap([r, g, b]) = format {
+        format!("slint::Color::from_rgb_uint8({r}, {g}, {b})")
+    } else {
+        "slint::Color{}".to_string()
+    };
+    let count = data.len();
+    let data = data.iter().map(ToString::to_string).join(", ");
+    let data_name = format!("{name}_data");
+    file.declarations.push(Declaration::Var(Var {
+        ty: "inline const uint8_t".into(),
+        name: data_name.clone(),
+        array_size: Some(count),
+        init: Some(format!("{{ {data} }}")),
+    }));
+    let texture_name = format!("{name}_texture");
+    file.declarations.push(Declaration::Var(Var {
+        ty: "inline const slint::cbindgen_private::types::StaticTexture".into(),
+        name: texture_name.clone(),
+        array_size: None,
+        init: Some(format!(
+            "{{
+                .rect = {{ {r_x}, {r_y}, {r_w}, {r_h} }},
+                .format = slint::cbindgen_private::types::PixelFormat::{format},
+                .color = {color},
+                .index = 0,
+                }}"
+        )),
+    }));
+    let init = format!("slint::cbindgen_private::types::StaticTextures {{
+            .size = {{ {width}, {height} }},
+            .original_size = {{ {unscaled_width}, {unscaled_height} }},
+            .data = slint::cbindgen_private::Slice<uint8_t>{{  {data_name} , {count} }},
+            .textures = slint::cbindgen_private::Slice<slint::cbindgen_private::types::StaticTexture>{{ &{texture_name}, 1 }}
+        }}");
+    file.declarations.push(Declaration::Var(Var {
+        ty: "inline const slint::cbindgen_private::types::StaticTextures".into(),
+        name: name.into(),
+        array_size: None,
+        init: Some(init),
+    }))
+}
+
 fn generate_struct(
     file: &mut File,
     name: &str,
@@ -2642,6 +2670,7 @@ fn compile_expression(expr: &llr::Expression, ctx: &EvaluationContext) -> String
             }
         }
         Expression::BoolLiteral(b) => b.to_string(),
+        Expression::NullLiteral(ty) => format!("{}()", ty.cpp_type().unwrap()),
         Expression::PropertyReference(nr) => {
             let access = access_member(nr, ctx);
             format!(r#"{}.get()"#, access)
@@ -2707,6 +2736,9 @@ fn compile_expression(expr: &llr::Expression, ctx: &EvaluationContext) -> String
                 (Type::Brush, Type::Color) => {
                     format!("{}.color()", f)
                 }
+                (_, Type::Optional(_)) => {
+                    format!("{}({})", to.cpp_type().unwrap(), f)
+                }
                 (Type::Struct { .. }, Type::Struct{ fields, name: Some(_), ..}) => {
                     format!(
                         "[&](const auto &o){{ {struct_name} s; {fields} return s; }}({obj})",
@@ -2867,6 +2899,9 @@ fn compile_expression(expr: &llr::Expression, ctx: &EvaluationContext) -> String
                 crate::expression_tree::ImageReference::EmbeddedTexture{resource_id} => {
                     format!("slint::private_api::image_from_embedded_textures(&slint_embedded_resource_{resource_id})")
                 },
+                crate::expression_tree::ImageReference::EmbeddedAnimation{resource_id} => {
+                    format!("slint::private_api::image_from_embedded_animation({{ slint_embedded_resource_{resource_id}, std::size(slint_embedded_resource_{resource_id}) }})")
+                },
             };
             match &nine_slice {
                 Some([a, b, c, d]) => {
@@ -2889,6 +2924,25 @@ fn compile_expression(expr: &llr::Expression, ctx: &EvaluationContext) -> String
                 false_code
             )
         }
+        Expression::NullCoalescing { lhs, rhs } => {
+            let ty = expr.ty(ctx);
+            let lhs_code = compile_expression(lhs, ctx);
+            let rhs_code = compile_expression(rhs, ctx);
+            let value = if matches!(ty, Type::Optional(_)) { "optional" } else { "*optional" };
+            format!(
+                "[&]() -> {ty} {{ if (auto optional = {lhs_code}) return {value}; return {rhs_code}; }}()",
+                ty = ty.cpp_type().unwrap(),
+            )
+        }
+        Expression::OptionalChaining { optional, variable, body } => {
+            let ty = expr.ty(ctx).cpp_type().unwrap();
+            let optional_code = compile_expression(optional, ctx);
+            let variable = ident(variable);
+            let body_code = compile_expression(body, ctx);
+            format!(
+                "[&]() -> {ty} {{ const auto optional = {optional_code}; if (!optional) return {ty}(); const auto &{variable} = *optional; return {body_code}; }}()"
+            )
+        }
         Expression::Array { element_ty, values, as_model } => {
             let ty = element_ty.cpp_type().unwrap();
             let mut val = values.iter().map(|e| format!("{ty} ( {expr} )", expr = compile_expression(e, ctx), ty = ty));
@@ -2956,15 +3010,31 @@ fn compile_expression(expr: &llr::Expression, ctx: &EvaluationContext) -> String
                 stops_it.join(", "), angle, stops.len()
             )
         }
-        Expression::RadialGradient{ stops} => {
+        Expression::RadialGradient{ shape, extent, center_x, center_y, stops } => {
+            let center_x = compile_expression(center_x, ctx);
+            let center_y = compile_expression(center_y, ctx);
+            let mut stops_it = stops.iter().map(|(color, stop)| {
+                let color = compile_expression(color, ctx);
+                let position = compile_expression(stop, ctx);
+                format!("slint::private_api::GradientStop{{ {}, {}, }}", color, position)
+            });
+            format!(
+                "[&] {{ const slint::private_api::GradientStop stops[] = {{ {} }}; return slint::Brush(slint::private_api::RadialGradientBrush(slint::cbindgen_private::types::RadialGradientShape::{:?}, slint::cbindgen_private::types::RadialGradientExtent::{:?}, {}, {}, stops, {})); }}()",
+                stops_it.join(", "), shape, extent, center_x, center_y, stops.len()
+            )
+        }
+        Expression::ConicGradient{ from_angle, center_x, center_y, stops } => {
+            let from_angle = compile_expression(from_angle, ctx);
+            let center_x = compile_expression(center_x, ctx);
+            let center_y = compile_expression(center_y, ctx);
             let mut stops_it = stops.iter().map(|(color, stop)| {
                 let color = compile_expression(color, ctx);
                 let position = compile_expression(stop, ctx);
                 format!("slint::private_api::GradientStop{{ {}, {}, }}", color, position)
             });
             format!(
-                "[&] {{ const slint::private_api::GradientStop stops[] = {{ {} }}; return slint::Brush(slint::private_api::RadialGradientBrush(stops, {})); }}()",
-                stops_it.join(", "), stops.len()
+                "[&] {{ const slint::private_api::GradientStop stops[] = {{ {} }}; return slint::Brush(slint::private_api::ConicGradientBrush({}, {}, {}, stops, {})); }}()",
+                stops_it.join(", "), from_angle, center_x, center_y, stops.len()
             )
         }
         Expression::EnumerationValue(value) => {
@@ -3029,6 +3099,83 @@ fn compile_expression(expr: &llr::Expression, ctx: &EvaluationContext) -> String
                 rhs_code = rhs_code
             )
         }
+        Expression::ForLoop { variable, model, body } => {
+            let variable = ident(variable);
+            let model_code = compile_expression(model, ctx);
+            let body_code = compile_expression(body, ctx);
+            // When the body is a struct, the loop evaluates to the struct that stopped it
+            let (result_init, body_code, result) = match body.ty(ctx) {
+                ty @ Type::Struct { .. } => {
+                    let condition = compile_expression(
+                        &llr::Expression::StructFieldAccess {
+                            base: Box::new(llr::Expression::ReadLocalVariable {
+                                name: "loop_result".into(),
+                                ty: ty.clone(),
+                            }),
+                            name: "condition".into(),
+                        },
+                        ctx,
+                    );
+                    (
+                        format!("{} result{{}};", ty.cpp_type().unwrap()),
+                        format!("auto loop_result = {body_code}; if (!({condition})) {{ result = loop_result; break; }}"),
+                        "return result;".to_owned(),
+                    )
+                }
+                _ => (String::new(), format!("if (!({body_code})) break;"), String::new()),
+            };
+            if matches!(model.ty(ctx), Type::Array(_)) {
+                format!(
+                    "[&]{{ const auto model = {model_code}; {result_init} for (long int index = 0, count = slint::private_api::model_length(model); index < count; ++index) {{ auto {variable} = slint::private_api::access_array_index(model, index); {body_code} }} {result} }}()"
+                )
+            } else {
+                format!(
+                    "[&]{{ {result_init} for (int {variable} = 0, count = static_cast<int>({model_code}); {variable} < count; ++{variable}) {{ {body_code} }} {result} }}()"
+                )
+            }
+        }
+        Expression::ModelOperation { model, operation } => {
+            let model_code = compile_expression(model, ctx);
+            let Type::Array(row_ty) = model.ty(ctx) else {
+                panic!("Expression::ModelOperation's model is not an array")
+            };
+            let row_ty = row_ty.cpp_type().unwrap();
+            let model_ty = format!("std::shared_ptr<slint::Model<{row_ty}>>");
+            // The adapters need a source model, even if the model is empty
+            let source = format!("{model_ty} model = {model_code}; if (!model) model = std::make_shared<slint::VectorModel<{row_ty}>>();");
+            match operation {
+                llr::ModelOperation::Filter { variable, body } => {
+                    let variable = ident(variable);
+                    let body_code = compile_expression(body, ctx);
+                    format!(
+                        "[&]() -> {model_ty} {{ {source} return std::make_shared<slint::FilterModel<{row_ty}>>(model, [=](const {row_ty} &{variable}) -> bool {{ return {body_code}; }}); }}()"
+                    )
+                }
+                llr::ModelOperation::Map { variable, body } => {
+                    let mapped_ty = body.ty(ctx).cpp_type().unwrap();
+                    let variable = ident(variable);
+                    let body_code = compile_expression(body, ctx);
+                    format!(
+                        "[&]() -> std::shared_ptr<slint::Model<{mapped_ty}>> {{ {source} return std::make_shared<slint::MapModel<{row_ty}, {mapped_ty}>>(model, [=](const {row_ty} &{variable}) -> {mapped_ty} {{ return {body_code}; }}); }}()"
+                    )
+                }
+                llr::ModelOperation::SortBy { variable, body } => {
+                    let key_ty = body.ty(ctx).cpp_type().unwrap();
+                    let variable = ident(variable);
+                    let body_code = compile_expression(body, ctx);
+                    format!(
+                        "[&]() -> {model_ty} {{ {source} auto key = [=](const {row_ty} &{variable}) -> {key_ty} {{ return {body_code}; }}; return std::make_shared<slint::SortModel<{row_ty}>>(model, [key](const {row_ty} &a, const {row_ty} &b) {{ return key(a) < key(b); }}); }}()"
+                    )
+                }
+                llr::ModelOperation::Slice { start, end } => {
+                    let start = compile_expression(start, ctx);
+                    let end = compile_expression(end, ctx);
+                    format!(
+                        "[&]() -> {model_ty} {{ const {model_ty} model = {model_code}; int count = slint::private_api::model_length(model); int start = std::clamp(int({start}), 0, count); int end = std::clamp(int({end}), start, count); std::vector<{row_ty}> rows; for (int i = start; i < end; ++i) rows.push_back(slint::private_api::access_array_index(model, i)); return std::make_shared<slint::VectorModel<{row_ty}>>(std::move(rows)); }}()"
+                    )
+                }
+            }
+        }
     }
 }
 
@@ -3149,6 +3296,61 @@ fn compile_builtin_function_call(
             ctx.generator_state.conditional_includes.cstdlib.set(true);
             format!("[](const auto &a){{ float res = 0; slint::cbindgen_private::slint_string_to_float(&a, &res); return res; }}({})", a.next().unwrap())
         }
+        BuiltinFunction::StringToInt => {
+            format!("[](const auto &a){{ int res = 0; slint::cbindgen_private::slint_string_to_int(&a, &res); return res; }}({})", a.next().unwrap())
+        }
+        BuiltinFunction::StringLength => {
+            format!("[](const auto &a){{ return slint::cbindgen_private::slint_shared_string_length(&a); }}({})", a.next().unwrap())
+        }
+        BuiltinFunction::StringIsEmpty => {
+            format!("{}.empty()", a.next().unwrap())
+        }
+        BuiltinFunction::StringToUppercase
+        | BuiltinFunction::StringToLowercase
+        | BuiltinFunction::StringTrim => {
+            let f = match function {
+                BuiltinFunction::StringToUppercase => "to_uppercase",
+                BuiltinFunction::StringToLowercase => "to_lowercase",
+                _ => "trim",
+            };
+            format!("[](const auto &a){{ slint::SharedString res; slint::cbindgen_private::slint_shared_string_{f}(&a, &res); return res; }}({})", a.next().unwrap())
+        }
+        BuiltinFunction::StringContains
+        | BuiltinFunction::StringStartsWith
+        | BuiltinFunction::StringEndsWith => {
+            let f = match function {
+                BuiltinFunction::StringContains => "contains",
+                BuiltinFunction::StringStartsWith => "starts_with",
+                _ => "ends_with",
+            };
+            let (s, other) = (a.next().unwrap(), a.next().unwrap());
+            format!("[](const auto &a, const auto &b){{ return slint::cbindgen_private::slint_shared_string_{f}(&a, &b); }}({s}, {other})")
+        }
+        BuiltinFunction::StringReplace => {
+            let (s, from, to) = (a.next().unwrap(), a.next().unwrap(), a.next().unwrap());
+            format!("[](const auto &a, const auto &from, const auto &to){{ slint::SharedString res; slint::cbindgen_private::slint_shared_string_replace(&a, &from, &to, &res); return res; }}({s}, {from}, {to})")
+        }
+        BuiltinFunction::StringSplit => {
+            let (s, separator) = (a.next().unwrap(), a.next().unwrap());
+            format!("[](const auto &a, const auto &separator){{ slint::SharedVector<slint::SharedString> res; slint::cbindgen_private::slint_shared_string_split(&a, &separator, &res); return std::make_shared<slint::VectorModel<slint::SharedString>>(std::vector<slint::SharedString>(res.begin(), res.end())); }}({s}, {separator})")
+        }
+        BuiltinFunction::StringSubstring => {
+            let (s, start, length) = (a.next().unwrap(), a.next().unwrap(), a.next().unwrap());
+            format!("[](const auto &a, int start, int length){{ slint::SharedString res; slint::cbindgen_private::slint_shared_string_substring(&a, start, length, &res); return res; }}({s}, {start}, {length})")
+        }
+        BuiltinFunction::StringCharAt => {
+            let (s, index) = (a.next().unwrap(), a.next().unwrap());
+            format!("[](const auto &a, int index){{ slint::SharedString res; slint::cbindgen_private::slint_shared_string_char_at(&a, index, &res); return res; }}({s}, {index})")
+        }
+        BuiltinFunction::NumberToFixed | BuiltinFunction::NumberFormat => {
+            let (value, decimals) = (a.next().unwrap(), a.next().unwrap());
+            let grouping = function == BuiltinFunction::NumberFormat;
+            format!("[](double value, int decimals){{ slint::SharedString res; slint::cbindgen_private::slint_format_number(value, decimals, {grouping}, &res); return res; }}({value}, {decimals})")
+        }
+        BuiltinFunction::NumberToPadded => {
+            let (value, digits) = (a.next().unwrap(), a.next().unwrap());
+            format!("[](double value, int digits){{ slint::SharedString res; slint::cbindgen_private::slint_format_padded_number(value, digits, &res); return res; }}({value}, {digits})")
+        }
         BuiltinFunction::ColorRgbaStruct => {
             format!("{}.to_argb_uint()", a.next().unwrap())
         }
@@ -3176,6 +3378,29 @@ fn compile_builtin_function_call(
         BuiltinFunction::ArrayLength => {
             format!("slint::private_api::model_length({})", a.next().unwrap())
         }
+        BuiltinFunction::ArrayIndexOf | BuiltinFunction::ArrayContains => {
+            let value_ty = arguments[1].ty(ctx).cpp_type().unwrap();
+            let (found, not_found) = match function {
+                BuiltinFunction::ArrayIndexOf => ("int(i)", "-1"),
+                _ => ("true", "false"),
+            };
+            format!(
+                "[](const auto &model, const {value_ty} &value) {{ for (long int i = 0, count = slint::private_api::model_length(model); i < count; ++i) {{ if (slint::private_api::access_array_index(model, i) == value) return {found}; }} return {not_found}; }}({model}, {value})",
+                model = a.next().unwrap(),
+                value = a.next().unwrap(),
+            )
+        }
+        BuiltinFunction::ArraySum | BuiltinFunction::ArrayMin | BuiltinFunction::ArrayMax => {
+            let accumulate = match function {
+                BuiltinFunction::ArraySum => "result += row;",
+                BuiltinFunction::ArrayMin => "result = i == 0 ? row : std::min(result, row);",
+                _ => "result = i == 0 ? row : std::max(result, row);",
+            };
+            format!(
+                "[](const auto &model) {{ float result = 0; for (long int i = 0, count = slint::private_api::model_length(model); i < count; ++i) {{ float row = slint::private_api::access_array_index(model, i); {accumulate} }} return result; }}({model})",
+                model = a.next().unwrap(),
+            )
+        }
         BuiltinFunction::Rgb => {
             format!("slint::Color::from_argb_uint8(std::clamp(static_cast<float>({a}) * 255., 0., 255.), std::clamp(static_cast<int>({r}), 0, 255), std::clamp(static_cast<int>({g}), 0, 255), std::clamp(static_cast<int>({b}), 0, 255))",
                 r = a.next().unwrap(),
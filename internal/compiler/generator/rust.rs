@@ -31,6 +31,7 @@
 type ParentCtx<'a> = llr_ParentCtx<'a, TokenStream>;
 
 fn ident(ident: &str) -> proc_macro2::Ident {
+    let ident = crate::generator::mangle_generic_name(ident);
     if ident.contains('-') {
         format_ident!("r#{}", ident.replace('-', "_"))
     } else {
@@ -74,7 +75,7 @@ fn rust_primitive_type(ty: &Type) -> Option<proc_macro2::TokenStream> {
         Type::Float32 => Some(quote!(f32)),
         Type::String => Some(quote!(sp::SharedString)),
         Type::Color => Some(quote!(sp::Color)),
-        Type::ComponentFactory => Some(quote!(slint::ComponentFactory)),
+        Type::ComponentFactory(_) => Some(quote!(slint::ComponentFactory)),
         Type::Duration => Some(quote!(i64)),
         Type::Angle => Some(quote!(f32)),
         Type::PhysicalLength => Some(quote!(sp::Coord)),
@@ -93,6 +94,10 @@ fn rust_primitive_type(ty: &Type) -> Option<proc_macro2::TokenStream> {
             let inner = rust_primitive_type(o)?;
             Some(quote!(sp::ModelRc<#inner>))
         }
+        Type::Optional(o) => {
+            let inner = rust_primitive_type(o)?;
+            Some(quote!(::core::option::Option<#inner>))
+        }
         Type::Enumeration(e) => {
             let i = ident(&e.name);
             if e.node.is_some() {
@@ -1975,6 +1980,10 @@ fn compile_expression(expr: &Expression, ctx: &EvaluationContext) -> TokenStream
         Expression::NumberLiteral(n) if n.is_finite() => quote!(#n),
         Expression::NumberLiteral(_) => quote!(0.),
         Expression::BoolLiteral(b) => quote!(#b),
+        Expression::NullLiteral(ty) => {
+            let ty = rust_primitive_type(ty).unwrap();
+            quote!(<#ty>::None)
+        }
         Expression::Cast { from, to } => {
             let f = compile_expression(from, ctx);
             match (from.ty(ctx), to) {
@@ -2046,6 +2055,13 @@ fn compile_expression(expr: &Expression, ctx: &EvaluationContext) -> TokenStream
                 (Type::String, Type::PathData) => {
                     quote!(sp::PathData::Commands(#f))
                 }
+                (_, Type::Optional(inner)) if inner.as_unit_product().is_some() => {
+                    let inner = rust_primitive_type(inner).unwrap();
+                    quote!(::core::option::Option::Some((#f) as #inner))
+                }
+                (_, Type::Optional(_)) => {
+                    quote!(::core::option::Option::Some(#f))
+                }
                 _ => f,
             }
         }
@@ -2225,6 +2241,12 @@ fn compile_expression(expr: &Expression, ctx: &EvaluationContext) -> TokenStream
                         sp::Image::from(sp::ImageInner::StaticTextures(&#symbol))
                     )
                 }
+                crate::expression_tree::ImageReference::EmbeddedAnimation { resource_id } => {
+                    let symbol = format_ident!("SLINT_EMBEDDED_RESOURCE_{}", resource_id);
+                    quote!(
+                        sp::image_from_embedded_animation(sp::Slice::from_slice(&#symbol))
+                    )
+                }
             };
             match &nine_slice {
                 Some([a, b, c, d]) => {
@@ -2246,6 +2268,33 @@ fn compile_expression(expr: &Expression, ctx: &EvaluationContext) -> TokenStream
                 }
             )
         }
+        Expression::NullCoalescing { lhs, rhs } => {
+            let lhs_code = compile_expression(lhs, ctx);
+            let mut rhs_code = compile_expression(rhs, ctx);
+            let ty = rhs.ty(ctx);
+            let value = if matches!(ty, Type::Optional(_)) {
+                quote!(::core::option::Option::Some(value))
+            } else {
+                if ty.as_unit_product().is_some() {
+                    let ty = rust_primitive_type(&ty).unwrap();
+                    rhs_code = quote!((#rhs_code) as #ty);
+                }
+                quote!(value)
+            };
+            quote!(match #lhs_code {
+                ::core::option::Option::Some(value) => #value,
+                ::core::option::Option::None => #rhs_code,
+            })
+        }
+        Expression::OptionalChaining { optional, variable, body } => {
+            let optional = compile_expression(optional, ctx);
+            let variable = ident(variable);
+            let body = compile_expression(body, ctx);
+            quote!(match #optional {
+                ::core::option::Option::Some(#variable) => #body,
+                ::core::option::Option::None => ::core::option::Option::None,
+            })
+        }
         Expression::Array { values, element_ty, as_model } => {
             let val = values.iter().map(|e| compile_expression(e, ctx));
             if *as_model {
@@ -2333,15 +2382,37 @@ fn compile_expression(expr: &Expression, ctx: &EvaluationContext) -> TokenStream
                 sp::LinearGradientBrush::new(#angle as _, [#(#stops),*])
             ))
         }
-        Expression::RadialGradient { stops } => {
+        Expression::RadialGradient { shape, extent, center_x, center_y, stops } => {
+            let shape = format_ident!("{:?}", shape);
+            let extent = format_ident!("{:?}", extent);
+            let center_x = compile_expression(center_x, ctx);
+            let center_y = compile_expression(center_y, ctx);
             let stops = stops.iter().map(|(color, stop)| {
                 let color = compile_expression(color, ctx);
                 let position = compile_expression(stop, ctx);
                 quote!(sp::GradientStop{ color: #color, position: #position as _ })
             });
-            quote!(slint::Brush::RadialGradient(
-                sp::RadialGradientBrush::new_circle([#(#stops),*])
-            ))
+            quote!(slint::Brush::RadialGradient(sp::RadialGradientBrush::new(
+                sp::RadialGradientShape::#shape,
+                sp::RadialGradientExtent::#extent,
+                sp::euclid::point2(#center_x as _, #center_y as _),
+                [#(#stops),*],
+            )))
+        }
+        Expression::ConicGradient { from_angle, center_x, center_y, stops } => {
+            let from_angle = compile_expression(from_angle, ctx);
+            let center_x = compile_expression(center_x, ctx);
+            let center_y = compile_expression(center_y, ctx);
+            let stops = stops.iter().map(|(color, stop)| {
+                let color = compile_expression(color, ctx);
+                let position = compile_expression(stop, ctx);
+                quote!(sp::GradientStop{ color: #color, position: #position as _ })
+            });
+            quote!(slint::Brush::ConicGradient(sp::ConicGradientBrush::new(
+                #from_angle as _,
+                sp::euclid::point2(#center_x as _, #center_y as _),
+                [#(#stops),*],
+            )))
         }
         Expression::EnumerationValue(value) => {
             let base_ident = ident(&value.enumeration.name);
@@ -2410,6 +2481,103 @@ fn compile_expression(expr: &Expression, ctx: &EvaluationContext) -> TokenStream
                 }
             }
         }
+        Expression::ForLoop { variable, model, body } => {
+            let variable = ident(variable);
+            let model_code = compile_expression(model, ctx);
+            let body_code = compile_expression(body, ctx);
+            // When the body is a struct, the loop evaluates to the struct that stopped it
+            let (result_init, body_code, result) = match body.ty(ctx) {
+                ty @ Type::Struct { .. } => {
+                    let condition = compile_expression(
+                        &Expression::StructFieldAccess {
+                            base: Box::new(Expression::ReadLocalVariable {
+                                name: "loop_result".into(),
+                                ty,
+                            }),
+                            name: "condition".into(),
+                        },
+                        ctx,
+                    );
+                    (
+                        quote!(let mut result = ::core::default::Default::default();),
+                        quote!(let loop_result = #body_code; if !(#condition) { result = loop_result; break; }),
+                        quote!(result),
+                    )
+                }
+                _ => (quote!(), quote!(if !(#body_code) { break; }), quote!()),
+            };
+            if matches!(model.ty(ctx), Type::Array(_)) {
+                quote!(match &#model_code { x => {
+                    x.model_tracker().track_row_count_changes();
+                    #result_init
+                    for index in 0..x.row_count() {
+                        let #variable = x.row_data_tracked(index).unwrap_or_default();
+                        #body_code
+                    }
+                    #result
+                }})
+            } else {
+                quote!({
+                    #result_init
+                    for #variable in 0..(#model_code as i32) {
+                        #body_code
+                    }
+                    #result
+                })
+            }
+        }
+        Expression::ModelOperation { model, operation } => {
+            let model_code = compile_expression(model, ctx);
+            let Type::Array(row_ty) = model.ty(ctx) else {
+                panic!("Expression::ModelOperation's model is not an array")
+            };
+            let row_ty = rust_primitive_type(&row_ty).unwrap();
+            match operation {
+                llr::ModelOperation::Filter { variable, body } => {
+                    let variable = ident(variable);
+                    let body = compile_expression(body, ctx);
+                    quote!(sp::ModelRc::new(sp::FilterModel::new(#model_code, move |row: &#row_ty| -> bool {
+                        let #variable = row.clone();
+                        (#body) as _
+                    })))
+                }
+                llr::ModelOperation::Map { variable, body } => {
+                    let mapped_ty = rust_primitive_type(&body.ty(ctx)).unwrap();
+                    let variable = ident(variable);
+                    let body = compile_expression(body, ctx);
+                    quote!(sp::ModelRc::new(sp::MapModel::new(#model_code, move |#variable: #row_ty| -> #mapped_ty {
+                        (#body) as _
+                    })))
+                }
+                llr::ModelOperation::SortBy { variable, body } => {
+                    let key_ty = rust_primitive_type(&body.ty(ctx)).unwrap();
+                    let variable = ident(variable);
+                    let body = compile_expression(body, ctx);
+                    quote!({
+                        let key = move |row: &#row_ty| -> #key_ty {
+                            let #variable = row.clone();
+                            (#body) as _
+                        };
+                        sp::ModelRc::new(sp::SortModel::new(#model_code, move |a, b| {
+                            key(a).partial_cmp(&key(b)).unwrap_or(::core::cmp::Ordering::Equal)
+                        }))
+                    })
+                }
+                llr::ModelOperation::Slice { start, end } => {
+                    let start = compile_expression(start, ctx);
+                    let end = compile_expression(end, ctx);
+                    quote!(match &#model_code { x => {
+                        x.model_tracker().track_row_count_changes();
+                        let count = x.row_count() as i32;
+                        let start = (#start as i32).clamp(0, count);
+                        let end = (#end as i32).clamp(start, count);
+                        sp::ModelRc::new(sp::VecModel::<#row_ty>::from(
+                            (start..end).map(|i| x.row_data_tracked(i as usize).unwrap_or_default()).collect::<sp::Vec<_>>()
+                        ))
+                    }})
+                }
+            }
+        }
     }
 }
 
@@ -2592,6 +2760,57 @@ fn compile_builtin_function_call(
             quote!(#(#a)*.as_str().parse::<f64>().unwrap_or_default())
         }
         BuiltinFunction::StringIsFloat => quote!(#(#a)*.as_str().parse::<f64>().is_ok()),
+        BuiltinFunction::StringToInt => {
+            quote!(#(#a)*.as_str().parse::<i32>().unwrap_or_default())
+        }
+        BuiltinFunction::StringLength => quote!(sp::string_functions::length(&#(#a)*)),
+        BuiltinFunction::StringIsEmpty => quote!(#(#a)*.is_empty()),
+        BuiltinFunction::StringToUppercase => {
+            quote!(sp::string_functions::to_uppercase(&#(#a)*))
+        }
+        BuiltinFunction::StringToLowercase => {
+            quote!(sp::string_functions::to_lowercase(&#(#a)*))
+        }
+        BuiltinFunction::StringTrim => quote!(sp::string_functions::trim(&#(#a)*)),
+        BuiltinFunction::StringContains => {
+            let (s, needle) = (a.next().unwrap(), a.next().unwrap());
+            quote!(sp::string_functions::contains(&#s, &#needle))
+        }
+        BuiltinFunction::StringStartsWith => {
+            let (s, prefix) = (a.next().unwrap(), a.next().unwrap());
+            quote!(sp::string_functions::starts_with(&#s, &#prefix))
+        }
+        BuiltinFunction::StringEndsWith => {
+            let (s, suffix) = (a.next().unwrap(), a.next().unwrap());
+            quote!(sp::string_functions::ends_with(&#s, &#suffix))
+        }
+        BuiltinFunction::StringReplace => {
+            let (s, from, to) = (a.next().unwrap(), a.next().unwrap(), a.next().unwrap());
+            quote!(sp::string_functions::replace(&#s, &#from, &#to))
+        }
+        BuiltinFunction::StringSplit => {
+            let (s, separator) = (a.next().unwrap(), a.next().unwrap());
+            quote!(sp::ModelRc::new(sp::SharedVectorModel::from(
+                sp::string_functions::split(&#s, &#separator)
+            )))
+        }
+        BuiltinFunction::StringSubstring => {
+            let (s, start, length) = (a.next().unwrap(), a.next().unwrap(), a.next().unwrap());
+            quote!(sp::string_functions::substring(&#s, #start as i32, #length as i32))
+        }
+        BuiltinFunction::StringCharAt => {
+            let (s, index) = (a.next().unwrap(), a.next().unwrap());
+            quote!(sp::string_functions::char_at(&#s, #index as i32))
+        }
+        BuiltinFunction::NumberToFixed | BuiltinFunction::NumberFormat => {
+            let (value, decimals) = (a.next().unwrap(), a.next().unwrap());
+            let grouping = function == BuiltinFunction::NumberFormat;
+            quote!(sp::format_number(#value as f64, #decimals as i32, #grouping))
+        }
+        BuiltinFunction::NumberToPadded => {
+            let (value, digits) = (a.next().unwrap(), a.next().unwrap());
+            quote!(sp::format_padded_number(#value as f64, #digits as i32))
+        }
         BuiltinFunction::ColorRgbaStruct => quote!( #(#a)*.to_argb_u8()),
         BuiltinFunction::ColorHsvaStruct => quote!( #(#a)*.to_hsva()),
         BuiltinFunction::ColorBrighter => {
@@ -2627,6 +2846,31 @@ fn compile_builtin_function_call(
                 x.row_count() as i32
             }})
         }
+        BuiltinFunction::ArrayIndexOf | BuiltinFunction::ArrayContains => {
+            let value_ty = rust_primitive_type(&arguments[1].ty(ctx)).unwrap();
+            let (model, value) = (a.next().unwrap(), a.next().unwrap());
+            let index = quote!(match &#model { x => {
+                let value: #value_ty = (#value) as _;
+                x.model_tracker().track_row_count_changes();
+                (0..x.row_count()).find(|i| x.row_data_tracked(*i).as_ref() == Some(&value))
+            }});
+            if matches!(function, BuiltinFunction::ArrayIndexOf) {
+                quote!(#index.map_or(-1, |i| i as i32))
+            } else {
+                quote!(#index.is_some())
+            }
+        }
+        BuiltinFunction::ArraySum | BuiltinFunction::ArrayMin | BuiltinFunction::ArrayMax => {
+            let reduce = match function {
+                BuiltinFunction::ArraySum => quote!(sum::<f64>()),
+                BuiltinFunction::ArrayMin => quote!(reduce(f64::min).unwrap_or_default()),
+                _ => quote!(reduce(f64::max).unwrap_or_default()),
+            };
+            quote!(match &#(#a)* { x => {
+                x.model_tracker().track_row_count_changes();
+                (0..x.row_count()).map(|i| x.row_data_tracked(i).unwrap_or_default() as f64).#reduce as f32
+            }})
+        }
 
         BuiltinFunction::Rgb => {
             let (r, g, b, a) =
@@ -2681,7 +2925,9 @@ fn compile_builtin_function_call(
 /// Return a TokenStream for a name (as in [`Type::Struct::name`])
 fn struct_name_to_tokens(name: &str) -> TokenStream {
     // the name match the C++ signature so we need to change that to the rust namespace
-    let mut name = name.replace("slint::private_api::", "sp::").replace('-', "_");
+    let mut name = crate::generator::mangle_generic_name(name)
+        .replace("slint::private_api::", "sp::")
+        .replace('-', "_");
     if !name.contains("::") {
         name.insert_str(0, "r#")
     }
@@ -2787,36 +3033,26 @@ fn generate_resources(doc: &Document) -> Vec<TokenStream> {
                     quote!(static #symbol: &'static [u8] = #data;)
                 }
                 #[cfg(feature = "software-renderer")]
-                crate::embedded_resources::EmbeddedResourcesKind::TextureData(crate::embedded_resources::Texture {
-                    data, format, rect,
-                    total_size: crate::embedded_resources::Size{width, height},
-                    original_size: crate::embedded_resources::Size{width: unscaled_width, height: unscaled_height},
-                }) => {
-                    let (r_x, r_y, r_w, r_h) = (rect.x(), rect.y(), rect.width(), rect.height());
-                    let color = if let crate::embedded_resources::PixelFormat::AlphaMap([r, g, b]) = format {
-                        quote!(sp::Color::from_rgb_u8(#r, #g, #b))
-                    } else {
-                        quote!(sp::Color::from_argb_encoded(0))
-                    };
+                crate::embedded_resources::EmbeddedResourcesKind::TextureData(texture) => {
                     let symbol_data = format_ident!("SLINT_EMBEDDED_RESOURCE_{}_DATA", er.id);
-                    let data_size = data.len();
+                    generate_static_textures(&symbol, &symbol_data, texture, &link_section)
+                },
+                #[cfg(feature = "software-renderer")]
+                crate::embedded_resources::EmbeddedResourcesKind::AnimatedTextureData(frames) => {
+                    let frame_count = frames.len();
+                    let (frame_statics, frames) : (Vec<_>, Vec<_>) = frames.iter().enumerate().map(|(i, frame)| {
+                        let frame_symbol = format_ident!("SLINT_EMBEDDED_RESOURCE_{}_FRAME_{}", er.id, i);
+                        let frame_symbol_data = format_ident!("SLINT_EMBEDDED_RESOURCE_{}_FRAME_{}_DATA", er.id, i);
+                        let duration = frame.duration;
+                        (
+                            generate_static_textures(&frame_symbol, &frame_symbol_data, &frame.texture, &link_section),
+                            quote!(sp::StaticAnimationFrame { textures: &#frame_symbol, duration: #duration }),
+                        )
+                    }).unzip();
                     quote!(
+                        #(#frame_statics)*
                         #link_section
-                        static #symbol_data : [u8; #data_size]= [#(#data),*];
-                        #link_section
-                        static #symbol: sp::StaticTextures = sp::StaticTextures{
-                            size: sp::IntSize::new(#width as _, #height as _),
-                            original_size: sp::IntSize::new(#unscaled_width as _, #unscaled_height as _),
-                            data: sp::Slice::from_slice(&#symbol_data),
-                            textures: sp::Slice::from_slice(&[
-                                sp::StaticTexture {
-                                    rect: sp::euclid::rect(#r_x as _, #r_y as _, #r_w as _, #r_h as _),
-                                    format: #format,
-                                    color: #color,
-                                    index: 0,
-                                }
-                            ])
-                        };
+                        static #symbol: [sp::StaticAnimationFrame; #frame_count] = [#(#frames),*];
                     )
                 },
                 #[cfg(feature = "software-renderer")]
@@ -2887,6 +3123,48 @@ fn generate_resources(doc: &Document) -> Vec<TokenStream> {
         .collect()
 }
 
+#[cfg(feature = "software-renderer")]
+fn generate_static_textures(
+    symbol: &Ident,
+    symbol_data: &Ident,
+    texture: &crate::embedded_resources::Texture,
+    link_section: &Option<TokenStream>,
+) -> TokenStream {
+    let crate::embedded_resources::Texture {
+        data,
+        format,
+        rect,
+        total_size: crate::embedded_resources::Size { width, height },
+        original_size:
+            crate::embedded_resources::Size { width: unscaled_width, height: unscaled_height },
+    } = texture;
+    let (r_x, r_y, r_w, r_h) = (rect.x(), rect.y(), rect.width(), rect.height());
+    let color = if let crate::embedded_resources::PixelFormat::AlphaMap([r, g, b]) = format {
+        quote!(sp::Color::from_rgb_u8(#r, #g, #b))
+    } else {
+        quote!(sp::Color::from_argb_encoded(0))
+    };
+    let data_size = data.len();
+    quote!(
+        #link_section
+        static #symbol_data : [u8; #data_size]= [#(#data),*];
+        #link_section
+        static #symbol: sp::StaticTextures = sp::StaticTextures{
+            size: sp::IntSize::new(#width as _, #height as _),
+            original_size: sp::IntSize::new(#unscaled_width as _, #unscaled_height as _),
+            data: sp::Slice::from_slice(&#symbol_data),
+            textures: sp::Slice::from_slice(&[
+                sp::StaticTexture {
+                    rect: sp::euclid::rect(#r_x as _, #r_y as _, #r_w as _, #r_h as _),
+                    format: #format,
+                    color: #color,
+                    index: 0,
+                }
+            ])
+        };
+    )
+}
+
 fn generate_named_exports(doc: &Document) -> Vec<TokenStream> {
     doc.exports
         .iter()
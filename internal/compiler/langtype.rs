@@ -34,7 +34,9 @@ pub enum Type {
         args: Vec<Type>,
     },
 
-    ComponentFactory,
+    /// A component factory. When the type was declared as `component-factory<Foo>`,
+    /// the components it creates must implement the `Foo` interface
+    ComponentFactory(Option<Rc<Interface>>),
 
     // Other property types:
     Float32,
@@ -56,6 +58,9 @@ pub enum Type {
     Brush,
     /// This is usually a model
     Array(Box<Type>),
+    /// A value of the inner type, or null.
+    /// The `null` literal, before it is converted, is an optional of [`Type::Void`]
+    Optional(Box<Type>),
     Struct {
         fields: BTreeMap<String, Type>,
         /// When declared in .slint as  `struct Foo := { }`, then the name is "Foo"
@@ -67,6 +72,13 @@ pub enum Type {
         rust_attributes: Option<Vec<String>>,
     },
     Enumeration(Rc<Enumeration>),
+    /// An interface declared with `interface Foo { ... }`. Can only be used in `implements`
+    /// or as the type argument of a `component-factory`
+    Interface(Rc<Interface>),
+    /// A type parameter of a generic component or struct, such as `T` in `struct Pair<T, U>`.
+    /// It only exists while checking the body of the generic, and can't be converted to or from
+    /// any other type.
+    TypeParameter(String),
 
     /// A type made up of the product of several "unit" types.
     /// The first parameter is the unit, and the second parameter is the power.
@@ -92,7 +104,9 @@ fn eq(&self, other: &Self) -> bool {
             Type::Function { return_type: lhs_rt, args: lhs_args } => {
                 matches!(other, Type::Function { return_type: rhs_rt, args: rhs_args } if lhs_rt == rhs_rt && lhs_args == rhs_args)
             }
-            Type::ComponentFactory => matches!(other, Type::ComponentFactory),
+            Type::ComponentFactory(a) => {
+                matches!(other, Type::ComponentFactory(b) if a.as_ref().map(Rc::as_ptr) == b.as_ref().map(Rc::as_ptr))
+            }
             Type::Float32 => matches!(other, Type::Float32),
             Type::Int32 => matches!(other, Type::Int32),
             Type::String => matches!(other, Type::String),
@@ -110,10 +124,13 @@ fn eq(&self, other: &Self) -> bool {
             Type::Easing => matches!(other, Type::Easing),
             Type::Brush => matches!(other, Type::Brush),
             Type::Array(a) => matches!(other, Type::Array(b) if a == b),
+            Type::Optional(a) => matches!(other, Type::Optional(b) if a == b),
             Type::Struct { fields, name, node: _, rust_attributes: _ } => {
                 matches!(other, Type::Struct{fields:f,name:n,node:_, rust_attributes: _ } if fields == f && name == n)
             }
             Type::Enumeration(lhs) => matches!(other, Type::Enumeration(rhs) if lhs == rhs),
+            Type::Interface(lhs) => matches!(other, Type::Interface(rhs) if Rc::ptr_eq(lhs, rhs)),
+            Type::TypeParameter(lhs) => matches!(other, Type::TypeParameter(rhs) if lhs == rhs),
             Type::UnitProduct(a) => matches!(other, Type::UnitProduct(b) if a == b),
             Type::ElementReference => matches!(other, Type::ElementReference),
             Type::LayoutCache => matches!(other, Type::LayoutCache),
@@ -145,7 +162,10 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                 }
                 Ok(())
             }
-            Type::ComponentFactory => write!(f, "component-factory"),
+            Type::ComponentFactory(None) => write!(f, "component-factory"),
+            Type::ComponentFactory(Some(interface)) => {
+                write!(f, "component-factory<{}>", interface.name)
+            }
             Type::Function { return_type, args } => {
                 write!(f, "function(")?;
                 for (i, arg) in args.iter().enumerate() {
@@ -170,6 +190,8 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             Type::Bool => write!(f, "bool"),
             Type::Model => write!(f, "model"),
             Type::Array(t) => write!(f, "[{}]", t),
+            Type::Optional(t) if **t == Type::Void => write!(f, "null"),
+            Type::Optional(t) => write!(f, "{}?", t),
             Type::Struct { name: Some(name), .. } => write!(f, "{}", name),
             Type::Struct { fields, name: None, .. } => {
                 write!(f, "{{ ")?;
@@ -183,6 +205,8 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             Type::Easing => write!(f, "easing"),
             Type::Brush => write!(f, "brush"),
             Type::Enumeration(enumeration) => write!(f, "enum {}", enumeration.name),
+            Type::Interface(interface) => write!(f, "interface {}", interface.name),
+            Type::TypeParameter(name) => write!(f, "{}", name),
             Type::UnitProduct(vec) => {
                 const POWERS: &[char] = &['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
                 let mut x = vec.iter().map(|(unit, power)| {
@@ -214,7 +238,7 @@ pub fn is_property_type(&self) -> bool {
                 | Self::Int32
                 | Self::String
                 | Self::Color
-                | Self::ComponentFactory
+                | Self::ComponentFactory(_)
                 | Self::Duration
                 | Self::Angle
                 | Self::PhysicalLength
@@ -229,12 +253,16 @@ pub fn is_property_type(&self) -> bool {
                 | Self::Struct { .. }
                 | Self::Array(_)
                 | Self::Brush
+                | Self::TypeParameter(_)
                 | Self::InferredProperty
-        )
+        ) || matches!(self, Self::Optional(inner) if inner.is_property_type())
     }
 
     pub fn ok_for_public_api(&self) -> bool {
-        !matches!(self, Self::Easing)
+        match self {
+            Self::Optional(inner) => inner.ok_for_public_api(),
+            _ => !matches!(self, Self::Easing),
+        }
     }
 
     /// Assume it is an enumeration, panic if it isn't
@@ -288,6 +316,15 @@ pub fn can_convert(&self, other: &Self) -> bool {
             (Type::Struct { fields: a, .. }, Type::Struct { fields: b, .. }) => {
                 can_convert_struct(a, b)
             }
+            (Type::ComponentFactory(a), Type::ComponentFactory(b)) => match (a, b) {
+                // The components don't need to implement anything
+                (_, None) => true,
+                (Some(a), Some(b)) => a.extends(b),
+                (None, Some(_)) => false,
+            },
+            // The `null` literal
+            (Type::Optional(a), Type::Optional(_)) => **a == Type::Void,
+            (a, Type::Optional(b)) => **b != Type::Void && a.can_convert(b),
             (Type::UnitProduct(u), o) => match o.as_unit_product() {
                 Some(o) => unit_product_length_conversion(u.as_slice(), o.as_slice()).is_some(),
                 None => false,
@@ -315,7 +352,7 @@ pub fn default_unit(&self) -> Option<Unit> {
             Type::Void => None,
             Type::InferredProperty | Type::InferredCallback => None,
             Type::Callback { .. } => None,
-            Type::ComponentFactory => None,
+            Type::ComponentFactory(_) => None,
             Type::Function { .. } => None,
             Type::Float32 => None,
             Type::Int32 => None,
@@ -328,8 +365,11 @@ pub fn default_unit(&self) -> Option<Unit> {
             Type::Easing => None,
             Type::Brush => None,
             Type::Array(_) => None,
+            Type::Optional(_) => None,
             Type::Struct { .. } => None,
             Type::Enumeration(_) => None,
+            Type::Interface(_) => None,
+            Type::TypeParameter(_) => None,
             Type::UnitProduct(_) => None,
             Type::ElementReference => None,
             Type::LayoutCache => None,
@@ -778,6 +818,39 @@ pub fn try_value_from_string(self: Rc<Self>, value: &str) -> Option<EnumerationV
     }
 }
 
+/// An interface declared in .slint with `interface Foo { ... }`
+#[derive(Debug)]
+pub struct Interface {
+    pub name: String,
+    /// The properties and callbacks that a component implementing this interface must have
+    pub members: BTreeMap<String, (Type, PropertyVisibility)>,
+    /// For interfaces declared in .slint, this is the declaration node
+    pub node: Option<syntax_nodes::InterfaceDeclaration>,
+}
+
+impl Interface {
+    /// Returns true if a property or callback declared with the `actual` visibility can
+    /// implement a member of an interface declared with the `required` visibility
+    pub fn visibility_satisfies(required: PropertyVisibility, actual: PropertyVisibility) -> bool {
+        match (required, actual) {
+            (_, PropertyVisibility::InOut) => true,
+            (PropertyVisibility::Input, PropertyVisibility::Input) => true,
+            (PropertyVisibility::Output, PropertyVisibility::Output) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns true if `other` doesn't require anything that this interface doesn't also
+    /// require, so that every component implementing this interface also implements `other`
+    pub fn extends(&self, other: &Interface) -> bool {
+        other.members.iter().all(|(name, (ty, visibility))| {
+            self.members
+                .get(name)
+                .map_or(false, |(t, v)| t == ty && Self::visibility_satisfies(*visibility, *v))
+        })
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct EnumerationValue {
     pub value: usize, // index in enumeration.values
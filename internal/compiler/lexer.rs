@@ -140,7 +140,8 @@ pub fn lex_number(text: &str, _: &mut LexState) -> usize {
     let mut had_period = false;
     while let Some(c) = chars.next() {
         if !c.is_ascii_digit() {
-            if !had_period && c == '.' && len > 0 {
+            // `1..2` is a range, not the number `1.` followed by `.2`
+            if !had_period && c == '.' && len > 0 && !text[len + 1..].starts_with('.') {
                 had_period = true;
             } else {
                 if len > 0 {
@@ -263,6 +264,30 @@ fn compare(source: &str, expected: &[(SyntaxKind, &str)]) {
             (SyntaxKind::Identifier, "c"),
         ],
     );
+    compare(
+        r#"0..n 1.5..2"#,
+        &[
+            (SyntaxKind::NumberLiteral, "0"),
+            (SyntaxKind::DoubleDot, ".."),
+            (SyntaxKind::Identifier, "n"),
+            (SyntaxKind::Whitespace, " "),
+            (SyntaxKind::NumberLiteral, "1.5"),
+            (SyntaxKind::DoubleDot, ".."),
+            (SyntaxKind::NumberLiteral, "2"),
+        ],
+    );
+    compare(
+        r#"a?.b??c?d"#,
+        &[
+            (SyntaxKind::Identifier, "a"),
+            (SyntaxKind::QuestionDot, "?."),
+            (SyntaxKind::Identifier, "b"),
+            (SyntaxKind::QuestionQuestion, "??"),
+            (SyntaxKind::Identifier, "c"),
+            (SyntaxKind::Question, "?"),
+            (SyntaxKind::Identifier, "d"),
+        ],
+    );
     compare(
         r#"/*/**/*//**/*"#,
         &[
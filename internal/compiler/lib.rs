@@ -16,6 +16,7 @@
 use std::rc::Rc;
 
 pub mod builtin_macros;
+pub mod deprecated_syntax;
 pub mod diagnostics;
 pub mod embedded_resources;
 pub mod expression_tree;
@@ -97,6 +98,11 @@ pub struct CompilerConfiguration {
 
     /// C++ namespace
     pub cpp_namespace: Option<String>,
+
+    /// Compile all the exported components of the root file, instead of only the last one.
+    ///
+    /// Each of them becomes a root component of the document.
+    pub compile_all_exported_components: bool,
 }
 
 impl CompilerConfiguration {
@@ -167,6 +173,7 @@ pub fn new(output_format: crate::generator::OutputFormat) -> Self {
             enable_experimental,
             translation_domain: None,
             cpp_namespace,
+            compile_all_exported_components: false,
         }
     }
 }
@@ -17,6 +17,8 @@ pub enum Expression {
     NumberLiteral(f64),
     /// Bool
     BoolLiteral(bool),
+    /// `null`, of the given optional type
+    NullLiteral(Type),
 
     /// Reference to a property (which can also be a callback) or an element (property name is empty then).
     PropertyReference(PropertyReference),
@@ -127,6 +129,20 @@ pub enum Expression {
         false_expr: Box<Expression>,
     },
 
+    /// The value of the optional `lhs`, or `rhs` if `lhs` is null.
+    NullCoalescing {
+        lhs: Box<Expression>,
+        rhs: Box<Expression>,
+    },
+
+    /// Null if `optional` is null. Otherwise the value of `optional` is stored in the local
+    /// variable `variable` and this evaluates to the (optional) `body`.
+    OptionalChaining {
+        optional: Box<Expression>,
+        variable: String,
+        body: Box<Expression>,
+    },
+
     Array {
         element_ty: Type,
         values: Vec<Expression>,
@@ -147,6 +163,18 @@ pub enum Expression {
     },
 
     RadialGradient {
+        shape: crate::expression_tree::RadialGradientShape,
+        extent: crate::expression_tree::RadialGradientExtent,
+        center_x: Box<Expression>,
+        center_y: Box<Expression>,
+        /// First expression in the tuple is a color, second expression is the stop position
+        stops: Vec<(Expression, Expression)>,
+    },
+
+    ConicGradient {
+        from_angle: Box<Expression>,
+        center_x: Box<Expression>,
+        center_y: Box<Expression>,
         /// First expression in the tuple is a color, second expression is the stop position
         stops: Vec<(Expression, Expression)>,
     },
@@ -187,6 +215,39 @@ pub enum Expression {
         lhs: Box<Expression>,
         rhs: Box<Expression>,
     },
+
+    /// A `for` loop over an array, or over the range `0..model` if the model is a number.
+    /// The row is stored in the local variable `variable`, and the body is evaluated
+    /// for each row. The body is of type bool: when it evaluates to false, the loop stops.
+    /// The body can also be a struct with such a bool in its `condition` field: the loop then
+    /// evaluates to the struct that stopped it, or to the default value of the struct.
+    ForLoop {
+        variable: String,
+        model: Box<Expression>,
+        body: Box<Expression>,
+    },
+
+    /// A new model computed from `model`, which follows the changes of `model`.
+    ModelOperation {
+        model: Box<Expression>,
+        operation: ModelOperation,
+    },
+}
+
+/// The operation of an [`Expression::ModelOperation`].
+///
+/// The bodies only depend on the local variable `variable`, which holds the row, and on
+/// the local variables stored before the model operation.
+#[derive(Debug, Clone)]
+pub enum ModelOperation {
+    /// Only keep the rows for which the body evaluates to true
+    Filter { variable: String, body: Box<Expression> },
+    /// Each row is the result of the body
+    Map { variable: String, body: Box<Expression> },
+    /// Sort the rows by the value of the body, which is a number or a string
+    SortBy { variable: String, body: Box<Expression> },
+    /// The rows from `start` to `end` (excluded)
+    Slice { start: Box<Expression>, end: Box<Expression> },
 }
 
 impl Expression {
@@ -194,7 +255,9 @@ pub fn default_value_for_type(ty: &Type) -> Option<Self> {
         Some(match ty {
             Type::Invalid
             | Type::Callback { .. }
-            | Type::ComponentFactory
+            | Type::ComponentFactory(_)
+            | Type::Interface(_)
+            | Type::TypeParameter(_)
             | Type::Function { .. }
             | Type::Void
             | Type::InferredProperty
@@ -241,6 +304,7 @@ pub fn default_value_for_type(ty: &Type) -> Option<Self> {
             Type::Enumeration(enumeration) => {
                 Expression::EnumerationValue(enumeration.clone().default_value())
             }
+            Type::Optional(_) => Expression::NullLiteral(ty.clone()),
         })
     }
 
@@ -249,6 +313,7 @@ pub fn ty(&self, ctx: &dyn TypeResolutionContext) -> Type {
             Self::StringLiteral(_) => Type::String,
             Self::NumberLiteral(_) => Type::Float32,
             Self::BoolLiteral(_) => Type::Bool,
+            Self::NullLiteral(ty) => ty.clone(),
             Self::PropertyReference(prop) => ctx.property_ty(prop).clone(),
             Self::FunctionParameterReference { index } => ctx.arg_type(*index).clone(),
             Self::StoreLocalVariable { .. } => Type::Void,
@@ -289,11 +354,14 @@ pub fn ty(&self, ctx: &dyn TypeResolutionContext) -> Type {
             Self::UnaryOp { sub, .. } => sub.ty(ctx),
             Self::ImageReference { .. } => Type::Image,
             Self::Condition { false_expr, .. } => false_expr.ty(ctx),
+            Self::NullCoalescing { rhs, .. } => rhs.ty(ctx),
+            Self::OptionalChaining { body, .. } => body.ty(ctx),
             Self::Array { element_ty, .. } => Type::Array(element_ty.clone().into()),
             Self::Struct { ty, .. } => ty.clone(),
             Self::EasingCurve(_) => Type::Easing,
             Self::LinearGradient { .. } => Type::Brush,
             Self::RadialGradient { .. } => Type::Brush,
+            Self::ConicGradient { .. } => Type::Brush,
             Self::EnumerationValue(e) => Type::Enumeration(e.enumeration.clone()),
             Self::LayoutCacheAccess { .. } => Type::LogicalLength,
             Self::BoxLayoutFunction { sub_expression, .. } => sub_expression.ty(ctx),
@@ -301,6 +369,14 @@ pub fn ty(&self, ctx: &dyn TypeResolutionContext) -> Type {
                 Type::Array(super::lower_expression::grid_layout_cell_data_ty().into())
             }
             Self::MinMax { ty, .. } => ty.clone(),
+            Self::ForLoop { body, .. } => match body.ty(ctx) {
+                ty @ Type::Struct { .. } => ty,
+                _ => Type::Void,
+            },
+            Self::ModelOperation { model, operation } => match operation {
+                ModelOperation::Map { body, .. } => Type::Array(body.ty(ctx).into()),
+                _ => model.ty(ctx),
+            },
         }
     }
 }
@@ -311,6 +387,7 @@ macro_rules! visit_impl {
             Expression::StringLiteral(_) => {}
             Expression::NumberLiteral(_) => {}
             Expression::BoolLiteral(_) => {}
+            Expression::NullLiteral(_) => {}
             Expression::PropertyReference(_) => {}
             Expression::FunctionParameterReference { .. } => {}
             Expression::StoreLocalVariable { value, .. } => $visitor(value),
@@ -348,6 +425,14 @@ macro_rules! visit_impl {
                 $visitor(true_expr);
                 $visitor(false_expr);
             }
+            Expression::NullCoalescing { lhs, rhs } => {
+                $visitor(lhs);
+                $visitor(rhs);
+            }
+            Expression::OptionalChaining { optional, variable: _, body } => {
+                $visitor(optional);
+                $visitor(body);
+            }
             Expression::Array { values, .. } => values.$iter().for_each($visitor),
             Expression::Struct { values, .. } => values.$values().for_each($visitor),
             Expression::EasingCurve(_) => {}
@@ -358,7 +443,18 @@ macro_rules! visit_impl {
                     $visitor(b);
                 }
             }
-            Expression::RadialGradient { stops } => {
+            Expression::RadialGradient { center_x, center_y, stops, .. } => {
+                $visitor(center_x);
+                $visitor(center_y);
+                for (a, b) in stops {
+                    $visitor(a);
+                    $visitor(b);
+                }
+            }
+            Expression::ConicGradient { from_angle, center_x, center_y, stops } => {
+                $visitor(from_angle);
+                $visitor(center_x);
+                $visitor(center_y);
                 for (a, b) in stops {
                     $visitor(a);
                     $visitor(b);
@@ -382,6 +478,22 @@ macro_rules! visit_impl {
                 $visitor(lhs);
                 $visitor(rhs);
             }
+            Expression::ForLoop { variable: _, model, body } => {
+                $visitor(model);
+                $visitor(body);
+            }
+            Expression::ModelOperation { model, operation } => {
+                $visitor(model);
+                match operation {
+                    ModelOperation::Filter { variable: _, body }
+                    | ModelOperation::Map { variable: _, body }
+                    | ModelOperation::SortBy { variable: _, body } => $visitor(body),
+                    ModelOperation::Slice { start, end } => {
+                        $visitor(start);
+                        $visitor(end);
+                    }
+                }
+            }
         }
     };
 }
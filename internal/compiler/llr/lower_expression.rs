@@ -68,6 +68,7 @@ pub fn lower_expression(
             llr_Expression::NumberLiteral(unit.normalize(*n))
         }
         tree_Expression::BoolLiteral(b) => llr_Expression::BoolLiteral(*b),
+        tree_Expression::NullLiteral(ty) => llr_Expression::NullLiteral(ty.clone()),
         tree_Expression::CallbackReference(nr, _)
         | tree_Expression::PropertyReference(nr)
         | tree_Expression::FunctionReference(nr, _) => {
@@ -167,6 +168,17 @@ pub fn lower_expression(
                 false_expr: lower_expression(false_expr, ctx).into(),
             }
         }
+        tree_Expression::NullCoalescing { lhs, rhs } => llr_Expression::NullCoalescing {
+            lhs: Box::new(lower_expression(lhs, ctx)),
+            rhs: Box::new(lower_expression(rhs, ctx)),
+        },
+        tree_Expression::OptionalChaining { optional, variable, body } => {
+            llr_Expression::OptionalChaining {
+                optional: Box::new(lower_expression(optional, ctx)),
+                variable: variable.clone(),
+                body: Box::new(lower_expression(body, ctx)),
+            }
+        }
         tree_Expression::Array { element_ty, values } => llr_Expression::Array {
             element_ty: element_ty.clone(),
             values: values.iter().map(|e| lower_expression(e, ctx)).collect::<_>(),
@@ -188,16 +200,63 @@ pub fn lower_expression(
                 .map(|(a, b)| (lower_expression(a, ctx), lower_expression(b, ctx)))
                 .collect::<_>(),
         },
-        tree_Expression::RadialGradient { stops } => llr_Expression::RadialGradient {
-            stops: stops
-                .iter()
-                .map(|(a, b)| (lower_expression(a, ctx), lower_expression(b, ctx)))
-                .collect::<_>(),
-        },
+        tree_Expression::RadialGradient { shape, extent, center_x, center_y, stops } => {
+            llr_Expression::RadialGradient {
+                shape: *shape,
+                extent: *extent,
+                center_x: Box::new(lower_expression(center_x, ctx)),
+                center_y: Box::new(lower_expression(center_y, ctx)),
+                stops: stops
+                    .iter()
+                    .map(|(a, b)| (lower_expression(a, ctx), lower_expression(b, ctx)))
+                    .collect::<_>(),
+            }
+        }
+        tree_Expression::ConicGradient { from_angle, center_x, center_y, stops } => {
+            llr_Expression::ConicGradient {
+                from_angle: Box::new(lower_expression(from_angle, ctx)),
+                center_x: Box::new(lower_expression(center_x, ctx)),
+                center_y: Box::new(lower_expression(center_y, ctx)),
+                stops: stops
+                    .iter()
+                    .map(|(a, b)| (lower_expression(a, ctx), lower_expression(b, ctx)))
+                    .collect::<_>(),
+            }
+        }
         tree_Expression::EnumerationValue(e) => llr_Expression::EnumerationValue(e.clone()),
         tree_Expression::ReturnStatement(..) => {
             panic!("The remove return pass should have removed all return")
         }
+        tree_Expression::ForLoop { variable, model, body } => llr_Expression::ForLoop {
+            variable: variable.clone(),
+            model: Box::new(lower_expression(model, ctx)),
+            body: Box::new(lower_expression(body, ctx)),
+        },
+        tree_Expression::Break | tree_Expression::Continue => {
+            panic!("The remove return pass should have removed all break and continue")
+        }
+        tree_Expression::ModelOperation { model, operation } => {
+            use crate::expression_tree::ModelOperation as tree_ModelOperation;
+            use crate::llr::ModelOperation as llr_ModelOperation;
+            let lower = |e: &tree_Expression| Box::new(lower_expression(e, ctx));
+            llr_Expression::ModelOperation {
+                model: lower(model),
+                operation: match operation {
+                    tree_ModelOperation::Filter { variable, body } => {
+                        llr_ModelOperation::Filter { variable: variable.clone(), body: lower(body) }
+                    }
+                    tree_ModelOperation::Map { variable, body } => {
+                        llr_ModelOperation::Map { variable: variable.clone(), body: lower(body) }
+                    }
+                    tree_ModelOperation::SortBy { variable, body } => {
+                        llr_ModelOperation::SortBy { variable: variable.clone(), body: lower(body) }
+                    }
+                    tree_ModelOperation::Slice { start, end } => {
+                        llr_ModelOperation::Slice { start: lower(start), end: lower(end) }
+                    }
+                },
+            }
+        }
         tree_Expression::LayoutCacheAccess { layout_cache_prop, index, repeater_index } => {
             llr_Expression::LayoutCacheAccess {
                 layout_cache_prop: ctx.map_property_reference(layout_cache_prop),
@@ -20,6 +20,7 @@ fn expression_cost(exp: &Expression, ctx: &EvaluationContext) -> isize {
         Expression::StringLiteral(_) => ALLOC_COST,
         Expression::NumberLiteral(_) => 0,
         Expression::BoolLiteral(_) => 0,
+        Expression::NullLiteral(_) => 0,
         Expression::PropertyReference(_) => PROPERTY_ACCESS_COST,
         Expression::FunctionParameterReference { .. } => return isize::MAX,
         Expression::StoreLocalVariable { .. } => 0,
@@ -39,16 +40,21 @@ fn expression_cost(exp: &Expression, ctx: &EvaluationContext) -> isize {
         Expression::UnaryOp { .. } => 1,
         Expression::ImageReference { .. } => 1,
         Expression::Condition { .. } => 10,
+        Expression::NullCoalescing { .. } => 10,
+        Expression::OptionalChaining { .. } => 10,
         Expression::Array { .. } => ALLOC_COST,
         Expression::Struct { .. } => 1,
         Expression::EasingCurve(_) => 1,
         Expression::LinearGradient { .. } => ALLOC_COST,
         Expression::RadialGradient { .. } => ALLOC_COST,
+        Expression::ConicGradient { .. } => ALLOC_COST,
         Expression::EnumerationValue(_) => 0,
         Expression::LayoutCacheAccess { .. } => PROPERTY_ACCESS_COST,
         Expression::BoxLayoutFunction { .. } => return isize::MAX,
         Expression::ComputeDialogLayoutCells { .. } => return isize::MAX,
         Expression::MinMax { .. } => 10,
+        Expression::ForLoop { .. } => return isize::MAX,
+        Expression::ModelOperation { .. } => return isize::MAX,
     };
 
     exp.visit(|e| cost = cost.saturating_add(expression_cost(e, ctx)));
@@ -87,6 +93,22 @@ fn builtin_function_cost(function: &BuiltinFunction) -> isize {
         BuiltinFunction::ItemMemberFunction(..) => isize::MAX,
         BuiltinFunction::StringToFloat => 50,
         BuiltinFunction::StringIsFloat => 50,
+        BuiltinFunction::StringToInt => 50,
+        BuiltinFunction::StringLength => 50,
+        BuiltinFunction::StringIsEmpty => 10,
+        BuiltinFunction::StringToUppercase => 50,
+        BuiltinFunction::StringToLowercase => 50,
+        BuiltinFunction::StringTrim => 50,
+        BuiltinFunction::StringContains => 50,
+        BuiltinFunction::StringStartsWith => 50,
+        BuiltinFunction::StringEndsWith => 50,
+        BuiltinFunction::StringReplace => 50,
+        BuiltinFunction::StringSplit => 50,
+        BuiltinFunction::StringSubstring => 50,
+        BuiltinFunction::StringCharAt => 50,
+        BuiltinFunction::NumberToFixed => ALLOC_COST,
+        BuiltinFunction::NumberFormat => ALLOC_COST,
+        BuiltinFunction::NumberToPadded => ALLOC_COST,
         BuiltinFunction::ColorRgbaStruct => 50,
         BuiltinFunction::ColorHsvaStruct => 50,
         BuiltinFunction::ColorBrighter => 50,
@@ -96,6 +118,8 @@ fn builtin_function_cost(function: &BuiltinFunction) -> isize {
         BuiltinFunction::ColorWithAlpha => 50,
         BuiltinFunction::ImageSize => 50,
         BuiltinFunction::ArrayLength => 50,
+        BuiltinFunction::ArrayIndexOf | BuiltinFunction::ArrayContains => 50,
+        BuiltinFunction::ArraySum | BuiltinFunction::ArrayMin | BuiltinFunction::ArrayMax => 50,
         BuiltinFunction::Rgb => 50,
         BuiltinFunction::Hsv => 50,
         BuiltinFunction::ImplicitLayoutInfo(_) => isize::MAX,
@@ -8,7 +8,8 @@
 use crate::expression_tree::MinMaxOp;
 
 use super::{
-    EvaluationContext, Expression, ParentCtx, PropertyReference, PublicComponent, SubComponent,
+    EvaluationContext, Expression, ModelOperation, ParentCtx, PropertyReference, PublicComponent,
+    SubComponent,
 };
 
 pub fn pretty_print(root: &PublicComponent, writer: &mut dyn Write) -> Result {
@@ -169,6 +170,7 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result {
             Expression::StringLiteral(x) => write!(f, "{:?}", x),
             Expression::NumberLiteral(x) => write!(f, "{:?}", x),
             Expression::BoolLiteral(x) => write!(f, "{:?}", x),
+            Expression::NullLiteral(_) => write!(f, "null"),
             Expression::PropertyReference(x) => write!(f, "{}", DisplayPropertyRef(x, ctx)),
             Expression::FunctionParameterReference { index } => write!(f, "arg_{}", index),
             Expression::StoreLocalVariable { name, value } => {
@@ -226,6 +228,10 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result {
             Expression::Condition { condition, true_expr, false_expr } => {
                 write!(f, "({} ? {} : {})", e(condition), e(true_expr), e(false_expr))
             }
+            Expression::NullCoalescing { lhs, rhs } => write!(f, "({} ?? {})", e(lhs), e(rhs)),
+            Expression::OptionalChaining { optional, variable, body } => {
+                write!(f, "{}?.({} => {})", e(optional), variable, e(body))
+            }
             Expression::Array { values, .. } => {
                 write!(f, "[{}]", values.iter().map(e).join(", "))
             }
@@ -241,9 +247,21 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result {
                 e(angle),
                 stops.iter().map(|(e1, e2)| format!("{} {}", e(e1), e(e2))).join(", ")
             ),
-            Expression::RadialGradient { stops } => write!(
+            Expression::RadialGradient { shape, extent, center_x, center_y, stops } => write!(
                 f,
-                "@radial-gradient(circle, {})",
+                "@radial-gradient({:?} {:?} at {} {}, {})",
+                shape,
+                extent,
+                e(center_x),
+                e(center_y),
+                stops.iter().map(|(e1, e2)| format!("{} {}", e(e1), e(e2))).join(", ")
+            ),
+            Expression::ConicGradient { from_angle, center_x, center_y, stops } => write!(
+                f,
+                "@conic-gradient(from {} at {} {}, {})",
+                e(from_angle),
+                e(center_x),
+                e(center_y),
                 stops.iter().map(|(e1, e2)| format!("{} {}", e(e1), e(e2))).join(", ")
             ),
             Expression::EnumerationValue(x) => write!(f, "{}", x),
@@ -265,6 +283,23 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result {
                 MinMaxOp::Min => write!(f, "min({}, {})", e(lhs), e(rhs)),
                 MinMaxOp::Max => write!(f, "max({}, {})", e(lhs), e(rhs)),
             },
+            Expression::ForLoop { variable, model, body } => {
+                write!(f, "for {} in {} {{ {} }}", variable, e(model), e(body))
+            }
+            Expression::ModelOperation { model, operation } => match operation {
+                ModelOperation::Filter { variable, body } => {
+                    write!(f, "{}.filter({} => {})", e(model), variable, e(body))
+                }
+                ModelOperation::Map { variable, body } => {
+                    write!(f, "{}.map({} => {})", e(model), variable, e(body))
+                }
+                ModelOperation::SortBy { variable, body } => {
+                    write!(f, "{}.sort-by({} => {})", e(model), variable, e(body))
+                }
+                ModelOperation::Slice { start, end } => {
+                    write!(f, "{}.slice({}, {})", e(model), e(start), e(end))
+                }
+            },
         }
     }
 }
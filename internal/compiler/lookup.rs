@@ -43,6 +43,12 @@ pub struct LookupCtx<'a> {
 
     /// The token currently processed
     pub current_token: Option<NodeOrToken>,
+
+    /// The local variables declared with `let`, one entry per nested code block
+    pub local_variables: Vec<Vec<(String, Type)>>,
+
+    /// True when resolving the body of a `for` loop
+    pub inside_loop: bool,
 }
 
 impl<'a> LookupCtx<'a> {
@@ -57,6 +63,8 @@ pub fn empty_context(type_register: &'a TypeRegister, diag: &'a mut BuildDiagnos
             type_register,
             type_loader: None,
             current_token: None,
+            local_variables: Default::default(),
+            inside_loop: false,
         }
     }
 
@@ -82,6 +90,20 @@ pub fn is_local_element(&self, elem: &ElementRc) -> bool {
         )
         .map_or(true, |(x, y)| Rc::ptr_eq(&x, &y))
     }
+
+    /// Report an error on `node` and return true if a local variable named `name` is already in scope
+    pub fn check_local_variable_redeclaration(&mut self, name: &str, node: &dyn Spanned) -> bool {
+        if let Some(LookupResult::Expression {
+            expression: Expression::ReadLocalVariable { .. },
+            ..
+        }) = global_lookup().lookup(self, name)
+        {
+            self.diag.push_error("Redeclaration of local variables is not allowed".into(), node);
+            true
+        } else {
+            false
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -185,6 +207,28 @@ fn lookup(&self, ctx: &LookupCtx, name: &str) -> Option<LookupResult> {
     }
 }
 
+struct LocalVariableLookup;
+impl LookupObject for LocalVariableLookup {
+    fn for_each_entry<R>(
+        &self,
+        ctx: &LookupCtx,
+        f: &mut impl FnMut(&str, LookupResult) -> Option<R>,
+    ) -> Option<R> {
+        for scope in ctx.local_variables.iter().rev() {
+            for (name, ty) in scope.iter().rev() {
+                if let Some(r) = f(
+                    name,
+                    Expression::ReadLocalVariable { name: format!("local_{name}"), ty: ty.clone() }
+                        .into(),
+                ) {
+                    return Some(r);
+                }
+            }
+        }
+        None
+    }
+}
+
 struct ArgumentsLookup;
 impl LookupObject for ArgumentsLookup {
     fn for_each_entry<R>(
@@ -230,6 +274,9 @@ fn for_each_entry<R>(
             })
             .or_else(|| f("true", Expression::BoolLiteral(true).into()))
             .or_else(|| f("false", Expression::BoolLiteral(false).into()))
+            .or_else(|| {
+                f("null", Expression::NullLiteral(Type::Optional(Box::new(Type::Void))).into())
+            })
         // "root" is just a normal id
     }
 }
@@ -891,16 +938,22 @@ fn for_each_entry<R>(
 
 pub fn global_lookup() -> impl LookupObject {
     (
-        ArgumentsLookup,
+        LocalVariableLookup,
         (
-            SpecialIdLookup,
+            ArgumentsLookup,
             (
-                IdLookup,
+                SpecialIdLookup,
                 (
-                    InScopeLookup,
+                    IdLookup,
                     (
-                        LookupType,
-                        (BuiltinNamespaceLookup, (ReturnTypeSpecificLookup, BuiltinFunctionLookup)),
+                        InScopeLookup,
+                        (
+                            LookupType,
+                            (
+                                BuiltinNamespaceLookup,
+                                (ReturnTypeSpecificLookup, BuiltinFunctionLookup),
+                            ),
+                        ),
                     ),
                 ),
             ),
@@ -933,6 +986,7 @@ fn for_each_entry<R>(
                     None
                 }
                 Type::String => StringExpression(self).for_each_entry(ctx, f),
+                Type::Int32 | Type::Float32 => NumberExpression(self).for_each_entry(ctx, f),
                 Type::Brush | Type::Color => ColorExpression(self).for_each_entry(ctx, f),
                 Type::Image => ImageExpression(self).for_each_entry(ctx, f),
                 Type::Array(_) => ArrayExpression(self).for_each_entry(ctx, f),
@@ -952,6 +1006,7 @@ fn lookup(&self, ctx: &LookupCtx, name: &str) -> Option<LookupResult> {
                     })
                 }),
                 Type::String => StringExpression(self).lookup(ctx, name),
+                Type::Int32 | Type::Float32 => NumberExpression(self).lookup(ctx, name),
                 Type::Brush | Type::Color => ColorExpression(self).lookup(ctx, name),
                 Type::Image => ImageExpression(self).lookup(ctx, name),
                 Type::Array(_) => ArrayExpression(self).lookup(ctx, name),
@@ -978,10 +1033,56 @@ fn for_each_entry<R>(
                 )),
             })
         };
+        let function_call = |f: BuiltinFunction| {
+            LookupResult::from(Expression::FunctionCall {
+                function: Box::new(Expression::BuiltinFunctionReference(
+                    f,
+                    ctx.current_token.as_ref().map(|t| t.to_source_location()),
+                )),
+                source_location: ctx.current_token.as_ref().map(|t| t.to_source_location()),
+                arguments: vec![self.0.clone()],
+            })
+        };
         None.or_else(|| f("is-float", member_function(BuiltinFunction::StringIsFloat)))
             .or_else(|| f("to-float", member_function(BuiltinFunction::StringToFloat)))
+            .or_else(|| f("to-int", member_function(BuiltinFunction::StringToInt)))
+            .or_else(|| f("length", function_call(BuiltinFunction::StringLength)))
+            .or_else(|| f("is-empty", function_call(BuiltinFunction::StringIsEmpty)))
+            .or_else(|| f("to-upper", member_function(BuiltinFunction::StringToUppercase)))
+            .or_else(|| f("to-lower", member_function(BuiltinFunction::StringToLowercase)))
+            .or_else(|| f("trim", member_function(BuiltinFunction::StringTrim)))
+            .or_else(|| f("contains", member_function(BuiltinFunction::StringContains)))
+            .or_else(|| f("starts-with", member_function(BuiltinFunction::StringStartsWith)))
+            .or_else(|| f("ends-with", member_function(BuiltinFunction::StringEndsWith)))
+            .or_else(|| f("replace", member_function(BuiltinFunction::StringReplace)))
+            .or_else(|| f("split", member_function(BuiltinFunction::StringSplit)))
+            .or_else(|| f("substring", member_function(BuiltinFunction::StringSubstring)))
+            .or_else(|| f("char-at", member_function(BuiltinFunction::StringCharAt)))
+    }
+}
+struct NumberExpression<'a>(&'a Expression);
+impl<'a> LookupObject for NumberExpression<'a> {
+    fn for_each_entry<R>(
+        &self,
+        ctx: &LookupCtx,
+        f: &mut impl FnMut(&str, LookupResult) -> Option<R>,
+    ) -> Option<R> {
+        let member_function = |f: BuiltinFunction| {
+            LookupResult::from(Expression::MemberFunction {
+                base: Box::new(self.0.clone()),
+                base_node: ctx.current_token.clone(), // Note that this is not the base_node, but the function's node
+                member: Box::new(Expression::BuiltinFunctionReference(
+                    f,
+                    ctx.current_token.as_ref().map(|t| t.to_source_location()),
+                )),
+            })
+        };
+        None.or_else(|| f("to-fixed", member_function(BuiltinFunction::NumberToFixed)))
+            .or_else(|| f("format-number", member_function(BuiltinFunction::NumberFormat)))
+            .or_else(|| f("to-padded", member_function(BuiltinFunction::NumberToPadded)))
     }
 }
+
 struct ColorExpression<'a>(&'a Expression);
 impl<'a> LookupObject for ColorExpression<'a> {
     fn for_each_entry<R>(
@@ -1077,6 +1178,36 @@ fn for_each_entry<R>(
                 arguments: vec![self.0.clone()],
             })
         };
+        let function = |member: Expression| {
+            LookupResult::from(Expression::MemberFunction {
+                base: Box::new(self.0.clone()),
+                base_node: ctx.current_token.clone(), // Note that this is not the base_node, but the function's node
+                member: Box::new(member),
+            })
+        };
+        let builtin_function = |f: BuiltinFunction| {
+            function(Expression::BuiltinFunctionReference(
+                f,
+                ctx.current_token.as_ref().map(|t| t.to_source_location()),
+            ))
+        };
+        let model_operation = |m: BuiltinMacroFunction| {
+            function(Expression::BuiltinMacroReference(m, ctx.current_token.clone()))
+        };
+        // sum, min, and max are only available for arrays of numbers
+        let numeric_function = |f: BuiltinFunction| {
+            matches!(self.0.ty(), Type::Array(ty) if matches!(*ty, Type::Int32 | Type::Float32))
+                .then(|| builtin_function(f))
+        };
         None.or_else(|| f("length", member_function(BuiltinFunction::ArrayLength)))
+            .or_else(|| f("filter", model_operation(BuiltinMacroFunction::ArrayFilter)))
+            .or_else(|| f("map", model_operation(BuiltinMacroFunction::ArrayMap)))
+            .or_else(|| f("sort-by", model_operation(BuiltinMacroFunction::ArraySortBy)))
+            .or_else(|| f("slice", model_operation(BuiltinMacroFunction::ArraySlice)))
+            .or_else(|| f("index-of", builtin_function(BuiltinFunction::ArrayIndexOf)))
+            .or_else(|| f("contains", builtin_function(BuiltinFunction::ArrayContains)))
+            .or_else(|| f("sum", numeric_function(BuiltinFunction::ArraySum)?))
+            .or_else(|| f("min", numeric_function(BuiltinFunction::ArrayMin)?))
+            .or_else(|| f("max", numeric_function(BuiltinFunction::ArrayMax)?))
     }
 }
@@ -7,9 +7,9 @@
 
 // cSpell: ignore qualname
 
-use crate::diagnostics::{BuildDiagnostics, SourceLocation, Spanned};
+use crate::diagnostics::{BuildDiagnostics, DiagnosticLevel, SourceLocation, Spanned};
 use crate::expression_tree::{self, BindingExpression, Expression, Unit};
-use crate::langtype::{BuiltinElement, Enumeration, NativeClass, Type};
+use crate::langtype::{BuiltinElement, Enumeration, Interface, NativeClass, Type};
 use crate::langtype::{ElementType, PropertyLookupResult};
 use crate::layout::{LayoutConstraints, Orientation};
 use crate::namedreference::NamedReference;
@@ -17,7 +17,7 @@
 use crate::parser::{syntax_nodes, SyntaxKind, SyntaxNode};
 use crate::typeloader::ImportedTypes;
 use crate::typeregister::TypeRegister;
-use itertools::Either;
+use itertools::{Either, Itertools};
 use once_cell::unsync::OnceCell;
 use std::cell::{Cell, RefCell};
 use std::collections::btree_map::Entry;
@@ -50,6 +50,12 @@ pub struct Document {
     /// startup for custom font use.
     pub custom_fonts: Vec<(String, crate::parser::SyntaxToken)>,
     pub exports: Exports,
+    /// The other root components, when all the exported components are compiled
+    /// (see [`Self::use_exported_components_as_roots`])
+    pub extra_root_components: Vec<Rc<Component>>,
+    /// The types of this document in which its generic components and structs are instantiated
+    /// (see [`GenericDeclaration::instantiate`])
+    generics_scope: Option<Rc<RefCell<TypeRegister>>>,
 }
 
 impl Document {
@@ -65,11 +71,25 @@ pub fn from_node(
         let mut local_registry = TypeRegister::new(parent_registry);
         let mut inner_components = vec![];
         let mut inner_types = vec![];
+        let generics = RefCell::new(vec![]);
 
+        let process_generic =
+            |n: Either<syntax_nodes::Component, syntax_nodes::StructDeclaration>,
+             diag: &mut BuildDiagnostics,
+             local_registry: &mut TypeRegister| {
+                if let Some(generic) = GenericDeclaration::from_node(n, diag, local_registry) {
+                    local_registry.add_generic(generic.clone());
+                    generics.borrow_mut().push(generic);
+                }
+            };
         let mut process_component =
             |n: syntax_nodes::Component,
              diag: &mut BuildDiagnostics,
              local_registry: &mut TypeRegister| {
+                if n.TypeParameters().is_some() {
+                    process_generic(Either::Left(n), diag, local_registry);
+                    return;
+                }
                 let compo = Component::from_node(n, diag, local_registry);
                 local_registry.add(compo.clone());
                 inner_components.push(compo);
@@ -78,6 +98,10 @@ pub fn from_node(
                               diag: &mut BuildDiagnostics,
                               local_registry: &mut TypeRegister,
                               inner_types: &mut Vec<Type>| {
+            if n.TypeParameters().is_some() {
+                process_generic(Either::Right(n), diag, local_registry);
+                return;
+            }
             let rust_attributes = n.AtRustAttr().map(|child| vec![child.text().to_string()]);
             let mut ty =
                 type_struct_from_node(n.ObjectType(), diag, local_registry, rust_attributes);
@@ -122,6 +146,17 @@ pub fn from_node(
             local_registry.insert_type_with_name(ty.clone(), name);
             inner_types.push(ty);
         };
+        let process_interface =
+            |n: syntax_nodes::InterfaceDeclaration,
+             diag: &mut BuildDiagnostics,
+             local_registry: &mut TypeRegister| {
+                let Some(name) = parser::identifier_text(&n.DeclaredIdentifier()) else {
+                    assert!(diag.has_error());
+                    return;
+                };
+                let interface = interface_from_node(n, name.clone(), diag, local_registry);
+                local_registry.insert_type_with_name(Type::Interface(Rc::new(interface)), name);
+            };
 
         for n in node.children() {
             match n.kind() {
@@ -132,6 +167,9 @@ pub fn from_node(
                 SyntaxKind::EnumDeclaration => {
                     process_enum(n.into(), diag, &mut local_registry, &mut inner_types)
                 }
+                SyntaxKind::InterfaceDeclaration => {
+                    process_interface(n.into(), diag, &mut local_registry)
+                }
                 SyntaxKind::ExportsList => {
                     for n in n.children() {
                         match n.kind() {
@@ -147,6 +185,9 @@ pub fn from_node(
                             SyntaxKind::EnumDeclaration => {
                                 process_enum(n.into(), diag, &mut local_registry, &mut inner_types)
                             }
+                            SyntaxKind::InterfaceDeclaration => {
+                                process_interface(n.into(), diag, &mut local_registry)
+                            }
                             _ => {}
                         }
                     }
@@ -155,6 +196,7 @@ pub fn from_node(
             };
         }
         let mut exports = Exports::from_node(&node, &inner_components, &local_registry, diag);
+        exports.add_reexported_generics(reexports.generics.clone(), diag);
         exports.add_reexports(reexports, diag);
 
         let root_component = exports
@@ -237,6 +279,31 @@ pub fn from_node(
             }
         }
 
+        // The generics of this document are instantiated by the other documents in the types
+        // of this document.
+        let generics = generics.into_inner();
+        let generics_scope = (!generics.is_empty()).then(|| {
+            let scope = Rc::new(RefCell::new(local_registry.clone()));
+            for generic in &generics {
+                *generic.definition_scope.borrow_mut() = Rc::downgrade(&scope);
+            }
+            scope
+        });
+
+        // The instances of generic components and structs used in this document, including the
+        // imported ones, are then handled by the passes like any other component or struct of
+        // this document. The components used to check the generic components come first, so
+        // that their diagnostics are reported before the ones of the instances.
+        let instances = std::mem::take(&mut *local_registry.generic_instances.borrow_mut());
+        check_generic_instance_names(&instances, &local_registry, diag);
+        inner_components.extend(generics.iter().filter_map(|g| g.check_component.clone()));
+        for (_, instance) in instances {
+            match instance {
+                Either::Left(compo) => inner_components.push(compo),
+                Either::Right(ty) => inner_types.push(ty),
+            }
+        }
+
         Document {
             node: Some(node),
             root_component,
@@ -245,7 +312,72 @@ pub fn from_node(
             local_registry,
             custom_fonts,
             exports,
+            extra_root_components: vec![],
+            generics_scope,
+        }
+    }
+
+    /// Make every exported component a root component, so that the passes compile all of them.
+    ///
+    /// Exported components can also be used by other components, so each root is a new component
+    /// named after the export and inheriting the exported component. The last one becomes the
+    /// root component and the others are the extra root components.
+    pub fn use_exported_components_as_roots(&mut self) {
+        let mut roots = self
+            .exports
+            .iter()
+            .filter_map(|(name, compo_or_type)| {
+                let component = compo_or_type.as_ref().left().filter(|c| !c.is_global())?;
+                Some(Rc::new_cyclic(|weak| Component {
+                    node: component.node.clone(),
+                    id: name.name.clone(),
+                    root_element: Element {
+                        id: component.root_element.borrow().id.clone(),
+                        base_type: ElementType::Component(component.clone()),
+                        enclosing_component: weak.clone(),
+                        ..Default::default()
+                    }
+                    .make_rc(),
+                    implements: component.implements.clone(),
+                    ..Default::default()
+                }))
+            })
+            .collect::<Vec<_>>();
+        if let Some(last) = roots.pop() {
+            self.root_component = last;
+            self.extra_root_components = roots;
+        }
+    }
+
+    /// Returns the root component followed by the extra root components
+    pub fn root_components(&self) -> impl Iterator<Item = &Rc<Component>> {
+        std::iter::once(&self.root_component).chain(self.extra_root_components.iter())
+    }
+
+    /// Returns the sub components used by any of the root components, each of them once
+    pub fn used_sub_components(&self) -> Vec<Rc<Component>> {
+        self.collect_used_components(|used_types| &used_types.sub_components)
+    }
+
+    /// Returns the globals used by any of the root components, each of them once
+    pub fn used_globals(&self) -> Vec<Rc<Component>> {
+        self.collect_used_components(|used_types| &used_types.globals)
+    }
+
+    fn collect_used_components(
+        &self,
+        field: impl Fn(&UsedSubTypes) -> &Vec<Rc<Component>>,
+    ) -> Vec<Rc<Component>> {
+        let mut seen = HashSet::new();
+        let mut result = vec![];
+        for root in self.root_components() {
+            for c in field(&root.used_types.borrow()) {
+                if seen.insert(Rc::as_ptr(c)) {
+                    result.push(c.clone());
+                }
+            }
         }
+        result
     }
 }
 
@@ -303,6 +435,257 @@ pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Expression> {
     }
 }
 
+/// A component or a struct declared with type parameters, such as `component Picker<T>` or
+/// `struct Pair<T, U>`.
+///
+/// It is monomorphized: every distinct list of type arguments it is used with creates a new
+/// [`Component`] or [`Type::Struct`], in which the type parameters are replaced by the arguments.
+/// The body is checked once with the declaration, where the type parameters are
+/// [`Type::TypeParameter`], and the diagnostics of the instances are reported where they are
+/// instantiated.
+#[derive(Debug)]
+pub struct GenericDeclaration {
+    pub name: String,
+    pub type_parameters: Vec<String>,
+    pub node: Either<syntax_nodes::Component, syntax_nodes::StructDeclaration>,
+    /// For a generic component, the component created with the placeholders for the type
+    /// parameters, that is used to check the body. It is not generated.
+    check_component: Option<Rc<Component>>,
+    /// The instances created so far, with their type arguments
+    instances: RefCell<Vec<(Vec<Type>, Either<Rc<Component>, Type>)>>,
+    /// The types of the document that declares the generic, in which the body of the instances
+    /// is resolved when the generic is imported by another document. It is set once the
+    /// document is loaded, and is owned by the [`Document`].
+    definition_scope: RefCell<Weak<RefCell<TypeRegister>>>,
+}
+
+impl GenericDeclaration {
+    pub fn from_node(
+        node: Either<syntax_nodes::Component, syntax_nodes::StructDeclaration>,
+        diag: &mut BuildDiagnostics,
+        tr: &TypeRegister,
+    ) -> Option<Rc<Self>> {
+        let (name, type_parameters) = match &node {
+            Either::Left(c) => {
+                (parser::identifier_text(&c.DeclaredIdentifier()), c.TypeParameters())
+            }
+            Either::Right(s) => {
+                (parser::identifier_text(&s.DeclaredIdentifier()), s.TypeParameters())
+            }
+        };
+        let (Some(name), Some(type_parameters)) = (name, type_parameters) else {
+            debug_assert!(diag.has_error());
+            return None;
+        };
+        let mut parameters = Vec::<String>::new();
+        for param in type_parameters.DeclaredIdentifier() {
+            let param_name = unwrap_or_continue!(parser::identifier_text(&param); diag);
+            if parameters.contains(&param_name) {
+                diag.push_error(format!("Duplicated type parameter '{}'", param_name), &param);
+                continue;
+            }
+            parameters.push(param_name);
+        }
+        if parameters.is_empty() {
+            diag.push_error("Expected at least one type parameter".into(), &type_parameters);
+            return None;
+        }
+
+        let mut scope = tr.clone();
+        for param in &parameters {
+            scope.insert_type_with_name(Type::TypeParameter(param.clone()), param.clone());
+        }
+        let check_component = match &node {
+            Either::Left(component) => {
+                let id = format!("{}<{}>", name, parameters.join(", "));
+                Some(Component::from_node_with_id(component.clone(), id, diag, &scope))
+            }
+            Either::Right(struct_node) => {
+                type_struct_from_node(struct_node.ObjectType(), diag, &scope, None);
+                None
+            }
+        };
+
+        Some(Rc::new(Self {
+            name,
+            type_parameters: parameters,
+            node,
+            check_component,
+            instances: Default::default(),
+            definition_scope: Default::default(),
+        }))
+    }
+
+    /// Returns the instance of this generic for the given type arguments, creating it if it is
+    /// the first time it is used with these arguments.
+    ///
+    /// The type arguments are resolved in `tr`, the registry where the generic is instantiated.
+    /// The new instances are added to the instances of that registry, so that they belong to the
+    /// document that uses them.
+    pub fn instantiate(
+        self: &Rc<Self>,
+        type_arguments: Vec<Type>,
+        node: &dyn Spanned,
+        diag: &mut BuildDiagnostics,
+        tr: &TypeRegister,
+    ) -> Option<Either<Rc<Component>, Type>> {
+        if type_arguments.len() != self.type_parameters.len() {
+            diag.push_error(
+                format!(
+                    "'{}' expects {} type arguments, but {} are provided",
+                    self.name,
+                    self.type_parameters.len(),
+                    type_arguments.len()
+                ),
+                node,
+            );
+            return None;
+        }
+        if type_arguments.contains(&Type::Invalid) {
+            debug_assert!(diag.has_error());
+            return None;
+        }
+        // When checking the body of another generic, the instance is only needed for its
+        // declarations, so it is neither kept nor generated
+        let is_check = type_arguments.iter().any(contains_type_parameter);
+        if !is_check {
+            if let Some((_, instance)) =
+                self.instances.borrow().iter().find(|(args, _)| *args == type_arguments)
+            {
+                return Some(instance.clone());
+            }
+        }
+
+        let mut scope = match self.definition_scope.borrow().upgrade() {
+            Some(definition_scope) => {
+                let mut scope = TypeRegister::new(&definition_scope);
+                scope.generic_instances = tr.generic_instances.clone();
+                scope
+            }
+            None => tr.clone(),
+        };
+        for (param, arg) in self.type_parameters.iter().zip(&type_arguments) {
+            scope.insert_type_with_name(arg.clone(), param.clone());
+        }
+        let name = format!("{}<{}>", self.name, type_arguments.iter().join(", "));
+        let location = node.to_source_location();
+        let create_instance = |diag: &mut BuildDiagnostics| match &self.node {
+            Either::Left(component) => {
+                let component =
+                    Component::from_node_with_id(component.clone(), name.clone(), diag, &scope);
+                *component.generic_instantiation.borrow_mut() = Some(location.clone());
+                Either::Left(component)
+            }
+            Either::Right(struct_node) => {
+                let rust_attributes =
+                    struct_node.AtRustAttr().map(|child| vec![child.text().to_string()]);
+                let mut ty =
+                    type_struct_from_node(struct_node.ObjectType(), diag, &scope, rust_attributes);
+                if let Type::Struct { name: struct_name, .. } = &mut ty {
+                    *struct_name = Some(name.clone());
+                }
+                Either::Right(ty)
+            }
+        };
+        if is_check {
+            return Some(create_instance(&mut BuildDiagnostics::default()));
+        }
+        let instance = with_instance_diagnostics(diag, &name, &location, create_instance);
+        self.instances.borrow_mut().push((type_arguments, instance.clone()));
+        tr.generic_instances.borrow_mut().push((self.clone(), instance.clone()));
+        Some(instance)
+    }
+}
+
+/// Returns true if the type is, or contains, a type parameter
+fn contains_type_parameter(ty: &Type) -> bool {
+    match ty {
+        Type::TypeParameter(_) => true,
+        Type::Array(ty) | Type::Optional(ty) => contains_type_parameter(ty),
+        Type::Struct { fields, .. } => fields.values().any(contains_type_parameter),
+        _ => false,
+    }
+}
+
+/// Calls `f` with diagnostics that are then reported to `diag` at `location`, where the instance
+/// `name` of a generic was instantiated.
+///
+/// The body of the instance is in the declaration of the generic, so its diagnostics are
+/// reported at the place that is responsible for them. The ones that are exactly the same as a
+/// diagnostic already reported when checking the declaration are not repeated.
+pub fn with_instance_diagnostics<R>(
+    diag: &mut BuildDiagnostics,
+    name: &str,
+    location: &SourceLocation,
+    f: impl FnOnce(&mut BuildDiagnostics) -> R,
+) -> R {
+    let mut instance_diag = BuildDiagnostics::default();
+    instance_diag.enable_experimental = diag.enable_experimental;
+    // The passes rely on `has_error()` to know if an error was already reported
+    let previous_error = diag.iter().find(|d| d.level() == DiagnosticLevel::Error).cloned();
+    let skip = previous_error.is_some() as usize;
+    if let Some(error) = previous_error {
+        instance_diag.push_compiler_error(error);
+    }
+    let result = f(&mut instance_diag);
+    for d in instance_diag.into_iter().skip(skip) {
+        let already_reported = diag.iter().any(|reported| {
+            reported.level() == d.level()
+                && reported.message() == d.message()
+                && reported.source_file() == d.source_file()
+                && reported.line_column() == d.line_column()
+        });
+        if !already_reported {
+            diag.push_diagnostic_with_span(
+                format!("{} (in '{}')", d.message(), name),
+                location.clone(),
+                d.level(),
+            );
+        }
+    }
+    result
+}
+
+/// Report an error if the names that the generators give to the instances of the generics, such
+/// as `Pair_int_string` for `Pair<int, string>`, are the same as the name of another type.
+fn check_generic_instance_names(
+    instances: &[(Rc<GenericDeclaration>, Either<Rc<Component>, Type>)],
+    tr: &TypeRegister,
+    diag: &mut BuildDiagnostics,
+) {
+    let generated_name = |name: &str| crate::generator::mangle_generic_name(name).replace('-', "_");
+    let mut names = tr
+        .all_types()
+        .into_keys()
+        .chain(tr.all_elements().into_keys())
+        .map(|name| (generated_name(&name), name))
+        .collect::<HashMap<_, _>>();
+    for (generic, instance) in instances {
+        let name = match instance {
+            Either::Left(compo) => compo.id.clone(),
+            Either::Right(ty) => ty.to_string(),
+        };
+        match names.entry(generated_name(&name)) {
+            std::collections::hash_map::Entry::Occupied(other) if *other.get() != name => {
+                let message = format!(
+                    "'{}' and '{}' have the same name '{}' in the generated code",
+                    name,
+                    other.get(),
+                    other.key()
+                );
+                match &generic.node {
+                    Either::Left(n) => diag.push_error(message, &n.DeclaredIdentifier()),
+                    Either::Right(n) => diag.push_error(message, &n.DeclaredIdentifier()),
+                }
+            }
+            std::collections::hash_map::Entry::Occupied(_) => {}
+            std::collections::hash_map::Entry::Vacant(e) => {
+                e.insert(name);
+            }
+        }
+    }
+}
+
 /// A component is a type in the language which can be instantiated,
 /// Or is materialized for repeated expression.
 #[derive(Default, Debug)]
@@ -351,6 +734,13 @@ pub struct Component {
     /// This is the main entry point for the code generators. Such a component
     /// should have the full API, etc.
     pub is_root_component: Cell<bool>,
+
+    /// The interfaces listed after `implements` in the declaration of this component
+    pub implements: Vec<Rc<Interface>>,
+
+    /// When this component is an instance of a generic component, where it was first
+    /// instantiated. Its diagnostics are reported there (see [`Component::with_diagnostics`]).
+    pub generic_instantiation: RefCell<Option<SourceLocation>>,
 }
 
 impl Component {
@@ -358,12 +748,53 @@ pub fn from_node(
         node: syntax_nodes::Component,
         diag: &mut BuildDiagnostics,
         tr: &TypeRegister,
+    ) -> Rc<Self> {
+        let id = parser::identifier_text(&node.DeclaredIdentifier()).unwrap_or_default();
+        Self::from_node_with_id(node, id, diag, tr)
+    }
+
+    /// Calls `f` with the diagnostics in which the issues of this component are reported.
+    ///
+    /// These are the diagnostics of the instances of generics (see [`with_instance_diagnostics`])
+    /// if this component is one.
+    pub fn with_diagnostics<R>(
+        &self,
+        diag: &mut BuildDiagnostics,
+        f: impl FnOnce(&mut BuildDiagnostics) -> R,
+    ) -> R {
+        let generic_instantiation = self.generic_instantiation.borrow().clone();
+        match generic_instantiation {
+            Some(location) => with_instance_diagnostics(diag, &self.id, &location, f),
+            None => f(diag),
+        }
+    }
+
+    fn from_node_with_id(
+        node: syntax_nodes::Component,
+        id: String,
+        diag: &mut BuildDiagnostics,
+        tr: &TypeRegister,
     ) -> Rc<Self> {
         let mut child_insertion_point = None;
         let is_legacy_syntax = node.child_token(SyntaxKind::ColonEqual).is_some();
+        let implements = node
+            .ImplementsSpecifier()
+            .into_iter()
+            .flat_map(|i| i.QualifiedName())
+            .filter_map(|qualified_name| {
+                let name = QualifiedTypeName::from_node(qualified_name.clone());
+                match tr.lookup_qualified(&name.members) {
+                    Type::Interface(interface) => Some((interface, qualified_name)),
+                    _ => {
+                        diag.push_error(format!("'{name}' is not an interface"), &qualified_name);
+                        None
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
         let c = Component {
             node: Some(node.clone().into()),
-            id: parser::identifier_text(&node.DeclaredIdentifier()).unwrap_or_default(),
+            id,
             root_element: Element::from_node(
                 node.Element(),
                 "root".into(),
@@ -378,8 +809,10 @@ pub fn from_node(
                 tr,
             ),
             child_insertion_point: RefCell::new(child_insertion_point),
+            implements: implements.iter().map(|(interface, _)| interface.clone()).collect(),
             ..Default::default()
         };
+        check_implemented_interfaces(&c, &implements, diag);
         let c = Rc::new(c);
         let weak = Rc::downgrade(&c);
         recurse_elem(&c.root_element, &(), &mut |e, _| {
@@ -863,18 +1296,39 @@ pub fn from_node(
         let base_type = if let Some(base_node) = node.QualifiedName() {
             let base = QualifiedTypeName::from_node(base_node.clone());
             let base_string = base.to_string();
-            match parent_type.lookup_type_for_child_element(&base_string, tr) {
-                Ok(ElementType::Component(c)) if c.is_global() => {
-                    diag.push_error(
-                        "Cannot create an instance of a global component".into(),
-                        &base_node,
-                    );
-                    ElementType::Error
+            if let Some(type_arguments) = node.TypeArguments() {
+                match instantiate_generic(&base_node, type_arguments, diag, tr) {
+                    Some(Either::Left(c)) => ElementType::Component(c),
+                    Some(Either::Right(ty)) => {
+                        diag.push_error(
+                            format!("'{}' cannot be used as an element", ty),
+                            &base_node,
+                        );
+                        ElementType::Error
+                    }
+                    None => ElementType::Error,
                 }
-                Ok(ty) => ty,
-                Err(err) => {
-                    diag.push_error(err, &base_node);
-                    ElementType::Error
+            } else {
+                match parent_type.lookup_type_for_child_element(&base_string, tr) {
+                    Ok(ElementType::Component(c)) if c.is_global() => {
+                        diag.push_error(
+                            "Cannot create an instance of a global component".into(),
+                            &base_node,
+                        );
+                        ElementType::Error
+                    }
+                    Ok(ty) => ty,
+                    Err(_) if tr.lookup_generic(&base_string).is_some() => {
+                        diag.push_error(
+                            format!("Missing type arguments for '{}'", base_string),
+                            &base_node,
+                        );
+                        ElementType::Error
+                    }
+                    Err(err) => {
+                        diag.push_error(err, &base_node);
+                        ElementType::Error
+                    }
                 }
             }
         } else if parent_type == ElementType::Global {
@@ -960,28 +1414,7 @@ pub fn from_node(
                 }
             }
 
-            let mut visibility = None;
-            for token in prop_decl.children_with_tokens() {
-                if token.kind() != SyntaxKind::Identifier {
-                    continue;
-                }
-                match (token.as_token().unwrap().text(), visibility) {
-                    ("in", None) => visibility = Some(PropertyVisibility::Input),
-                    ("in", Some(_)) => diag.push_error("Extra 'in' keyword".into(), &token),
-                    ("out", None) => visibility = Some(PropertyVisibility::Output),
-                    ("out", Some(_)) => diag.push_error("Extra 'out' keyword".into(), &token),
-                    ("in-out" | "in_out", None) => visibility = Some(PropertyVisibility::InOut),
-                    ("in-out" | "in_out", Some(_)) => {
-                        diag.push_error("Extra 'in-out' keyword".into(), &token)
-                    }
-                    ("private", None) => visibility = Some(PropertyVisibility::Private),
-                    ("private", Some(_)) => {
-                        diag.push_error("Extra 'private' keyword".into(), &token)
-                    }
-                    _ => (),
-                }
-            }
-            let visibility = visibility.unwrap_or({
+            let visibility = property_visibility_from_node(&prop_decl, diag).unwrap_or({
                 if is_legacy_syntax {
                     PropertyVisibility::InOut
                 } else {
@@ -1757,6 +2190,155 @@ fn apply_default_type_properties(element: &mut Element) {
     }
 }
 
+/// Return the visibility given by the `in`, `out`, `in-out`, or `private` keyword of a property declaration
+fn property_visibility_from_node(
+    prop_decl: &syntax_nodes::PropertyDeclaration,
+    diag: &mut BuildDiagnostics,
+) -> Option<PropertyVisibility> {
+    let mut visibility = None;
+    for token in prop_decl.children_with_tokens() {
+        if token.kind() != SyntaxKind::Identifier {
+            continue;
+        }
+        match (token.as_token().unwrap().text(), visibility) {
+            ("in", None) => visibility = Some(PropertyVisibility::Input),
+            ("in", Some(_)) => diag.push_error("Extra 'in' keyword".into(), &token),
+            ("out", None) => visibility = Some(PropertyVisibility::Output),
+            ("out", Some(_)) => diag.push_error("Extra 'out' keyword".into(), &token),
+            ("in-out" | "in_out", None) => visibility = Some(PropertyVisibility::InOut),
+            ("in-out" | "in_out", Some(_)) => {
+                diag.push_error("Extra 'in-out' keyword".into(), &token)
+            }
+            ("private", None) => visibility = Some(PropertyVisibility::Private),
+            ("private", Some(_)) => diag.push_error("Extra 'private' keyword".into(), &token),
+            _ => (),
+        }
+    }
+    visibility
+}
+
+/// Create an [`Interface`] from a [`syntax_nodes::InterfaceDeclaration`]
+fn interface_from_node(
+    node: syntax_nodes::InterfaceDeclaration,
+    name: String,
+    diag: &mut BuildDiagnostics,
+    tr: &TypeRegister,
+) -> Interface {
+    let mut members = BTreeMap::new();
+    for prop_decl in node.PropertyDeclaration() {
+        let Some(prop_name) = parser::identifier_text(&prop_decl.DeclaredIdentifier()) else {
+            continue;
+        };
+        let visibility = property_visibility_from_node(&prop_decl, diag);
+        let prop_type = match prop_decl.Type() {
+            Some(type_node) => type_from_node(type_node, diag, tr),
+            None => {
+                diag.push_error(
+                    "Properties of an interface must have a type".into(),
+                    &prop_decl.DeclaredIdentifier(),
+                );
+                Type::Invalid
+            }
+        };
+        if let Some(binding) = prop_decl.BindingExpression() {
+            diag.push_error("Properties of an interface cannot have a binding".into(), &binding);
+        }
+        if let Some(binding) = prop_decl.TwoWayBinding() {
+            diag.push_error("Properties of an interface cannot have a binding".into(), &binding);
+        }
+        let visibility = match visibility {
+            Some(PropertyVisibility::Private) | None => {
+                diag.push_error(
+                    "Properties of an interface must be declared 'in', 'out', or 'in-out'".into(),
+                    &prop_decl.DeclaredIdentifier(),
+                );
+                PropertyVisibility::InOut
+            }
+            Some(visibility) => visibility,
+        };
+        if members.insert(prop_name.clone(), (prop_type, visibility)).is_some() {
+            diag.push_error(
+                format!("Duplicated member '{prop_name}' in interface '{name}'"),
+                &prop_decl.DeclaredIdentifier(),
+            );
+        }
+    }
+    for sig_decl in node.CallbackDeclaration() {
+        let Some(callback_name) = parser::identifier_text(&sig_decl.DeclaredIdentifier()) else {
+            continue;
+        };
+        if let Some(binding) = sig_decl.TwoWayBinding() {
+            diag.push_error("Callbacks of an interface cannot be aliases".into(), &binding);
+        }
+        let args = sig_decl.Type().map(|node_ty| type_from_node(node_ty, diag, tr)).collect();
+        let return_type =
+            sig_decl.ReturnType().map(|ret_ty| Box::new(type_from_node(ret_ty.Type(), diag, tr)));
+        let callback_type = Type::Callback { return_type, args };
+        if members
+            .insert(callback_name.clone(), (callback_type, PropertyVisibility::InOut))
+            .is_some()
+        {
+            diag.push_error(
+                format!("Duplicated member '{callback_name}' in interface '{name}'"),
+                &sig_decl.DeclaredIdentifier(),
+            );
+        }
+    }
+    Interface { name, members, node: Some(node) }
+}
+
+/// Report an error for each member of the interfaces listed after `implements` that the component lacks
+fn check_implemented_interfaces(
+    component: &Component,
+    implements: &[(Rc<Interface>, syntax_nodes::QualifiedName)],
+    diag: &mut BuildDiagnostics,
+) {
+    for (interface, qualified_name) in implements {
+        for error in interface_implementation_errors(component, interface) {
+            diag.push_error(error, qualified_name);
+        }
+    }
+}
+
+/// Returns an error message for each member of the interface that the component doesn't implement
+pub fn interface_implementation_errors(
+    component: &Component,
+    interface: &Interface,
+) -> Vec<String> {
+    let mut errors = vec![];
+    for (member, (ty, visibility)) in &interface.members {
+        let lookup = component.root_element.borrow().lookup_property(member);
+        if !lookup.is_valid() {
+            errors.push(format!(
+                "'{}' does not implement '{member}' from interface '{}'",
+                component.id, interface.name
+            ));
+            continue;
+        }
+        if !matches!(lookup.property_type, Type::InferredProperty | Type::InferredCallback)
+            && lookup.property_type != *ty
+        {
+            errors.push(format!(
+                "'{member}' of '{}' has type '{}' but interface '{}' requires '{ty}'",
+                component.id, lookup.property_type, interface.name
+            ));
+            continue;
+        }
+        if !Interface::visibility_satisfies(*visibility, lookup.property_visibility) {
+            let required = match visibility {
+                PropertyVisibility::Input => "'in' or 'in-out'",
+                PropertyVisibility::Output => "'out' or 'in-out'",
+                _ => "'in-out'",
+            };
+            errors.push(format!(
+                "'{member}' of '{}' must be declared {required} to implement interface '{}'",
+                component.id, interface.name
+            ));
+        }
+    }
+    errors
+}
+
 /// Create a Type for this node
 pub fn type_from_node(
     node: syntax_nodes::Type,
@@ -1766,9 +2348,33 @@ pub fn type_from_node(
     if let Some(qualified_type_node) = node.QualifiedName() {
         let qualified_type = QualifiedTypeName::from_node(qualified_type_node.clone());
 
+        if let Some(type_arguments) = node.TypeArguments() {
+            if tr.lookup_qualified(&qualified_type.members) == Type::ComponentFactory(None) {
+                return component_factory_type_from_node(type_arguments, diag, tr);
+            }
+            return match instantiate_generic(&qualified_type_node, type_arguments, diag, tr) {
+                Some(Either::Right(ty)) => ty,
+                Some(Either::Left(_)) => {
+                    diag.push_error(
+                        format!("'{}' is not a valid type", qualified_type),
+                        &qualified_type_node,
+                    );
+                    Type::Invalid
+                }
+                None => Type::Invalid,
+            };
+        }
+
         let prop_type = tr.lookup_qualified(&qualified_type.members);
 
-        if prop_type == Type::Invalid && tr.lookup_element(&qualified_type.to_string()).is_err() {
+        if prop_type == Type::Invalid && tr.lookup_generic(&qualified_type.to_string()).is_some() {
+            diag.push_error(
+                format!("Missing type arguments for '{}'", qualified_type),
+                &qualified_type_node,
+            );
+        } else if prop_type == Type::Invalid
+            && tr.lookup_element(&qualified_type.to_string()).is_err()
+        {
             diag.push_error(format!("Unknown type '{}'", qualified_type), &qualified_type_node);
         } else if !prop_type.is_property_type() {
             diag.push_error(
@@ -1781,12 +2387,66 @@ pub fn type_from_node(
         type_struct_from_node(object_node, diag, tr, None)
     } else if let Some(array_node) = node.ArrayType() {
         Type::Array(Box::new(type_from_node(array_node.Type(), diag, tr)))
+    } else if let Some(optional_node) = node.OptionalType() {
+        match type_from_node(optional_node.Type(), diag, tr) {
+            Type::Invalid => Type::Invalid,
+            ty => Type::Optional(Box::new(ty)),
+        }
     } else {
         assert!(diag.has_error());
         Type::Invalid
     }
 }
 
+/// Instantiate the generic component or struct named `qualified_name` with the given type arguments
+fn instantiate_generic(
+    qualified_name: &syntax_nodes::QualifiedName,
+    type_arguments: syntax_nodes::TypeArguments,
+    diag: &mut BuildDiagnostics,
+    tr: &TypeRegister,
+) -> Option<Either<Rc<Component>, Type>> {
+    let name = QualifiedTypeName::from_node(qualified_name.clone()).to_string();
+    let Some(generic) = tr.lookup_generic(&name) else {
+        if tr.lookup(&name) == Type::Invalid && tr.lookup_element(&name).is_err() {
+            diag.push_error(format!("Unknown type '{}'", name), qualified_name);
+        } else {
+            diag.push_error(format!("'{}' does not have type parameters", name), &type_arguments);
+        }
+        return None;
+    };
+    let arguments = type_arguments.Type().map(|ty| type_from_node(ty, diag, tr)).collect();
+    generic.instantiate(arguments, &type_arguments, diag, tr)
+}
+
+/// Resolve the type argument of `component-factory<Interface>`
+fn component_factory_type_from_node(
+    type_arguments: syntax_nodes::TypeArguments,
+    diag: &mut BuildDiagnostics,
+    tr: &TypeRegister,
+) -> Type {
+    let mut arguments = type_arguments.Type();
+    let (Some(argument), None) = (arguments.next(), arguments.next()) else {
+        diag.push_error(
+            "'component-factory' expects exactly one interface as type argument".into(),
+            &type_arguments,
+        );
+        return Type::Invalid;
+    };
+    let interface = argument
+        .QualifiedName()
+        .map(|qn| tr.lookup_qualified(&QualifiedTypeName::from_node(qn).members));
+    match interface {
+        Some(Type::Interface(interface)) => Type::ComponentFactory(Some(interface)),
+        _ => {
+            diag.push_error(
+                format!("'{}' is not an interface", argument.text().to_string().trim()),
+                &argument,
+            );
+            Type::Invalid
+        }
+    }
+}
+
 /// Create a [`Type::Struct`] from a [`syntax_nodes::ObjectType`]
 pub fn type_struct_from_node(
     object_node: syntax_nodes::ObjectType,
@@ -2329,6 +2989,9 @@ pub struct Exports {
     #[deref]
     components_or_types: Vec<(ExportedName, Either<Rc<Component>, Type>)>,
     last_exported_component: Option<Rc<Component>>,
+    /// The exported generic components and structs. They are not part of the other exports
+    /// because they are only instantiated by the documents that import them.
+    generics: Vec<(ExportedName, Rc<GenericDeclaration>)>,
 }
 
 impl Exports {
@@ -2338,12 +3001,17 @@ pub fn from_node(
         type_registry: &TypeRegister,
         diag: &mut BuildDiagnostics,
     ) -> Self {
+        let generic_exports = RefCell::new(Vec::new());
         let resolve_export_to_inner_component_or_import =
-            |internal_name: &str, internal_name_node: &dyn Spanned, diag: &mut BuildDiagnostics| {
+            |exported_name: &ExportedName,
+             internal_name: &str,
+             internal_name_node: &dyn Spanned,
+             diag: &mut BuildDiagnostics| {
                 if let Ok(ElementType::Component(c)) = type_registry.lookup_element(internal_name) {
                     Some(Either::Left(c))
-                } else if let ty @ Type::Struct { .. } | ty @ Type::Enumeration(_) =
-                    type_registry.lookup(internal_name)
+                } else if let ty @ Type::Struct { .. }
+                | ty @ Type::Enumeration(_)
+                | ty @ Type::Interface(_) = type_registry.lookup(internal_name)
                 {
                     Some(Either::Right(ty))
                 } else if type_registry.lookup_element(internal_name).is_ok()
@@ -2354,6 +3022,9 @@ pub fn from_node(
                         internal_name_node,
                     );
                     None
+                } else if let Some(generic) = type_registry.lookup_generic(internal_name) {
+                    generic_exports.borrow_mut().push((exported_name.clone(), generic));
+                    None
                 } else {
                     diag.push_error(format!("'{}' not found", internal_name,), internal_name_node);
                     None
@@ -2398,14 +3069,14 @@ pub fn from_node(
                             (internal_name.clone(), export_specifier.ExportIdentifier().into())
                         });
 
-                    Some((
-                        ExportedName { name, name_ident },
-                        resolve_export_to_inner_component_or_import(
-                            &internal_name,
-                            &export_specifier.ExportIdentifier(),
-                            diag,
-                        )?,
-                    ))
+                    let exported_name = ExportedName { name, name_ident };
+                    let compo_or_type = resolve_export_to_inner_component_or_import(
+                        &exported_name,
+                        &internal_name,
+                        &export_specifier.ExportIdentifier(),
+                        diag,
+                    )?;
+                    Some((exported_name, compo_or_type))
                 },
             ),
         );
@@ -2419,10 +3090,15 @@ pub fn from_node(
                         String::new()
                     });
 
-                let compo_or_type =
-                    resolve_export_to_inner_component_or_import(&name, &name_ident, diag)?;
+                let exported_name = ExportedName { name: name.clone(), name_ident };
+                let compo_or_type = resolve_export_to_inner_component_or_import(
+                    &exported_name,
+                    &name,
+                    &exported_name.name_ident,
+                    diag,
+                )?;
 
-                Some((ExportedName { name, name_ident }, compo_or_type))
+                Some((exported_name, compo_or_type))
             },
         ));
 
@@ -2434,6 +3110,7 @@ pub fn from_node(
                         .StructDeclaration()
                         .map(|st| st.DeclaredIdentifier())
                         .chain(exports.EnumDeclaration().map(|en| en.DeclaredIdentifier()))
+                        .chain(exports.InterfaceDeclaration().map(|i| i.DeclaredIdentifier()))
                 })
                 .filter_map(|name_ident| {
                     let name = parser::identifier_text(&name_ident).unwrap_or_else(|| {
@@ -2441,12 +3118,16 @@ pub fn from_node(
                         String::new()
                     });
 
-                    let name_ident = name_ident.into();
+                    let exported_name =
+                        ExportedName { name: name.clone(), name_ident: name_ident.into() };
+                    let compo_or_type = resolve_export_to_inner_component_or_import(
+                        &exported_name,
+                        &name,
+                        &exported_name.name_ident,
+                        diag,
+                    )?;
 
-                    let compo_or_type =
-                        resolve_export_to_inner_component_or_import(&name, &name_ident, diag)?;
-
-                    Some((ExportedName { name, name_ident }, compo_or_type))
+                    Some((exported_name, compo_or_type))
                 }),
         );
 
@@ -2471,7 +3152,24 @@ pub fn from_node(
             sorted_deduped_exports.push((exported_name, compo_or_type));
         }
 
-        if sorted_deduped_exports.is_empty() {
+        let mut generics: Vec<(ExportedName, Rc<GenericDeclaration>)> = Vec::new();
+        for (exported_name, generic) in generic_exports.into_inner() {
+            let is_duplicate = sorted_deduped_exports
+                .iter()
+                .map(|(name, _)| name)
+                .chain(generics.iter().map(|(name, _)| name))
+                .any(|name| name.name == exported_name.name);
+            if is_duplicate {
+                diag.push_error(
+                    format!("Duplicated export '{}'", exported_name.name),
+                    &exported_name.name_ident,
+                );
+                continue;
+            }
+            generics.push((exported_name, generic));
+        }
+
+        if sorted_deduped_exports.is_empty() && generics.is_empty() {
             if let Some(last_compo) = inner_components.last() {
                 if last_compo.is_global() {
                     diag.push_warning(
@@ -2494,7 +3192,7 @@ pub fn from_node(
             last_exported_component = inner_components.last().cloned();
         }
 
-        Self { components_or_types: sorted_deduped_exports, last_exported_component }
+        Self { components_or_types: sorted_deduped_exports, last_exported_component, generics }
     }
 
     pub fn add_reexports(
@@ -2504,7 +3202,10 @@ pub fn add_reexports(
     ) {
         for export in other_exports {
             match self.components_or_types.binary_search_by(|entry| entry.0.cmp(&export.0)) {
-                Ok(_) => {
+                Err(insert_pos) if self.find_generic(&export.0).is_none() => {
+                    self.components_or_types.insert(insert_pos, export);
+                }
+                _ => {
                     diag.push_warning(
                         format!(
                             "'{}' is already exported in this file; it will not be re-exported",
@@ -2513,9 +3214,26 @@ pub fn add_reexports(
                         &export.0.name_ident,
                     );
                 }
-                Err(insert_pos) => {
-                    self.components_or_types.insert(insert_pos, export);
-                }
+            }
+        }
+    }
+
+    pub fn add_reexported_generics(
+        &mut self,
+        other_generics: impl IntoIterator<Item = (ExportedName, Rc<GenericDeclaration>)>,
+        diag: &mut BuildDiagnostics,
+    ) {
+        for (exported_name, generic) in other_generics {
+            if self.find(&exported_name).is_some() || self.find_generic(&exported_name).is_some() {
+                diag.push_warning(
+                    format!(
+                        "'{}' is already exported in this file; it will not be re-exported",
+                        &*exported_name
+                    ),
+                    &exported_name.name_ident,
+                );
+            } else {
+                self.generics.push((exported_name, generic));
             }
         }
     }
@@ -2526,6 +3244,18 @@ pub fn find(&self, name: &str) -> Option<Either<Rc<Component>, Type>> {
             .ok()
             .map(|index| self.components_or_types[index].1.clone())
     }
+
+    pub fn find_generic(&self, name: &str) -> Option<Rc<GenericDeclaration>> {
+        self.generics
+            .iter()
+            .find(|(exported_name, _)| exported_name.as_str() == name)
+            .map(|(_, generic)| generic.clone())
+    }
+
+    /// The exported generic components and structs
+    pub fn generics(&self) -> &[(ExportedName, Rc<GenericDeclaration>)] {
+        &self.generics
+    }
 }
 
 impl std::iter::IntoIterator for Exports {
@@ -300,6 +300,9 @@ fn source_file(&self) -> Option<&SourceFile> {
         Arrow -> "->",
         OrOr -> "||",
         AndAnd -> "&&",
+        DoubleDot -> "..",
+        QuestionDot -> "?.",
+        QuestionQuestion -> "??",
         LBrace -> "{",
         RBrace -> "}",
         LParent -> "(",
@@ -326,12 +329,15 @@ fn source_file(&self) -> Option<&SourceFile> {
     }
     // syntax kind
     {
-        Document -> [ *Component, *ExportsList, *ImportSpecifier, *StructDeclaration, *EnumDeclaration ],
+        Document -> [ *Component, *ExportsList, *ImportSpecifier, *StructDeclaration, *EnumDeclaration,
+                      *InterfaceDeclaration ],
         /// `DeclaredIdentifier := Element { ... }`
-        Component -> [ DeclaredIdentifier, Element ],
+        Component -> [ DeclaredIdentifier, ?TypeParameters, ?ImplementsSpecifier, Element ],
+        /// `implements Foo, Bar` after the name of a component
+        ImplementsSpecifier -> [ *QualifiedName ],
         /// `id := Element { ... }`
         SubElement -> [ Element ],
-        Element -> [ ?QualifiedName, *PropertyDeclaration, *Binding, *CallbackConnection,
+        Element -> [ ?QualifiedName, ?TypeArguments, *PropertyDeclaration, *Binding, *CallbackConnection,
                      *CallbackDeclaration, *Function, *SubElement, *RepeatedElement,
                      *PropertyAnimation, *PropertyChangedCallback,
                      *TwoWayBinding, *States, *Transitions, ?ChildrenPlaceholder ],
@@ -361,18 +367,29 @@ fn source_file(&self) -> Option<&SourceFile> {
         /// the right-hand-side of a binding
         // Fixme: the test should be a or
         BindingExpression-> [ ?CodeBlock, ?Expression ],
-        CodeBlock-> [ *Expression, *ReturnStatement ],
+        CodeBlock-> [ *Expression, *ReturnStatement, *LetStatement, *BreakStatement,
+                      *ContinueStatement ],
         ReturnStatement -> [ ?Expression ],
+        /// `let foo: type = expr;`
+        LetStatement -> [ DeclaredIdentifier, ?Type, Expression ],
+        /// `for x in model { ... }` or `for i in start..end { ... }`
+        ForStatement -> [ DeclaredIdentifier, Expression, ?ForRangeEnd, CodeBlock ],
+        /// The `..end` part of the range in a `ForStatement`
+        ForRangeEnd -> [ Expression ],
+        BreakStatement -> [],
+        ContinueStatement -> [],
         // FIXME: the test should test that as alternative rather than several of them (but it can also be a literal)
         Expression-> [ ?Expression, ?FunctionCallExpression, ?IndexExpression, ?SelfAssignment,
                        ?ConditionalExpression, ?QualifiedName, ?BinaryExpression, ?Array, ?ObjectLiteral,
                        ?UnaryOpExpression, ?CodeBlock, ?StringTemplate, ?AtImageUrl, ?AtGradient, ?AtTr,
-                       ?MemberAccess ],
+                       ?MemberAccess, ?ForStatement, ?Lambda ],
+        /// `x => expr`, only allowed as argument of the model operations
+        Lambda -> [ DeclaredIdentifier, Expression ],
         /// Concatenate the Expressions to make a string (usually expended from a template string)
         StringTemplate -> [*Expression],
         /// `@image-url("foo.png")`
         AtImageUrl -> [],
-        /// `@linear-gradient(...)` or `@radial-gradient(...)`
+        /// `@linear-gradient(...)`, `@radial-gradient(...)` or `@conic-gradient(...)`
         AtGradient -> [*Expression],
         /// `@tr("foo", ...)`  // the string is a StringLiteral
         AtTr -> [?TrContext, ?TrPlural, *Expression],
@@ -393,6 +410,7 @@ fn source_file(&self) -> Option<&SourceFile> {
         /// `- expr`
         UnaryOpExpression -> [Expression],
         /// `(foo).bar`, where `foo` is the base expression, and `bar` is a Identifier.
+        /// Or `foo?.bar` if `foo` is optional.
         MemberAccess -> [Expression],
         /// `[ ... ]`
         Array -> [ *Expression ],
@@ -411,7 +429,8 @@ fn source_file(&self) -> Option<&SourceFile> {
         /// There is an identifier "in" or "out", the DeclaredIdentifier is the state name
         Transition -> [?DeclaredIdentifier, *PropertyAnimation],
         /// Export a set of declared components by name
-        ExportsList -> [ *ExportSpecifier, ?Component, *StructDeclaration, *ExportModule, *EnumDeclaration ],
+        ExportsList -> [ *ExportSpecifier, ?Component, *StructDeclaration, *ExportModule, *EnumDeclaration,
+                         *InterfaceDeclaration ],
         /// Declare the first identifier to be exported, either under its name or instead
         /// under the name of the second identifier.
         ExportSpecifier -> [ ExportIdentifier, ?ExportName ],
@@ -427,15 +446,23 @@ fn source_file(&self) -> Option<&SourceFile> {
         ExternalName -> [],
         InternalName -> [],
         /// The representation of a type
-        Type -> [ ?QualifiedName, ?ObjectType, ?ArrayType ],
+        Type -> [ ?QualifiedName, ?TypeArguments, ?ObjectType, ?ArrayType, ?OptionalType ],
         /// `{foo: string, bar: string} `
         ObjectType ->[ *ObjectTypeMember ],
         /// `foo: type` inside an ObjectType
         ObjectTypeMember -> [ Type ],
         /// `[ type ]`
         ArrayType -> [ Type ],
+        /// `type?`
+        OptionalType -> [ Type ],
         /// `struct Foo { ... }`
-        StructDeclaration -> [DeclaredIdentifier, ObjectType, ?AtRustAttr],
+        StructDeclaration -> [DeclaredIdentifier, ?TypeParameters, ObjectType, ?AtRustAttr],
+        /// `<T, U>` after the name of a generic component or struct. Each parameter is a DeclaredIdentifier
+        TypeParameters -> [ *DeclaredIdentifier ],
+        /// `<int, string>` after the name of a generic component or struct when it is used
+        TypeArguments -> [ *Type ],
+        /// `interface Foo { in property <int> bar; callback baz(); }`
+        InterfaceDeclaration -> [ DeclaredIdentifier, *PropertyDeclaration, *CallbackDeclaration ],
         /// `enum Foo { bli, bla, blu }`
         EnumDeclaration -> [DeclaredIdentifier, *EnumValue, ?AtRustAttr],
         /// The value is a Identifier
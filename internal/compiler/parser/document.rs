@@ -1,9 +1,13 @@
 // Copyright © SixtyFPS GmbH <info@slint.dev>
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.2 OR LicenseRef-Slint-commercial
 
-use super::element::{parse_element, parse_element_content};
+use super::element::{
+    parse_callback_declaration, parse_element, parse_element_content, parse_property_declaration,
+};
 use super::prelude::*;
-use super::r#type::{parse_enum_declaration, parse_rustattr, parse_struct_declaration};
+use super::r#type::{
+    parse_enum_declaration, parse_rustattr, parse_struct_declaration, parse_type_parameters,
+};
 
 #[cfg_attr(test, parser_test)]
 /// ```test,Document
@@ -15,6 +19,7 @@
 /// struct Foo { foo: foo }
 /// enum Foo { hello }
 /// @rust-attr(...) struct X {}
+/// interface I { in property <int> foo; }
 /// /* empty */
 /// ```
 pub fn parse_document(p: &mut impl Parser) -> bool {
@@ -52,6 +57,11 @@ pub fn parse_document(p: &mut impl Parser) -> bool {
                     break;
                 }
             }
+            "interface" if p.nth(1).kind() == SyntaxKind::Identifier => {
+                if !parse_interface_declaration(&mut *p, None) {
+                    break;
+                }
+            }
             "@" if p.nth(1).as_str() == "rust-attr" => {
                 let checkpoint = p.checkpoint();
                 if !parse_rustattr(&mut *p) {
@@ -99,6 +109,9 @@ pub fn parse_document(p: &mut impl Parser) -> bool {
 /// global Struct { property<int> xx; }
 /// component C { property<int> xx; }
 /// component C inherits D { }
+/// component C<T> inherits D<T> { in property <[T]> model; }
+/// component C implements I inherits D { }
+/// component C implements I, J { }
 /// ```
 pub fn parse_component(p: &mut impl Parser) -> bool {
     let simple_component = p.nth(1).kind() == SyntaxKind::ColonEqual;
@@ -118,6 +131,12 @@ pub fn parse_component(p: &mut impl Parser) -> bool {
         drop(p.start_node(SyntaxKind::Element));
         return false;
     }
+    if is_new_component && p.peek().kind() == SyntaxKind::LAngle {
+        parse_type_parameters(&mut *p);
+    }
+    if is_new_component && p.peek().as_str() == "implements" {
+        parse_implements_specifier(&mut *p);
+    }
     if is_global {
         if p.peek().kind() == SyntaxKind::ColonEqual {
             p.warning("':=' to declare a global is deprecated. Remove the ':='");
@@ -154,6 +173,71 @@ pub fn parse_component(p: &mut impl Parser) -> bool {
     parse_element(&mut *p)
 }
 
+#[cfg_attr(test, parser_test)]
+/// ```test,ImplementsSpecifier
+/// implements Foo
+/// implements Foo, Bar
+/// implements Module.Foo
+/// ```
+fn parse_implements_specifier(p: &mut impl Parser) {
+    debug_assert_eq!(p.peek().as_str(), "implements");
+    let mut p = p.start_node(SyntaxKind::ImplementsSpecifier);
+    p.consume(); // "implements"
+    loop {
+        if !parse_qualified_name(&mut *p) {
+            return;
+        }
+        if !p.test(SyntaxKind::Comma) {
+            return;
+        }
+    }
+}
+
+#[cfg_attr(test, parser_test)]
+/// ```test,InterfaceDeclaration
+/// interface Foo { }
+/// interface Foo { in property <int> bar; out property <string> baz; }
+/// interface Foo { in-out property <bool> checked; callback clicked(); pure callback get(int) -> string; }
+/// ```
+fn parse_interface_declaration<P: Parser>(p: &mut P, checkpoint: Option<P::Checkpoint>) -> bool {
+    debug_assert_eq!(p.peek().as_str(), "interface");
+    let mut p = p.start_node_at(checkpoint, SyntaxKind::InterfaceDeclaration);
+    p.consume(); // "interface"
+    {
+        let mut p = p.start_node(SyntaxKind::DeclaredIdentifier);
+        p.expect(SyntaxKind::Identifier);
+    }
+    if !p.expect(SyntaxKind::LBrace) {
+        return false;
+    }
+    let mut had_parse_error = false;
+    loop {
+        match p.peek().as_str() {
+            "}" => {
+                p.consume();
+                return true;
+            }
+            "callback" => parse_callback_declaration(&mut *p),
+            "pure" if p.nth(1).as_str() == "callback" => parse_callback_declaration(&mut *p),
+            "property" => parse_property_declaration(&mut *p),
+            "in" | "out" | "in-out" | "in_out" | "private" if p.nth(1).as_str() == "property" => {
+                parse_property_declaration(&mut *p)
+            }
+            _ => {
+                if p.peek().kind() == SyntaxKind::Eof {
+                    p.error("Expected '}'");
+                    return false;
+                }
+                p.consume();
+                if !had_parse_error {
+                    p.error("Only property and callback declarations are allowed in an interface");
+                    had_parse_error = true;
+                }
+            }
+        }
+    }
+}
+
 #[cfg_attr(test, parser_test)]
 /// ```test,QualifiedName
 /// Rectangle
@@ -185,6 +269,7 @@ pub fn parse_qualified_name(p: &mut impl Parser) -> bool {
 /// export Foo := Item { }
 /// export struct Foo := { foo: bar }
 /// export enum Foo { bar }
+/// export interface Foo { in property <int> bar; }
 /// export * from "foo";
 /// ```
 fn parse_export<P: Parser>(p: &mut P, checkpoint: Option<P::Checkpoint>) -> bool {
@@ -217,6 +302,8 @@ fn parse_export<P: Parser>(p: &mut P, checkpoint: Option<P::Checkpoint>) -> bool
         parse_struct_declaration(&mut *p, checkpoint)
     } else if p.peek().as_str() == "enum" {
         parse_enum_declaration(&mut *p, checkpoint)
+    } else if p.peek().as_str() == "interface" && p.nth(1).kind() == SyntaxKind::Identifier {
+        parse_interface_declaration(&mut *p, checkpoint)
     } else if p.peek().kind == SyntaxKind::Star {
         let mut p = p.start_node(SyntaxKind::ExportModule);
         p.consume(); // *
@@ -6,17 +6,24 @@
 use super::document::parse_qualified_name;
 use super::expressions::parse_expression;
 use super::prelude::*;
-use super::r#type::parse_type;
+use super::r#type::{parse_type, parse_type_arguments};
 use super::statements::parse_statement;
 
 #[cfg_attr(test, parser_test)]
 /// ```test,Element
 /// Item { }
 /// Item { property: value; SubElement { } }
+/// Picker<string> { }
 /// ```
 pub fn parse_element(p: &mut impl Parser) -> bool {
     let mut p = p.start_node(SyntaxKind::Element);
-    if !(parse_qualified_name(&mut *p) && p.expect(SyntaxKind::LBrace)) {
+    if !parse_qualified_name(&mut *p) {
+        return false;
+    }
+    if p.nth(0).kind() == SyntaxKind::LAngle {
+        parse_type_arguments(&mut *p);
+    }
+    if !p.expect(SyntaxKind::LBrace) {
         return false;
     }
 
@@ -50,6 +57,7 @@ pub fn parse_element_content(p: &mut impl Parser) {
             SyntaxKind::Identifier => match p.nth(1).kind() {
                 SyntaxKind::Colon => parse_property_binding(&mut *p),
                 SyntaxKind::ColonEqual | SyntaxKind::LBrace => parse_sub_element(&mut *p),
+                SyntaxKind::LAngle if p.peek().as_str() != "property" => parse_sub_element(&mut *p),
                 SyntaxKind::FatArrow | SyntaxKind::LParent if p.peek().as_str() != "if" => {
                     parse_callback_connection(&mut *p)
                 }
@@ -135,6 +143,7 @@ pub fn parse_element_content(p: &mut impl Parser) {
 /// Bar {}
 /// foo := Bar {}
 /// Bar { x : y ; }
+/// foo := Bar<int> {}
 /// ```
 /// Must consume at least one token
 fn parse_sub_element(p: &mut impl Parser) {
@@ -304,7 +313,7 @@ fn parse_two_way_binding(p: &mut impl Parser) {
 /// callback foobar <=> elem.foobar;
 /// ```
 /// Must consume at least one token
-fn parse_callback_declaration(p: &mut impl Parser) {
+pub fn parse_callback_declaration(p: &mut impl Parser) {
     let mut p = p.start_node(SyntaxKind::CallbackDeclaration);
     if p.peek().as_str() == "pure" {
         p.consume();
@@ -355,7 +364,7 @@ fn parse_callback_declaration(p: &mut impl Parser) {
 /// property<string> text <=> two.way;
 /// property alias <=> two.way;
 /// ```
-fn parse_property_declaration(p: &mut impl Parser) {
+pub fn parse_property_declaration(p: &mut impl Parser) {
     let checkpoint = p.checkpoint();
     while matches!(p.peek().as_str(), "in" | "out" | "in-out" | "in_out" | "private") {
         p.consume();
@@ -30,6 +30,8 @@
 /// array[index]
 /// {object:42}
 /// "foo".bar.something().something.xx({a: 1.foo}.a)
+/// foo?.bar?.baz ?? 42
+/// a ?? b ?? c
 /// ```
 pub fn parse_expression(p: &mut impl Parser) -> bool {
     p.peek(); // consume the whitespace so they aren't part of the Expression node
@@ -41,6 +43,8 @@ pub fn parse_expression(p: &mut impl Parser) -> bool {
 enum OperatorPrecedence {
     /// ` ?: `
     Default,
+    /// `??`
+    NullCoalescing,
     /// `||`, `&&`
     Logical,
     /// `==` `!=` `>=` `<=` `<` `>`
@@ -91,12 +95,12 @@ fn parse_expression_helper(p: &mut impl Parser, precedence: OperatorPrecedence)
 
     loop {
         match p.nth(0).kind() {
-            SyntaxKind::Dot => {
+            SyntaxKind::Dot | SyntaxKind::QuestionDot => {
                 {
                     let _ = p.start_node_at(checkpoint.clone(), SyntaxKind::Expression);
                 }
                 let mut p = p.start_node_at(checkpoint.clone(), SyntaxKind::MemberAccess);
-                p.consume(); // '.'
+                p.consume(); // '.' or '?.'
                 if !p.expect(SyntaxKind::Identifier) {
                     return false;
                 }
@@ -201,6 +205,19 @@ fn parse_expression_helper(p: &mut impl Parser, precedence: OperatorPrecedence)
         parse_expression_helper(&mut *p, OperatorPrecedence::Logical);
     }
 
+    if precedence >= OperatorPrecedence::NullCoalescing {
+        return true;
+    }
+
+    while p.nth(0).kind() == SyntaxKind::QuestionQuestion {
+        {
+            let _ = p.start_node_at(checkpoint.clone(), SyntaxKind::Expression);
+        }
+        let mut p = p.start_node_at(checkpoint.clone(), SyntaxKind::BinaryExpression);
+        p.consume();
+        parse_expression_helper(&mut *p, OperatorPrecedence::NullCoalescing);
+    }
+
     if p.nth(0).kind() == SyntaxKind::Question {
         {
             let _ = p.start_node_at(checkpoint.clone(), SyntaxKind::Expression);
@@ -232,13 +249,16 @@ fn parse_at_keyword(p: &mut impl Parser) {
         "radial-gradient" | "radial_gradient" => {
             parse_gradient(p);
         }
+        "conic-gradient" | "conic_gradient" => {
+            parse_gradient(p);
+        }
         "tr" => {
             parse_tr(p);
         }
         _ => {
             p.consume();
             p.test(SyntaxKind::Identifier); // consume the identifier, so that autocomplete works
-            p.error("Expected 'image-url', 'tr', 'linear-gradient' or 'radial-gradient' after '@'");
+            p.error("Expected 'image-url', 'tr', 'linear-gradient', 'radial-gradient' or 'conic-gradient' after '@'");
         }
     }
 }
@@ -292,12 +312,19 @@ fn parse_object_notation(p: &mut impl Parser) {
 /// (foo)
 /// (foo, bar, foo)
 /// (foo, bar(), xx+xx,)
+/// (x => x > 5)
+/// (item => item.name, 3)
 /// ```
 fn parse_function_arguments(p: &mut impl Parser) {
     p.expect(SyntaxKind::LParent);
 
     while p.nth(0).kind() != SyntaxKind::RParent {
-        parse_expression(&mut *p);
+        if p.nth(0).kind() == SyntaxKind::Identifier && p.nth(1).kind() == SyntaxKind::FatArrow {
+            let mut p = p.start_node(SyntaxKind::Expression);
+            parse_lambda(&mut *p);
+        } else {
+            parse_expression(&mut *p);
+        }
         if !p.test(SyntaxKind::Comma) {
             break;
         }
@@ -305,6 +332,21 @@ fn parse_function_arguments(p: &mut impl Parser) {
     p.expect(SyntaxKind::RParent);
 }
 
+#[cfg_attr(test, parser_test)]
+/// ```test,Lambda
+/// x => x > 5
+/// item => item.name.to-lower()
+/// ```
+fn parse_lambda(p: &mut impl Parser) {
+    let mut p = p.start_node(SyntaxKind::Lambda);
+    {
+        let mut p = p.start_node(SyntaxKind::DeclaredIdentifier);
+        p.expect(SyntaxKind::Identifier);
+    }
+    p.expect(SyntaxKind::FatArrow);
+    parse_expression(&mut *p);
+}
+
 #[cfg_attr(test, parser_test)]
 /// ```test,StringTemplate
 /// "foo\{bar}"
@@ -340,6 +382,8 @@ fn parse_template_string(p: &mut impl Parser) {
 /// @linear-gradient(217deg, rgba(255,0,0,0.8), rgba(255,0,0,0) 70.71%)
 /// @linear_gradient(217deg, rgba(255,0,0,0.8), rgba(255,0,0,0) 70.71%)
 /// @radial-gradient(circle, #e66465, blue 50%, #9198e5)
+/// @conic-gradient(red, yellow, lime, aqua, blue, magenta, red)
+/// @conic-gradient(from 90deg at 25% 50%, #e66465, #9198e5 50%)
 /// ```
 fn parse_gradient(p: &mut impl Parser) {
     let mut p = p.start_node(SyntaxKind::AtGradient);
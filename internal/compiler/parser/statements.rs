@@ -4,6 +4,7 @@
 use super::element::parse_code_block;
 use super::expressions::parse_expression;
 use super::prelude::*;
+use super::r#type::parse_type;
 
 #[cfg_attr(test, parser_test)]
 /// ```test
@@ -14,6 +15,11 @@
 /// if (true) { foo = bar; } else { bar = foo;  }
 /// return;
 /// if (true) { return 42; }
+/// let foo = 42;
+/// let foo: int = 42;
+/// for x in model { foo += x; }
+/// for i in 0..10 { if (i > 5) { break; } }
+/// continue;
 /// ```
 pub fn parse_statement(p: &mut impl Parser) -> bool {
     if p.nth(0).kind() == SyntaxKind::RBrace {
@@ -40,6 +46,34 @@ pub fn parse_statement(p: &mut impl Parser) -> bool {
         return true;
     }
 
+    if p.peek().as_str() == "let" && p.nth(1).kind() == SyntaxKind::Identifier {
+        parse_let_statement(p);
+        return true;
+    }
+
+    if p.peek().as_str() == "for"
+        && p.nth(1).kind() == SyntaxKind::Identifier
+        && p.nth(2).as_str() == "in"
+    {
+        let mut p = p.start_node(SyntaxKind::Expression);
+        parse_for_statement(&mut *p);
+        return true;
+    }
+
+    if matches!(p.peek().as_str(), "break" | "continue")
+        && matches!(p.nth(1).kind(), SyntaxKind::Semicolon | SyntaxKind::RBrace)
+    {
+        let kind = if p.peek().as_str() == "break" {
+            SyntaxKind::BreakStatement
+        } else {
+            SyntaxKind::ContinueStatement
+        };
+        let mut p = p.start_node_at(checkpoint, kind);
+        p.expect(SyntaxKind::Identifier); // "break" or "continue"
+        p.test(SyntaxKind::Semicolon);
+        return true;
+    }
+
     if p.peek().as_str() == "return" {
         let mut p = p.start_node_at(checkpoint, SyntaxKind::ReturnStatement);
         p.expect(SyntaxKind::Identifier); // "return"
@@ -67,6 +101,55 @@ pub fn parse_statement(p: &mut impl Parser) -> bool {
     p.test(SyntaxKind::Semicolon)
 }
 
+#[cfg_attr(test, parser_test)]
+/// ```test,LetStatement
+/// let foo = 42;
+/// let foo: string = "hello";
+/// let foo: [int] = [1, 2, 3];
+/// let foo: { a: int, b: string } = { a: 1, b: "x" };
+/// ```
+fn parse_let_statement(p: &mut impl Parser) {
+    let mut p = p.start_node(SyntaxKind::LetStatement);
+    debug_assert_eq!(p.peek().as_str(), "let");
+    p.expect(SyntaxKind::Identifier); // "let"
+    {
+        let mut p = p.start_node(SyntaxKind::DeclaredIdentifier);
+        p.expect(SyntaxKind::Identifier);
+    }
+    if p.test(SyntaxKind::Colon) {
+        parse_type(&mut *p);
+    }
+    p.expect(SyntaxKind::Equal);
+    parse_expression(&mut *p);
+    p.expect(SyntaxKind::Semicolon);
+}
+
+#[cfg_attr(test, parser_test)]
+/// ```test,ForStatement
+/// for x in model { }
+/// for x in [1, 2, 3] { foo += x; }
+/// for i in 0..10 { if (i == 2) { continue; } foo = i; }
+/// for i in a.b..(c + 1) { break; }
+/// ```
+fn parse_for_statement(p: &mut impl Parser) {
+    let mut p = p.start_node(SyntaxKind::ForStatement);
+    debug_assert_eq!(p.peek().as_str(), "for");
+    p.expect(SyntaxKind::Identifier); // "for"
+    {
+        let mut p = p.start_node(SyntaxKind::DeclaredIdentifier);
+        p.expect(SyntaxKind::Identifier);
+    }
+    debug_assert_eq!(p.peek().as_str(), "in");
+    p.expect(SyntaxKind::Identifier); // "in"
+    parse_expression(&mut *p);
+    if p.nth(0).kind() == SyntaxKind::DoubleDot {
+        let mut p = p.start_node(SyntaxKind::ForRangeEnd);
+        p.expect(SyntaxKind::DoubleDot);
+        parse_expression(&mut *p);
+    }
+    parse_code_block(&mut *p);
+}
+
 #[cfg_attr(test, parser_test)]
 /// ```test,ConditionalExpression
 /// if (true) { foo = bar; } else { bar = foo;  }
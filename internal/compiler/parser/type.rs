@@ -11,16 +11,74 @@
 /// string
 /// [ int ]
 /// {a: string, b: int}
+/// Pair<int, [string]>
+/// int?
+/// [string?]?
 /// ```
 pub fn parse_type(p: &mut impl Parser) {
     let mut p = p.start_node(SyntaxKind::Type);
+    let checkpoint = p.checkpoint();
     match p.nth(0).kind() {
         SyntaxKind::LBrace => parse_type_object(&mut *p),
         SyntaxKind::LBracket => parse_type_array(&mut *p),
         _ => {
-            parse_qualified_name(&mut *p);
+            if parse_qualified_name(&mut *p) && p.nth(0).kind() == SyntaxKind::LAngle {
+                parse_type_arguments(&mut *p);
+            }
+        }
+    }
+    if p.nth(0).kind() == SyntaxKind::Question {
+        {
+            let _ = p.start_node_at(checkpoint.clone(), SyntaxKind::Type);
+        }
+        let mut p = p.start_node_at(checkpoint, SyntaxKind::OptionalType);
+        p.consume(); // '?'
+    }
+}
+
+#[cfg_attr(test, parser_test)]
+/// ```test,TypeParameters
+/// <T>
+/// <T, U>
+/// <T, U,>
+/// ```
+pub fn parse_type_parameters(p: &mut impl Parser) {
+    let mut p = p.start_node(SyntaxKind::TypeParameters);
+    if !p.expect(SyntaxKind::LAngle) {
+        return;
+    }
+    while p.nth(0).kind() != SyntaxKind::RAngle {
+        {
+            let mut p = p.start_node(SyntaxKind::DeclaredIdentifier);
+            if !p.expect(SyntaxKind::Identifier) {
+                return;
+            }
+        }
+        if !p.test(SyntaxKind::Comma) {
+            break;
+        }
+    }
+    p.expect(SyntaxKind::RAngle);
+}
+
+#[cfg_attr(test, parser_test)]
+/// ```test,TypeArguments
+/// <int>
+/// <string, [Foo<int>]>
+/// <{a: int}, Pair<int, int>,>
+/// ```
+pub fn parse_type_arguments(p: &mut impl Parser) {
+    let mut p = p.start_node(SyntaxKind::TypeArguments);
+    if !p.expect(SyntaxKind::LAngle) {
+        return;
+    }
+    while p.nth(0).kind() != SyntaxKind::RAngle {
+        parse_type(&mut *p);
+        if !p.test(SyntaxKind::Comma) {
+            break;
         }
     }
+    p.expect(SyntaxKind::RAngle);
 }
 
 #[cfg_attr(test, parser_test)]
@@ -72,6 +130,7 @@ pub fn parse_type_array(p: &mut impl Parser) {
 /// struct Bar := {}
 /// struct Foo { foo: bar, xxx: { aaa: bbb, } }
 /// struct Bar {}
+/// struct Pair<T, U> { first: T, second: U }
 /// ```
 pub fn parse_struct_declaration<P: Parser>(p: &mut P, checkpoint: Option<P::Checkpoint>) -> bool {
     debug_assert_eq!(p.peek().as_str(), "struct");
@@ -82,7 +141,9 @@ pub fn parse_struct_declaration<P: Parser>(p: &mut P, checkpoint: Option<P::Chec
         p.expect(SyntaxKind::Identifier);
     }
 
-    if p.peek().kind() == SyntaxKind::ColonEqual {
+    if p.peek().kind() == SyntaxKind::LAngle {
+        parse_type_parameters(&mut *p);
+    } else if p.peek().kind() == SyntaxKind::ColonEqual {
         p.warning("':=' to declare a struct is deprecated. Remove the ':='");
         p.consume();
     }
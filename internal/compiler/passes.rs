@@ -6,7 +6,6 @@
 mod border_radius;
 mod check_expressions;
 mod check_public_api;
-mod check_rotation;
 mod clip;
 mod collect_custom_fonts;
 mod collect_globals;
@@ -77,29 +76,24 @@ pub async fn run_passes(
     };
 
     let global_type_registry = type_loader.global_type_registry.clone();
-    let root_component = &doc.root_component;
-    root_component.is_root_component.set(true);
+    for root_component in doc.root_components() {
+        root_component.is_root_component.set(true);
+    }
     run_import_passes(doc, type_loader, diag);
+    if type_loader.compiler_config.compile_all_exported_components {
+        check_public_api::declare_exported_properties_in_roots(doc);
+    }
     check_public_api::check_public_api(doc, diag);
 
-    collect_subcomponents::collect_subcomponents(root_component);
-    for component in (root_component.used_types.borrow().sub_components.iter())
-        .chain(std::iter::once(root_component))
-    {
-        compile_paths::compile_paths(
-            component,
-            &doc.local_registry,
-            type_loader.compiler_config.embed_resources,
-            diag,
-        );
+    collect_all_subcomponents(doc);
+    for component in doc.used_sub_components().iter().chain(doc.root_components()) {
+        compile_paths::compile_paths(component, &doc.local_registry, diag);
         lower_tabwidget::lower_tabwidget(component, type_loader, diag).await;
     }
 
-    collect_subcomponents::collect_subcomponents(root_component);
+    collect_all_subcomponents(doc);
 
-    for component in (root_component.used_types.borrow().sub_components.iter())
-        .chain(std::iter::once(root_component))
-    {
+    for component in doc.used_sub_components().iter().chain(doc.root_components()) {
         apply_default_properties_from_style::apply_default_properties_from_style(
             component,
             &style_metrics,
@@ -113,15 +107,14 @@ pub async fn run_passes(
     }
 
     inlining::inline(doc, inlining::InlineSelection::InlineOnlyRequiredComponents);
-    collect_subcomponents::collect_subcomponents(root_component);
+    collect_all_subcomponents(doc);
 
-    focus_handling::call_focus_on_init(root_component);
-
-    ensure_window::ensure_window(root_component, &doc.local_registry, &style_metrics);
+    for root_component in doc.root_components() {
+        focus_handling::call_focus_on_init(root_component);
+        ensure_window::ensure_window(root_component, &doc.local_registry, &style_metrics);
+    }
 
-    for component in (root_component.used_types.borrow().sub_components.iter())
-        .chain(std::iter::once(root_component))
-    {
+    for component in doc.used_sub_components().iter().chain(doc.root_components()) {
         border_radius::handle_border_radius(component, diag);
         flickable::handle_flickable(component, &global_type_registry.borrow());
         lower_component_container::lower_component_container(component, &doc.local_registry, diag);
@@ -180,20 +173,22 @@ pub async fn run_passes(
         }
         materialize_fake_properties::materialize_fake_properties(component);
     }
-    lower_layout::check_window_layout(root_component);
+    for root_component in doc.root_components() {
+        lower_layout::check_window_layout(root_component);
+    }
     collect_globals::collect_globals(doc, diag);
 
     if type_loader.compiler_config.inline_all_elements {
         inlining::inline(doc, inlining::InlineSelection::InlineAllComponents);
-        root_component.used_types.borrow_mut().sub_components.clear();
+        for root_component in doc.root_components() {
+            root_component.used_types.borrow_mut().sub_components.clear();
+        }
     }
 
     binding_analysis::binding_analysis(doc, diag);
     unique_id::assign_unique_id(doc);
 
-    for component in (root_component.used_types.borrow().sub_components.iter())
-        .chain(std::iter::once(root_component))
-    {
+    for component in doc.used_sub_components().iter().chain(doc.root_components()) {
         deduplicate_property_read::deduplicate_property_read(component);
         optimize_useless_rectangles::optimize_useless_rectangles(component);
         move_declarations::move_declarations(component);
@@ -201,9 +196,7 @@ pub async fn run_passes(
 
     remove_aliases::remove_aliases(doc, diag);
 
-    for component in (root_component.used_types.borrow().sub_components.iter())
-        .chain(std::iter::once(root_component))
-    {
+    for component in doc.used_sub_components().iter().chain(doc.root_components()) {
         if !diag.has_error() {
             // binding loop causes panics in const_propagation
             const_propagation::const_propagation(component);
@@ -214,9 +207,7 @@ pub async fn run_passes(
 
     collect_structs_and_enums::collect_structs_and_enums(doc);
 
-    for component in (root_component.used_types.borrow().sub_components.iter())
-        .chain(std::iter::once(root_component))
-    {
+    for component in doc.used_sub_components().iter().chain(doc.root_components()) {
         generate_item_indices::generate_item_indices(component);
     }
 
@@ -226,7 +217,7 @@ pub async fn run_passes(
     remove_return::remove_return(doc);
 
     embed_images::embed_images(
-        root_component,
+        doc,
         type_loader.compiler_config.embed_resources,
         type_loader.compiler_config.scale_factor,
         &type_loader.compiler_config.resource_url_mapper,
@@ -242,9 +233,7 @@ pub async fn run_passes(
             // Include at least the default font sizes used in the MCU backend
             let mut font_pixel_sizes =
                 vec![(12. * type_loader.compiler_config.scale_factor) as i16];
-            for component in (root_component.used_types.borrow().sub_components.iter())
-                .chain(std::iter::once(root_component))
-            {
+            for component in doc.used_sub_components().iter().chain(doc.root_components()) {
                 embed_glyphs::collect_font_sizes_used(
                     component,
                     type_loader.compiler_config.scale_factor,
@@ -253,27 +242,38 @@ pub async fn run_passes(
                 embed_glyphs::scan_string_literals(component, &mut characters_seen);
             }
 
-            embed_glyphs::embed_glyphs(
-                root_component,
-                type_loader.compiler_config.scale_factor,
-                font_pixel_sizes,
-                characters_seen,
-                std::iter::once(doc).chain(type_loader.all_documents()),
-                diag,
-            );
+            for root_component in doc.root_components() {
+                embed_glyphs::embed_glyphs(
+                    root_component,
+                    type_loader.compiler_config.scale_factor,
+                    font_pixel_sizes.clone(),
+                    characters_seen.clone(),
+                    std::iter::once(doc).chain(type_loader.all_documents()),
+                    diag,
+                );
+            }
         }
         _ => {
             // Create font registration calls for custom fonts, unless we're embedding pre-rendered glyphs
-            collect_custom_fonts::collect_custom_fonts(
-                root_component,
-                std::iter::once(doc).chain(type_loader.all_documents()),
-                type_loader.compiler_config.embed_resources
-                    == crate::EmbedResourcesKind::EmbedAllResources,
-            );
+            for root_component in doc.root_components() {
+                collect_custom_fonts::collect_custom_fonts(
+                    root_component,
+                    std::iter::once(doc).chain(type_loader.all_documents()),
+                    type_loader.compiler_config.embed_resources
+                        == crate::EmbedResourcesKind::EmbedAllResources,
+                );
+            }
         }
     }
 }
 
+/// Fill the used_types.sub_components of all the root components
+fn collect_all_subcomponents(doc: &crate::object_tree::Document) {
+    for root_component in doc.root_components() {
+        collect_subcomponents::collect_subcomponents(root_component);
+    }
+}
+
 /// Run the passes on imported documents
 pub fn run_import_passes(
     doc: &crate::object_tree::Document,
@@ -285,6 +285,5 @@ pub fn run_import_passes(
     focus_handling::replace_forward_focus_bindings_with_focus_functions(doc, diag);
     check_expressions::check_expressions(doc, diag);
     purity_check::purity_check(doc, diag);
-    check_rotation::check_rotation(doc, diag);
     unique_id::check_unique_id(doc, diag);
 }
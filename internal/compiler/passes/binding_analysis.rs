@@ -31,11 +31,14 @@
 type ReverseAliases = HashMap<NamedReference, Vec<NamedReference>>;
 
 pub fn binding_analysis(doc: &Document, diag: &mut BuildDiagnostics) {
-    let component = &doc.root_component;
     let mut reverse_aliases = Default::default();
-    mark_used_base_properties(component);
-    propagate_is_set_on_aliases(component, &mut reverse_aliases);
-    perform_binding_analysis(component, &reverse_aliases, diag);
+    for component in doc.root_components() {
+        mark_used_base_properties(component);
+        propagate_is_set_on_aliases(component, &mut reverse_aliases);
+    }
+    for component in doc.root_components() {
+        perform_binding_analysis(component, &reverse_aliases, diag);
+    }
 }
 
 /// A reference to a property which might be deep in a component path.
@@ -12,13 +12,18 @@
 /// - Make sure that there is no uncalled member function or macro
 pub fn check_expressions(doc: &crate::object_tree::Document, diag: &mut BuildDiagnostics) {
     for component in &doc.inner_components {
-        visit_all_expressions(component, |e, _| check_expression(component, e, diag));
+        component.with_diagnostics(diag, |diag| {
+            visit_all_expressions(component, |e, _| check_expression(component, e, diag));
+        });
     }
 }
 
 fn check_expression(component: &Rc<Component>, e: &Expression, diag: &mut BuildDiagnostics) {
     match e {
-        Expression::MemberFunction { .. } => {
+        Expression::MemberFunction { member, .. } => {
+            if let Expression::BuiltinMacroReference(_, node) = &**member {
+                diag.push_error("Builtin function must be called".into(), node);
+            }
             // Must already have been be reported.
             debug_assert!(diag.has_error());
         }
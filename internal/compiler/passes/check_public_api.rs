@@ -6,10 +6,14 @@
 use std::rc::Rc;
 
 use crate::diagnostics::{BuildDiagnostics, DiagnosticLevel};
+use crate::langtype::{ElementType, Interface, Type};
 use crate::object_tree::{Component, Document, PropertyVisibility};
+use crate::parser;
 
 pub fn check_public_api(doc: &Document, diag: &mut BuildDiagnostics) {
-    check_public_api_component(&doc.root_component, diag);
+    for root_component in doc.root_components() {
+        check_public_api_component(root_component, diag);
+    }
     for (export_name, e) in &*doc.exports {
         if let Some(c) = e.as_ref().left() {
             if c.is_global() {
@@ -17,6 +21,39 @@ pub fn check_public_api(doc: &Document, diag: &mut BuildDiagnostics) {
                 c.exported_global_names.borrow_mut().push(export_name.clone());
                 check_public_api_component(c, diag)
             }
+        } else if let Some(Type::Interface(i)) = e.as_ref().right() {
+            check_public_api_interface(i, diag)
+        }
+    }
+}
+
+/// The root components created by [`Document::use_exported_components_as_roots`] inherit the
+/// exported components. Declare the properties of these in the roots, so that they are part of
+/// the public API while the properties of their own base are not.
+pub fn declare_exported_properties_in_roots(doc: &Document) {
+    for root_component in doc.root_components() {
+        let mut root_elem = root_component.root_element.borrow_mut();
+        if let ElementType::Component(base) = &root_elem.base_type {
+            let declarations = base.root_element.borrow().property_declarations.clone();
+            root_elem.property_declarations = declarations;
+        }
+    }
+}
+
+fn check_public_api_interface(interface: &Interface, diag: &mut BuildDiagnostics) {
+    let Some(node) = &interface.node else { return };
+    for prop_decl in node.PropertyDeclaration() {
+        let Some((ty, _)) = parser::identifier_text(&prop_decl.DeclaredIdentifier())
+            .and_then(|name| interface.members.get(&name))
+        else {
+            continue;
+        };
+        if !ty.ok_for_public_api() {
+            diag.push_diagnostic(
+                format!("Properties of type {} are not supported yet for public API. The property cannot be part of an interface", ty),
+                &prop_decl,
+                DiagnosticLevel::Warning,
+            );
         }
     }
 }
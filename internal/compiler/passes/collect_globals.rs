@@ -13,19 +13,21 @@
 
 /// Fill the root_component's used_types.globals
 pub fn collect_globals(doc: &Document, _diag: &mut BuildDiagnostics) {
-    doc.root_component.used_types.borrow_mut().globals.clear();
-    let mut set = HashSet::new();
-    let mut sorted_globals = vec![];
-    for (_, ty) in &*doc.exports {
-        if let Some(c) = ty.as_ref().left() {
-            if c.is_global() && set.insert(ByAddress(c.clone())) {
-                collect_in_component(c, &mut set, &mut sorted_globals);
-                sorted_globals.push(c.clone());
+    for root_component in doc.root_components() {
+        root_component.used_types.borrow_mut().globals.clear();
+        let mut set = HashSet::new();
+        let mut sorted_globals = vec![];
+        for (_, ty) in &*doc.exports {
+            if let Some(c) = ty.as_ref().left() {
+                if c.is_global() && set.insert(ByAddress(c.clone())) {
+                    collect_in_component(c, &mut set, &mut sorted_globals);
+                    sorted_globals.push(c.clone());
+                }
             }
         }
+        collect_in_component(root_component, &mut set, &mut sorted_globals);
+        root_component.used_types.borrow_mut().globals = sorted_globals;
     }
-    collect_in_component(&doc.root_component, &mut set, &mut sorted_globals);
-    doc.root_component.used_types.borrow_mut().globals = sorted_globals;
 }
 
 fn collect_in_component(
@@ -11,6 +11,12 @@
 
 /// Fill the root_component's used_types.structs
 pub fn collect_structs_and_enums(doc: &Document) {
+    for root_component in doc.root_components() {
+        collect_structs_and_enums_in_root(doc, root_component);
+    }
+}
+
+fn collect_structs_and_enums_in_root(doc: &Document, root_component: &Rc<Component>) {
     let mut hash = BTreeMap::new();
 
     for (_, exp) in doc.exports.iter() {
@@ -19,13 +25,13 @@ pub fn collect_structs_and_enums(doc: &Document) {
         }
     }
 
-    for component in (doc.root_component.used_types.borrow().sub_components.iter())
-        .chain(std::iter::once(&doc.root_component))
+    for component in (root_component.used_types.borrow().sub_components.iter())
+        .chain(std::iter::once(root_component))
     {
         collect_types_in_component(component, &mut hash)
     }
 
-    let mut used_types = doc.root_component.used_types.borrow_mut();
+    let mut used_types = root_component.used_types.borrow_mut();
     let used_struct_and_enums = &mut used_types.structs_and_enums;
     *used_struct_and_enums = Vec::with_capacity(hash.len());
     while let Some(next) = hash.iter().next() {
@@ -91,7 +97,7 @@ fn visit_declared_type(ty: &Type, visitor: &mut impl FnMut(&String, &Type)) {
                 visit_declared_type(sub_ty, visitor);
             }
         }
-        Type::Array(x) => visit_declared_type(x, visitor),
+        Type::Array(x) | Type::Optional(x) => visit_declared_type(x, visitor),
         Type::Callback { return_type, args } => {
             if let Some(rt) = return_type {
                 visit_declared_type(rt, visitor);
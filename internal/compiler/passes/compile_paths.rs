@@ -14,14 +14,12 @@
 use crate::langtype::ElementType;
 use crate::langtype::Type;
 use crate::object_tree::*;
-use crate::EmbedResourcesKind;
 use std::cell::RefCell;
 use std::rc::Rc;
 
 pub fn compile_paths(
     component: &Rc<Component>,
     tr: &crate::typeregister::TypeRegister,
-    _embed_resources: EmbedResourcesKind,
     diag: &mut BuildDiagnostics,
 ) {
     let path_type = tr.lookup_element("Path").unwrap();
@@ -37,14 +35,6 @@ pub fn compile_paths(
             _ => return,
         };
 
-        #[cfg(feature = "software-renderer")]
-        if _embed_resources == EmbedResourcesKind::EmbedTextures {
-            diag.push_warning(
-                "Path element is not supported with the software renderer".into(),
-                &*elem_.borrow(),
-            )
-        }
-
         let element_types = &accepted_type.additional_accepted_child_types;
 
         let commands_binding =
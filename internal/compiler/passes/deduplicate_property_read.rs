@@ -125,6 +125,12 @@ fn collect_unconditional_read_count(expr: &Expression, result: &DedupPropState)
         Expression::Condition { condition, .. } => {
             condition.visit(|sub| collect_unconditional_read_count(sub, result))
         }
+        Expression::NullCoalescing { lhs, .. } => {
+            lhs.visit(|sub| collect_unconditional_read_count(sub, result))
+        }
+        Expression::OptionalChaining { optional, .. } => {
+            optional.visit(|sub| collect_unconditional_read_count(sub, result))
+        }
         Expression::SelfAssignment { .. } => {
             result.counts.borrow_mut().has_set = true;
         }
@@ -146,6 +152,14 @@ fn process_conditional_expressions(expr: &mut Expression, state: &DedupPropState
             process_expression(true_expr, state);
             process_expression(false_expr, state);
         }
+        Expression::NullCoalescing { lhs, rhs } => {
+            lhs.visit_mut(|sub| process_conditional_expressions(sub, state));
+            process_expression(rhs, state);
+        }
+        Expression::OptionalChaining { optional, body, .. } => {
+            optional.visit_mut(|sub| process_conditional_expressions(sub, state));
+            process_expression(body, state);
+        }
         Expression::SelfAssignment { .. } => {
             state.counts.borrow_mut().has_set = true;
         }
@@ -167,6 +181,12 @@ fn do_replacements(expr: &mut Expression, state: &DedupPropState) {
         Expression::Condition { condition, .. } => {
             condition.visit_mut(|sub| do_replacements(sub, state));
         }
+        Expression::NullCoalescing { lhs, .. } => {
+            lhs.visit_mut(|sub| do_replacements(sub, state));
+        }
+        Expression::OptionalChaining { optional, .. } => {
+            optional.visit_mut(|sub| do_replacements(sub, state));
+        }
         _ => expr.visit_mut(|sub| do_replacements(sub, state)),
     }
 }
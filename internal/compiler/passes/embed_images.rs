@@ -15,21 +15,19 @@
 use std::rc::Rc;
 
 pub async fn embed_images(
-    component: &Rc<Component>,
+    doc: &Document,
     embed_files: EmbedResourcesKind,
     scale_factor: f64,
     resource_url_mapper: &Option<Rc<dyn Fn(&str) -> Pin<Box<dyn Future<Output = Option<String>>>>>>,
     diag: &mut BuildDiagnostics,
 ) {
-    let global_embedded_resources = &component.embedded_file_resources;
+    let global_embedded_resources = &doc.root_component.embedded_file_resources;
 
-    let all_components = component
-        .used_types
-        .borrow()
-        .sub_components
+    let all_components = doc
+        .used_sub_components()
         .iter()
-        .chain(component.used_types.borrow().globals.iter())
-        .chain(std::iter::once(component))
+        .chain(doc.used_globals().iter())
+        .chain(doc.root_components())
         .cloned()
         .collect::<Vec<_>>();
 
@@ -66,6 +64,15 @@ pub async fn embed_images(
             )
         });
     }
+
+    // The components used by several roots were only processed once, so all the roots share the
+    // embedded resources
+    for root_component in &doc.extra_root_components {
+        root_component
+            .embedded_file_resources
+            .borrow_mut()
+            .clone_from(&global_embedded_resources.borrow());
+    }
 }
 
 fn collect_image_urls_from_expression(e: &Expression, urls: &mut HashMap<String, Option<String>>) {
@@ -142,20 +149,36 @@ fn embed_image(
                 let mut kind = EmbeddedResourcesKind::RawData;
                 #[cfg(feature = "software-renderer")]
                 if _embed_files == EmbedResourcesKind::EmbedTextures {
-                    match load_image(_file, _scale_factor) {
-                        Ok((img, source_format, original_size)) => {
-                            kind = EmbeddedResourcesKind::TextureData(generate_texture(
-                                img,
-                                source_format,
-                                original_size,
-                            ))
-                        }
-                        Err(err) => {
-                            diag.push_error(
-                                format!("Cannot load image file {}: {}", path, err),
-                                source_location,
-                            );
-                            return ImageReference::None;
+                    if let Some(frames) = load_animation(&_file, _scale_factor) {
+                        kind = EmbeddedResourcesKind::AnimatedTextureData(
+                            frames
+                                .into_iter()
+                                .map(|(img, duration, original_size)| AnimationFrame {
+                                    texture: generate_texture(
+                                        img,
+                                        SourceFormat::Rgba,
+                                        original_size,
+                                    ),
+                                    duration,
+                                })
+                                .collect(),
+                        );
+                    } else {
+                        match load_image(_file, _scale_factor) {
+                            Ok((img, source_format, original_size)) => {
+                                kind = EmbeddedResourcesKind::TextureData(generate_texture(
+                                    img,
+                                    source_format,
+                                    original_size,
+                                ))
+                            }
+                            Err(err) => {
+                                diag.push_error(
+                                    format!("Cannot load image file {}: {}", path, err),
+                                    source_location,
+                                );
+                                return ImageReference::None;
+                            }
                         }
                     }
                 }
@@ -171,6 +194,10 @@ fn embed_image(
         EmbeddedResourcesKind::TextureData { .. } => {
             ImageReference::EmbeddedTexture { resource_id: e.id }
         }
+        #[cfg(feature = "software-renderer")]
+        EmbeddedResourcesKind::AnimatedTextureData { .. } => {
+            ImageReference::EmbeddedAnimation { resource_id: e.id }
+        }
         _ => ImageReference::EmbeddedData {
             resource_id: e.id,
             extension: std::path::Path::new(path)
@@ -355,6 +382,55 @@ enum SourceFormat {
     Rgba,
 }
 
+/// Decode the frames of an animated GIF, PNG (APNG), or WebP file, with their duration in milliseconds.
+///
+/// Returns None if the file is not animated, in which case it should be loaded with [`load_image`].
+#[cfg(feature = "animated-images")]
+fn load_animation(
+    file: &crate::fileaccess::VirtualFile,
+    scale_factor: f64,
+) -> Option<Vec<(image::RgbaImage, u32, Size)>> {
+    let data = match file.builtin_contents {
+        Some(data) => std::borrow::Cow::Borrowed(data),
+        None => std::borrow::Cow::Owned(std::fs::read(&file.canon_path).ok()?),
+    };
+    let format = image::guess_format(&data).ok()?;
+    let frames = i_slint_common::animated_images::load_animation(
+        std::io::Cursor::new(data.as_ref()),
+        format,
+    )?;
+    Some(
+        frames
+            .into_iter()
+            .map(|frame| {
+                let mut image = image::DynamicImage::ImageRgba8(frame.buffer);
+                let (original_width, original_height) = image.dimensions();
+                if scale_factor < 1. {
+                    image = image.resize_exact(
+                        (original_width as f64 * scale_factor) as u32,
+                        (original_height as f64 * scale_factor) as u32,
+                        image::imageops::FilterType::Gaussian,
+                    );
+                }
+                (
+                    image.to_rgba8(),
+                    frame.duration,
+                    Size { width: original_width, height: original_height },
+                )
+            })
+            .collect(),
+    )
+}
+
+/// Without the `animated-images` feature, only the first frame of animated images is embedded.
+#[cfg(all(feature = "software-renderer", not(feature = "animated-images")))]
+fn load_animation(
+    _file: &crate::fileaccess::VirtualFile,
+    _scale_factor: f64,
+) -> Option<Vec<(image::RgbaImage, u32, Size)>> {
+    None
+}
+
 #[cfg(feature = "software-renderer")]
 fn load_image(
     file: crate::fileaccess::VirtualFile,
@@ -32,45 +32,47 @@ pub fn replace_forward_focus_bindings_with_focus_functions(
     diag: &mut BuildDiagnostics,
 ) {
     for component in doc.inner_components.iter() {
-        // Phase 1: Collect all forward-forward bindings
-        let mut local_forwards = LocalFocusForwards::collect(component, diag);
+        component.with_diagnostics(diag, |diag| {
+            // Phase 1: Collect all forward-forward bindings
+            let mut local_forwards = LocalFocusForwards::collect(component, diag);
 
-        // Phase 2: Filter out focus-forward bindings that aren't callable
-        local_forwards.remove_uncallable_forwards();
+            // Phase 2: Filter out focus-forward bindings that aren't callable
+            local_forwards.remove_uncallable_forwards();
 
-        // Phase 3: For `focus-forward` in the root element, create `focus()` and `clear-focus()` functions that are callable from the outside
-        if let Some((root_focus_forward, focus_forward_location)) =
-            local_forwards.focus_forward_for_element(&component.root_element)
-        {
-            for function in FocusFunctionType::iter() {
-                if let Some(set_or_clear_focus_code) = call_set_focus_function(
-                    &root_focus_forward,
-                    Some(&focus_forward_location),
-                    function,
-                ) {
-                    component.root_element.borrow_mut().property_declarations.insert(
-                        function.name().into(),
-                        PropertyDeclaration {
-                            property_type: Type::Function {
-                                return_type: Type::Void.into(),
-                                args: vec![],
+            // Phase 3: For `focus-forward` in the root element, create `focus()` and `clear-focus()` functions that are callable from the outside
+            if let Some((root_focus_forward, focus_forward_location)) =
+                local_forwards.focus_forward_for_element(&component.root_element)
+            {
+                for function in FocusFunctionType::iter() {
+                    if let Some(set_or_clear_focus_code) = call_set_focus_function(
+                        &root_focus_forward,
+                        Some(&focus_forward_location),
+                        function,
+                    ) {
+                        component.root_element.borrow_mut().property_declarations.insert(
+                            function.name().into(),
+                            PropertyDeclaration {
+                                property_type: Type::Function {
+                                    return_type: Type::Void.into(),
+                                    args: vec![],
+                                },
+                                visibility: PropertyVisibility::Public,
+                                ..Default::default()
                             },
-                            visibility: PropertyVisibility::Public,
-                            ..Default::default()
-                        },
-                    );
-                    component.root_element.borrow_mut().bindings.insert(
-                        function.name().into(),
-                        RefCell::new(set_or_clear_focus_code.into()),
-                    );
+                        );
+                        component.root_element.borrow_mut().bindings.insert(
+                            function.name().into(),
+                            RefCell::new(set_or_clear_focus_code.into()),
+                        );
+                    }
                 }
             }
-        }
 
-        // Phase 4: All calls to `.focus()` may need to be changed with `focus-forward` resolved or changed from the built-in
-        // SetFocusItem() call to a regular function call to the component's focus() function.
-        visit_all_expressions(component, |e, _| {
-            local_forwards.resolve_focus_calls_in_expression(e)
+            // Phase 4: All calls to `.focus()` may need to be changed with `focus-forward` resolved or changed from the built-in
+            // SetFocusItem() call to a regular function call to the component's focus() function.
+            visit_all_expressions(component, |e, _| {
+                local_forwards.resolve_focus_calls_in_expression(e)
+            });
         });
     }
 }
@@ -52,15 +52,13 @@ fn inline_components_recursively(component: &Rc<Component>, inline_selection: In
             .iter()
             .for_each(|p| inline_components_recursively(&p.component, inline_selection))
     }
-    inline_components_recursively(&doc.root_component, inline_selection);
+    for root_component in doc.root_components() {
+        inline_components_recursively(root_component, inline_selection);
 
-    let mut init_code = doc.root_component.init_code.borrow_mut();
-    let inlined_init_code = core::mem::take(&mut init_code.inlined_init_code);
-    init_code.constructor_code.splice(0..0, inlined_init_code.into_values());
-}
-
-fn clone_tuple<U: Clone, V: Clone>((u, v): (&U, &V)) -> (U, V) {
-    (u.clone(), v.clone())
+        let mut init_code = root_component.init_code.borrow_mut();
+        let inlined_init_code = core::mem::take(&mut init_code.inlined_init_code);
+        init_code.constructor_code.splice(0..0, inlined_init_code.into_values());
+    }
 }
 
 fn element_key(e: ElementRc) -> ByAddress<ElementRc> {
@@ -83,9 +81,10 @@ fn inline_element(
     let mut elem_mut = elem.borrow_mut();
     let priority_delta = 1 + elem_mut.inline_depth;
     elem_mut.base_type = inlined_component.root_element.borrow().base_type.clone();
-    elem_mut.property_declarations.extend(
-        inlined_component.root_element.borrow().property_declarations.iter().map(clone_tuple),
-    );
+    for (name, decl) in inlined_component.root_element.borrow().property_declarations.iter() {
+        // Keep the declarations that are already there, they may be exposed in the public API
+        elem_mut.property_declarations.entry(name.clone()).or_insert_with(|| decl.clone());
+    }
 
     for (p, a) in inlined_component.root_element.borrow().property_analysis.borrow().iter() {
         elem_mut.property_analysis.borrow_mut().entry(p.clone()).or_default().merge_with_base(a);
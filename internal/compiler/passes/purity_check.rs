@@ -7,29 +7,31 @@
 /// Check that pure expression only call pure functions
 pub fn purity_check(doc: &crate::object_tree::Document, diag: &mut BuildDiagnostics) {
     for component in &doc.inner_components {
-        crate::object_tree::recurse_elem_including_sub_components_no_borrow(
-            component,
-            &(),
-            &mut |elem, &()| {
-                let level = match elem.borrow().is_legacy_syntax {
-                    true => crate::diagnostics::DiagnosticLevel::Warning,
-                    false => crate::diagnostics::DiagnosticLevel::Error,
-                };
-                crate::object_tree::visit_element_expressions(elem, |expr, name, _| {
-                    if let Some(name) = name {
-                        let lookup = elem.borrow().lookup_property(name);
-                        if lookup.declared_pure.unwrap_or(false)
-                            || lookup.property_type.is_property_type()
-                        {
-                            ensure_pure(expr, Some(diag), level);
-                        }
-                    } else {
-                        // model expression must be pure
-                        ensure_pure(expr, Some(diag), level);
+        component.with_diagnostics(diag, |diag| {
+            crate::object_tree::recurse_elem_including_sub_components_no_borrow(
+                component,
+                &(),
+                &mut |elem, &()| {
+                    let level = match elem.borrow().is_legacy_syntax {
+                        true => crate::diagnostics::DiagnosticLevel::Warning,
+                        false => crate::diagnostics::DiagnosticLevel::Error,
                     };
-                })
-            },
-        )
+                    crate::object_tree::visit_element_expressions(elem, |expr, name, _| {
+                        if let Some(name) = name {
+                            let lookup = elem.borrow().lookup_property(name);
+                            if lookup.declared_pure.unwrap_or(false)
+                                || lookup.property_type.is_property_type()
+                            {
+                                ensure_pure(expr, Some(diag), level);
+                            }
+                        } else {
+                            // model expression must be pure
+                            ensure_pure(expr, Some(diag), level);
+                        };
+                    })
+                },
+            )
+        });
     }
 }
 
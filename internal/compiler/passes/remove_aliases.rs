@@ -78,9 +78,11 @@ pub fn remove_aliases(doc: &Document, diag: &mut BuildDiagnostics) {
         }
     };
 
-    for component in (doc.root_component.used_types.borrow().sub_components.iter())
-        .chain(doc.root_component.used_types.borrow().globals.iter())
-        .chain(std::iter::once(&doc.root_component))
+    for component in doc
+        .used_sub_components()
+        .iter()
+        .chain(doc.used_globals().iter())
+        .chain(doc.root_components())
     {
         recurse_elem_including_sub_components(component, &(), &mut |e, &()| process_element(e));
     }
@@ -105,9 +107,11 @@ pub fn remove_aliases(doc: &Document, diag: &mut BuildDiagnostics) {
         }
     }
 
-    for component in (doc.root_component.used_types.borrow().sub_components.iter())
-        .chain(doc.root_component.used_types.borrow().globals.iter())
-        .chain(std::iter::once(&doc.root_component))
+    for component in doc
+        .used_sub_components()
+        .iter()
+        .chain(doc.used_globals().iter())
+        .chain(doc.root_components())
     {
         // Do the replacements
         visit_all_named_references(component, &mut |nr: &mut NamedReference| {
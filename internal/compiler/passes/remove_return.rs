@@ -8,15 +8,13 @@
 
 pub fn remove_return(doc: &crate::object_tree::Document) {
     for component in doc
-        .root_component
-        .used_types
-        .borrow()
-        .sub_components
+        .used_sub_components()
         .iter()
-        .chain(doc.root_component.used_types.borrow().globals.iter())
-        .chain(std::iter::once(&doc.root_component))
+        .chain(doc.used_globals().iter())
+        .chain(doc.root_components())
     {
         crate::object_tree::visit_all_expressions(component, |e, _| {
+            lower_loop_control(e);
             let mut ret_ty = None;
             fn visit(e: &Expression, ret_ty: &mut Option<Type>) {
                 if ret_ty.is_some() {
@@ -37,6 +35,97 @@ fn visit(e: &Expression, ret_ty: &mut Option<Type>) {
     }
 }
 
+/// Remove the `break`, `continue` and `return` from the body of the `for` loops.
+///
+/// They are treated like a `return` from the body, which then evaluates to a boolean:
+/// `continue` returns true, `break` returns false, and reaching the end of the body returns true.
+///
+/// When the body contains a `return`, the body evaluates instead to a struct whose `condition`
+/// field is that boolean, and which also tells whether the function returns and with what value.
+/// The loop evaluates to the struct that stopped it, so the pending return can be done after the loop.
+fn lower_loop_control(e: &mut Expression) {
+    // Inner loops are lowered first, so the `break`, `continue` and `return` left are for this loop
+    e.visit_mut(lower_loop_control);
+    let Expression::ForLoop { body, .. } = e else { return };
+
+    fn find_return_type(e: &Expression, ret_ty: &mut Option<Type>) {
+        match e {
+            _ if ret_ty.is_some() => {}
+            Expression::ReturnStatement(x) => {
+                *ret_ty = Some(x.as_ref().map_or(Type::Void, |x| x.ty()));
+            }
+            _ => e.visit(|e| find_return_type(e, ret_ty)),
+        }
+    }
+    let mut ret_ty = None;
+    find_return_type(body, &mut ret_ty);
+
+    // The value of the body when exiting it with the given condition, and maybe a returned value
+    let body_value = |condition: bool, returned: Option<Expression>| match &ret_ty {
+        Some(ret_ty) => loop_result(condition, returned, ret_ty),
+        None => Expression::BoolLiteral(condition),
+    };
+    fn to_return(e: &mut Expression, body_value: &dyn Fn(bool, Option<Expression>) -> Expression) {
+        match e {
+            Expression::Break => {
+                *e = Expression::ReturnStatement(Some(body_value(false, None).into()))
+            }
+            Expression::Continue => {
+                *e = Expression::ReturnStatement(Some(body_value(true, None).into()))
+            }
+            Expression::ReturnStatement(x) => {
+                let returned = x.take().map_or(Expression::CodeBlock(vec![]), |x| *x);
+                *e = Expression::ReturnStatement(Some(body_value(false, Some(returned)).into()))
+            }
+            _ => e.visit_mut(|e| to_return(e, body_value)),
+        }
+    }
+    to_return(body, &body_value);
+
+    let end_of_body = body_value(true, None);
+    let ctx = RemoveReturnContext { ret_ty: end_of_body.ty() };
+    let new_body = Expression::CodeBlock(vec![std::mem::take(&mut **body), end_of_body]);
+    **body = process_expression(new_body, &ctx).to_expression(&ctx.ret_ty);
+
+    let Some(ret_ty) = ret_ty else { return };
+    static COUNT: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+    let name = format!("loop_result{}", COUNT.fetch_add(1, std::sync::atomic::Ordering::Relaxed));
+    let load = |field: &str| Expression::StructFieldAccess {
+        base: Box::new(Expression::ReadLocalVariable {
+            name: name.clone(),
+            ty: ctx.ret_ty.clone(),
+        }),
+        name: field.into(),
+    };
+    *e = Expression::CodeBlock(vec![
+        Expression::StoreLocalVariable { name: name.clone(), value: std::mem::take(e).into() },
+        Expression::Condition {
+            condition: load(FIELD_RETURNING).into(),
+            true_expr: Expression::ReturnStatement(
+                (!matches!(ret_ty, Type::Void | Type::Invalid))
+                    .then(|| load(FIELD_RETURNED).into()),
+            )
+            .into(),
+            false_expr: Expression::CodeBlock(vec![]).into(),
+        },
+    ]);
+}
+
+/// The value of the body of a `for` loop which contains a `return`. `returned` is the value
+/// returned from the function, if the loop stops because of a `return`.
+fn loop_result(condition: bool, returned: Option<Expression>, ret_ty: &Type) -> Expression {
+    let returning = returned.is_some();
+    let returned = returned.unwrap_or_else(|| Expression::default_value_for_type(ret_ty));
+    make_struct(
+        [
+            (FIELD_CONDITION, Type::Bool, Expression::BoolLiteral(condition)),
+            (FIELD_RETURNING, Type::Bool, Expression::BoolLiteral(returning)),
+            (FIELD_RETURNED, ret_ty.clone(), returned),
+        ]
+        .into_iter(),
+    )
+}
+
 fn process_expression(e: Expression, ctx: &RemoveReturnContext) -> ExpressionResult {
     let ty = e.ty();
     match e {
@@ -242,6 +331,7 @@ fn from(v: Expression) -> Self {
 const FIELD_CONDITION: &str = "condition";
 const FIELD_ACTUAL: &str = "actual";
 const FIELD_RETURNED: &str = "returned";
+const FIELD_RETURNING: &str = "returning";
 
 impl ExpressionResult {
     fn to_expression(self, ty: &Type) -> Expression {
@@ -16,7 +16,7 @@
 use crate::parser::{identifier_text, syntax_nodes, NodeOrToken, SyntaxKind, SyntaxNode};
 use crate::typeregister::TypeRegister;
 use core::num::IntErrorKind;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
 /// This represents a scope for the Component, where Component is the repeated component, but
@@ -43,6 +43,8 @@ fn resolve_expression(
             type_register,
             type_loader: Some(type_loader),
             current_token: None,
+            local_variables: vec![],
+            inside_loop: false,
         };
 
         let new_expr = match node.kind() {
@@ -83,43 +85,45 @@ pub fn resolve_expressions(
     resolve_two_way_bindings(doc, &doc.local_registry, diag);
 
     for component in doc.inner_components.iter() {
-        let scope = ComponentScope(vec![]);
-
-        recurse_elem(&component.root_element, &scope, &mut |elem, scope| {
-            let mut new_scope = scope.clone();
-            let mut is_repeated = elem.borrow().repeated.is_some();
-            new_scope.0.push(elem.clone());
-            visit_element_expressions(elem, |expr, property_name, property_type| {
-                if is_repeated {
-                    // The first expression is always the model and it needs to be resolved with the parent scope
-                    debug_assert!(matches!(
-                        elem.borrow().repeated.as_ref().unwrap().model,
-                        Expression::Invalid
-                    )); // should be Invalid because it is taken by the visit_element_expressions function
-                    resolve_expression(
-                        expr,
-                        property_name,
-                        property_type(),
-                        scope,
-                        &doc.local_registry,
-                        type_loader,
-                        diag,
-                    );
-                    is_repeated = false;
-                } else {
-                    resolve_expression(
-                        expr,
-                        property_name,
-                        property_type(),
-                        &new_scope,
-                        &doc.local_registry,
-                        type_loader,
-                        diag,
-                    )
-                }
-            });
-            new_scope
-        })
+        component.with_diagnostics(diag, |diag| {
+            let scope = ComponentScope(vec![]);
+
+            recurse_elem(&component.root_element, &scope, &mut |elem, scope| {
+                let mut new_scope = scope.clone();
+                let mut is_repeated = elem.borrow().repeated.is_some();
+                new_scope.0.push(elem.clone());
+                visit_element_expressions(elem, |expr, property_name, property_type| {
+                    if is_repeated {
+                        // The first expression is always the model and it needs to be resolved with the parent scope
+                        debug_assert!(matches!(
+                            elem.borrow().repeated.as_ref().unwrap().model,
+                            Expression::Invalid
+                        )); // should be Invalid because it is taken by the visit_element_expressions function
+                        resolve_expression(
+                            expr,
+                            property_name,
+                            property_type(),
+                            scope,
+                            &doc.local_registry,
+                            type_loader,
+                            diag,
+                        );
+                        is_repeated = false;
+                    } else {
+                        resolve_expression(
+                            expr,
+                            property_name,
+                            property_type(),
+                            &new_scope,
+                            &doc.local_registry,
+                            type_loader,
+                            diag,
+                        )
+                    }
+                });
+                new_scope
+            })
+        });
     }
 }
 
@@ -174,15 +178,24 @@ pub fn from_binding_expression_node(node: SyntaxNode, ctx: &mut LookupCtx) -> Se
     fn from_codeblock_node(node: syntax_nodes::CodeBlock, ctx: &mut LookupCtx) -> Expression {
         debug_assert_eq!(node.kind(), SyntaxKind::CodeBlock);
 
+        // new scope for the local variables declared in this block
+        ctx.local_variables.push(Vec::new());
+
         let mut statements_or_exprs = node
             .children()
             .filter_map(|n| match n.kind() {
                 SyntaxKind::Expression => Some(Self::from_expression_node(n.into(), ctx)),
                 SyntaxKind::ReturnStatement => Some(Self::from_return_statement(n.into(), ctx)),
+                SyntaxKind::LetStatement => Some(Self::from_let_statement(n.into(), ctx)),
+                SyntaxKind::BreakStatement | SyntaxKind::ContinueStatement => {
+                    Some(Self::from_loop_control_statement(n, ctx))
+                }
                 _ => None,
             })
             .collect::<Vec<_>>();
 
+        ctx.local_variables.pop();
+
         let exit_points_and_return_types = statements_or_exprs
             .iter()
             .enumerate()
@@ -210,6 +223,127 @@ fn from_codeblock_node(node: syntax_nodes::CodeBlock, ctx: &mut LookupCtx) -> Ex
         Expression::CodeBlock(statements_or_exprs)
     }
 
+    fn from_let_statement(node: syntax_nodes::LetStatement, ctx: &mut LookupCtx) -> Expression {
+        let name = identifier_text(&node.DeclaredIdentifier()).unwrap_or_default();
+
+        if ctx.check_local_variable_redeclaration(&name, &node) {
+            return Expression::Invalid;
+        }
+
+        let value = Self::from_expression_node(node.Expression(), ctx);
+        let ty = match node.Type() {
+            Some(ty) => type_from_node(ty, ctx.diag, ctx.type_register),
+            None => value.ty(),
+        };
+        if ty == Type::Void {
+            ctx.diag.push_error("Cannot declare a local variable of type void".into(), &node);
+            return Expression::Invalid;
+        }
+
+        // There is always a scope, since we are necessarily within a code block
+        ctx.local_variables.last_mut().unwrap().push((name.clone(), ty.clone()));
+
+        Expression::StoreLocalVariable {
+            // prefix the name so that it doesn't conflict with the temporaries of the generated code
+            name: format!("local_{name}"),
+            value: Box::new(value.maybe_convert_to(ty, &node, ctx.diag)),
+        }
+    }
+
+    fn from_for_statement(node: syntax_nodes::ForStatement, ctx: &mut LookupCtx) -> Expression {
+        let name = identifier_text(&node.DeclaredIdentifier()).unwrap_or_default();
+
+        ctx.check_local_variable_redeclaration(&name, &node.DeclaredIdentifier());
+
+        let model = Self::from_expression_node(node.Expression(), ctx);
+        let range_end = node.ForRangeEnd().map(|end| {
+            let end_node = end.Expression();
+            Self::from_expression_node(end_node.clone(), ctx).maybe_convert_to(
+                Type::Int32,
+                &end_node,
+                ctx.diag,
+            )
+        });
+
+        let variable_ty = if range_end.is_some() {
+            Type::Int32
+        } else {
+            match model.ty() {
+                Type::Array(ty) => *ty,
+                Type::Invalid => Type::Invalid,
+                ty => {
+                    ctx.diag.push_error(
+                        format!("Cannot iterate over a value of type '{ty}'. Expected an array or a range"),
+                        &node.Expression(),
+                    );
+                    Type::Invalid
+                }
+            }
+        };
+
+        ctx.local_variables.push(vec![(name.clone(), variable_ty)]);
+        let was_inside_loop = std::mem::replace(&mut ctx.inside_loop, true);
+        let body = Self::from_codeblock_node(node.CodeBlock(), ctx);
+        ctx.inside_loop = was_inside_loop;
+        ctx.local_variables.pop();
+
+        let variable = format!("local_{name}");
+        let Some(end) = range_end else {
+            return Expression::ForLoop { variable, model: model.into(), body: body.into() };
+        };
+
+        // `for i in start..end { body }` is lowered to
+        // `{ let start_var = start; for index in (end - start_var) { let i = index + start_var; body } }`
+        static COUNT: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+        let id = COUNT.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let start_var = format!("for_range_start{id}");
+        let index_var = format!("for_range_index{id}");
+        let read =
+            |name: &str| Expression::ReadLocalVariable { name: name.into(), ty: Type::Int32 };
+        Expression::CodeBlock(vec![
+            Expression::StoreLocalVariable {
+                name: start_var.clone(),
+                value: model.maybe_convert_to(Type::Int32, &node.Expression(), ctx.diag).into(),
+            },
+            Expression::ForLoop {
+                variable: index_var.clone(),
+                model: Expression::BinaryExpression {
+                    lhs: end.into(),
+                    rhs: read(&start_var).into(),
+                    op: '-',
+                }
+                .into(),
+                body: Expression::CodeBlock(vec![
+                    Expression::StoreLocalVariable {
+                        name: variable,
+                        value: Expression::BinaryExpression {
+                            lhs: read(&index_var).into(),
+                            rhs: read(&start_var).into(),
+                            op: '+',
+                        }
+                        .into(),
+                    },
+                    body,
+                ])
+                .into(),
+            },
+        ])
+    }
+
+    fn from_loop_control_statement(node: SyntaxNode, ctx: &mut LookupCtx) -> Expression {
+        let is_break = node.kind() == SyntaxKind::BreakStatement;
+        if !ctx.inside_loop {
+            let keyword = if is_break { "break" } else { "continue" };
+            ctx.diag.push_error(format!("'{keyword}' can only be used within a 'for' loop"), &node);
+            return Expression::Invalid;
+        }
+        if is_break {
+            Expression::Break
+        } else {
+            Expression::Continue
+        }
+    }
+
     fn from_return_statement(
         node: syntax_nodes::ReturnStatement,
         ctx: &mut LookupCtx,
@@ -311,6 +445,17 @@ fn from_expression_node(node: syntax_nodes::Expression, ctx: &mut LookupCtx) ->
                 node.FunctionCallExpression().map(|n| Self::from_function_call_node(n, ctx))
             })
             .or_else(|| node.MemberAccess().map(|n| Self::from_member_access_node(n, ctx)))
+            .or_else(|| node.ForStatement().map(|n| Self::from_for_statement(n, ctx)))
+            .or_else(|| {
+                node.Lambda().map(|n| {
+                    ctx.diag.push_error(
+                        "Lambdas can only be used as argument of 'filter', 'map', or 'sort-by'"
+                            .into(),
+                        &n,
+                    );
+                    Self::Invalid
+                })
+            })
             .or_else(|| node.IndexExpression().map(|n| Self::from_index_expression_node(n, ctx)))
             .or_else(|| node.SelfAssignment().map(|n| Self::from_self_assignment_node(n, ctx)))
             .or_else(|| node.BinaryExpression().map(|n| Self::from_binary_expression_node(n, ctx)))
@@ -409,13 +554,30 @@ fn from_at_image_url_node(node: syntax_nodes::AtImageUrl, ctx: &mut LookupCtx) -
 
     fn from_at_gradient(node: syntax_nodes::AtGradient, ctx: &mut LookupCtx) -> Self {
         enum GradKind {
-            Linear { angle: Box<Expression> },
-            Radial,
+            Linear {
+                angle: Box<Expression>,
+            },
+            Radial {
+                shape: RadialGradientShape,
+                extent: RadialGradientExtent,
+                center_x: Box<Expression>,
+                center_y: Box<Expression>,
+            },
+            Conic {
+                from_angle: Box<Expression>,
+                center_x: Box<Expression>,
+                center_y: Box<Expression>,
+            },
+        }
+
+        fn is_gradient_keyword(n: &NodeOrToken, keyword: &str) -> bool {
+            matches!(n, NodeOrToken::Node(n) if n.text().to_string().trim() == keyword)
         }
 
         let mut subs = node
             .children_with_tokens()
-            .filter(|n| matches!(n.kind(), SyntaxKind::Comma | SyntaxKind::Expression));
+            .filter(|n| matches!(n.kind(), SyntaxKind::Comma | SyntaxKind::Expression))
+            .peekable();
 
         let grad_token = node.child_token(SyntaxKind::Identifier).unwrap();
         let grad_text = grad_token.text();
@@ -446,26 +608,111 @@ enum GradKind {
             );
             GradKind::Linear { angle }
         } else if grad_text.starts_with("radial") {
-            if !matches!(subs.next(), Some(NodeOrToken::Node(n)) if n.text().to_string().trim() == "circle")
+            let mut shape = None;
+            let mut extent = None;
+            let mut center_x = Box::new(Expression::NumberLiteral(0.5, Unit::None));
+            let mut center_y = Box::new(Expression::NumberLiteral(0.5, Unit::None));
+            let mut has_prefix = false;
+            let mut has_position = false;
+            while let Some(NodeOrToken::Node(n)) =
+                subs.peek().filter(|n| n.kind() == SyntaxKind::Expression).cloned()
             {
-                ctx.diag.push_error("Expected 'circle': currently, only @radial-gradient(circle, ...) are supported".into(), &node);
-                return Expression::Invalid;
+                let (new_shape, new_extent) = match n.text().to_string().trim() {
+                    "circle" => (Some(RadialGradientShape::Circle), None),
+                    "ellipse" => (Some(RadialGradientShape::Ellipse), None),
+                    "closest-side" => (None, Some(RadialGradientExtent::ClosestSide)),
+                    "farthest-side" => (None, Some(RadialGradientExtent::FarthestSide)),
+                    "closest-corner" => (None, Some(RadialGradientExtent::ClosestCorner)),
+                    "farthest-corner" => (None, Some(RadialGradientExtent::FarthestCorner)),
+                    "at" => {
+                        subs.next();
+                        has_prefix = true;
+                        has_position = true;
+                        let Some((x, y)) = Self::from_gradient_position(&node, &mut subs, ctx)
+                        else {
+                            return Expression::Invalid;
+                        };
+                        (center_x, center_y) = (x, y);
+                        break;
+                    }
+                    // Without shape, size, or position, this is already the first color stop
+                    _ => break,
+                };
+                subs.next();
+                if (new_shape.is_some() && shape.is_some())
+                    || (new_extent.is_some() && extent.is_some())
+                {
+                    ctx.diag.push_error("Duplicated shape or size in @radial-gradient".into(), &n);
+                    return Expression::Invalid;
+                }
+                shape = shape.or(new_shape);
+                extent = extent.or(new_extent);
+                has_prefix = true;
             }
-            let comma = subs.next();
-            if matches!(&comma, Some(NodeOrToken::Node(n)) if n.text().to_string().trim() == "at") {
-                ctx.diag.push_error("'at' in @radial-gradient is not yet supported".into(), &comma);
-                return Expression::Invalid;
+            if has_prefix {
+                let comma = subs.next();
+                if comma.as_ref().map_or(false, |s| s.kind() != SyntaxKind::Comma) {
+                    ctx.diag.push_error(
+                        "The shape, size, and position of @radial-gradient must be followed by a comma"
+                            .into(),
+                        comma.as_ref().map_or(&node, |x| x as &dyn Spanned),
+                    );
+                    return Expression::Invalid;
+                }
             }
-            if comma.as_ref().map_or(false, |s| s.kind() != SyntaxKind::Comma) {
-                ctx.diag.push_error(
-                    "'circle' must be followed by a comma".into(),
-                    comma.as_ref().map_or(&node, |x| x as &dyn Spanned),
-                );
-                return Expression::Invalid;
+            // A plain `circle` keeps the radius it had before the size could be specified
+            let legacy_circle = shape == Some(RadialGradientShape::Circle) && !has_position;
+            let extent = extent.unwrap_or(if legacy_circle {
+                RadialGradientExtent::AverageSide
+            } else {
+                RadialGradientExtent::default()
+            });
+            GradKind::Radial { shape: shape.unwrap_or_default(), extent, center_x, center_y }
+        } else if grad_text.starts_with("conic") {
+            let mut from_angle = Box::new(Expression::NumberLiteral(0., Unit::Deg));
+            let mut center_x = Box::new(Expression::NumberLiteral(0.5, Unit::None));
+            let mut center_y = Box::new(Expression::NumberLiteral(0.5, Unit::None));
+            let mut has_prefix = false;
+            if subs.peek().map_or(false, |n| is_gradient_keyword(n, "from")) {
+                subs.next();
+                has_prefix = true;
+                match subs.next() {
+                    Some(n @ NodeOrToken::Node(_)) if !is_gradient_keyword(&n, "at") => {
+                        let n = n.into_node().unwrap();
+                        let angle_expr = syntax_nodes::Expression::from(n);
+                        from_angle = Box::new(
+                            Expression::from_expression_node(angle_expr.clone(), ctx)
+                                .maybe_convert_to(Type::Angle, &angle_expr, ctx.diag),
+                        );
+                    }
+                    _ => {
+                        ctx.diag.push_error("Expected angle expression after 'from'".into(), &node);
+                        return Expression::Invalid;
+                    }
+                }
+            }
+            if subs.peek().map_or(false, |n| is_gradient_keyword(n, "at")) {
+                subs.next();
+                has_prefix = true;
+                let Some((x, y)) = Self::from_gradient_position(&node, &mut subs, ctx) else {
+                    return Expression::Invalid;
+                };
+                (center_x, center_y) = (x, y);
+            }
+            if has_prefix {
+                let comma = subs.next();
+                if comma.as_ref().map_or(false, |s| s.kind() != SyntaxKind::Comma) {
+                    ctx.diag.push_error(
+                        "The angle and position of @conic-gradient must be followed by a comma"
+                            .into(),
+                        comma.as_ref().map_or(&node, |x| x as &dyn Spanned),
+                    );
+                    return Expression::Invalid;
+                }
             }
-            GradKind::Radial
+            GradKind::Conic { from_angle, center_x, center_y }
         } else {
-            // Parser should have ensured we have one of the linear or radial gradient
+            // Parser should have ensured we have one of the linear, radial, or conic gradient
             panic!("Not a gradient {grad_text:?}");
         };
 
@@ -511,6 +758,18 @@ enum Stop {
                         break;
                     }
                     Stop::Color(col) => {
+                        // The stops of conic gradients can also be angles, relative to the full turn
+                        let e = if matches!(grad_kind, GradKind::Conic { .. })
+                            && e.ty() == Type::Angle
+                        {
+                            Expression::BinaryExpression {
+                                lhs: Box::new(e),
+                                rhs: Box::new(Expression::NumberLiteral(360., Unit::Deg)),
+                                op: '/',
+                            }
+                        } else {
+                            e
+                        };
                         stops.push((col, e.maybe_convert_to(Type::Float32, &n, ctx.diag)))
                     }
                 }
@@ -570,8 +829,66 @@ enum Stop {
 
         match grad_kind {
             GradKind::Linear { angle } => Expression::LinearGradient { angle, stops },
-            GradKind::Radial => Expression::RadialGradient { stops },
+            GradKind::Radial { shape, extent, center_x, center_y } => {
+                Expression::RadialGradient { shape, extent, center_x, center_y, stops }
+            }
+            GradKind::Conic { from_angle, center_x, center_y } => {
+                Expression::ConicGradient { from_angle, center_x, center_y, stops }
+            }
+        }
+    }
+
+    /// Resolve the position that follows the `at` keyword in a gradient, such as `at 25% 75%`
+    /// or `at left top`, and return the x and y coordinates as a ratio of the size of the shape.
+    fn from_gradient_position(
+        node: &syntax_nodes::AtGradient,
+        subs: &mut std::iter::Peekable<impl Iterator<Item = NodeOrToken>>,
+        ctx: &mut LookupCtx,
+    ) -> Option<(Box<Expression>, Box<Expression>)> {
+        let mut values = vec![];
+        while let Some(NodeOrToken::Node(n)) = subs.next_if(|n| n.kind() == SyntaxKind::Expression)
+        {
+            values.push(syntax_nodes::Expression::from(n));
         }
+        let keyword = |e: &syntax_nodes::Expression| match e.text().to_string().trim() {
+            "left" | "top" => Some(0.),
+            "center" => Some(0.5),
+            "right" | "bottom" => Some(1.),
+            _ => None,
+        };
+        let is_vertical =
+            |e: &syntax_nodes::Expression| matches!(e.text().to_string().trim(), "top" | "bottom");
+        let (x, y) = match values.as_slice() {
+            [v] if is_vertical(v) => (None, Some(v)),
+            [v] => (Some(v), None),
+            [a, b] if is_vertical(a) || matches!(b.text().to_string().trim(), "left" | "right") => {
+                (Some(b), Some(a))
+            }
+            [a, b] => (Some(a), Some(b)),
+            _ => {
+                ctx.diag.push_error(
+                    "Expected one or two values for the position after 'at'".into(),
+                    values.get(2).map_or(node as &dyn Spanned, |v| v as &dyn Spanned),
+                );
+                return None;
+            }
+        };
+        let mut resolve = |e: Option<&syntax_nodes::Expression>| {
+            Box::new(match e {
+                None => Expression::NumberLiteral(0.5, Unit::None),
+                Some(e) => match keyword(e) {
+                    Some(value) => Expression::NumberLiteral(value, Unit::None),
+                    None => Expression::from_expression_node(e.clone(), ctx).maybe_convert_to(
+                        Type::Float32,
+                        e,
+                        ctx.diag,
+                    ),
+                },
+            })
+        };
+        let x = resolve(x);
+        let y = resolve(y);
+        Some((x, y))
     }
 
     fn from_at_tr(node: syntax_nodes::AtTr, ctx: &mut LookupCtx) -> Expression {
@@ -895,6 +1212,12 @@ fn from_function_call_node(
                 .unwrap_or_else(|| Self::from_expression_node(n, ctx))
         });
 
+        if let Expression::MemberFunction { base, member, .. } = &function {
+            if let Expression::BuiltinMacroReference(mac, _) = &**member {
+                return Self::from_model_operation(&node, (**base).clone(), mac, sub_expr, ctx);
+            }
+        }
+
         let sub_expr = sub_expr.map(|n| {
             (Self::from_expression_node(n.clone(), ctx), Some(NodeOrToken::from((*n).clone())))
         });
@@ -915,7 +1238,24 @@ fn from_function_call_node(
         };
         arguments.extend(sub_expr);
 
-        let arguments = match function.ty() {
+        let function_ty = match (&*function, arguments.first()) {
+            // The value to look for has the type of the elements of the array
+            (
+                Expression::BuiltinFunctionReference(
+                    BuiltinFunction::ArrayIndexOf | BuiltinFunction::ArrayContains,
+                    _,
+                ),
+                Some((array, _)),
+            ) => match (function.ty(), array.ty()) {
+                (Type::Function { return_type, .. }, Type::Array(element_ty)) => {
+                    Type::Function { return_type, args: vec![Type::Model, *element_ty] }
+                }
+                (ty, _) => ty,
+            },
+            _ => function.ty(),
+        };
+
+        let arguments = match function_ty {
             Type::Function { args, .. } | Type::Callback { args, .. } => {
                 if arguments.len() != args.len() {
                     ctx.diag.push_error(
@@ -952,12 +1292,151 @@ fn from_function_call_node(
         }
     }
 
+    /// `model.filter(x => ...)`, `model.map(x => ...)`, `model.sort-by(x => ...)`,
+    /// or `model.slice(start, end)`
+    fn from_model_operation(
+        node: &syntax_nodes::FunctionCallExpression,
+        model: Expression,
+        mac: &BuiltinMacroFunction,
+        args: impl Iterator<Item = syntax_nodes::Expression>,
+        ctx: &mut LookupCtx,
+    ) -> Expression {
+        let args = args.collect::<Vec<_>>();
+        let (name, row_ty) = match (mac, model.ty()) {
+            (BuiltinMacroFunction::ArrayFilter, Type::Array(ty)) => ("filter", *ty),
+            (BuiltinMacroFunction::ArrayMap, Type::Array(ty)) => ("map", *ty),
+            (BuiltinMacroFunction::ArraySortBy, Type::Array(ty)) => ("sort-by", *ty),
+            (BuiltinMacroFunction::ArraySlice, _) => {
+                let [start, end] = args.as_slice() else {
+                    ctx.diag.push_error(
+                        "'slice' expects two arguments: the start and the end".into(),
+                        node,
+                    );
+                    return Expression::Invalid;
+                };
+                let mut index = |n: &syntax_nodes::Expression| {
+                    Box::new(Self::from_expression_node(n.clone(), ctx).maybe_convert_to(
+                        Type::Int32,
+                        n,
+                        ctx.diag,
+                    ))
+                };
+                let operation = ModelOperation::Slice { start: index(start), end: index(end) };
+                return Expression::ModelOperation { model: Box::new(model), operation };
+            }
+            _ => unreachable!("model operations are only looked up in arrays"),
+        };
+
+        let lambda = match args.as_slice() {
+            [arg] => arg.Lambda(),
+            _ => None,
+        };
+        let Some(lambda) = lambda else {
+            ctx.diag.push_error(
+                format!("'{name}' expects a lambda as argument, such as `{name}(x => x)`"),
+                node,
+            );
+            return Expression::Invalid;
+        };
+
+        let param = identifier_text(&lambda.DeclaredIdentifier()).unwrap_or_default();
+        ctx.check_local_variable_redeclaration(&param, &lambda.DeclaredIdentifier());
+
+        let body_node = lambda.Expression();
+        ctx.local_variables.push(vec![(param.clone(), row_ty)]);
+        let was_inside_loop = std::mem::replace(&mut ctx.inside_loop, false);
+        let mut body = Self::from_expression_node(body_node.clone(), ctx);
+        ctx.inside_loop = was_inside_loop;
+        ctx.local_variables.pop();
+
+        match mac {
+            BuiltinMacroFunction::ArrayFilter => {
+                body = body.maybe_convert_to(Type::Bool, &body_node, ctx.diag);
+            }
+            BuiltinMacroFunction::ArrayMap => {
+                if body.ty() == Type::Void {
+                    ctx.diag
+                        .push_error("The lambda of 'map' must return a value".into(), &body_node);
+                }
+            }
+            _ => {
+                let ty = body.ty();
+                if ty != Type::Invalid && ty != Type::String && ty.as_unit_product().is_none() {
+                    ctx.diag.push_error(
+                        format!(
+                            "Cannot sort by a value of type '{ty}'. Expected a number or a string"
+                        ),
+                        &body_node,
+                    );
+                }
+            }
+        }
+
+        // Everything in the body that doesn't depend on the lambda's parameter is evaluated
+        // when the model is created, so that the body can be evaluated outside of the component.
+        static COUNT: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+        let id = COUNT.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let variable = format!("local_{param}");
+        let mut locals = HashSet::from([variable.clone()]);
+        collect_local_variables(&body, &mut locals);
+        let mut statements = Vec::new();
+        hoist_lambda_captures(&mut body, &locals, &format!("lambda{id}_capture"), &mut statements);
+        check_lambda_body(&body, &body_node, ctx.diag);
+
+        let body = Box::new(body);
+        let operation = match mac {
+            BuiltinMacroFunction::ArrayFilter => ModelOperation::Filter { variable, body },
+            BuiltinMacroFunction::ArrayMap => ModelOperation::Map { variable, body },
+            _ => ModelOperation::SortBy { variable, body },
+        };
+        let model_operation = Expression::ModelOperation { model: Box::new(model), operation };
+        if statements.is_empty() {
+            model_operation
+        } else {
+            statements.push(model_operation);
+            Expression::CodeBlock(statements)
+        }
+    }
+
     fn from_member_access_node(
         node: syntax_nodes::MemberAccess,
         ctx: &mut LookupCtx,
     ) -> Expression {
         let base = Self::from_expression_node(node.Expression(), ctx);
-        maybe_lookup_object(base, node.child_token(SyntaxKind::Identifier).into_iter(), ctx)
+        if node.child_token(SyntaxKind::QuestionDot).is_none() {
+            return maybe_lookup_object(
+                base,
+                node.child_token(SyntaxKind::Identifier).into_iter(),
+                ctx,
+            );
+        }
+        let inner_ty = match base.ty() {
+            Type::Optional(inner) => *inner,
+            Type::Invalid => return Expression::Invalid,
+            ty => {
+                ctx.diag.push_error(
+                    format!("'?.' can only be used on optional values, not on {}", ty),
+                    &node,
+                );
+                return Expression::Invalid;
+            }
+        };
+        let variable = "optional_value".to_string();
+        let body = maybe_lookup_object(
+            Expression::ReadLocalVariable { name: variable.clone(), ty: inner_ty },
+            node.child_token(SyntaxKind::Identifier).into_iter(),
+            ctx,
+        );
+        let body = match body.ty() {
+            Type::Invalid => return Expression::Invalid,
+            Type::Function { .. } | Type::Callback { .. } => {
+                ctx.diag.push_error("Functions cannot be called with '?.'".into(), &node);
+                return Expression::Invalid;
+            }
+            Type::Optional(_) => body,
+            ty => body.maybe_convert_to(Type::Optional(Box::new(ty)), &node, ctx.diag),
+        };
+        Expression::OptionalChaining { optional: Box::new(base), variable, body: Box::new(body) }
     }
 
     fn from_self_assignment_node(
@@ -1005,6 +1484,9 @@ fn from_binary_expression_node(
         node: syntax_nodes::BinaryExpression,
         ctx: &mut LookupCtx,
     ) -> Expression {
+        if node.child_token(SyntaxKind::QuestionQuestion).is_some() {
+            return Self::from_null_coalescing_node(node, ctx);
+        }
         let op = None
             .or_else(|| node.child_token(SyntaxKind::Plus).and(Some('+')))
             .or_else(|| node.child_token(SyntaxKind::Minus).and(Some('-')))
@@ -1026,7 +1508,15 @@ fn from_binary_expression_node(
 
         let expected_ty = match operator_class(op) {
             OperatorClass::ComparisonOp => {
-                Self::common_target_type_for_type_list([lhs.ty(), rhs.ty()].iter().cloned())
+                let ty =
+                    Self::common_target_type_for_type_list([lhs.ty(), rhs.ty()].iter().cloned());
+                if matches!(ty, Type::Optional(_)) && !matches!(op, '=' | '!') {
+                    ctx.diag.push_error(
+                        "Optional values can only be compared with '==' and '!='".into(),
+                        &node,
+                    );
+                }
+                ty
             }
             OperatorClass::LogicalOp => Type::Bool,
             OperatorClass::ArithmeticOp => {
@@ -1093,6 +1583,38 @@ fn from_binary_expression_node(
         }
     }
 
+    fn from_null_coalescing_node(
+        node: syntax_nodes::BinaryExpression,
+        ctx: &mut LookupCtx,
+    ) -> Expression {
+        let (lhs_n, rhs_n) = node.Expression();
+        let lhs = Self::from_expression_node(lhs_n.clone(), ctx);
+        let rhs = Self::from_expression_node(rhs_n.clone(), ctx);
+        let value_ty = match lhs.ty() {
+            // `null ?? rhs`
+            Type::Optional(inner) if *inner == Type::Void => return rhs,
+            Type::Optional(inner) => *inner,
+            Type::Invalid => return Expression::Invalid,
+            ty => {
+                ctx.diag.push_error(
+                    format!("The left side of '??' must be an optional value, not {}", ty),
+                    &lhs_n,
+                );
+                return Expression::Invalid;
+            }
+        };
+        // `a ?? b` is optional if `b` is optional
+        let result_ty = if matches!(rhs.ty(), Type::Optional(_)) {
+            Type::Optional(Box::new(value_ty))
+        } else {
+            value_ty
+        };
+        Expression::NullCoalescing {
+            lhs: Box::new(lhs),
+            rhs: Box::new(rhs.maybe_convert_to(result_ty, &rhs_n, ctx.diag)),
+        }
+    }
+
     fn from_unaryop_expression_node(
         node: syntax_nodes::UnaryOpExpression,
         ctx: &mut LookupCtx,
@@ -1297,6 +1819,23 @@ pub fn common_target_type_for_type_list(types: impl Iterator<Item = Type>) -> Ty
                         Self::common_target_type_for_type_list([*lhs, *rhs].into_iter()).into()
                     }),
                     (Type::Color, Type::Brush) | (Type::Brush, Type::Color) => Type::Brush,
+                    (Type::Optional(lhs), Type::Optional(rhs)) => {
+                        Type::Optional(if *lhs == Type::Void {
+                            rhs
+                        } else if *rhs == Type::Void {
+                            lhs
+                        } else {
+                            Self::common_target_type_for_type_list([*lhs, *rhs].into_iter()).into()
+                        })
+                    }
+                    (Type::Optional(optional), other) | (other, Type::Optional(optional)) => {
+                        Type::Optional(if *optional == Type::Void {
+                            other.into()
+                        } else {
+                            Self::common_target_type_for_type_list([*optional, other].into_iter())
+                                .into()
+                        })
+                    }
                     (target_type, expr_ty) => {
                         if expr_ty.can_convert(&target_type) {
                             target_type
@@ -1490,6 +2029,13 @@ fn maybe_lookup_object(
                 }
                 let ty_descr = match base.ty() {
                     Type::Struct { .. } => String::new(),
+                    Type::Optional(_) => {
+                        ctx.diag.push_error(
+                            format!("Cannot access the field '{}' of an optional value. Use '?.' instead of '.'", next.text()),
+                            &next,
+                        );
+                        return Expression::Invalid;
+                    }
                     ty => format!(" of {}", ty),
                 };
                 ctx.diag.push_error(
@@ -1503,6 +2049,109 @@ fn maybe_lookup_object(
     base
 }
 
+/// Collect the names of the local variables declared within the body of a lambda
+fn collect_local_variables(expr: &Expression, locals: &mut HashSet<String>) {
+    match expr {
+        Expression::StoreLocalVariable { name, .. } => {
+            locals.insert(name.clone());
+        }
+        Expression::ModelOperation {
+            operation:
+                ModelOperation::Filter { variable, .. }
+                | ModelOperation::Map { variable, .. }
+                | ModelOperation::SortBy { variable, .. },
+            ..
+        } => {
+            locals.insert(variable.clone());
+        }
+        Expression::OptionalChaining { variable, .. } => {
+            locals.insert(variable.clone());
+        }
+        _ => {}
+    }
+    expr.visit(|e| collect_local_variables(e, locals));
+}
+
+fn depends_on_local_variables(expr: &Expression, locals: &HashSet<String>) -> bool {
+    let mut result =
+        matches!(expr, Expression::ReadLocalVariable { name, .. } if locals.contains(name));
+    expr.visit(|e| result = result || depends_on_local_variables(e, locals));
+    result
+}
+
+/// Replace the largest sub-expressions of the lambda's body that do not depend on the `locals`
+/// by a local variable. The statements that store these local variables are added to `statements`.
+fn hoist_lambda_captures(
+    expr: &mut Expression,
+    locals: &HashSet<String>,
+    prefix: &str,
+    statements: &mut Vec<Expression>,
+) {
+    let ty = expr.ty();
+    let is_literal = matches!(
+        expr,
+        Expression::StringLiteral(_)
+            | Expression::NumberLiteral(..)
+            | Expression::BoolLiteral(_)
+            | Expression::NullLiteral(_)
+            | Expression::EnumerationValue(_)
+    );
+    if !is_literal
+        && ty.is_property_type()
+        && ty != Type::ElementReference
+        && !depends_on_local_variables(expr, locals)
+    {
+        let name = format!("{prefix}{}", statements.len());
+        let value =
+            std::mem::replace(expr, Expression::ReadLocalVariable { name: name.clone(), ty });
+        statements.push(Expression::StoreLocalVariable { name, value: Box::new(value) });
+    } else {
+        expr.visit_mut(|e| hoist_lambda_captures(e, locals, prefix, statements));
+    }
+}
+
+/// Report what remains in the body of a lambda after [`hoist_lambda_captures`] and that
+/// can't be evaluated without the component.
+fn check_lambda_body(
+    expr: &Expression,
+    node: &syntax_nodes::Expression,
+    diag: &mut BuildDiagnostics,
+) {
+    match expr {
+        Expression::SelfAssignment { .. } => {
+            diag.push_error("Assignments are not allowed in a lambda".into(), node);
+        }
+        Expression::ReturnStatement(..) => {
+            diag.push_error("'return' is not allowed in a lambda".into(), node);
+        }
+        Expression::ForLoop { .. } => {
+            diag.push_error("'for' loops are not allowed in a lambda".into(), node);
+        }
+        Expression::FunctionCall { function, arguments, .. } => {
+            match &**function {
+                Expression::BuiltinFunctionReference(f, _) if f.is_pure() => {}
+                Expression::BuiltinFunctionReference(..) => {
+                    diag.push_error("Only pure functions can be called in a lambda".into(), node)
+                }
+                _ => diag.push_error(
+                    "Functions and callbacks cannot be called with the parameter of a lambda"
+                        .into(),
+                    node,
+                ),
+            }
+            arguments.iter().for_each(|e| check_lambda_body(e, node, diag));
+        }
+        // The bodies of nested lambdas were already checked
+        Expression::ModelOperation { model, operation: ModelOperation::Slice { start, end } } => {
+            check_lambda_body(model, node, diag);
+            check_lambda_body(start, node, diag);
+            check_lambda_body(end, node, diag);
+        }
+        Expression::ModelOperation { model, .. } => check_lambda_body(model, node, diag),
+        _ => expr.visit(|e| check_lambda_body(e, node, diag)),
+    }
+}
+
 /// Go through all the two way binding and resolve them first
 fn resolve_two_way_bindings(
     doc: &Document,
@@ -1510,114 +2159,124 @@ fn resolve_two_way_bindings(
     diag: &mut BuildDiagnostics,
 ) {
     for component in doc.inner_components.iter() {
-        let scope = ComponentScope(vec![]);
-
-        recurse_elem(&component.root_element, &scope, &mut |elem, scope| {
-            let mut new_scope = scope.clone();
-            new_scope.0.push(elem.clone());
-            for (prop_name, binding) in &elem.borrow().bindings {
-                let mut binding = binding.borrow_mut();
-                if let Expression::Uncompiled(node) = binding.expression.clone() {
-                    if let Some(n) = syntax_nodes::TwoWayBinding::new(node.clone()) {
-                        let lhs_lookup = elem.borrow().lookup_property(prop_name);
-                        if lhs_lookup.property_type == Type::Invalid {
-                            // An attempt to resolve this already failed when trying to resolve the property type
-                            assert!(diag.has_error());
-                            continue;
-                        }
-                        let mut lookup_ctx = LookupCtx {
-                            property_name: Some(prop_name.as_str()),
-                            property_type: lhs_lookup.property_type.clone(),
-                            component_scope: &new_scope.0,
-                            diag,
-                            arguments: vec![],
-                            type_register,
-                            type_loader: None,
-                            current_token: Some(node.clone().into()),
-                        };
-
-                        binding.expression = Expression::Invalid;
-
-                        if let Some(nr) = resolve_two_way_binding(n, &mut lookup_ctx) {
-                            binding.two_way_bindings.push(nr.clone());
-
-                            nr.element()
-                                .borrow()
-                                .property_analysis
-                                .borrow_mut()
-                                .entry(nr.name().to_string())
-                                .or_default()
-                                .is_linked = true;
-
-                            // Check the compatibility.
-                            let mut rhs_lookup = nr.element().borrow().lookup_property(nr.name());
-                            rhs_lookup.is_local_to_component &=
-                                lookup_ctx.is_local_element(&nr.element());
-
-                            if !rhs_lookup.is_valid_for_assignment() {
-                                match (
-                                    lhs_lookup.property_visibility,
-                                    rhs_lookup.property_visibility,
-                                ) {
-                                    (PropertyVisibility::Input, PropertyVisibility::Input)
-                                        if !lhs_lookup.is_local_to_component =>
-                                    {
-                                        assert!(rhs_lookup.is_local_to_component);
-                                        marked_linked_read_only(elem, prop_name);
-                                    }
-                                    (
-                                        PropertyVisibility::Output | PropertyVisibility::Private,
-                                        PropertyVisibility::Output | PropertyVisibility::Input,
-                                    ) => {
-                                        assert!(lhs_lookup.is_local_to_component);
-                                        marked_linked_read_only(elem, prop_name);
+        component.with_diagnostics(diag, |diag| {
+            let scope = ComponentScope(vec![]);
+
+            recurse_elem(&component.root_element, &scope, &mut |elem, scope| {
+                let mut new_scope = scope.clone();
+                new_scope.0.push(elem.clone());
+                for (prop_name, binding) in &elem.borrow().bindings {
+                    let mut binding = binding.borrow_mut();
+                    if let Expression::Uncompiled(node) = binding.expression.clone() {
+                        if let Some(n) = syntax_nodes::TwoWayBinding::new(node.clone()) {
+                            let lhs_lookup = elem.borrow().lookup_property(prop_name);
+                            if lhs_lookup.property_type == Type::Invalid {
+                                // An attempt to resolve this already failed when trying to resolve the property type
+                                assert!(diag.has_error());
+                                continue;
+                            }
+                            let mut lookup_ctx = LookupCtx {
+                                property_name: Some(prop_name.as_str()),
+                                property_type: lhs_lookup.property_type.clone(),
+                                component_scope: &new_scope.0,
+                                diag,
+                                arguments: vec![],
+                                type_register,
+                                type_loader: None,
+                                current_token: Some(node.clone().into()),
+                                local_variables: vec![],
+                                inside_loop: false,
+                            };
+
+                            binding.expression = Expression::Invalid;
+
+                            if let Some(nr) = resolve_two_way_binding(n, &mut lookup_ctx) {
+                                binding.two_way_bindings.push(nr.clone());
+
+                                nr.element()
+                                    .borrow()
+                                    .property_analysis
+                                    .borrow_mut()
+                                    .entry(nr.name().to_string())
+                                    .or_default()
+                                    .is_linked = true;
+
+                                // Check the compatibility.
+                                let mut rhs_lookup =
+                                    nr.element().borrow().lookup_property(nr.name());
+                                rhs_lookup.is_local_to_component &=
+                                    lookup_ctx.is_local_element(&nr.element());
+
+                                if !rhs_lookup.is_valid_for_assignment() {
+                                    match (
+                                        lhs_lookup.property_visibility,
+                                        rhs_lookup.property_visibility,
+                                    ) {
+                                        (PropertyVisibility::Input, PropertyVisibility::Input)
+                                            if !lhs_lookup.is_local_to_component =>
+                                        {
+                                            assert!(rhs_lookup.is_local_to_component);
+                                            marked_linked_read_only(elem, prop_name);
+                                        }
+                                        (
+                                            PropertyVisibility::Output
+                                            | PropertyVisibility::Private,
+                                            PropertyVisibility::Output | PropertyVisibility::Input,
+                                        ) => {
+                                            assert!(lhs_lookup.is_local_to_component);
+                                            marked_linked_read_only(elem, prop_name);
+                                        }
+                                        (PropertyVisibility::Input, PropertyVisibility::Output)
+                                            if !lhs_lookup.is_local_to_component =>
+                                        {
+                                            assert!(!rhs_lookup.is_local_to_component);
+                                            marked_linked_read_only(elem, prop_name);
+                                        }
+                                        _ => {
+                                            if lookup_ctx.is_legacy_component() {
+                                                diag.push_warning(
+                                                    format!(
+                                                        "Link to a {} property is deprecated",
+                                                        rhs_lookup.property_visibility
+                                                    ),
+                                                    &node,
+                                                );
+                                            } else {
+                                                diag.push_error(
+                                                    format!(
+                                                        "Cannot link to a {} property",
+                                                        rhs_lookup.property_visibility
+                                                    ),
+                                                    &node,
+                                                )
+                                            }
+                                        }
                                     }
-                                    (PropertyVisibility::Input, PropertyVisibility::Output)
-                                        if !lhs_lookup.is_local_to_component =>
+                                } else if !lhs_lookup.is_valid_for_assignment() {
+                                    if rhs_lookup.is_local_to_component
+                                        && rhs_lookup.property_visibility
+                                            == PropertyVisibility::InOut
                                     {
-                                        assert!(!rhs_lookup.is_local_to_component);
-                                        marked_linked_read_only(elem, prop_name);
-                                    }
-                                    _ => {
                                         if lookup_ctx.is_legacy_component() {
-                                            diag.push_warning(
-                                                format!(
-                                                    "Link to a {} property is deprecated",
-                                                    rhs_lookup.property_visibility
-                                                ),
-                                                &node,
-                                            );
+                                            debug_assert!(!diag.is_empty()); // warning should already be reported
                                         } else {
                                             diag.push_error(
-                                                format!(
-                                                    "Cannot link to a {} property",
-                                                    rhs_lookup.property_visibility
-                                                ),
+                                                "Cannot link input property".into(),
                                                 &node,
-                                            )
+                                            );
                                         }
-                                    }
-                                }
-                            } else if !lhs_lookup.is_valid_for_assignment() {
-                                if rhs_lookup.is_local_to_component
-                                    && rhs_lookup.property_visibility == PropertyVisibility::InOut
-                                {
-                                    if lookup_ctx.is_legacy_component() {
-                                        debug_assert!(!diag.is_empty()); // warning should already be reported
                                     } else {
-                                        diag.push_error("Cannot link input property".into(), &node);
+                                        // This is allowed, but then the rhs must also become read only.
+                                        marked_linked_read_only(&nr.element(), nr.name());
                                     }
-                                } else {
-                                    // This is allowed, but then the rhs must also become read only.
-                                    marked_linked_read_only(&nr.element(), nr.name());
                                 }
                             }
                         }
                     }
                 }
-            }
-            new_scope
-        })
+                new_scope
+            })
+        });
     }
 
     fn marked_linked_read_only(elem: &ElementRc, prop_name: &str) {
@@ -12,12 +12,14 @@
 /// It currently does so by adding a number to the existing id
 pub fn assign_unique_id(doc: &Document) {
     let mut count = 0;
-    assign_unique_id_in_component(&doc.root_component, &mut count);
-    for c in &doc.root_component.used_types.borrow().sub_components {
+    for c in doc.root_components() {
+        assign_unique_id_in_component(c, &mut count);
+    }
+    for c in &doc.used_sub_components() {
         assign_unique_id_in_component(c, &mut count);
     }
 
-    rename_globals(&doc.root_component, count);
+    rename_globals(&doc.used_globals(), count);
 }
 
 fn assign_unique_id_in_component(component: &Rc<Component>, count: &mut u32) {
@@ -34,8 +36,8 @@ fn assign_unique_id_in_component(component: &Rc<Component>, count: &mut u32) {
 }
 
 /// Give globals unique name
-fn rename_globals(component: &Rc<Component>, mut count: u32) {
-    for g in &component.used_types.borrow().globals {
+fn rename_globals(globals: &[Rc<Component>], mut count: u32) {
+    for g in globals {
         count += 1;
         let mut root = g.root_element.borrow_mut();
         if matches!(&root.base_type, ElementType::Builtin(_)) {
@@ -52,7 +54,9 @@ fn rename_globals(component: &Rc<Component>, mut count: u32) {
 /// Checks that all ids in the Component are unique
 pub fn check_unique_id(doc: &Document, diag: &mut BuildDiagnostics) {
     for component in &doc.inner_components {
-        check_unique_id_in_component(component, diag);
+        component.with_diagnostics(diag, |diag| {
+            check_unique_id_in_component(component, diag);
+        });
     }
 }
 
@@ -194,6 +194,18 @@ fn load_dependencies_recursively_impl<'a: 'b, 'b>(
                                     }),
                                     state.diag,
                                 );
+                                exports.add_reexported_generics(
+                                    doc.exports.generics().iter().map(|(exported_name, generic)| {
+                                        (
+                                            ExportedName {
+                                                name: exported_name.name.clone(),
+                                                name_ident: (**export_module_syntax_node).clone(),
+                                            },
+                                            generic.clone(),
+                                        )
+                                    }),
+                                    state.diag,
+                                );
                                 Some((exports, export_module_syntax_node.clone()))
                             }
                         }
@@ -463,8 +475,9 @@ pub async fn load_root_file(
         diag: &mut BuildDiagnostics,
     ) -> PathBuf {
         let path = crate::pathutils::clean_path(path);
+        let compile_all_exported_components = self.compiler_config.compile_all_exported_components;
         let state = RefCell::new(BorrowedTypeLoader { tl: self, diag });
-        let (path, doc) = Self::load_file_no_pass(
+        let (path, mut doc) = Self::load_file_no_pass(
             &state,
             &path,
             version,
@@ -477,6 +490,9 @@ pub async fn load_root_file(
 
         let mut state = state.borrow_mut();
         let state = &mut *state;
+        if compile_all_exported_components {
+            doc.use_exported_components_as_roots();
+        }
         if !state.diag.has_error() {
             crate::passes::run_passes(&doc, state.tl, state.diag).await;
         }
@@ -579,6 +595,12 @@ fn register_imported_types(
             let imported_type = match imported_type {
                 Some(ty) => ty,
                 None => {
+                    if let Some(generic) = doc.exports.find_generic(&import_name.external_name) {
+                        registry_to_populate
+                            .borrow_mut()
+                            .add_generic_with_name(import_name.internal_name, generic);
+                        continue;
+                    }
                     build_diagnostics.push_error(
                         format!(
                             "No exported type called '{}' found in \"{}\"",
@@ -3,6 +3,7 @@
 
 // cSpell: ignore imum
 
+use itertools::Either;
 use std::cell::RefCell;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::rc::Rc;
@@ -11,7 +12,7 @@
 use crate::langtype::{
     BuiltinElement, BuiltinPropertyInfo, ElementType, Enumeration, PropertyLookupResult, Type,
 };
-use crate::object_tree::{Component, PropertyVisibility};
+use crate::object_tree::{Component, GenericDeclaration, PropertyVisibility};
 
 pub const RESERVED_GEOMETRY_PROPERTIES: &[(&str, Type)] = &[
     ("x", Type::LogicalLength),
@@ -216,12 +217,20 @@ pub fn reserved_member_function(name: &str) -> Option<BuiltinFunction> {
     None
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct TypeRegister {
     /// The set of property types.
     types: HashMap<String, Type>,
     /// The set of element types
     elements: HashMap<String, ElementType>,
+    /// The generic components and structs, which need type arguments to become an element or a type
+    generics: HashMap<String, Rc<GenericDeclaration>>,
+    /// The instances of generics created in this registry, which belong to the document that is
+    /// being loaded. It is shared with the clones of the registry and with the scopes in which
+    /// the instances are created, so that the instances of other generics used in their body are
+    /// also collected.
+    pub(crate) generic_instances:
+        Rc<RefCell<Vec<(Rc<GenericDeclaration>, Either<Rc<Component>, Type>)>>>,
     supported_property_animation_types: HashSet<String>,
     pub(crate) property_animation_type: ElementType,
     pub(crate) empty_type: ElementType,
@@ -251,7 +260,7 @@ fn builtin_internal() -> Self {
         register.insert_type(Type::PhysicalLength);
         register.insert_type(Type::LogicalLength);
         register.insert_type(Type::Color);
-        register.insert_type(Type::ComponentFactory);
+        register.insert_type(Type::ComponentFactory(None));
         register.insert_type(Type::Duration);
         register.insert_type(Type::Image);
         register.insert_type(Type::Bool);
@@ -436,6 +445,13 @@ pub fn lookup_element(&self, name: &str) -> Result<ElementType, String> {
         })
     }
 
+    pub fn lookup_generic(&self, name: &str) -> Option<Rc<GenericDeclaration>> {
+        self.generics
+            .get(name)
+            .cloned()
+            .or_else(|| self.parent_registry.as_ref().and_then(|r| r.borrow().lookup_generic(name)))
+    }
+
     pub fn lookup_builtin_element(&self, name: &str) -> Option<ElementType> {
         self.parent_registry.as_ref().map_or_else(
             || self.elements.get(name).cloned(),
@@ -458,6 +474,14 @@ pub fn add_with_name(&mut self, name: String, comp: Rc<Component>) {
         self.elements.insert(name, ElementType::Component(comp));
     }
 
+    pub fn add_generic(&mut self, generic: Rc<GenericDeclaration>) {
+        self.generics.insert(generic.name.clone(), generic);
+    }
+
+    pub fn add_generic_with_name(&mut self, name: String, generic: Rc<GenericDeclaration>) {
+        self.generics.insert(name, generic);
+    }
+
     pub fn add_builtin(&mut self, builtin: Rc<BuiltinElement>) {
         self.elements.insert(builtin.name.clone(), ElementType::Builtin(builtin));
     }
@@ -34,9 +34,9 @@
 pub(crate) mod color;
 pub use color::*;
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", feature = "software-renderer-path"))]
 mod path;
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", feature = "software-renderer-path"))]
 pub use path::*;
 
 mod brush;
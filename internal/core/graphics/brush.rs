@@ -26,9 +26,12 @@ pub enum Brush {
     /// The linear gradient variant of a brush describes the gradient stops for a fill
     /// where all color stops are along a line that's rotated by the specified angle.
     LinearGradient(LinearGradientBrush),
-    /// The radial gradient variant of a brush describes a circle variant centered
-    /// in the middle
+    /// The radial gradient variant of a brush describes the gradient stops for a fill
+    /// where the colors are interpolated along circles or ellipses around a center point.
     RadialGradient(RadialGradientBrush),
+    /// The conic gradient variant of a brush describes the gradient stops for a fill
+    /// where the colors are interpolated around a center point, starting at the specified angle.
+    ConicGradient(ConicGradientBrush),
 }
 
 /// Construct a brush with transparent color
@@ -50,6 +53,9 @@ pub fn color(&self) -> Color {
             Brush::RadialGradient(gradient) => {
                 gradient.stops().next().map(|stop| stop.color).unwrap_or_default()
             }
+            Brush::ConicGradient(gradient) => {
+                gradient.stops().next().map(|stop| stop.color).unwrap_or_default()
+            }
         }
     }
 
@@ -66,6 +72,7 @@ pub fn is_transparent(&self) -> bool {
             Brush::SolidColor(c) => c.alpha() == 0,
             Brush::LinearGradient(_) => false,
             Brush::RadialGradient(_) => false,
+            Brush::ConicGradient(_) => false,
         }
     }
 
@@ -82,6 +89,7 @@ pub fn is_opaque(&self) -> bool {
             Brush::SolidColor(c) => c.alpha() == 255,
             Brush::LinearGradient(g) => g.stops().all(|s| s.color.alpha() == 255),
             Brush::RadialGradient(g) => g.stops().all(|s| s.color.alpha() == 255),
+            Brush::ConicGradient(g) => g.stops().all(|s| s.color.alpha() == 255),
         }
     }
 
@@ -99,11 +107,23 @@ pub fn brighter(&self, factor: f32) -> Self {
                     position: s.position,
                 }),
             )),
-            Brush::RadialGradient(g) => {
-                Brush::RadialGradient(RadialGradientBrush::new_circle(g.stops().map(|s| {
-                    GradientStop { color: s.color.brighter(factor), position: s.position }
-                })))
-            }
+            Brush::RadialGradient(g) => Brush::RadialGradient(RadialGradientBrush::new(
+                g.shape(),
+                g.extent(),
+                g.center(),
+                g.stops().map(|s| GradientStop {
+                    color: s.color.brighter(factor),
+                    position: s.position,
+                }),
+            )),
+            Brush::ConicGradient(g) => Brush::ConicGradient(ConicGradientBrush::new(
+                g.angle(),
+                g.center(),
+                g.stops().map(|s| GradientStop {
+                    color: s.color.brighter(factor),
+                    position: s.position,
+                }),
+            )),
         }
     }
 
@@ -119,7 +139,16 @@ pub fn darker(&self, factor: f32) -> Self {
                 g.stops()
                     .map(|s| GradientStop { color: s.color.darker(factor), position: s.position }),
             )),
-            Brush::RadialGradient(g) => Brush::RadialGradient(RadialGradientBrush::new_circle(
+            Brush::RadialGradient(g) => Brush::RadialGradient(RadialGradientBrush::new(
+                g.shape(),
+                g.extent(),
+                g.center(),
+                g.stops()
+                    .map(|s| GradientStop { color: s.color.darker(factor), position: s.position }),
+            )),
+            Brush::ConicGradient(g) => Brush::ConicGradient(ConicGradientBrush::new(
+                g.angle(),
+                g.center(),
                 g.stops()
                     .map(|s| GradientStop { color: s.color.darker(factor), position: s.position }),
             )),
@@ -142,11 +171,23 @@ pub fn transparentize(&self, amount: f32) -> Self {
                     position: s.position,
                 }),
             )),
-            Brush::RadialGradient(g) => {
-                Brush::RadialGradient(RadialGradientBrush::new_circle(g.stops().map(|s| {
-                    GradientStop { color: s.color.transparentize(amount), position: s.position }
-                })))
-            }
+            Brush::RadialGradient(g) => Brush::RadialGradient(RadialGradientBrush::new(
+                g.shape(),
+                g.extent(),
+                g.center(),
+                g.stops().map(|s| GradientStop {
+                    color: s.color.transparentize(amount),
+                    position: s.position,
+                }),
+            )),
+            Brush::ConicGradient(g) => Brush::ConicGradient(ConicGradientBrush::new(
+                g.angle(),
+                g.center(),
+                g.stops().map(|s| GradientStop {
+                    color: s.color.transparentize(amount),
+                    position: s.position,
+                }),
+            )),
         }
     }
 
@@ -163,11 +204,23 @@ pub fn with_alpha(&self, alpha: f32) -> Self {
                     position: s.position,
                 }),
             )),
-            Brush::RadialGradient(g) => {
-                Brush::RadialGradient(RadialGradientBrush::new_circle(g.stops().map(|s| {
-                    GradientStop { color: s.color.with_alpha(alpha), position: s.position }
-                })))
-            }
+            Brush::RadialGradient(g) => Brush::RadialGradient(RadialGradientBrush::new(
+                g.shape(),
+                g.extent(),
+                g.center(),
+                g.stops().map(|s| GradientStop {
+                    color: s.color.with_alpha(alpha),
+                    position: s.position,
+                }),
+            )),
+            Brush::ConicGradient(g) => Brush::ConicGradient(ConicGradientBrush::new(
+                g.angle(),
+                g.center(),
+                g.stops().map(|s| GradientStop {
+                    color: s.color.with_alpha(alpha),
+                    position: s.position,
+                }),
+            )),
         }
     }
 }
@@ -204,20 +257,180 @@ pub fn stops(&self) -> impl Iterator<Item = &GradientStop> {
     }
 }
 
-/// The RadialGradientBrush describes a way of filling a shape with a circular gradient
+/// The shape of a radial gradient
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum RadialGradientShape {
+    /// The gradient is a circle
+    Circle,
+    /// The gradient is an ellipse, with the aspect ratio defined by the extent
+    #[default]
+    Ellipse,
+}
+
+/// How far a radial gradient extends, like the `<radial-extent>` of CSS
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum RadialGradientExtent {
+    /// The gradient ends at the side of the shape that is the closest to the center
+    ClosestSide,
+    /// The gradient ends at the side of the shape that is the farthest from the center
+    FarthestSide,
+    /// The gradient ends at the corner of the shape that is the closest to the center
+    ClosestCorner,
+    /// The gradient ends at the corner of the shape that is the farthest from the center
+    #[default]
+    FarthestCorner,
+    /// The gradient ends at the average distance from the center to the sides of the shape.
+    /// This is the size of `@radial-gradient(circle, ...)` when no size or position is specified.
+    AverageSide,
+}
+
+/// The RadialGradientBrush describes a way of filling a shape with a gradient where the colors
+/// are interpolated along circles or ellipses around a center point.
 #[derive(Clone, PartialEq, Debug)]
 #[repr(transparent)]
 pub struct RadialGradientBrush(SharedVector<GradientStop>);
 
 impl RadialGradientBrush {
     /// Creates a new circle radial gradient, centered in the middle and described
-    /// by the provided color stops.
+    /// by the provided color stops. The radius is the average of half the width and half
+    /// the height of the shape.
     pub fn new_circle(stops: impl IntoIterator<Item = GradientStop>) -> Self {
-        Self(stops.into_iter().collect())
+        Self::new(
+            RadialGradientShape::Circle,
+            RadialGradientExtent::AverageSide,
+            Point2D::new(0.5, 0.5),
+            stops,
+        )
     }
-    /// Returns the color stops of the linear gradient.
+    /// Creates a new radial gradient, described by its shape, its extent, its center, and the
+    /// provided color stops.
+    ///
+    /// The center is relative to the size of the shape: (0.5, 0.5) is in the middle.
+    pub fn new(
+        shape: RadialGradientShape,
+        extent: RadialGradientExtent,
+        center: Point2D<f32>,
+        stops: impl IntoIterator<Item = GradientStop>,
+    ) -> Self {
+        let stop_iter = stops.into_iter();
+        let mut encoded = SharedVector::with_capacity(stop_iter.size_hint().0 + 4);
+        // The gradient's first four stops are fake stops to store the center, shape, and extent
+        encoded.push(GradientStop { color: Default::default(), position: center.x });
+        encoded.push(GradientStop { color: Default::default(), position: center.y });
+        encoded.push(GradientStop { color: Default::default(), position: shape as u8 as f32 });
+        encoded.push(GradientStop { color: Default::default(), position: extent as u8 as f32 });
+        encoded.extend(stop_iter);
+        Self(encoded)
+    }
+    /// Returns the center of the gradient, relative to the size of the shape.
+    pub fn center(&self) -> Point2D<f32> {
+        Point2D::new(self.0[0].position, self.0[1].position)
+    }
+    /// Returns the shape of the gradient.
+    pub fn shape(&self) -> RadialGradientShape {
+        if self.0[2].position == RadialGradientShape::Circle as u8 as f32 {
+            RadialGradientShape::Circle
+        } else {
+            RadialGradientShape::Ellipse
+        }
+    }
+    /// Returns the extent of the gradient.
+    pub fn extent(&self) -> RadialGradientExtent {
+        match self.0[3].position as u8 {
+            0 => RadialGradientExtent::ClosestSide,
+            1 => RadialGradientExtent::FarthestSide,
+            2 => RadialGradientExtent::ClosestCorner,
+            4 => RadialGradientExtent::AverageSide,
+            _ => RadialGradientExtent::FarthestCorner,
+        }
+    }
+    /// Returns the color stops of the radial gradient.
     pub fn stops(&self) -> impl Iterator<Item = &GradientStop> {
-        self.0.iter()
+        // skip the fake stops that just contain the center, shape, and extent
+        self.0.iter().skip(4)
+    }
+    /// Returns the horizontal and vertical radius of the gradient when it fills a shape of the
+    /// given size. For circles, both are equal.
+    pub fn radii(&self, size: Size2D<f32>) -> Size2D<f32> {
+        let center = self.center();
+        let (x, y) = (center.x * size.width, center.y * size.height);
+        let (left, right) = (x.abs(), (size.width - x).abs());
+        let (top, bottom) = (y.abs(), (size.height - y).abs());
+        let (width, height) = match self.extent() {
+            RadialGradientExtent::ClosestSide | RadialGradientExtent::ClosestCorner => {
+                (left.min(right), top.min(bottom))
+            }
+            RadialGradientExtent::FarthestSide | RadialGradientExtent::FarthestCorner => {
+                (left.max(right), top.max(bottom))
+            }
+            RadialGradientExtent::AverageSide => ((left + right) / 2., (top + bottom) / 2.),
+        };
+        let to_corner = matches!(
+            self.extent(),
+            RadialGradientExtent::ClosestCorner | RadialGradientExtent::FarthestCorner
+        );
+        match self.shape() {
+            RadialGradientShape::Circle => {
+                let radius = if to_corner {
+                    (width * width + height * height).sqrt()
+                } else if self.extent() == RadialGradientExtent::ClosestSide {
+                    width.min(height)
+                } else if self.extent() == RadialGradientExtent::AverageSide {
+                    (width + height) / 2.
+                } else {
+                    width.max(height)
+                };
+                Size2D::new(radius, radius)
+            }
+            // An ellipse going through the corner keeps the aspect ratio of the one touching the sides
+            RadialGradientShape::Ellipse if to_corner => {
+                Size2D::new(width, height) * core::f32::consts::SQRT_2
+            }
+            RadialGradientShape::Ellipse => Size2D::new(width, height),
+        }
+    }
+}
+
+/// The ConicGradientBrush describes a way of filling a shape with colors that are interpolated
+/// around a center point, clockwise, like the hands of a clock.
+#[derive(Clone, PartialEq, Debug)]
+#[repr(transparent)]
+pub struct ConicGradientBrush(SharedVector<GradientStop>);
+
+impl ConicGradientBrush {
+    /// Creates a new conic gradient, described by the angle of the first stop, the center
+    /// and the provided color stops.
+    ///
+    /// The angle need to be specified in degrees, where 0 is at the top. The center is
+    /// relative to the size of the shape: (0.5, 0.5) is in the middle.
+    pub fn new(
+        angle: f32,
+        center: Point2D<f32>,
+        stops: impl IntoIterator<Item = GradientStop>,
+    ) -> Self {
+        let stop_iter = stops.into_iter();
+        let mut encoded = SharedVector::with_capacity(stop_iter.size_hint().0 + 3);
+        // The gradient's first three stops are fake stops to store the angle and the center
+        encoded.push(GradientStop { color: Default::default(), position: angle });
+        encoded.push(GradientStop { color: Default::default(), position: center.x });
+        encoded.push(GradientStop { color: Default::default(), position: center.y });
+        encoded.extend(stop_iter);
+        Self(encoded)
+    }
+    /// Returns the angle of the first stop, in degrees.
+    pub fn angle(&self) -> f32 {
+        self.0[0].position
+    }
+    /// Returns the center of the gradient, relative to the size of the shape.
+    pub fn center(&self) -> Point2D<f32> {
+        Point2D::new(self.0[1].position, self.0[2].position)
+    }
+    /// Returns the color stops of the conic gradient.
+    pub fn stops(&self) -> impl Iterator<Item = &GradientStop> {
+        // skip the fake stops that just contain the angle and the center
+        self.0.iter().skip(3)
     }
 }
 
@@ -302,7 +515,7 @@ fn interpolate(&self, target_value: &Self, t: f32) -> Self {
             }
             (Brush::SolidColor(col), Brush::RadialGradient(grad)) => {
                 let mut new_grad = grad.clone();
-                for x in new_grad.0.make_mut_slice().iter_mut() {
+                for x in new_grad.0.make_mut_slice().iter_mut().skip(4) {
                     x.color = col.interpolate(&x.color, t);
                 }
                 Brush::RadialGradient(new_grad)
@@ -310,12 +523,21 @@ fn interpolate(&self, target_value: &Self, t: f32) -> Self {
             (a @ Brush::RadialGradient(_), b @ Brush::SolidColor(_)) => {
                 Self::interpolate(b, a, 1. - t)
             }
-            (Brush::RadialGradient(lhs), Brush::RadialGradient(rhs)) => {
+            (Brush::RadialGradient(lhs), Brush::RadialGradient(rhs))
+                if lhs.shape() == rhs.shape() && lhs.extent() == rhs.extent() =>
+            {
                 if lhs.0.len() < rhs.0.len() {
                     Self::interpolate(target_value, self, 1. - t)
                 } else {
                     let mut new_grad = lhs.clone();
                     let mut iter = new_grad.0.make_mut_slice().iter_mut();
+                    for target in [rhs.center().x, rhs.center().y] {
+                        let value = &mut iter.next().unwrap().position;
+                        *value = value.interpolate(&target, t);
+                    }
+                    // The shape and the extent are the same
+                    iter.next();
+                    iter.next();
                     let mut last_color = Color::default();
                     for s2 in rhs.stops() {
                         let s1 = iter.next().unwrap();
@@ -330,8 +552,38 @@ fn interpolate(&self, target_value: &Self, t: f32) -> Self {
                     Brush::RadialGradient(new_grad)
                 }
             }
-            (a @ Brush::LinearGradient(_), b @ Brush::RadialGradient(_))
-            | (a @ Brush::RadialGradient(_), b @ Brush::LinearGradient(_)) => {
+            (Brush::SolidColor(col), Brush::ConicGradient(grad)) => {
+                let mut new_grad = grad.clone();
+                for x in new_grad.0.make_mut_slice().iter_mut().skip(3) {
+                    x.color = col.interpolate(&x.color, t);
+                }
+                Brush::ConicGradient(new_grad)
+            }
+            (a @ Brush::ConicGradient(_), b @ Brush::SolidColor(_)) => {
+                Self::interpolate(b, a, 1. - t)
+            }
+            (Brush::ConicGradient(lhs), Brush::ConicGradient(rhs)) => {
+                if lhs.0.len() < rhs.0.len() {
+                    Self::interpolate(target_value, self, 1. - t)
+                } else {
+                    let mut new_grad = lhs.clone();
+                    let mut iter = new_grad.0.make_mut_slice().iter_mut();
+                    for target in [rhs.angle(), rhs.center().x, rhs.center().y] {
+                        let value = &mut iter.next().unwrap().position;
+                        *value = value.interpolate(&target, t);
+                    }
+                    for s2 in rhs.stops() {
+                        let s1 = iter.next().unwrap();
+                        s1.color = s1.color.interpolate(&s2.color, t);
+                        s1.position = s1.position.interpolate(&s2.position, t);
+                    }
+                    for x in iter {
+                        x.position = x.position.interpolate(&1.0, t);
+                    }
+                    Brush::ConicGradient(new_grad)
+                }
+            }
+            (a, b) => {
                 // Just go to an intermediate color.
                 let color = Color::interpolate(&b.color(), &a.color(), t);
                 if t < 0.5 {
@@ -344,6 +596,62 @@ fn interpolate(&self, target_value: &Self, t: f32) -> Self {
     }
 }
 
+#[test]
+#[allow(clippy::float_cmp)] // We want bit-wise equality here
+fn test_conic_gradient_encoding() {
+    let stops: SharedVector<GradientStop> = [
+        GradientStop { position: 0.0, color: Color::from_argb_u8(255, 255, 0, 0) },
+        GradientStop { position: 1.0, color: Color::from_argb_u8(255, 0, 0, 255) },
+    ]
+    .into();
+    let grad = ConicGradientBrush::new(45., Point2D::new(0.25, 0.75), stops.clone());
+    assert_eq!(grad.angle(), 45.);
+    assert_eq!(grad.center(), Point2D::new(0.25, 0.75));
+    assert!(grad.stops().eq(stops.iter()));
+}
+
+#[test]
+#[allow(clippy::float_cmp)] // We want bit-wise equality here
+fn test_radial_gradient_encoding() {
+    let stops: SharedVector<GradientStop> = [
+        GradientStop { position: 0.0, color: Color::from_argb_u8(255, 255, 0, 0) },
+        GradientStop { position: 1.0, color: Color::from_argb_u8(255, 0, 0, 255) },
+    ]
+    .into();
+    let grad = RadialGradientBrush::new(
+        RadialGradientShape::Circle,
+        RadialGradientExtent::ClosestCorner,
+        Point2D::new(0.25, 0.75),
+        stops.clone(),
+    );
+    assert_eq!(grad.shape(), RadialGradientShape::Circle);
+    assert_eq!(grad.extent(), RadialGradientExtent::ClosestCorner);
+    assert_eq!(grad.center(), Point2D::new(0.25, 0.75));
+    assert!(grad.stops().eq(stops.iter()));
+}
+
+#[test]
+fn test_radial_gradient_radii() {
+    use RadialGradientExtent::*;
+    use RadialGradientShape::*;
+    let radii = |shape, extent, center: (f32, f32)| {
+        RadialGradientBrush::new(shape, extent, center.into(), [])
+            .radii(Size2D::new(100., 50.))
+            .to_tuple()
+    };
+    assert_eq!(radii(Circle, ClosestSide, (0.5, 0.5)), (25., 25.));
+    assert_eq!(radii(Circle, AverageSide, (0.5, 0.5)), (37.5, 37.5));
+    assert_eq!(RadialGradientBrush::new_circle([]).radii(Size2D::new(100., 50.)).width, 37.5);
+    assert_eq!(radii(Circle, FarthestSide, (0.25, 0.5)), (75., 75.));
+    assert_eq!(radii(Ellipse, ClosestSide, (0.5, 0.5)), (50., 25.));
+    assert_eq!(radii(Ellipse, FarthestSide, (0.25, 0.2)), (75., 40.));
+    let (x, y) = radii(Circle, ClosestCorner, (0.3, 0.)); // corner at (30, 0)
+    assert!((x - 30.).abs() < 0.001 && (y - 30.).abs() < 0.001);
+    let (x, y) = radii(Ellipse, FarthestCorner, (0.5, 0.5));
+    assert!((x - 50. * core::f32::consts::SQRT_2).abs() < 0.001);
+    assert!((y - 25. * core::f32::consts::SQRT_2).abs() < 0.001);
+}
+
 #[test]
 #[allow(clippy::float_cmp)] // We want bit-wise equality here
 fn test_linear_gradient_encoding() {
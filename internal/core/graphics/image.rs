@@ -48,6 +48,16 @@ pub struct OpaqueImageVTable {
     pub static NINE_SLICE_VT for NineSliceImage
 }
 
+OpaqueImageVTable_static! {
+    /// VTable for RC wrapped animated image helper struct.
+    pub static ANIMATED_IMAGE_VT for AnimatedImage
+}
+
+OpaqueImageVTable_static! {
+    /// VTable for RC wrapped image that is loaded in the background.
+    pub static PENDING_IMAGE_VT for PendingImage
+}
+
 /// SharedPixelBuffer is a container for storing image data as pixels. It is
 /// internally reference counted and cheap to clone.
 ///
@@ -277,6 +287,16 @@ pub struct StaticTextures {
     pub textures: Slice<'static, StaticTexture>,
 }
 
+/// A frame of an animated image that was embedded in the binary by the compiler
+#[repr(C)]
+#[derive(Clone, PartialEq, Debug)]
+pub struct StaticAnimationFrame {
+    /// The content of the frame
+    pub textures: &'static StaticTextures,
+    /// How long the frame is shown, in milliseconds
+    pub duration: u32,
+}
+
 /// ImageCacheKey encapsulates the different ways of indexing images in the
 /// cache of decoded images.
 #[derive(PartialEq, Eq, Debug, Hash, Clone)]
@@ -312,6 +332,8 @@ pub fn new(resource: &ImageInner) -> Option<Self> {
             #[cfg(not(target_arch = "wasm32"))]
             ImageInner::BorrowedOpenGLTexture(..) => return None,
             ImageInner::NineSlice(nine) => vtable::VRc::borrow(nine).cache_key(),
+            ImageInner::Animated(animated) => vtable::VRc::borrow(animated).cache_key(),
+            ImageInner::Pending(..) => return None,
         };
         if matches!(key, ImageCacheKey::Invalid) {
             None
@@ -345,6 +367,115 @@ fn cache_key(&self) -> ImageCacheKey {
     }
 }
 
+/// Represent an animated image, such as an animated GIF, as a sequence of frames
+pub struct AnimatedImage {
+    /// The frames and how long each of them is shown, in milliseconds
+    frames: alloc::vec::Vec<(ImageInner, u32)>,
+    cache_key: ImageCacheKey,
+}
+
+impl AnimatedImage {
+    /// Creates an animated image from the frames and how long each of them is shown, in milliseconds.
+    pub fn new(frames: alloc::vec::Vec<(ImageInner, u32)>, cache_key: ImageCacheKey) -> Self {
+        Self { frames, cache_key }
+    }
+
+    /// Returns the frames and how long each of them is shown, in milliseconds
+    pub fn frames(&self) -> &[(ImageInner, u32)] {
+        &self.frames
+    }
+
+    /// Returns the duration of one iteration of the animation, in milliseconds
+    pub fn duration(&self) -> u64 {
+        self.frames.iter().map(|(_, duration)| *duration as u64).sum()
+    }
+
+    /// Returns the frame to show `elapsed` milliseconds after the animation started.
+    ///
+    /// When `looping` is false, the last frame stays once the animation is over.
+    pub fn frame_at(&self, elapsed: u64, looping: bool) -> Image {
+        let duration = self.duration();
+        if duration == 0 {
+            return Image(self.frames.first().map(|(f, _)| f.clone()).unwrap_or_default());
+        }
+        let mut elapsed = if looping { elapsed % duration } else { elapsed };
+        for (frame, frame_duration) in &self.frames {
+            if elapsed < *frame_duration as u64 {
+                return Image(frame.clone());
+            }
+            elapsed -= *frame_duration as u64;
+        }
+        Image(self.frames.last().map(|(f, _)| f.clone()).unwrap_or_default())
+    }
+}
+
+impl OpaqueImage for AnimatedImage {
+    fn size(&self) -> IntSize {
+        self.frames.first().map(|(f, _)| f.size()).unwrap_or_default()
+    }
+    fn cache_key(&self) -> ImageCacheKey {
+        self.cache_key.clone()
+    }
+}
+
+#[derive(Clone, Default, PartialEq)]
+enum PendingImageState {
+    #[default]
+    Loading,
+    Loaded(ImageInner),
+    Error,
+}
+
+/// An image that is loaded in the background, see [`Image::load_from_path_in_background`].
+///
+/// It is empty until the image is loaded. Its state is stored in a property, so that the bindings
+/// that depend on the image are re-evaluated once it is loaded.
+pub struct PendingImage {
+    state: core::pin::Pin<alloc::boxed::Box<crate::Property<PendingImageState>>>,
+}
+
+impl Default for PendingImage {
+    fn default() -> Self {
+        Self { state: alloc::boxed::Box::pin(Default::default()) }
+    }
+}
+
+impl PendingImage {
+    /// Returns the loaded image, or an empty image if it is still loading or couldn't be loaded
+    pub fn image(&self) -> Image {
+        match self.state.as_ref().get() {
+            PendingImageState::Loaded(image) => Image(image),
+            _ => Image::default(),
+        }
+    }
+
+    /// Returns whether the image is loaded, still loading, or couldn't be loaded
+    pub fn status(&self) -> crate::items::ImageLoadingStatus {
+        match self.state.as_ref().get() {
+            PendingImageState::Loading => crate::items::ImageLoadingStatus::Loading,
+            PendingImageState::Loaded(_) => crate::items::ImageLoadingStatus::Ready,
+            PendingImageState::Error => crate::items::ImageLoadingStatus::Error,
+        }
+    }
+
+    /// Sets the result of the loading, or None if the image couldn't be loaded
+    fn finish(&self, image: Option<Image>) {
+        self.state.set(match image {
+            Some(image) => PendingImageState::Loaded(image.0),
+            None => PendingImageState::Error,
+        });
+    }
+}
+
+impl OpaqueImage for PendingImage {
+    fn size(&self) -> IntSize {
+        self.image().size()
+    }
+    fn cache_key(&self) -> ImageCacheKey {
+        ImageCacheKey::Invalid
+    }
+}
+
 /// A resource is a reference to binary data, for example images. They can be accessible on the file
 /// system or embedded in the resulting binary. Or they might be URLs to a web server and a downloaded
 /// is necessary before they can be used.
@@ -369,6 +500,8 @@ pub enum ImageInner {
     #[cfg(not(target_arch = "wasm32"))]
     BorrowedOpenGLTexture(BorrowedOpenGLTexture) = 6,
     NineSlice(vtable::VRc<OpaqueImageVTable, NineSliceImage>) = 7,
+    Animated(vtable::VRc<OpaqueImageVTable, AnimatedImage>) = 8,
+    Pending(vtable::VRc<OpaqueImageVTable, PendingImage>) = 9,
 }
 
 impl ImageInner {
@@ -454,6 +587,12 @@ pub fn render_to_buffer(
                 Some(SharedImageBuffer::RGBA8Premultiplied(buffer))
             }
             ImageInner::NineSlice(nine) => nine.0.render_to_buffer(None),
+            ImageInner::Animated(animated) => {
+                animated.frames.first()?.0.render_to_buffer(_target_size_for_scalable_source)
+            }
+            ImageInner::Pending(pending) => {
+                pending.image().0.render_to_buffer(_target_size_for_scalable_source)
+            }
             _ => None,
         }
     }
@@ -483,6 +622,8 @@ pub fn size(&self) -> IntSize {
             #[cfg(not(target_arch = "wasm32"))]
             ImageInner::BorrowedOpenGLTexture(BorrowedOpenGLTexture { size, .. }) => *size,
             ImageInner::NineSlice(nine) => nine.0.size(),
+            ImageInner::Animated(animated) => animated.size(),
+            ImageInner::Pending(pending) => pending.size(),
         }
     }
 }
@@ -503,6 +644,8 @@ fn eq(&self, other: &Self) -> bool {
             #[cfg(not(target_arch = "wasm32"))]
             (Self::BorrowedOpenGLTexture(l0), Self::BorrowedOpenGLTexture(r0)) => l0 == r0,
             (Self::NineSlice(l), Self::NineSlice(r)) => l.0 == r.0 && l.1 == r.1,
+            (Self::Animated(l), Self::Animated(r)) => vtable::VRc::ptr_eq(l, r),
+            (Self::Pending(l), Self::Pending(r)) => vtable::VRc::ptr_eq(l, r),
             _ => false,
         }
     }
@@ -637,6 +780,76 @@ pub fn load_from_path(path: &std::path::Path) -> Result<Self, LoadImageError> {
         })
     }
 
+    #[cfg(all(feature = "image-decoders", not(target_arch = "wasm32")))]
+    /// Load an Image from a path to a file containing an image, without blocking the event loop.
+    ///
+    /// The image is decoded in a background thread, and the returned future completes once the
+    /// image is ready. Like [`Self::load_from_path`], decoded images are cached, so loading the
+    /// same path again completes right away.
+    ///
+    /// ```rust,no_run
+    /// # use i_slint_core::graphics::Image;
+    /// # fn set_image(_: Image) {}
+    /// slint::spawn_local(async move {
+    ///     match Image::load_from_path_async(std::path::Path::new("photo.jpg")).await {
+    ///         Ok(image) => set_image(image),
+    ///         Err(_) => eprintln!("Could not load the image"),
+    ///     }
+    /// })
+    /// .unwrap();
+    /// ```
+    pub fn load_from_path_async(
+        path: &std::path::Path,
+    ) -> impl core::future::Future<Output = Result<Self, LoadImageError>> + 'static {
+        let path: Option<SharedString> = path.to_str().map(Into::into);
+        async move {
+            self::cache::load_image_from_path_async(path.ok_or(LoadImageError(()))?)
+                .await
+                .ok_or(LoadImageError(()))
+        }
+    }
+
+    #[cfg(all(feature = "image-decoders", not(target_arch = "wasm32")))]
+    /// Load an Image from a path to a file containing an image in a background thread, and
+    /// return a placeholder right away.
+    ///
+    /// The returned image is empty until the image is decoded, and then shows the loaded image.
+    /// The [`Self::loading_status`] function, and the `loading-status` property of the `Image`
+    /// element that shows it, tell whether the image is still loading or couldn't be loaded,
+    /// so that a placeholder can be shown in the meantime.
+    ///
+    /// This must be called from the thread that runs the event loop.
+    pub fn load_from_path_in_background(path: &std::path::Path) -> Self {
+        let pending = vtable::VRc::new(PendingImage::default());
+        let Some(path) = path.to_str().map(SharedString::from) else {
+            pending.finish(None);
+            return Image(ImageInner::Pending(pending));
+        };
+        let weak = vtable::VRc::downgrade(&pending);
+        let spawned = crate::future::spawn_local(async move {
+            let image = self::cache::load_image_from_path_async(path).await;
+            if let Some(pending) = weak.upgrade() {
+                pending.finish(image);
+            }
+        });
+        if spawned.is_err() {
+            // Without event loop, there is nothing to run the future
+            pending.finish(None);
+        }
+        Image(ImageInner::Pending(pending))
+    }
+
+    /// Returns whether the image is still being loaded, or couldn't be loaded.
+    ///
+    /// This is only the case for the images returned by [`Self::load_from_path_in_background`],
+    /// other images are always [`ImageLoadingStatus::Ready`](crate::items::ImageLoadingStatus::Ready).
+    pub fn loading_status(&self) -> crate::items::ImageLoadingStatus {
+        match &self.0 {
+            ImageInner::Pending(pending) => pending.status(),
+            _ => crate::items::ImageLoadingStatus::Ready,
+        }
+    }
+
     /// Creates a new Image from the specified shared pixel buffer, where each pixel has three color
     /// channels (red, green and blue) encoded as u8.
     pub fn from_rgb8(buffer: SharedPixelBuffer<Rgb8Pixel>) -> Self {
@@ -754,6 +967,10 @@ pub fn path(&self) -> Option<&std::path::Path> {
                 }
                 _ => None,
             },
+            ImageInner::Animated(animated) => match &animated.cache_key {
+                ImageCacheKey::Path(path) => Some(std::path::Path::new(path.as_str())),
+                _ => None,
+            },
             _ => None,
         }
     }
@@ -838,6 +1055,23 @@ pub fn load_image_from_embedded_data(data: Slice<'static, u8>, format: Slice<'_,
     })
 }
 
+/// Create an animated image from frames embedded in the binary.
+/// This is called by the generated code.
+pub fn image_from_embedded_animation(frames: Slice<'static, StaticAnimationFrame>) -> Image {
+    let cache_key = frames.first().map_or(ImageCacheKey::Invalid, |f| {
+        ImageCacheKey::from_embedded_image_data(&f.textures.data)
+    });
+    let frames = frames
+        .iter()
+        .map(|f| (ImageInner::StaticTextures(f.textures), f.duration))
+        .collect::<alloc::vec::Vec<_>>();
+    match frames.len() {
+        0 => Image::default(),
+        1 => Image(frames[0].0.clone()),
+        _ => Image(ImageInner::Animated(vtable::VRc::new(AnimatedImage::new(frames, cache_key)))),
+    }
+}
+
 #[test]
 fn test_image_size_from_buffer_without_backend() {
     {
@@ -850,6 +1084,70 @@ fn test_image_size_from_buffer_without_backend() {
     }
 }
 
+#[cfg(all(feature = "image-decoders", not(target_arch = "wasm32")))]
+#[test]
+fn test_load_image_from_path_async() {
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../examples/printerdemo/ui/images/cat.jpg");
+    let image = runtime.block_on(Image::load_from_path_async(&path)).unwrap();
+    assert_eq!(image.size(), Image::load_from_path(&path).unwrap().size());
+    assert_eq!(image.path(), Some(path.as_path()));
+    assert!(runtime
+        .block_on(Image::load_from_path_async(std::path::Path::new("does-not-exist.png")))
+        .is_err());
+}
+
+#[test]
+fn test_animated_image_frame_at() {
+    let frame = |width| ImageInner::EmbeddedImage {
+        cache_key: ImageCacheKey::Invalid,
+        buffer: SharedImageBuffer::RGB8(SharedPixelBuffer::new(width, 1)),
+    };
+    let animated = AnimatedImage::new(
+        alloc::vec![(frame(1), 100), (frame(2), 50), (frame(3), 100)],
+        ImageCacheKey::Invalid,
+    );
+    assert_eq!(animated.duration(), 250);
+    assert_eq!(animated.size(), [1, 1].into());
+    assert_eq!(animated.frame_at(0, true).size(), [1, 1].into());
+    assert_eq!(animated.frame_at(120, true).size(), [2, 1].into());
+    assert_eq!(animated.frame_at(150, true).size(), [3, 1].into());
+    assert_eq!(animated.frame_at(260, true).size(), [1, 1].into());
+    assert_eq!(animated.frame_at(260, false).size(), [3, 1].into());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_animated_image_path() {
+    let frame = || ImageInner::EmbeddedImage {
+        cache_key: ImageCacheKey::Invalid,
+        buffer: SharedImageBuffer::RGB8(SharedPixelBuffer::new(1, 1)),
+    };
+    let image = Image(ImageInner::Animated(vtable::VRc::new(AnimatedImage::new(
+        alloc::vec![(frame(), 100), (frame(), 100)],
+        ImageCacheKey::Path("animation.gif".into()),
+    ))));
+    assert_eq!(image.path(), Some(std::path::Path::new("animation.gif")));
+}
+
+#[test]
+fn test_pending_image() {
+    use crate::items::ImageLoadingStatus;
+    let pending = vtable::VRc::new(PendingImage::default());
+    let image = Image(ImageInner::Pending(pending.clone()));
+    assert_eq!(image.loading_status(), ImageLoadingStatus::Loading);
+    assert_eq!(image.size(), Default::default());
+    pending.finish(Some(Image::from_rgb8(SharedPixelBuffer::new(320, 200))));
+    assert_eq!(image.loading_status(), ImageLoadingStatus::Ready);
+    assert_eq!(image.size(), [320, 200].into());
+
+    let failed = vtable::VRc::new(PendingImage::default());
+    failed.finish(None);
+    assert_eq!(Image(ImageInner::Pending(failed)).loading_status(), ImageLoadingStatus::Error);
+    assert_eq!(Image::default().loading_status(), ImageLoadingStatus::Ready);
+}
+
 #[cfg(feature = "svg")]
 #[test]
 fn test_image_size_from_svg() {
@@ -1184,6 +1482,10 @@ pub extern "C" fn slint_image_path(image: &Image) -> Option<&SharedString> {
                 },
                 _ => None,
             },
+            ImageInner::Animated(animated) => match &animated.cache_key {
+                ImageCacheKey::Path(path) => Some(path),
+                _ => None,
+            },
             _ => None,
         }
     }
@@ -1196,6 +1498,14 @@ pub extern "C" fn slint_image_path(image: &Image) -> Option<&SharedString> {
         core::ptr::write(image, Image::from(ImageInner::StaticTextures(textures)));
     }
 
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_image_from_embedded_animation(
+        frames: Slice<'static, StaticAnimationFrame>,
+        image: *mut Image,
+    ) {
+        core::ptr::write(image, image_from_embedded_animation(frames));
+    }
+
     #[no_mangle]
     pub unsafe extern "C" fn slint_image_compare_equal(image1: &Image, image2: &Image) -> bool {
         return image1.eq(image2);
@@ -28,6 +28,10 @@ fn weight(&self, key: &ImageCacheKey, value: &ImageInner) -> usize {
             #[cfg(not(target_arch = "wasm32"))]
             ImageInner::BorrowedOpenGLTexture(..) => 0, // Assume storage in GPU memory
             ImageInner::NineSlice(nine) => self.weight(key, &nine.0),
+            ImageInner::Animated(animated) => {
+                animated.frames().iter().map(|(frame, _)| self.weight(key, frame)).sum()
+            }
+            ImageInner::Pending(_) => 0, // Not cached, only the loaded image is
         }
     }
 }
@@ -95,18 +99,7 @@ pub(crate) fn load_image_from_path(&mut self, path: &SharedString) -> Option<Ima
                 )));
             }
 
-            image::open(std::path::Path::new(&path.as_str())).map_or_else(
-                |decode_err| {
-                    eprintln!("Error loading image from {}: {}", &path, decode_err);
-                    None
-                },
-                |image| {
-                    Some(ImageInner::EmbeddedImage {
-                        cache_key,
-                        buffer: dynamic_image_to_shared_image_buffer(image),
-                    })
-                },
-            )
+            decode_image_from_path(path).map(|decoded| decoded.into_image_inner(cache_key))
         });
     }
 
@@ -133,6 +126,12 @@ pub(crate) fn load_image_from_embedded_data(
             let format = std::str::from_utf8(format.as_slice())
                 .ok()
                 .and_then(image::ImageFormat::from_extension);
+            if let Some(frames) = format
+                .and_then(|format| decode_animation(std::io::Cursor::new(data.as_slice()), format))
+            {
+                return Some(DecodedImage::Animated(frames).into_image_inner(cache_key));
+            }
+
             let maybe_image = if let Some(format) = format {
                 image::load_from_memory_with_format(data.as_slice(), format)
             } else {
@@ -153,6 +152,176 @@ pub(crate) fn load_image_from_embedded_data(
     }
 }
 
+/// Load the image at the given path, like [`ImageCache::load_image_from_path`], but decode it in a
+/// background thread so that the event loop keeps running in the meantime.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn load_image_from_path_async(path: SharedString) -> Option<Image> {
+    let cache_key = ImageCacheKey::Path(path.clone());
+    if path.is_empty() || path.ends_with(".svg") || path.ends_with(".svgz") {
+        // SVGs are only parsed here and rendered later, so there is no need for a thread
+        return IMAGE_CACHE
+            .with(|global_cache| global_cache.borrow_mut().load_image_from_path(&path));
+    }
+    if let Some(image) =
+        IMAGE_CACHE.with(|global_cache| global_cache.borrow_mut().0.get(&cache_key).cloned())
+    {
+        return Some(Image(image));
+    }
+
+    let decoded = run_in_thread(move || decode_image_from_path(&path)).await??;
+    let image = decoded.into_image_inner(cache_key.clone());
+    IMAGE_CACHE.with(|global_cache| {
+        global_cache.borrow_mut().0.put_with_weight(cache_key, image.clone()).ok()
+    });
+    Some(Image(image))
+}
+
+/// Run `f` in the image decoder thread, and returns a future that completes with its result.
+///
+/// There is a single decoder thread, started on first use, that runs the functions one after the
+/// other, so that loading many images at once doesn't start as many threads.
+/// The future completes with None if the thread couldn't be started or if `f` panicked.
+#[cfg(not(target_arch = "wasm32"))]
+fn run_in_thread<T: Send + 'static>(
+    f: impl FnOnce() -> T + Send + 'static,
+) -> impl core::future::Future<Output = Option<T>> {
+    struct SharedState<T> {
+        result: Option<T>,
+        finished: bool,
+        waker: Option<core::task::Waker>,
+    }
+    /// Marks the state as finished and wakes the future up even if `f` panics
+    struct FinishOnDrop<T>(std::sync::Arc<std::sync::Mutex<SharedState<T>>>);
+    impl<T> Drop for FinishOnDrop<T> {
+        fn drop(&mut self) {
+            let mut state = self.0.lock().unwrap_or_else(|e| e.into_inner());
+            state.finished = true;
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+
+    let state = std::sync::Arc::new(std::sync::Mutex::new(SharedState {
+        result: None,
+        finished: false,
+        waker: None,
+    }));
+    let guard = FinishOnDrop(state.clone());
+    type DecoderJob = Box<dyn FnOnce() + Send>;
+    static DECODER: std::sync::Mutex<Option<std::sync::mpsc::Sender<DecoderJob>>> =
+        std::sync::Mutex::new(None);
+    let job: DecoderJob = Box::new(move || {
+        let result = f();
+        guard.0.lock().unwrap_or_else(|e| e.into_inner()).result = Some(result);
+    });
+
+    let mut decoder = DECODER.lock().unwrap_or_else(|e| e.into_inner());
+    let sender = decoder.get_or_insert_with(|| {
+        let (sender, receiver) = std::sync::mpsc::channel::<DecoderJob>();
+        // If the thread can't be spawned, the receiver is dropped and sending fails
+        let _ = std::thread::Builder::new().name("Slint image decoder".into()).spawn(move || {
+            for job in receiver {
+                // A panic only affects its own job, whose guard wakes its future up
+                let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(job));
+            }
+        });
+        sender
+    });
+    if sender.send(job).is_err() {
+        // The job and its guard are dropped, try to start the thread again next time
+        *decoder = None;
+    }
+    drop(decoder);
+    core::future::poll_fn(move |cx| {
+        let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.finished {
+            core::task::Poll::Ready(state.result.take())
+        } else {
+            state.waker = Some(cx.waker().clone());
+            core::task::Poll::Pending
+        }
+    })
+}
+
+/// An image decoded from a file. Unlike [`ImageInner`], it can be sent to another thread.
+enum DecodedImage {
+    Still(SharedImageBuffer),
+    /// The frames of an animated image, and how long each of them is shown, in milliseconds
+    Animated(Vec<(SharedImageBuffer, u32)>),
+}
+
+impl DecodedImage {
+    fn into_image_inner(self, cache_key: ImageCacheKey) -> ImageInner {
+        match self {
+            DecodedImage::Still(buffer) => ImageInner::EmbeddedImage { cache_key, buffer },
+            DecodedImage::Animated(frames) => {
+                let frames = frames
+                    .into_iter()
+                    .map(|(buffer, duration)| {
+                        (
+                            ImageInner::EmbeddedImage { cache_key: ImageCacheKey::Invalid, buffer },
+                            duration,
+                        )
+                    })
+                    .collect();
+                ImageInner::Animated(vtable::VRc::new(super::AnimatedImage::new(frames, cache_key)))
+            }
+        }
+    }
+}
+
+/// Decode the image file at the given path, which must not be an SVG.
+#[cfg(not(target_arch = "wasm32"))]
+fn decode_image_from_path(path: &str) -> Option<DecodedImage> {
+    if let Some(frames) = image::ImageFormat::from_path(path).ok().and_then(|format| {
+        let file = std::fs::File::open(path).ok()?;
+        decode_animation(std::io::BufReader::new(file), format)
+    }) {
+        return Some(DecodedImage::Animated(frames));
+    }
+
+    image::open(std::path::Path::new(path)).map_or_else(
+        |decode_err| {
+            eprintln!("Error loading image from {}: {}", path, decode_err);
+            None
+        },
+        |image| Some(DecodedImage::Still(dynamic_image_to_shared_image_buffer(image))),
+    )
+}
+
+/// Decode all the frames of an animated GIF, PNG (APNG) or WebP image.
+///
+/// Returns None if the image is not animated, in which case it should be decoded as a still image.
+#[cfg(feature = "animated-images")]
+fn decode_animation(
+    reader: impl std::io::BufRead + std::io::Seek,
+    format: image::ImageFormat,
+) -> Option<Vec<(SharedImageBuffer, u32)>> {
+    let frames = i_slint_common::animated_images::load_animation(reader, format)?;
+    let frames = frames
+        .into_iter()
+        .map(|frame| {
+            let buffer = SharedImageBuffer::RGBA8(SharedPixelBuffer::clone_from_slice(
+                frame.buffer.as_raw(),
+                frame.buffer.width(),
+                frame.buffer.height(),
+            ));
+            (buffer, frame.duration)
+        })
+        .collect();
+    Some(frames)
+}
+
+/// Without the `animated-images` feature, only the first frame of animated images is shown.
+#[cfg(not(feature = "animated-images"))]
+fn decode_animation(
+    _reader: impl std::io::BufRead + std::io::Seek,
+    _format: image::ImageFormat,
+) -> Option<Vec<(SharedImageBuffer, u32)>> {
+    None
+}
+
 fn dynamic_image_to_shared_image_buffer(dynamic_image: image::DynamicImage) -> SharedImageBuffer {
     if dynamic_image.color().has_alpha() {
         let rgba8image = dynamic_image.to_rgba8();
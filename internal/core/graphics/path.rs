@@ -249,17 +249,59 @@ pub fn iter(
     /// the bounding rectangle of the path is used.
     pub fn fit(&mut self, width: f32, height: f32, viewbox: Option<lyon_path::math::Box2D>) {
         if width > 0. || height > 0. {
-            let viewbox =
-                viewbox.unwrap_or_else(|| lyon_algorithms::aabb::bounding_box(self.iter()));
-            self.transform = lyon_algorithms::fit::fit_box(
+            let viewbox = viewbox.unwrap_or_else(|| bounding_box(self.iter()));
+            self.transform = fit_box(
                 &viewbox,
                 &lyon_path::math::Box2D::from_size(lyon_path::math::Size::new(width, height)),
-                lyon_algorithms::fit::FitStyle::Min,
             );
         }
     }
 }
 
+/// The bounding box of the path, including the extent of the curves (but not their control points)
+fn bounding_box(
+    events: impl Iterator<Item = lyon_path::Event<lyon_path::math::Point, lyon_path::math::Point>>,
+) -> lyon_path::math::Box2D {
+    use lyon_path::math::{point, Box2D};
+    let mut min = point(f32::MAX, f32::MAX);
+    let mut max = point(f32::MIN, f32::MIN);
+    let mut add = |b: Box2D| {
+        min = min.min(b.min);
+        max = max.max(b.max);
+    };
+    for event in events {
+        match event {
+            lyon_path::Event::Begin { at } => add(Box2D::new(at, at)),
+            lyon_path::Event::Line { to, .. } => add(Box2D::new(to, to)),
+            lyon_path::Event::Quadratic { from, ctrl, to } => {
+                add(lyon_geom::QuadraticBezierSegment { from, ctrl, to }.bounding_box())
+            }
+            lyon_path::Event::Cubic { from, ctrl1, ctrl2, to } => {
+                add(lyon_geom::CubicBezierSegment { from, ctrl1, ctrl2, to }.bounding_box())
+            }
+            lyon_path::Event::End { .. } => {}
+        }
+    }
+    if min.x > max.x {
+        // There was no event in the path
+        return Box2D::zero();
+    }
+    Box2D::new(min, max)
+}
+
+/// The transform that scales `src` uniformly so that it fits into `dst`, centered
+fn fit_box(
+    src: &lyon_path::math::Box2D,
+    dst: &lyon_path::math::Box2D,
+) -> lyon_path::math::Transform {
+    let scale = f32::min(dst.width() / src.width(), dst.height() / src.height());
+    let src_center = src.min.lerp(src.max, 0.5);
+    let dst_center = dst.min.lerp(dst.max, 0.5);
+    lyon_path::math::Transform::translation(-src_center.x, -src_center.y)
+        .then_scale(scale, scale)
+        .then_translate(dst_center.to_vector())
+}
+
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq)]
 /// PathData represents a path described by either high-level elements or low-level
@@ -294,6 +336,7 @@ pub fn iter(self) -> Option<PathDataIterator> {
                 PathData::Events(events, coordinates) => {
                     LyonPathIteratorVariant::FromEvents(events, coordinates)
                 }
+                #[cfg(feature = "std")]
                 PathData::Commands(commands) => {
                     let mut builder = lyon_path::Path::builder();
                     let mut parser = lyon_extra::parser::PathParser::new();
@@ -309,6 +352,19 @@ pub fn iter(self) -> Option<PathDataIterator> {
                         }
                     }
                 }
+                // Parsing SVG commands at run-time needs the standard library
+                #[cfg(not(feature = "std"))]
+                PathData::Commands(_) => {
+                    static WARNED: core::sync::atomic::AtomicBool =
+                        core::sync::atomic::AtomicBool::new(false);
+                    if !WARNED.load(core::sync::atomic::Ordering::Relaxed) {
+                        WARNED.store(true, core::sync::atomic::Ordering::Relaxed);
+                        crate::debug_log!(
+                            "Path commands computed at run-time can't be rendered without the std feature"
+                        );
+                    }
+                    return None;
+                }
             },
             transform: Default::default(),
         }
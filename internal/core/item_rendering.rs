@@ -17,10 +17,12 @@
 use crate::{Brush, Coord};
 #[cfg(not(feature = "std"))]
 use alloc::boxed::Box;
+#[cfg(not(feature = "std"))]
+use alloc::collections::{btree_map::Entry, BTreeMap as ItemCacheMap};
 use core::cell::{Cell, RefCell};
 use core::pin::Pin;
 #[cfg(feature = "std")]
-use std::collections::HashMap;
+use std::collections::{hash_map::Entry, HashMap as ItemCacheMap};
 use vtable::VRc;
 
 /// This structure must be present in items that are Rendered and contains information.
@@ -72,22 +74,19 @@ pub fn get_entry<'a, T>(
 /// cache is automatically invalided when the property gets dirty.
 /// [`ItemCache::component_destroyed`] must be called to clear the cache for that
 /// component.
-#[cfg(feature = "std")]
 pub struct ItemCache<T> {
     /// The pointer is a pointer to a component
-    map: RefCell<HashMap<*const vtable::Dyn, HashMap<u32, CachedGraphicsData<T>>>>,
+    map: RefCell<ItemCacheMap<*const vtable::Dyn, ItemCacheMap<u32, CachedGraphicsData<T>>>>,
     /// Track if the window scale factor changes; used to clear the cache if necessary.
     window_scale_factor_tracker: Pin<Box<PropertyTracker>>,
 }
 
-#[cfg(feature = "std")]
 impl<T> Default for ItemCache<T> {
     fn default() -> Self {
         Self { map: Default::default(), window_scale_factor_tracker: Box::pin(Default::default()) }
     }
 }
 
-#[cfg(feature = "std")]
 impl<T: Clone> ItemCache<T> {
     /// Returns the cached value associated to the `item_rc` if it is still valid.
     /// Otherwise call the `update_fn` to compute that value, and track property access
@@ -96,7 +95,7 @@ pub fn get_or_update_cache_entry(&self, item_rc: &ItemRc, update_fn: impl FnOnce
         let component = &(**item_rc.item_tree()) as *const _;
         let mut borrowed = self.map.borrow_mut();
         match borrowed.entry(component).or_default().entry(item_rc.index()) {
-            std::collections::hash_map::Entry::Occupied(mut entry) => {
+            Entry::Occupied(mut entry) => {
                 let mut tracker = entry.get_mut().dependency_tracker.take();
                 drop(borrowed);
                 let maybe_new_data = tracker
@@ -113,7 +112,7 @@ pub fn get_or_update_cache_entry(&self, item_rc: &ItemRc, update_fn: impl FnOnce
                     e.data.clone()
                 }
             }
-            std::collections::hash_map::Entry::Vacant(_) => {
+            Entry::Vacant(_) => {
                 drop(borrowed);
                 let new_entry = CachedGraphicsData::new(update_fn);
                 let data = new_entry.data.clone();
@@ -239,6 +238,44 @@ pub fn render_component_items(
     renderer.restore_state();
 }
 
+/// Returns the rectangle that the rendering of the item may cover, in the same coordinate as its geometry.
+///
+/// This is the item's geometry, except for items that draw outside of it, such as the BoxShadow.
+fn item_rendering_bounding_rect(item: Pin<ItemRef>, geometry: LogicalRect) -> LogicalRect {
+    match ItemRef::downcast_pin::<BoxShadow>(item) {
+        Some(shadow) => {
+            let blur = shadow.blur().max(Default::default()).get();
+            let offset = LogicalVector::from_lengths(shadow.offset_x(), shadow.offset_y());
+            geometry.union(&geometry.translate(offset).inflate(blur, blur))
+        }
+        None => geometry,
+    }
+}
+
+/// Returns the square, centered on `center`, that contains `rect` rotated by any angle around
+/// `center`, inflated by `extra_radius`.
+fn rotation_bounding_rect(
+    rect: LogicalRect,
+    center: LogicalPoint,
+    extra_radius: f32,
+) -> LogicalRect {
+    let rect = rect.cast::<f32>();
+    let center = center.cast::<f32>();
+    let radius = [
+        rect.min(),
+        rect.max(),
+        euclid::point2(rect.min_x(), rect.max_y()),
+        euclid::point2(rect.max_x(), rect.min_y()),
+    ]
+    .iter()
+    .map(|corner| corner.distance_to(center))
+    .fold(0., f32::max)
+        + extra_radius;
+    euclid::Rect::new(center - euclid::vec2(radius, radius), euclid::size2(radius, radius) * 2.)
+        .round_out()
+        .cast()
+}
+
 /// Compute the bounding rect of all children. This does /not/ include item's own bounding rect. Remember to run this
 /// via `evaluate_no_tracking`.
 pub fn item_children_bounding_rect(
@@ -254,7 +291,9 @@ pub fn item_children_bounding_rect(
 
             let local_clip_rect = clip_rect.translate(-item_geometry.origin.to_vector());
 
-            if let Some(clipped_item_geometry) = item_geometry.intersection(clip_rect) {
+            if let Some(clipped_item_geometry) =
+                item_rendering_bounding_rect(item, item_geometry).intersection(clip_rect)
+            {
                 bounding_rect = bounding_rect.union(&clipped_item_geometry);
             }
 
@@ -327,7 +366,7 @@ fn draw_text_input(
         _self_rc: &ItemRc,
         _size: LogicalSize,
     );
-    #[cfg(feature = "std")]
+    #[cfg(any(feature = "std", feature = "software-renderer-path"))]
     fn draw_path(&mut self, path: Pin<&Path>, _self_rc: &ItemRc, _size: LogicalSize);
     fn draw_box_shadow(
         &mut self,
@@ -354,6 +393,24 @@ fn visit_layer(
         RenderingResult::ContinueRenderingChildren
     }
 
+    // Apply the rotation of the Rotate element. The default implementation calls rotate, but the
+    // renderer may choose an alternate way of rotating the children, such as rendering them in a layer.
+    fn visit_rotate(
+        &mut self,
+        rotate_item: Pin<&Rotate>,
+        _self_rc: &ItemRc,
+        _size: LogicalSize,
+    ) -> RenderingResult {
+        let origin = LogicalVector::from_lengths(
+            rotate_item.rotation_origin_x(),
+            rotate_item.rotation_origin_y(),
+        );
+        self.translate(origin);
+        self.rotate(rotate_item.rotation_angle());
+        self.translate(-origin);
+        RenderingResult::ContinueRenderingChildren
+    }
+
     // Apply the bounds of the Clip element, if enabled. The default implementation calls
     // combine_clip, but the render may choose an alternate way of implementing the clip.
     // For example the GL backend uses a layered rendering approach.
@@ -399,6 +456,11 @@ fn combine_clip(
     fn translation(&self) -> LogicalVector {
         unimplemented!()
     }
+    /// Returns the bounding box, in window coordinates, of a rectangle in the current
+    /// transformed coordinate. The default implementation only applies the translation.
+    fn map_to_window(&self, rect: LogicalRect) -> LogicalRect {
+        rect.translate(self.translation())
+    }
     fn rotate(&mut self, angle_in_degrees: f32);
     /// Apply the opacity (between 0 and 1) for all following items until the next call to restore_state.
     fn apply_opacity(&mut self, opacity: f32);
@@ -446,8 +508,24 @@ fn metrics(&self) -> crate::graphics::rendering_metrics_collector::RenderingMetr
     }
 }
 
+/// The geometry of an item, as it was when the item was last rendered
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CachedItemGeometry {
+    /// The geometry of the item, relative to its parent
+    pub geometry: LogicalRect,
+    /// The area covered by the rendering of the item, in the same coordinate as `geometry`
+    pub bounding_rect: LogicalRect,
+}
+
+impl CachedItemGeometry {
+    fn new(item_rc: &ItemRc) -> Self {
+        let geometry = item_rc.geometry();
+        Self { geometry, bounding_rect: item_rendering_bounding_rect(item_rc.borrow(), geometry) }
+    }
+}
+
 /// The cache that needs to be held by the Window for the partial rendering
-pub type PartialRenderingCache = RenderingCache<LogicalRect>;
+pub type PartialRenderingCache = RenderingCache<CachedItemGeometry>;
 
 /// A region composed of a few rectangles that need to be redrawn.
 #[derive(Default, Clone, Debug)]
@@ -551,6 +629,30 @@ pub fn intersection(&self, other: LogicalRect) -> DirtyRegion {
         ret
     }
 
+    /// Returns a region with at most `max_count` rectangles, by merging the rectangles whose
+    /// union adds the smallest area.
+    #[must_use]
+    pub(crate) fn merged(&self, max_count: usize) -> DirtyRegion {
+        let mut ret = self.clone();
+        while ret.count > max_count.max(1) {
+            let mut best = (0, 1, Coord::MAX);
+            for i in 0..ret.count {
+                for j in i + 1..ret.count {
+                    let (a, b) = (ret.rectangles[i], ret.rectangles[j]);
+                    let extra = a.union(&b).area() - a.area() - b.area();
+                    if extra < best.2 {
+                        best = (i, j, extra);
+                    }
+                }
+            }
+            let (i, j, _) = best;
+            ret.rectangles[i] = ret.rectangles[i].union(&ret.rectangles[j]);
+            ret.rectangles[j] = ret.rectangles[ret.count - 1];
+            ret.count -= 1;
+        }
+        ret
+    }
+
     fn draw_intersects(&self, clipped_geom: LogicalRect) -> bool {
         let b = clipped_geom.to_box2d();
         self.iter().any(|r| r.intersects(&b))
@@ -597,6 +699,18 @@ struct ComputeDirtyRegionState {
             old_offset: euclid::Vector2D<Coord, LogicalPx>,
             clipped: LogicalRect,
             must_refresh_children: bool,
+            /// When in a rotated sub-tree, the center of the outermost rotation (with the offset
+            /// and the old offset), and the extra radius that covers the nested rotations.
+            rotation: Option<(LogicalPoint, LogicalPoint, f32)>,
+        }
+
+        impl ComputeDirtyRegionState {
+            fn rotation(&self) -> Option<(LogicalPoint, f32)> {
+                self.rotation.map(|(center, _, extra_radius)| (center, extra_radius))
+            }
+            fn old_rotation(&self) -> Option<(LogicalPoint, f32)> {
+                self.rotation.map(|(_, old_center, extra_radius)| (old_center, extra_radius))
+            }
         }
 
         crate::item_tree::visit_items(
@@ -606,6 +720,7 @@ struct ComputeDirtyRegionState {
                 let mut new_state = *state;
                 let mut borrowed = self.cache.borrow_mut();
                 let item_rc = ItemRc::new(component.clone(), index);
+                let mut is_dirty = false;
 
                 match item.cached_rendering_data_offset().get_entry(&mut borrowed) {
                     Some(CachedGraphicsData {
@@ -613,39 +728,58 @@ struct ComputeDirtyRegionState {
                         dependency_tracker: Some(tr),
                     }) => {
                         if tr.is_dirty() {
+                            is_dirty = true;
                             let old_geom = *cached_geom;
                             drop(borrowed);
-                            let geom =
-                                crate::properties::evaluate_no_tracking(|| item_rc.geometry());
-
-                            self.mark_dirty_rect(old_geom, state.old_offset, &state.clipped);
-                            self.mark_dirty_rect(geom, state.offset, &state.clipped);
-
-                            new_state.offset += geom.origin.to_vector();
-                            new_state.old_offset += old_geom.origin.to_vector();
+                            let geom = crate::properties::evaluate_no_tracking(|| {
+                                CachedItemGeometry::new(&item_rc)
+                            });
+
+                            self.mark_dirty_rect(
+                                old_geom.bounding_rect,
+                                state.old_offset,
+                                state.old_rotation(),
+                                &state.clipped,
+                            );
+                            self.mark_dirty_rect(
+                                geom.bounding_rect,
+                                state.offset,
+                                state.rotation(),
+                                &state.clipped,
+                            );
+
+                            new_state.offset += geom.geometry.origin.to_vector();
+                            new_state.old_offset += old_geom.geometry.origin.to_vector();
                             if ItemRef::downcast_pin::<Clip>(item).is_some()
                                 || ItemRef::downcast_pin::<Opacity>(item).is_some()
+                                || ItemRef::downcast_pin::<Rotate>(item).is_some()
                             {
-                                // When the opacity or the clip change, this will impact all the children, including
-                                // the ones outside the element, regardless if they are themselves dirty or not.
+                                // When the opacity, the clip, or the rotation change, this will impact all the children,
+                                // including the ones outside the element, regardless if they are themselves dirty or not.
                                 new_state.must_refresh_children = true;
                             }
-
-                            ItemVisitorResult::Continue(new_state)
                         } else {
                             tr.as_ref().register_as_dependency_to_current_binding();
 
                             if state.must_refresh_children
                                 || new_state.offset != new_state.old_offset
                             {
+                                let rect = cached_geom.bounding_rect;
                                 self.mark_dirty_rect(
-                                    *cached_geom,
+                                    rect,
                                     state.old_offset,
+                                    state.old_rotation(),
+                                    &state.clipped,
+                                );
+                                self.mark_dirty_rect(
+                                    rect,
+                                    state.offset,
+                                    state.rotation(),
                                     &state.clipped,
                                 );
-                                self.mark_dirty_rect(*cached_geom, state.offset, &state.clipped);
                             }
 
+                            let cached_geom = cached_geom.geometry;
                             new_state.offset += cached_geom.origin.to_vector();
                             new_state.old_offset += cached_geom.origin.to_vector();
                             if crate::properties::evaluate_no_tracking(|| is_clipping_item(item)) {
@@ -658,12 +792,11 @@ struct ComputeDirtyRegionState {
                                     )
                                     .unwrap_or_default();
                             }
-                            ItemVisitorResult::Continue(new_state)
                         }
                     }
                     _ => {
                         drop(borrowed);
-                        let geom = crate::properties::evaluate_no_tracking(|| {
+                        let rect = crate::properties::evaluate_no_tracking(|| {
                             let geom = item_rc.geometry();
                             new_state.offset += geom.origin.to_vector();
                             new_state.old_offset += geom.origin.to_vector();
@@ -673,18 +806,52 @@ struct ComputeDirtyRegionState {
                                     .intersection(&geom.translate(state.offset))
                                     .unwrap_or_default();
                             }
-                            geom
+                            item_rendering_bounding_rect(item, geom)
+                        });
+                        self.mark_dirty_rect(rect, state.offset, state.rotation(), &state.clipped);
+                    }
+                }
+
+                if let Some(rotate) = ItemRef::downcast_pin::<Rotate>(item) {
+                    let (angle, origin) = crate::properties::evaluate_no_tracking(|| {
+                        (
+                            rotate.rotation_angle(),
+                            LogicalVector::from_lengths(
+                                rotate.rotation_origin_x(),
+                                rotate.rotation_origin_y(),
+                            ),
+                        )
+                    });
+                    // When the angle changed, the children might have been rotated before
+                    if is_dirty || angle % 360. != 0. {
+                        let center = LogicalPoint::default() + new_state.offset + origin;
+                        let old_center = LogicalPoint::default() + new_state.old_offset + origin;
+                        new_state.rotation = Some(match state.rotation {
+                            // The items rotated around the inner center are within that distance
+                            // of the outer center, plus twice the distance between the centers.
+                            Some((outer, old_outer, extra_radius)) => (
+                                outer,
+                                old_outer,
+                                extra_radius
+                                    + (center - outer)
+                                        .cast::<f32>()
+                                        .length()
+                                        .max((old_center - old_outer).cast::<f32>().length())
+                                        * 2.,
+                            ),
+                            None => (center, old_center, 0.),
                         });
-                        self.mark_dirty_rect(geom, state.offset, &state.clipped);
-                        ItemVisitorResult::Continue(new_state)
                     }
                 }
+
+                ItemVisitorResult::Continue(new_state)
             },
             ComputeDirtyRegionState {
                 offset: origin.to_vector(),
                 old_offset: origin.to_vector(),
                 clipped: LogicalRect::from_size(size),
                 must_refresh_children: false,
+                rotation: None,
             },
         );
     }
@@ -693,10 +860,15 @@ fn mark_dirty_rect(
         &mut self,
         rect: LogicalRect,
         offset: euclid::Vector2D<Coord, LogicalPx>,
+        rotation: Option<(LogicalPoint, f32)>,
         clip_rect: &LogicalRect,
     ) {
         if !rect.is_empty() {
-            if let Some(rect) = rect.translate(offset).intersection(clip_rect) {
+            let mut rect = rect.translate(offset);
+            if let Some((center, extra_radius)) = rotation {
+                rect = rotation_bounding_rect(rect, center, extra_radius);
+            }
+            if let Some(rect) = rect.intersection(clip_rect) {
                 self.dirty_region.add_rect(rect);
             }
         }
@@ -705,17 +877,22 @@ fn mark_dirty_rect(
     fn do_rendering(
         cache: &RefCell<PartialRenderingCache>,
         rendering_data: &CachedRenderingData,
-        render_fn: impl FnOnce() -> LogicalRect,
+        render_fn: impl FnOnce() -> CachedItemGeometry,
     ) {
-        let mut cache = cache.borrow_mut();
-        if let Some(entry) = rendering_data.get_entry(&mut cache) {
-            entry
-                .dependency_tracker
-                .get_or_insert_with(|| Box::pin(PropertyTracker::default()))
-                .as_ref()
-                .evaluate(render_fn);
+        // Don't keep the cache borrowed while rendering, as the renderer may render other
+        // items with this cache (for example the children of a layer)
+        let tracker = rendering_data
+            .get_entry(&mut cache.borrow_mut())
+            .map(|entry| entry.dependency_tracker.take());
+        if let Some(tracker) = tracker {
+            let tracker = tracker.unwrap_or_else(|| Box::pin(PropertyTracker::default()));
+            tracker.as_ref().evaluate(render_fn);
+            if let Some(entry) = rendering_data.get_entry(&mut cache.borrow_mut()) {
+                entry.dependency_tracker = Some(tracker);
+            }
         } else {
             let cache_entry = crate::graphics::CachedGraphicsData::new(render_fn);
+            let mut cache = cache.borrow_mut();
             rendering_data.cache_index.set(cache.insert(cache_entry));
             rendering_data.cache_generation.set(cache.generation());
         }
@@ -733,7 +910,7 @@ fn $fn(&mut self, obj: Pin<&$Ty>, item_rc: &ItemRc, size: LogicalSize) $(-> $Ret
             let mut ret = None;
             Self::do_rendering(&self.cache, &obj.cached_rendering_data, || {
                 ret = Some(self.actual_renderer.$fn(obj, item_rc, size));
-                item_rc.geometry()
+                CachedItemGeometry::new(item_rc)
             });
             ret.unwrap_or_default()
         }
@@ -746,7 +923,7 @@ fn $fn(&mut self, obj: Pin<&$Ty>, item_rc: &ItemRc, size: LogicalSize, cache: &C
             let mut ret = None;
             Self::do_rendering(&self.cache, &cache, || {
                 ret = Some(self.actual_renderer.$fn(obj, item_rc, size, &cache));
-                item_rc.geometry()
+                CachedItemGeometry::new(item_rc)
             });
             ret.unwrap_or_default()
         }
@@ -761,32 +938,33 @@ fn filter_item(&mut self, item_rc: &ItemRc) -> (bool, LogicalRect) {
                 // Make sure we register a dependency on the clip
                 clip.clip();
             }
-            item_rc.geometry()
+            CachedItemGeometry::new(item_rc)
         };
 
         let rendering_data = item.cached_rendering_data_offset();
         let mut cache = self.cache.borrow_mut();
-        let item_geometry = match rendering_data.get_entry(&mut cache) {
+        let (item_geometry, bounding_rect) = match rendering_data.get_entry(&mut cache) {
             Some(CachedGraphicsData { data, dependency_tracker }) => {
                 dependency_tracker
                     .get_or_insert_with(|| Box::pin(PropertyTracker::default()))
                     .as_ref()
                     .evaluate_if_dirty(|| *data = eval());
-                *data
+                (data.geometry, data.bounding_rect)
             }
             None => {
                 let cache_entry = crate::graphics::CachedGraphicsData::new(eval);
                 let geom = cache_entry.data;
                 rendering_data.cache_index.set(cache.insert(cache_entry));
                 rendering_data.cache_generation.set(cache.generation());
-                geom
+                (geom.geometry, geom.bounding_rect)
             }
         };
 
-        let clipped_geom = self.get_current_clip().intersection(&item_geometry);
-        let draw = clipped_geom.map_or(false, |clipped_geom| {
-            let clipped_geom = clipped_geom.translate(self.translation());
-            self.dirty_region.draw_intersects(clipped_geom)
+        // Items such as the BoxShadow draw outside of their geometry
+        let clipped_rect = self.get_current_clip().intersection(&bounding_rect);
+        let draw = clipped_rect.map_or(false, |clipped_rect| {
+            let clipped_rect = self.map_to_window(clipped_rect);
+            self.dirty_region.draw_intersects(clipped_rect)
         });
 
         (draw, item_geometry)
@@ -797,12 +975,14 @@ fn filter_item(&mut self, item_rc: &ItemRc) -> (bool, LogicalRect) {
     forward_rendering_call2!(fn draw_image(dyn RenderImage));
     forward_rendering_call!(fn draw_text(Text));
     forward_rendering_call!(fn draw_text_input(TextInput));
-    #[cfg(feature = "std")]
+    #[cfg(any(feature = "std", feature = "software-renderer-path"))]
     forward_rendering_call!(fn draw_path(Path));
     forward_rendering_call!(fn draw_box_shadow(BoxShadow));
 
     forward_rendering_call!(fn visit_clip(Clip) -> RenderingResult);
     forward_rendering_call!(fn visit_opacity(Opacity) -> RenderingResult);
+    forward_rendering_call!(fn visit_layer(Layer) -> RenderingResult);
+    forward_rendering_call!(fn visit_rotate(Rotate) -> RenderingResult);
 
     fn combine_clip(
         &mut self,
@@ -823,6 +1003,9 @@ fn translate(&mut self, distance: LogicalVector) {
     fn translation(&self) -> LogicalVector {
         self.actual_renderer.translation()
     }
+    fn map_to_window(&self, rect: LogicalRect) -> LogicalRect {
+        self.actual_renderer.map_to_window(rect)
+    }
 
     fn rotate(&mut self, angle_in_degrees: f32) {
         self.actual_renderer.rotate(angle_in_degrees)
@@ -29,8 +29,8 @@
 pub use crate::item_tree::ItemRc;
 use crate::layout::LayoutInfo;
 use crate::lengths::{
-    LogicalBorderRadius, LogicalLength, LogicalPoint, LogicalRect, LogicalSize, LogicalVector,
-    PointLengths, RectLengths,
+    LogicalBorderRadius, LogicalLength, LogicalPoint, LogicalRect, LogicalSize, PointLengths,
+    RectLengths,
 };
 #[cfg(feature = "rtti")]
 use crate::rtti::*;
@@ -51,9 +51,9 @@
 pub use text::*;
 mod image;
 pub use self::image::*;
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", feature = "software-renderer-path"))]
 mod path;
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", feature = "software-renderer-path"))]
 pub use path::*;
 
 /// Alias for `&mut dyn ItemRenderer`. Required so cbindgen generates the ItemVTable
@@ -1194,15 +1194,10 @@ fn focus_event(
     fn render(
         self: Pin<&Self>,
         backend: &mut ItemRendererRef,
-        _self_rc: &ItemRc,
-        _size: LogicalSize,
+        self_rc: &ItemRc,
+        size: LogicalSize,
     ) -> RenderingResult {
-        let origin =
-            LogicalVector::from_lengths(self.rotation_origin_x(), self.rotation_origin_y());
-        (*backend).translate(origin);
-        (*backend).rotate(self.rotation_angle());
-        (*backend).translate(-origin);
-        RenderingResult::ContinueRenderingChildren
+        (*backend).visit_rotate(self, self_rc, size)
     }
 }
 
@@ -1458,7 +1453,7 @@ fn slint_get_ImageItemVTable() -> ImageItemVTable for ImageItem
     fn slint_get_ClippedImageVTable() -> ClippedImageVTable for ClippedImage
 }
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", feature = "software-renderer-path"))]
 declare_item_vtable! {
     fn slint_get_PathVTable() -> PathVTable for Path
 }
@@ -8,8 +8,8 @@
 Lookup the [`crate::items`] module documentation.
 */
 use super::{
-    ImageFit, ImageHorizontalAlignment, ImageRendering, ImageTiling, ImageVerticalAlignment, Item,
-    ItemConsts, ItemRc, RenderingResult,
+    ImageFit, ImageHorizontalAlignment, ImageLoadingStatus, ImageRendering, ImageTiling,
+    ImageVerticalAlignment, Item, ItemConsts, ItemRc, RenderingResult,
 };
 use crate::input::{
     FocusEvent, FocusEventResult, InputEventFilterResult, InputEventResult, KeyEvent,
@@ -25,6 +25,7 @@
 use crate::{Brush, Coord, Property};
 use alloc::rc::Rc;
 use const_field_offset::FieldOffsets;
+use core::cell::Cell;
 use core::pin::Pin;
 use i_slint_core_macros::*;
 
@@ -39,11 +40,29 @@ pub struct ImageItem {
     pub image_fit: Property<ImageFit>,
     pub image_rendering: Property<ImageRendering>,
     pub colorize: Property<Brush>,
+    pub playing: Property<bool>,
+    pub looping: Property<bool>,
+    pub loading_status: Property<ImageLoadingStatus>,
     pub cached_rendering_data: CachedRenderingData,
+    /// Milliseconds elapsed since the start of the animation, for animated images
+    animation_elapsed: Cell<u64>,
+    /// The animation tick at which the animation was last advanced, or 0 if it is stopped
+    animation_last_tick: Cell<u64>,
+    /// The animated image whose animation is tracked by the two fields above
+    animation_source: Cell<crate::graphics::Image>,
 }
 
 impl Item for ImageItem {
-    fn init(self: Pin<&Self>, _self_rc: &ItemRc) {}
+    fn init(self: Pin<&Self>, self_rc: &ItemRc) {
+        let weak = self_rc.downgrade();
+        self.loading_status.set_binding(move || {
+            weak.upgrade()
+                .and_then(|self_rc| {
+                    Some(super::ItemRef::downcast_pin::<Self>(self_rc.borrow())?.source())
+                })
+                .map_or(ImageLoadingStatus::Ready, |source| source.loading_status())
+        });
+    }
 
     fn layout_info(
         self: Pin<&Self>,
@@ -116,7 +135,14 @@ fn target_size(self: Pin<&Self>) -> LogicalSize {
     }
 
     fn source(self: Pin<&Self>) -> crate::graphics::Image {
-        self.source()
+        current_animation_frame(
+            self.source(),
+            || self.playing(),
+            || self.looping(),
+            &self.animation_elapsed,
+            &self.animation_last_tick,
+            &self.animation_source,
+        )
     }
 
     fn source_clip(self: Pin<&Self>) -> Option<crate::graphics::IntRect> {
@@ -172,11 +198,30 @@ pub struct ClippedImage {
     pub horizontal_tiling: Property<ImageTiling>,
     pub vertical_tiling: Property<ImageTiling>,
 
+    pub playing: Property<bool>,
+    pub looping: Property<bool>,
+    pub loading_status: Property<ImageLoadingStatus>,
+
     pub cached_rendering_data: CachedRenderingData,
+    /// Milliseconds elapsed since the start of the animation, for animated images
+    animation_elapsed: Cell<u64>,
+    /// The animation tick at which the animation was last advanced, or 0 if it is stopped
+    animation_last_tick: Cell<u64>,
+    /// The animated image whose animation is tracked by the two fields above
+    animation_source: Cell<crate::graphics::Image>,
 }
 
 impl Item for ClippedImage {
-    fn init(self: Pin<&Self>, _self_rc: &ItemRc) {}
+    fn init(self: Pin<&Self>, self_rc: &ItemRc) {
+        let weak = self_rc.downgrade();
+        self.loading_status.set_binding(move || {
+            weak.upgrade()
+                .and_then(|self_rc| {
+                    Some(super::ItemRef::downcast_pin::<Self>(self_rc.borrow())?.source())
+                })
+                .map_or(ImageLoadingStatus::Ready, |source| source.loading_status())
+        });
+    }
 
     fn layout_info(
         self: Pin<&Self>,
@@ -253,7 +298,14 @@ fn target_size(self: Pin<&Self>) -> LogicalSize {
     }
 
     fn source(self: Pin<&Self>) -> crate::graphics::Image {
-        self.source()
+        current_animation_frame(
+            self.source(),
+            || self.playing(),
+            || self.looping(),
+            &self.animation_elapsed,
+            &self.animation_last_tick,
+            &self.animation_source,
+        )
     }
 
     fn source_clip(self: Pin<&Self>) -> Option<crate::graphics::IntRect> {
@@ -292,3 +344,79 @@ impl ItemConsts for ClippedImage {
         CachedRenderingData,
     > = ClippedImage::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
 }
+
+/// Returns the frame of the `source` to render if it is an animated image, and advance the
+/// animation according to the animation tick. Images that are loaded in the background are
+/// replaced by the loaded image, and other images are returned unchanged.
+///
+/// The animation restarts from the first frame when the `source` is changed.
+fn current_animation_frame(
+    source: crate::graphics::Image,
+    playing: impl Fn() -> bool,
+    looping: impl Fn() -> bool,
+    elapsed: &Cell<u64>,
+    last_tick: &Cell<u64>,
+    last_source: &Cell<crate::graphics::Image>,
+) -> crate::graphics::Image {
+    let source = match (&source).into() {
+        crate::graphics::ImageInner::Pending(pending) => pending.image(),
+        _ => source,
+    };
+    let inner: &crate::graphics::ImageInner = (&source).into();
+    let crate::graphics::ImageInner::Animated(animated) = inner else {
+        // Don't keep the previous animation alive
+        last_source.take();
+        return source;
+    };
+    if last_source.replace(source.clone()) != source {
+        elapsed.set(0);
+        last_tick.set(0);
+    }
+    let looping = looping();
+    if playing() && (looping || elapsed.get() < animated.duration()) {
+        let now = crate::animations::animation_tick();
+        let last = last_tick.replace(now);
+        if last != 0 {
+            elapsed.set(elapsed.get() + now.saturating_sub(last));
+        }
+    } else {
+        last_tick.set(0);
+    }
+    animated.frame_at(elapsed.get(), looping)
+}
+
+#[test]
+fn animation_restarts_when_source_changes() {
+    use crate::graphics::{AnimatedImage, Image, ImageCacheKey, ImageInner};
+    use crate::graphics::{SharedImageBuffer, SharedPixelBuffer};
+    let animation = || {
+        let frame = |width| ImageInner::EmbeddedImage {
+            cache_key: ImageCacheKey::Invalid,
+            buffer: SharedImageBuffer::RGB8(SharedPixelBuffer::new(width, 1)),
+        };
+        Image::from(ImageInner::Animated(vtable::VRc::new(AnimatedImage::new(
+            alloc::vec![(frame(1), 100), (frame(2), 100)],
+            ImageCacheKey::Invalid,
+        ))))
+    };
+    let (first, second) = (animation(), animation());
+    let elapsed = Cell::new(0);
+    let last_tick = Cell::new(0);
+    let last_source = Cell::default();
+    let frame = |source: &Image| {
+        current_animation_frame(
+            source.clone(),
+            || false,
+            || true,
+            &elapsed,
+            &last_tick,
+            &last_source,
+        )
+    };
+
+    assert_eq!(frame(&first).size(), [1, 1].into());
+    elapsed.set(150);
+    assert_eq!(frame(&first).size(), [2, 1].into());
+    assert_eq!(frame(&second).size(), [1, 1].into());
+    assert_eq!(elapsed.get(), 0);
+}
@@ -74,7 +74,7 @@
 #[doc(inline)]
 pub use graphics::RgbaColor;
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", feature = "software-renderer-path"))]
 #[doc(inline)]
 pub use graphics::PathData;
 
@@ -10,16 +10,20 @@
 mod draw_functions;
 mod fixed;
 mod fonts;
+#[cfg(feature = "software-renderer-path")]
+mod path;
 
 use self::fonts::GlyphRenderer;
 use crate::api::Window;
 use crate::graphics::rendering_metrics_collector::{RefreshMode, RenderingMetricsCollector};
 use crate::graphics::{BorderRadius, PixelFormat, SharedImageBuffer, SharedPixelBuffer};
-use crate::item_rendering::{CachedRenderingData, DirtyRegion, RenderBorderRectangle, RenderImage};
+use crate::item_rendering::{
+    CachedRenderingData, DirtyRegion, ItemRenderer, RenderBorderRectangle, RenderImage,
+};
 use crate::items::{ItemRc, TextOverflow};
 use crate::lengths::{
-    LogicalBorderRadius, LogicalLength, LogicalPoint, LogicalRect, LogicalSize, LogicalVector,
-    PhysicalPx, PointLengths, RectLengths, ScaleFactor, SizeLengths,
+    LogicalBorderRadius, LogicalLength, LogicalPoint, LogicalPx, LogicalRect, LogicalSize,
+    LogicalVector, PhysicalPx, PointLengths, RectLengths, ScaleFactor, SizeLengths,
 };
 use crate::renderer::{Renderer, RendererSealed};
 use crate::textlayout::{AbstractFont, FontMetrics, TextParagraphLayout};
@@ -36,7 +40,10 @@
 use num_traits::Float;
 use num_traits::NumCast;
 
-pub use draw_functions::{PremultipliedRgbaColor, Rgb565Pixel, TargetPixel};
+pub use draw_functions::{
+    dither_to_monochrome, Argb8888Pixel, Bgr565Pixel, DitheredPixel, Gray8Pixel,
+    PremultipliedRgbaColor, Rgb565Pixel, Rgb565SwappedPixel, TargetPixel,
+};
 
 type PhysicalLength = euclid::Length<i16, PhysicalPx>;
 type PhysicalRect = euclid::Rect<i16, PhysicalPx>;
@@ -207,6 +214,80 @@ fn process_line(
     );
 }
 
+/// A [`LineBufferProvider`] that renders into a frame buffer with one bit per pixel, such as
+/// the ones of e-paper displays.
+///
+/// The lines are rendered in gray and then converted with [`dither_to_monochrome`]: the
+/// most significant bit of each byte is the left most pixel, and a bit set means white.
+pub struct MonochromeLineBuffer<'a> {
+    buffer: &'a mut [u8],
+    stride: usize,
+    line: Vec<Gray8Pixel>,
+}
+
+impl<'a> MonochromeLineBuffer<'a> {
+    /// Create a new line buffer provider for the given frame buffer.
+    ///
+    /// `stride` is the number of bytes of each line in the buffer.
+    pub fn new(buffer: &'a mut [u8], stride: usize) -> Self {
+        Self { buffer, stride, line: Vec::new() }
+    }
+}
+
+impl LineBufferProvider for MonochromeLineBuffer<'_> {
+    type TargetPixel = Gray8Pixel;
+
+    fn process_line(
+        &mut self,
+        line: usize,
+        range: core::ops::Range<usize>,
+        render_fn: impl FnOnce(&mut [Self::TargetPixel]),
+    ) {
+        self.line.clear();
+        self.line.resize(range.len(), Gray8Pixel::default());
+        render_fn(&mut self.line);
+        let output = &mut self.buffer[line * self.stride..(line + 1) * self.stride];
+        dither_to_monochrome(&self.line, range.start, line, output);
+    }
+}
+
+/// A [`LineBufferProvider`] that renders each line with 8 bits per color component, and then
+/// converts it to the pixel format of the frame buffer with ordered dithering.
+///
+/// This avoids the banding of the gradients when rendering into a [`Rgb565Pixel`] or
+/// [`Gray8Pixel`] frame buffer. See also [`SoftwareRenderer::render_dithered`].
+pub struct DitheredLineBuffer<'a, P: DitheredPixel> {
+    buffer: &'a mut [P],
+    stride: usize,
+    line: Vec<crate::graphics::Rgb8Pixel>,
+}
+
+impl<'a, P: DitheredPixel> DitheredLineBuffer<'a, P> {
+    /// Create a new line buffer provider for the given frame buffer.
+    ///
+    /// `stride` is the number of pixels of each line in the buffer.
+    pub fn new(buffer: &'a mut [P], stride: usize) -> Self {
+        Self { buffer, stride, line: Vec::new() }
+    }
+}
+
+impl<P: DitheredPixel> LineBufferProvider for DitheredLineBuffer<'_, P> {
+    type TargetPixel = crate::graphics::Rgb8Pixel;
+
+    fn process_line(
+        &mut self,
+        line: usize,
+        range: core::ops::Range<usize>,
+        render_fn: impl FnOnce(&mut [Self::TargetPixel]),
+    ) {
+        self.line.clear();
+        self.line.resize(range.len(), Default::default());
+        render_fn(&mut self.line);
+        let x = range.start;
+        P::dither_line(&self.line, x, line, &mut self.buffer[line * self.stride..][range]);
+    }
+}
+
 #[cfg(not(cbindgen))]
 const PHYSICAL_REGION_MAX_SIZE: usize = DirtyRegion::MAX_COUNT;
 // cbindgen can't understand associated const correctly, so hardcode the value
@@ -228,6 +309,30 @@ pub struct PhysicalRegion {
 }
 
 impl PhysicalRegion {
+    fn from_rect(rect: PhysicalRect) -> Self {
+        let mut region = Self::default();
+        if !rect.is_empty() {
+            region.rectangles[0] = rect.to_box2d();
+            region.count = 1;
+        }
+        region
+    }
+
+    /// Convert the logical `region` to the screen frame of reference.
+    fn from_dirty_region(
+        region: &DirtyRegion,
+        factor: ScaleFactor,
+        rotation: RotationInfo,
+    ) -> Self {
+        let mut i = region
+            .iter()
+            .map(|r| (r.cast() * factor).to_rect().round_out().cast().transformed(rotation));
+        Self {
+            rectangles: core::array::from_fn(|_| i.next().unwrap_or_default().to_box2d()),
+            count: region.iter().count(),
+        }
+    }
+
     fn iter_box(&self) -> impl Iterator<Item = euclid::Box2D<i16, PhysicalPx>> + '_ {
         (0..self.count).map(|x| self.rectangles[x])
     }
@@ -267,6 +372,42 @@ pub fn iter(
             )
         })
     }
+
+    /// Returns an iterator over rectangles that cover the region and that don't overlap.
+    ///
+    /// Unlike with [`Self::iter()`], each pixel of the region is in exactly one rectangle, so
+    /// they can be used to schedule the transfer of the region to the screen. There might be
+    /// more rectangles than in [`Self::iter()`].
+    pub fn iter_disjoint(
+        &self,
+    ) -> impl Iterator<Item = (crate::api::PhysicalPosition, crate::api::PhysicalSize)> {
+        let mut rectangles = Vec::<euclid::Box2D<i16, PhysicalPx>>::new();
+        let mut line_ranges = Vec::new();
+        let mut line = 0;
+        while let Some(next) = region_line_ranges(self, line, &mut line_ranges) {
+            for r in &line_ranges {
+                // Extend the rectangle of the previous band if it has the same horizontal range
+                if let Some(prev) = rectangles
+                    .iter_mut()
+                    .find(|b| b.max.y == line && b.min.x == r.start && b.max.x == r.end)
+                {
+                    prev.max.y = next;
+                } else {
+                    rectangles.push(euclid::Box2D::new(
+                        euclid::point2(r.start, line),
+                        euclid::point2(r.end, next),
+                    ));
+                }
+            }
+            line = next;
+        }
+        rectangles.into_iter().map(|r| {
+            (
+                crate::api::PhysicalPosition { x: r.min.x as _, y: r.min.y as _ },
+                crate::api::PhysicalSize { width: r.width() as _, height: r.height() as _ },
+            )
+        })
+    }
 }
 
 /// Computes what are the x ranges that intersects the region for specified y line.
@@ -358,6 +499,13 @@ pub struct SoftwareRenderer {
     maybe_window_adapter: RefCell<Option<Weak<dyn crate::window::WindowAdapter>>>,
     rotation: Cell<RenderingRotation>,
     rendering_metrics_collector: Option<Rc<RenderingMetricsCollector>>,
+    box_shadow_cache: RefCell<BoxShadowCache>,
+    /// The maximum number of rectangles in the region that is redrawn
+    max_dirty_rect_count: Cell<usize>,
+    /// The content of the layers and of the cached pixmaps
+    layer_cache: crate::item_rendering::ItemCache<Option<SharedImageBuffer>>,
+    /// The children of the rotated items
+    rotated_layer_cache: crate::item_rendering::ItemCache<RotatedLayer>,
 }
 
 impl Default for SoftwareRenderer {
@@ -371,6 +519,10 @@ fn default() -> Self {
             maybe_window_adapter: Default::default(),
             rotation: Default::default(),
             rendering_metrics_collector: RenderingMetricsCollector::new("software"),
+            box_shadow_cache: Default::default(),
+            max_dirty_rect_count: Cell::new(DirtyRegion::MAX_COUNT),
+            layer_cache: Default::default(),
+            rotated_layer_cache: Default::default(),
         }
     }
 }
@@ -421,20 +573,104 @@ pub fn rendering_rotation(&self) -> RenderingRotation {
     /// Internal function to apply a dirty region depending on the dirty_tracking_policy.
     /// Returns the region to actually draw.
     fn apply_dirty_region(&self, dirty_region: &mut DirtyRegion, screen_size: LogicalSize) {
-        let screen_region = LogicalRect::from_size(screen_size);
-
         if self.force_screen_refresh.take() {
-            *dirty_region = screen_region.into();
+            *dirty_region = LogicalRect::from_size(screen_size).into();
         }
+        let prev_frame_dirty = self.prev_frame_dirty.replace(dirty_region.clone());
+        *dirty_region = self.region_to_redraw(dirty_region, &prev_frame_dirty, screen_size);
+    }
 
-        *dirty_region = match self.repaint_buffer_type() {
+    /// Returns the region that needs to be redrawn, given the region of the items that changed
+    /// and the region that was dirty in the previous frame.
+    fn region_to_redraw(
+        &self,
+        dirty_region: &DirtyRegion,
+        prev_frame_dirty: &DirtyRegion,
+        screen_size: LogicalSize,
+    ) -> DirtyRegion {
+        let screen_region = LogicalRect::from_size(screen_size);
+        match self.repaint_buffer_type() {
             RepaintBufferType::NewBuffer => screen_region.into(),
             RepaintBufferType::ReusedBuffer => dirty_region.clone(),
-            RepaintBufferType::SwappedBuffers => {
-                dirty_region.union(&self.prev_frame_dirty.replace(dirty_region.clone()))
-            }
+            RepaintBufferType::SwappedBuffers => dirty_region.union(prev_frame_dirty),
         }
         .intersection(screen_region)
+        .merged(self.max_dirty_rect_count.get())
+    }
+
+    /// Returns the region that the next call to [`Self::render()`] or [`Self::render_by_line()`]
+    /// is going to redraw, in the screen frame of reference (affected by the rotation).
+    ///
+    /// This can be used to prepare the transfer of the dirty rectangles to the screen before
+    /// the rendering. The region may grow if properties change before the rendering.
+    pub fn dirty_region(&self) -> PhysicalRegion {
+        let Some(window) = self.maybe_window_adapter.borrow().as_ref().and_then(|w| w.upgrade())
+        else {
+            return Default::default();
+        };
+        let window_inner = WindowInner::from_pub(window.window());
+        let Some(window_item) = window_inner.window_item() else { return Default::default() };
+        let window_item = window_item.as_pin_ref();
+        let factor = ScaleFactor::new(window_inner.scale_factor());
+        let logical_size = LogicalSize::from_lengths(window_item.width(), window_item.height());
+        let size: PhysicalSize = (logical_size.cast() * factor).cast();
+
+        let mut renderer = crate::item_rendering::PartialRenderer::new(
+            &self.partial_cache,
+            self.force_dirty.borrow().clone(),
+            (),
+        );
+        window_inner.draw_contents(|components| {
+            for (component, origin) in components {
+                renderer.compute_dirty_regions(component, *origin, logical_size);
+            }
+        });
+        if self.force_screen_refresh.get() {
+            renderer.dirty_region = LogicalRect::from_size(logical_size).into();
+        }
+        let prev_frame_dirty = self.prev_frame_dirty.take();
+        let dirty_region =
+            self.region_to_redraw(&renderer.dirty_region, &prev_frame_dirty, logical_size);
+        self.prev_frame_dirty.set(prev_frame_dirty);
+
+        let rotation = RotationInfo { orientation: self.rotation.get(), screen_size: size };
+        PhysicalRegion::from_dirty_region(&dirty_region, factor, rotation)
+    }
+
+    /// Set the maximum number of rectangles in the region that is redrawn.
+    ///
+    /// When more parts of the window are dirty, the rectangles whose union adds the smallest
+    /// area are merged. Use 1 to always redraw the bounding rectangle of the dirty region.
+    /// The value is clamped between 1 and the number of rectangles that a [`PhysicalRegion`]
+    /// can hold.
+    pub fn set_max_dirty_rect_count(&self, count: usize) {
+        self.max_dirty_rect_count.set(count.clamp(1, DirtyRegion::MAX_COUNT));
+    }
+
+    /// Mark a rectangle of the window as dirty, so that it is redrawn in the next frame, no matter
+    /// if the items in it changed or not.
+    ///
+    /// The rectangle is in physical pixels, in the window frame of reference (not affected by
+    /// the rotation).
+    pub fn mark_dirty_rect(
+        &self,
+        origin: crate::api::PhysicalPosition,
+        size: crate::api::PhysicalSize,
+    ) {
+        let scale_factor = self
+            .maybe_window_adapter
+            .borrow()
+            .as_ref()
+            .and_then(|w| w.upgrade())
+            .map_or(1., |w| w.window().scale_factor());
+        let rect = euclid::rect::<f32, PhysicalPx>(
+            origin.x as _,
+            origin.y as _,
+            size.width as _,
+            size.height as _,
+        ) / ScaleFactor::new(scale_factor);
+        // Include the pixels that are only partially covered by the logical rect
+        self.force_dirty.borrow_mut().add_rect(rect.round_out().cast());
     }
 
     /// Render the window to the given frame buffer.
@@ -455,6 +691,8 @@ pub fn render(&self, buffer: &mut [impl TargetPixel], pixel_stride: usize) -> Ph
         else {
             return Default::default();
         };
+        self.layer_cache.clear_cache_if_scale_factor_changed(window.window());
+        self.rotated_layer_cache.clear_cache_if_scale_factor_changed(window.window());
         let window_inner = WindowInner::from_pub(window.window());
         let factor = ScaleFactor::new(window_inner.scale_factor());
         let rotation = self.rotation.get();
@@ -494,6 +732,7 @@ pub fn render(&self, buffer: &mut [impl TargetPixel], pixel_stride: usize) -> Ph
                 dirty_region: Default::default(),
             },
             rotation,
+            self,
         );
         let mut renderer = crate::item_rendering::PartialRenderer::new(
             &self.partial_cache,
@@ -509,18 +748,13 @@ pub fn render(&self, buffer: &mut [impl TargetPixel], pixel_stride: usize) -> Ph
                 }
                 self.apply_dirty_region(&mut renderer.dirty_region, logical_size);
                 let rotation = RotationInfo { orientation: rotation, screen_size: size };
-                let mut i = renderer.dirty_region.iter().map(|r| {
-                    (r.cast() * factor).to_rect().round_out().cast().transformed(rotation)
-                });
-                let dirty_region = PhysicalRegion {
-                    rectangles: core::array::from_fn(|_| i.next().unwrap_or_default().to_box2d()),
-                    count: renderer.dirty_region.iter().count(),
-                };
-                drop(i);
+                let dirty_region =
+                    PhysicalRegion::from_dirty_region(&renderer.dirty_region, factor, rotation);
 
                 let mut bg = TargetPixel::background();
-                // TODO: gradient background
-                TargetPixel::blend(&mut bg, background.color().into());
+                if let Brush::SolidColor(color) = background {
+                    TargetPixel::blend(&mut bg, color.into());
+                }
                 let mut line = 0;
                 while let Some(next) = region_line_ranges(
                     &dirty_region,
@@ -539,6 +773,16 @@ pub fn render(&self, buffer: &mut [impl TargetPixel], pixel_stride: usize) -> Ph
 
                 renderer.actual_renderer.processor.dirty_region = dirty_region.clone();
 
+                if !matches!(background, Brush::SolidColor(_)) {
+                    renderer.actual_renderer.draw_gradient_rounded_rectangle(
+                        LogicalRect::from_size(logical_size),
+                        Default::default(),
+                        Default::default(),
+                        background.clone(),
+                        Brush::default(),
+                    );
+                }
+
                 for (component, origin) in components {
                     crate::item_rendering::render_component_items(
                         component,
@@ -598,6 +842,8 @@ pub fn render_by_line(&self, line_buffer: impl LineBufferProvider) -> PhysicalRe
         else {
             return Default::default();
         };
+        self.layer_cache.clear_cache_if_scale_factor_changed(window.window());
+        self.rotated_layer_cache.clear_cache_if_scale_factor_changed(window.window());
         let window_inner = WindowInner::from_pub(window.window());
         let component_rc = window_inner.component();
         let component = crate::item_tree::ItemTreeRc::borrow_pin(&component_rc);
@@ -618,6 +864,29 @@ pub fn render_by_line(&self, line_buffer: impl LineBufferProvider) -> PhysicalRe
             PhysicalRegion { ..Default::default() }
         }
     }
+
+    /// Render the window to the given frame buffer, like [`Self::render`], but with ordered
+    /// dithering to avoid the banding of the gradients in formats with less than 8 bits per
+    /// color component.
+    ///
+    /// The lines are rendered one by one with a [`DitheredLineBuffer`], so the screen rotation
+    /// and the partial rendering work the same way as with [`Self::render_by_line`].
+    pub fn render_dithered(
+        &self,
+        buffer: &mut [impl DitheredPixel],
+        pixel_stride: usize,
+    ) -> PhysicalRegion {
+        self.render_by_line(DitheredLineBuffer::new(buffer, pixel_stride))
+    }
+
+    /// Render the window to a frame buffer with one bit per pixel, such as the ones of e-paper
+    /// displays.
+    ///
+    /// `stride` is the number of bytes of each line in the buffer. The pixels are stored as
+    /// described in [`MonochromeLineBuffer`].
+    pub fn render_monochrome(&self, buffer: &mut [u8], stride: usize) -> PhysicalRegion {
+        self.render_by_line(MonochromeLineBuffer::new(buffer, stride))
+    }
 }
 
 #[doc(hidden)]
@@ -759,12 +1028,14 @@ fn text_input_cursor_rect_for_byte_offset(
 
     fn free_graphics_resources(
         &self,
-        _component: crate::item_tree::ItemTreeRef,
+        component: crate::item_tree::ItemTreeRef,
         items: &mut dyn Iterator<Item = Pin<crate::items::ItemRef<'_>>>,
     ) -> Result<(), crate::platform::PlatformError> {
         for item in items {
             item.cached_rendering_data_offset().release(&mut self.partial_cache.borrow_mut());
         }
+        self.layer_cache.component_destroyed(component);
+        self.rotated_layer_cache.component_destroyed(component);
         // We don't have a way to determine the screen region of the delete items, what's in the cache is relative. So
         // as a last resort, refresh everything.
         self.force_screen_refresh.set(true);
@@ -802,6 +1073,8 @@ fn default_font_size(&self) -> LogicalLength {
     fn set_window_adapter(&self, window_adapter: &Rc<dyn WindowAdapter>) {
         *self.maybe_window_adapter.borrow_mut() = Some(Rc::downgrade(window_adapter));
         self.partial_cache.borrow_mut().clear();
+        self.layer_cache.clear_all();
+        self.rotated_layer_cache.clear_all();
     }
 }
 
@@ -812,13 +1085,14 @@ fn render_window_frame_by_line(
     renderer: &SoftwareRenderer,
     mut line_buffer: impl LineBufferProvider,
 ) -> PhysicalRegion {
-    let mut scene = prepare_scene(window, size, renderer);
+    let mut scene = prepare_scene(window, &background, size, renderer);
 
     let to_draw_tr = scene.dirty_region.bounding_rect();
 
     let mut background_color = TargetPixel::background();
-    // FIXME gradient
-    TargetPixel::blend(&mut background_color, background.color().into());
+    if let Brush::SolidColor(color) = background {
+        TargetPixel::blend(&mut background_color, color.into());
+    }
 
     while scene.current_line < to_draw_tr.origin.y_length() + to_draw_tr.size.height_length() {
         for r in &scene.current_line_ranges {
@@ -886,13 +1160,13 @@ fn render_window_frame_by_line(
                                     extra_right_clip,
                                 );
                             }
-                            SceneCommand::Gradient { gradient_index } => {
-                                let g = &scene.vectors.gradients[gradient_index as usize];
-
-                                draw_functions::draw_gradient_line(
+                            SceneCommand::GradientRoundedRectangle { rectangle_index } => {
+                                let rr = &scene.vectors.gradient_rounded_rectangles
+                                    [rectangle_index as usize];
+                                draw_functions::draw_gradient_rounded_rectangle_line(
                                     &PhysicalRect { origin: span.pos, size: span.size },
                                     scene.current_line,
-                                    g,
+                                    rr,
                                     range_buffer,
                                     extra_left_clip,
                                 );
@@ -915,7 +1189,7 @@ struct SceneVectors {
     textures: Vec<SceneTexture<'static>>,
     rounded_rectangles: Vec<RoundedRectangle>,
     shared_buffers: Vec<SharedBufferCommand>,
-    gradients: Vec<GradientCommand>,
+    gradient_rounded_rectangles: Vec<GradientRoundedRectangle>,
 }
 
 struct Scene {
@@ -1171,9 +1445,9 @@ enum SceneCommand {
     RoundedRectangle {
         rectangle_index: u16,
     },
-    /// rectangle_index is an index in the [`SceneVectors::rounded_gradients`] array
-    Gradient {
-        gradient_index: u16,
+    /// rectangle_index is an index in the [`SceneVectors::gradient_rounded_rectangles`] array
+    GradientRoundedRectangle {
+        rectangle_index: u16,
     },
 }
 
@@ -1299,30 +1573,28 @@ struct RoundedRectangle {
     bottom_clip: PhysicalLength,
 }
 
-/// Goes from color 1 to color2
+/// A rectangle, with optional rounded corners and border, whose background or border
+/// is filled with a gradient.
 ///
-/// depending of `flags & 0b1`
-///  - if false: on the left side, goes from `start` to 1, on the right side, goes from 0 to `1-start`
-///  - if true: on the left side, goes from 0 to `1-start`, on the right side, goes from `start` to `1`
+/// Unlike [`RoundedRectangle`], the geometry is not expressed as clips, but is the full
+/// rectangle in screen coordinates, so that the gradient can be computed for each pixel.
 #[derive(Debug)]
-struct GradientCommand {
-    color1: PremultipliedRgbaColor,
-    color2: PremultipliedRgbaColor,
-    start: u8,
-    /// bit 0: if the slope is positive or negative
-    /// bit 1: if we should fill with color1 on the left side when left_clip is negative (or transparent)
-    /// bit 2: if we should fill with color2 on the left side when right_clip is negative (or transparent)
-    flags: u8,
-    /// If positive, the clip has the same meaning as in RoundedRectangle.
-    /// If negative, that means the "stop" is only starting or stopping at that point
-    left_clip: PhysicalLength,
-    right_clip: PhysicalLength,
-    top_clip: PhysicalLength,
-    bottom_clip: PhysicalLength,
+struct GradientRoundedRectangle {
+    /// The whole rectangle, before clipping, in physical screen coordinates
+    rect: euclid::Rect<f32, PhysicalPx>,
+    radius: BorderRadius<f32, PhysicalPx>,
+    border_width: f32,
+    background: Brush,
+    border: Brush,
+    /// Angle in degree to add to linear gradients to take the screen rotation into account
+    angle_offset: f32,
+    /// The global opacity, between 0 and 1
+    alpha: f32,
 }
 
 fn prepare_scene(
     window: &WindowInner,
+    background: &Brush,
     size: PhysicalSize,
     software_renderer: &SoftwareRenderer,
 ) -> Scene {
@@ -1333,6 +1605,7 @@ fn prepare_scene(
         window,
         PrepareScene::default(),
         software_renderer.rotation.get(),
+        software_renderer,
     );
     let mut renderer = crate::item_rendering::PartialRenderer::new(
         &software_renderer.partial_cache,
@@ -1350,15 +1623,17 @@ fn prepare_scene(
         software_renderer.apply_dirty_region(&mut renderer.dirty_region, logical_size);
         let rotation =
             RotationInfo { orientation: software_renderer.rotation.get(), screen_size: size };
-        let mut i = renderer
-            .dirty_region
-            .iter()
-            .map(|r| (r.cast() * factor).to_rect().round_out().cast().transformed(rotation));
-        dirty_region = PhysicalRegion {
-            rectangles: core::array::from_fn(|_| i.next().unwrap_or_default().to_box2d()),
-            count: renderer.dirty_region.iter().count(),
-        };
-        drop(i);
+        dirty_region = PhysicalRegion::from_dirty_region(&renderer.dirty_region, factor, rotation);
+
+        if !matches!(background, Brush::SolidColor(_)) {
+            renderer.actual_renderer.draw_gradient_rounded_rectangle(
+                LogicalRect::from_size(logical_size),
+                Default::default(),
+                Default::default(),
+                background.clone(),
+                Brush::default(),
+            );
+        }
 
         for (component, origin) in components {
             crate::item_rendering::render_component_items(component, &mut renderer, *origin);
@@ -1400,7 +1675,11 @@ trait ProcessScene {
     fn process_rectangle(&mut self, geometry: PhysicalRect, color: PremultipliedRgbaColor);
     fn process_rounded_rectangle(&mut self, geometry: PhysicalRect, data: RoundedRectangle);
     fn process_shared_image_buffer(&mut self, geometry: PhysicalRect, buffer: SharedBufferCommand);
-    fn process_gradient(&mut self, geometry: PhysicalRect, gradient: GradientCommand);
+    fn process_gradient_rounded_rectangle(
+        &mut self,
+        geometry: PhysicalRect,
+        data: GradientRoundedRectangle,
+    );
 }
 
 struct RenderToBuffer<'a, TargetPixel> {
@@ -1491,12 +1770,16 @@ fn process_rounded_rectangle(&mut self, geometry: PhysicalRect, rr: RoundedRecta
         });
     }
 
-    fn process_gradient(&mut self, geometry: PhysicalRect, g: GradientCommand) {
+    fn process_gradient_rounded_rectangle(
+        &mut self,
+        geometry: PhysicalRect,
+        rr: GradientRoundedRectangle,
+    ) {
         self.foreach_ranges(&geometry, |line, buffer, extra_left_clip, _extra_right_clip| {
-            draw_functions::draw_gradient_line(
+            draw_functions::draw_gradient_rounded_rectangle_line(
                 &geometry,
                 PhysicalLength::new(line),
-                &g,
+                &rr,
                 buffer,
                 extra_left_clip,
             );
@@ -1562,21 +1845,65 @@ fn process_rounded_rectangle(&mut self, geometry: PhysicalRect, data: RoundedRec
         }
     }
 
-    fn process_gradient(&mut self, geometry: PhysicalRect, gradient: GradientCommand) {
+    fn process_gradient_rounded_rectangle(
+        &mut self,
+        geometry: PhysicalRect,
+        data: GradientRoundedRectangle,
+    ) {
         let size = geometry.size;
         if !size.is_empty() {
-            let gradient_index = self.vectors.gradients.len() as u16;
-            self.vectors.gradients.push(gradient);
+            let rectangle_index = self.vectors.gradient_rounded_rectangles.len() as u16;
+            self.vectors.gradient_rounded_rectangles.push(data);
             self.items.push(SceneItem {
                 pos: geometry.origin,
                 size,
                 z: self.items.len() as u16,
-                command: SceneCommand::Gradient { gradient_index },
+                command: SceneCommand::GradientRoundedRectangle { rectangle_index },
             });
         }
     }
 }
 
+/// Number of box shadow alpha maps kept in the [`BoxShadowCache`]
+const BOX_SHADOW_CACHE_SIZE: usize = 8;
+
+/// The parameters, in physical pixels, from which the alpha map of a box shadow is computed
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct BoxShadowKey {
+    width: i32,
+    height: i32,
+    radius: i32,
+    blur: i32,
+}
+
+/// Cache of the alpha maps of the box shadows, so that they don't need to be blurred on every frame.
+///
+/// The alpha map doesn't depend on the color nor on the offset, so an entry can be shared by
+/// several shadows. The least recently used entries are evicted first.
+#[derive(Default)]
+struct BoxShadowCache(Vec<(BoxShadowKey, Rc<[u8]>)>);
+
+impl BoxShadowCache {
+    fn get_or_insert_with(&mut self, key: BoxShadowKey, f: impl FnOnce() -> Rc<[u8]>) -> Rc<[u8]> {
+        if let Some(pos) = self.0.iter().position(|(k, _)| *k == key) {
+            let entry = self.0.remove(pos);
+            self.0.insert(0, entry);
+        } else {
+            self.0.truncate(BOX_SHADOW_CACHE_SIZE - 1);
+            self.0.insert(0, (key, f()));
+        }
+        self.0[0].1.clone()
+    }
+}
+
+/// The children of a `Rotate` item, rendered without the rotation
+#[derive(Clone)]
+struct RotatedLayer {
+    /// The area covered by the layer, in physical pixels relative to the `Rotate` item
+    area: PhysicalRect,
+    buffer: Rc<[PremultipliedRgbaColor]>,
+}
+
 struct SceneBuilder<'a, T> {
     processor: T,
     state_stack: Vec<RenderState>,
@@ -1584,6 +1911,7 @@ struct SceneBuilder<'a, T> {
     scale_factor: ScaleFactor,
     window: &'a WindowInner,
     rotation: RotationInfo,
+    software_renderer: &'a SoftwareRenderer,
 }
 
 impl<'a, T: ProcessScene> SceneBuilder<'a, T> {
@@ -1593,6 +1921,7 @@ fn new(
         window: &'a WindowInner,
         processor: T,
         orientation: RenderingRotation,
+        software_renderer: &'a SoftwareRenderer,
     ) -> Self {
         Self {
             processor,
@@ -1604,10 +1933,12 @@ fn new(
                     LogicalPoint::default(),
                     (screen_size.cast() / scale_factor).cast(),
                 ),
+                transform: None,
             },
             scale_factor,
             window,
             rotation: RotationInfo { orientation, screen_size },
+            software_renderer,
         }
     }
 
@@ -1921,6 +2252,261 @@ fn alpha_color(&self, color: Color) -> Color {
             color
         }
     }
+
+    /// Draw the `area` of an item that is in a rotated sub-tree, using `render_fn`.
+    ///
+    /// The item is first rendered without transformation into an intermediate buffer, which is
+    /// then resampled into the window with the transformation applied.
+    fn draw_transformed(
+        &mut self,
+        area: LogicalRect,
+        render_fn: impl FnOnce(&mut SceneBuilder<'_, RenderToBuffer<'_, PremultipliedRgbaColor>>),
+    ) {
+        if self.current_state.transform.is_none() {
+            return;
+        }
+        let Some(area) = area.intersection(&self.current_state.clip) else { return };
+        let area: PhysicalRect = (area.cast() * self.scale_factor).round_out().cast();
+        if area.is_empty() {
+            return;
+        }
+
+        let mut buffer =
+            vec![PremultipliedRgbaColor::default(); area.width() as usize * area.height() as usize];
+        let mut renderer = SceneBuilder::new(
+            area.size,
+            self.scale_factor,
+            self.window,
+            RenderToBuffer {
+                buffer: &mut buffer,
+                stride: area.width() as usize,
+                dirty_range_cache: vec![],
+                dirty_region: PhysicalRegion::from_rect(PhysicalRect::from_size(area.size)),
+            },
+            RenderingRotation::NoRotation,
+            self.software_renderer,
+        );
+        // The item coordinates are the same as in this renderer, only the origin is moved
+        // to the top left of the buffer.
+        renderer.current_state.offset =
+            LogicalPoint::default() - (area.origin.cast() / self.scale_factor).cast().to_vector();
+        renderer.current_state.clip = self.current_state.clip;
+        render_fn(&mut renderer);
+        drop(renderer);
+        self.draw_transformed_buffer(&buffer, area);
+    }
+
+    /// Draw `buffer`, which covers `area` in physical pixels relative to the current offset, with
+    /// the transformation of the rotated sub-tree applied.
+    fn draw_transformed_buffer(&mut self, buffer: &[PremultipliedRgbaColor], area: PhysicalRect) {
+        let Some(transform) = self.current_state.transform else { return };
+        let scale = self.scale_factor.get();
+        let offset = self.current_state.offset.cast::<f32>();
+        let buffer_to_window = euclid::Transform2D::<f32, PhysicalPx, PhysicalPx>::translation(
+            area.origin.x as f32,
+            area.origin.y as f32,
+        )
+        .then_scale(1. / scale, 1. / scale)
+        .then_translate(euclid::vec2(offset.x, offset.y))
+        .then(&transform.transform.with_source::<PhysicalPx>().with_destination::<PhysicalPx>())
+        .then_scale(scale, scale);
+        let Some(window_to_buffer) = buffer_to_window.inverse() else { return };
+
+        let physical_clip = (transform.window_clip.cast() * self.scale_factor).round().cast();
+        let Some(target) = buffer_to_window
+            .outer_transformed_rect(&euclid::Rect::from_size(area.size.cast()))
+            .round_out()
+            .cast()
+            .intersection(&physical_clip)
+        else {
+            return;
+        };
+
+        let image = draw_functions::transform_buffer(buffer, area.size, target, &window_to_buffer);
+        self.processor.process_shared_image_buffer(
+            target.transformed(self.rotation),
+            SharedBufferCommand {
+                buffer: SharedBufferData::SharedImage(SharedImageBuffer::RGBA8Premultiplied(image)),
+                source_rect: PhysicalRect::from_size(target.size),
+                extra: SceneTextureExtra {
+                    colorize: Default::default(),
+                    alpha: (self.current_state.alpha * 255.) as u8,
+                    rotation: self.rotation.orientation,
+                    dx: Fixed::from_integer(1),
+                    dy: Fixed::from_integer(1),
+                    off_x: Fixed::from_integer(0),
+                    off_y: Fixed::from_integer(0),
+                },
+            },
+        );
+    }
+
+    /// Draw an image of pre-multiplied pixels at the current position, with `alpha_tint`
+    /// applied on top of the current opacity.
+    fn draw_layer_image(&mut self, image: SharedImageBuffer, alpha_tint: f32) {
+        let physical_clip = (self.current_state.clip.cast() * self.scale_factor).cast();
+        let source_rect = euclid::rect(0, 0, image.width() as _, image.height() as _);
+
+        if let Some(clipped_src) = source_rect.intersection(&physical_clip) {
+            let geometry = clipped_src
+                .translate(
+                    (self.current_state.offset.cast() * self.scale_factor).to_vector().cast(),
+                )
+                .round_in();
+
+            self.processor.process_shared_image_buffer(
+                geometry.cast().transformed(self.rotation),
+                SharedBufferCommand {
+                    buffer: SharedBufferData::SharedImage(image),
+                    source_rect,
+                    extra: SceneTextureExtra {
+                        colorize: Default::default(),
+                        alpha: (self.current_state.alpha * alpha_tint * 255.) as u8,
+                        rotation: self.rotation.orientation,
+                        dx: Fixed::from_integer(1),
+                        dy: Fixed::from_integer(1),
+                        off_x: Fixed::from_integer(clipped_src.min_x() as _),
+                        off_y: Fixed::from_integer(clipped_src.min_y() as _),
+                    },
+                },
+            );
+        }
+    }
+
+    /// Render the children of `item_rc` in a new buffer that covers `area`, in physical pixels
+    /// relative to the position of the item.
+    fn render_children_to_buffer(
+        item_rc: &ItemRc,
+        area: PhysicalRect,
+        scale_factor: ScaleFactor,
+        window: &WindowInner,
+        software_renderer: &SoftwareRenderer,
+    ) -> Vec<PremultipliedRgbaColor> {
+        let size = area.size;
+        let mut buffer =
+            vec![PremultipliedRgbaColor::default(); size.width as usize * size.height as usize];
+        let mut layer_renderer = SceneBuilder::new(
+            size,
+            scale_factor,
+            window,
+            RenderToBuffer {
+                buffer: &mut buffer,
+                stride: size.width as usize,
+                dirty_range_cache: vec![],
+                dirty_region: PhysicalRegion::from_rect(PhysicalRect::from_size(size)),
+            },
+            RenderingRotation::NoRotation,
+            software_renderer,
+        );
+        // The item coordinates are the same as in this renderer, only the origin is moved
+        // to the top left of the buffer.
+        let origin = (area.origin.cast() / scale_factor).cast().to_vector();
+        layer_renderer.current_state.offset = LogicalPoint::default() - origin;
+        layer_renderer.current_state.clip = layer_renderer.current_state.clip.translate(origin);
+        // Rendering through a PartialRenderer registers the dependencies of the children
+        // to the cache entry, if any, so the layer is rendered again when one of them changes.
+        let mut renderer = crate::item_rendering::PartialRenderer::new(
+            &software_renderer.partial_cache,
+            LogicalRect::from_size((size.cast() / scale_factor).cast()).into(),
+            layer_renderer,
+        );
+        crate::item_rendering::render_item_children(
+            &mut renderer,
+            item_rc.item_tree(),
+            item_rc.index() as isize,
+        );
+        drop(renderer);
+        buffer
+    }
+
+    /// Render the children of `item_rc` in a buffer and blend it with `alpha_tint`.
+    ///
+    /// The buffer is kept in the layer cache until a property used by the children changes.
+    #[cfg(feature = "std")]
+    fn render_and_blend_layer(
+        &mut self,
+        alpha_tint: f32,
+        item_rc: &ItemRc,
+    ) -> crate::items::RenderingResult {
+        let current_clip = self.current_state.clip;
+        let scale_factor = self.scale_factor;
+        let window = self.window;
+        let software_renderer = self.software_renderer;
+        let layer = software_renderer.layer_cache.get_or_update_cache_entry(item_rc, || {
+            // We don't need to include the size of the opacity item itself, since it has no content.
+            let children_rect = crate::properties::evaluate_no_tracking(|| {
+                item_rc.geometry().union(&crate::item_rendering::item_children_bounding_rect(
+                    item_rc.item_tree(),
+                    item_rc.index() as isize,
+                    &current_clip,
+                ))
+            });
+            let size: PhysicalSize = (children_rect.size.cast() * scale_factor).ceil().cast();
+            if size.is_empty() {
+                return None;
+            }
+            let buffer = Self::render_children_to_buffer(
+                item_rc,
+                PhysicalRect::from_size(size),
+                scale_factor,
+                window,
+                software_renderer,
+            );
+            Some(SharedImageBuffer::RGBA8Premultiplied(SharedPixelBuffer::clone_from_slice(
+                bytemuck::cast_slice::<_, u8>(&buffer),
+                size.width as u32,
+                size.height as u32,
+            )))
+        });
+
+        if let Some(layer) = layer {
+            if self.current_state.transform.is_some() {
+                let layer_size = PhysicalSize::new(layer.width() as _, layer.height() as _);
+                let area = LogicalRect::from_size((layer_size.cast() / scale_factor).cast());
+                self.draw_transformed(area, |r| r.draw_layer_image(layer, alpha_tint));
+            } else {
+                self.draw_layer_image(layer, alpha_tint);
+            }
+        }
+        crate::items::RenderingResult::ContinueRenderingWithoutChildren
+    }
+
+    /// Draw a rectangle, with optional rounded corners and border, when the background or
+    /// the border is not a plain color.
+    ///
+    /// `geom` is relative to the current offset.
+    #[allow(clippy::unnecessary_cast)] // Coord
+    fn draw_gradient_rounded_rectangle(
+        &mut self,
+        geom: LogicalRect,
+        radius: LogicalBorderRadius,
+        border_width: LogicalLength,
+        background: Brush,
+        border: Brush,
+    ) {
+        let Some(clipped) = geom.intersection(&self.current_state.clip) else { return };
+        let offset = self.current_state.offset.to_vector();
+        let radius = radius
+            .min(LogicalBorderRadius::from_length(geom.width_length() / 2 as Coord))
+            .min(LogicalBorderRadius::from_length(geom.height_length() / 2 as Coord));
+        let geometry = (clipped.translate(offset).cast() * self.scale_factor)
+            .round()
+            .cast()
+            .transformed(self.rotation);
+        self.processor.process_gradient_rounded_rectangle(
+            geometry,
+            GradientRoundedRectangle {
+                rect: (geom.translate(offset).cast() * self.scale_factor)
+                    .transformed(self.rotation),
+                radius: (radius.cast() * self.scale_factor).transformed(self.rotation),
+                border_width: (border_width.cast() * self.scale_factor).get(),
+                background,
+                border,
+                angle_offset: self.rotation.orientation.angle(),
+                alpha: self.current_state.alpha,
+            },
+        );
+    }
 }
 
 struct SelectionInfo {
@@ -1934,6 +2520,17 @@ struct RenderState {
     alpha: f32,
     offset: LogicalPoint,
     clip: LogicalRect,
+    /// Set when rendering a rotated sub-tree
+    transform: Option<ItemTransform>,
+}
+
+/// The transformation of the items in a rotated sub-tree
+#[derive(Clone, Copy)]
+struct ItemTransform {
+    /// Maps the item coordinates, translated by [`RenderState::offset`], to the window coordinates
+    transform: euclid::Transform2D<f32, LogicalPx, LogicalPx>,
+    /// The clip, in window coordinates, that was active when the rotation was applied
+    window_clip: LogicalRect,
 }
 
 impl<'a, T: ProcessScene> crate::item_rendering::ItemRenderer for SceneBuilder<'a, T> {
@@ -1941,9 +2538,15 @@ impl<'a, T: ProcessScene> crate::item_rendering::ItemRenderer for SceneBuilder<'
     fn draw_rectangle(
         &mut self,
         rect: Pin<&crate::items::Rectangle>,
-        _: &ItemRc,
+        self_rc: &ItemRc,
         size: LogicalSize,
     ) {
+        if self.current_state.transform.is_some() {
+            self.draw_transformed(LogicalRect::from(size), |r| {
+                r.draw_rectangle(rect, self_rc, size)
+            });
+            return;
+        }
         let geom = LogicalRect::from(size);
         if self.should_draw(&geom) {
             let clipped = match geom.intersection(&self.current_state.clip) {
@@ -1952,99 +2555,14 @@ fn draw_rectangle(
             };
 
             let background = rect.background();
-            if let Brush::LinearGradient(g) = background {
-                let geom2 = (geom.cast() * self.scale_factor).transformed(self.rotation);
-                let clipped2 = (clipped.cast() * self.scale_factor).transformed(self.rotation);
-                let act_rect = (clipped.translate(self.current_state.offset.to_vector()).cast()
-                    * self.scale_factor)
-                    .round()
-                    .cast()
-                    .transformed(self.rotation);
-                let axis_angle = (360. - self.rotation.orientation.angle()) % 360.;
-                let angle = g.angle() - axis_angle;
-                let tan = angle.to_radians().tan().abs();
-                let start = if !tan.is_finite() {
-                    255.
-                } else {
-                    let h = tan * geom2.width() as f32;
-                    255. * h / (h + geom2.height() as f32)
-                } as u8;
-                let mut angle = angle as i32 % 360;
-                if angle < 0 {
-                    angle += 360;
-                }
-                let mut stops = g.stops().copied().peekable();
-                let mut idx = 0;
-                let stop_count = g.stops().count();
-                while let (Some(mut s1), Some(mut s2)) = (stops.next(), stops.peek().copied()) {
-                    let mut flags = 0;
-                    if (angle % 180) > 90 {
-                        flags |= 0b1;
-                    }
-                    if angle <= 90 || angle > 270 {
-                        core::mem::swap(&mut s1, &mut s2);
-                        s1.position = 1. - s1.position;
-                        s2.position = 1. - s2.position;
-                        if idx == 0 {
-                            flags |= 0b100;
-                        }
-                        if idx == stop_count - 2 {
-                            flags |= 0b010;
-                        }
-                    } else {
-                        if idx == 0 {
-                            flags |= 0b010;
-                        }
-                        if idx == stop_count - 2 {
-                            flags |= 0b100;
-                        }
-                    }
-
-                    idx += 1;
-
-                    let (adjust_left, adjust_right) = if (angle % 180) > 90 {
-                        (
-                            (geom2.width() * s1.position).floor() as i16,
-                            (geom2.width() * (1. - s2.position)).ceil() as i16,
-                        )
-                    } else {
-                        (
-                            (geom2.width() * (1. - s2.position)).ceil() as i16,
-                            (geom2.width() * s1.position).floor() as i16,
-                        )
-                    };
-
-                    let gr = GradientCommand {
-                        color1: self.alpha_color(s1.color).into(),
-                        color2: self.alpha_color(s2.color).into(),
-                        start,
-                        flags,
-                        top_clip: Length::new(
-                            (clipped2.min_y() - geom2.min_y()) as i16
-                                - (geom2.height() * s1.position).floor() as i16,
-                        ),
-                        bottom_clip: Length::new(
-                            (geom2.max_y() - clipped2.max_y()) as i16
-                                - (geom2.height() * (1. - s2.position)).ceil() as i16,
-                        ),
-                        left_clip: Length::new(
-                            (clipped2.min_x() - geom2.min_x()) as i16 - adjust_left,
-                        ),
-                        right_clip: Length::new(
-                            (geom2.max_x() - clipped2.max_x()) as i16 - adjust_right,
-                        ),
-                    };
-
-                    let size_y = act_rect.height_length() + gr.top_clip + gr.bottom_clip;
-                    let size_x = act_rect.width_length() + gr.left_clip + gr.right_clip;
-                    if size_x.get() == 0 || size_y.get() == 0 {
-                        // the position are too close to each other
-                        // FIXME: For the first or the last, we should draw a plain color to the end
-                        continue;
-                    }
-
-                    self.processor.process_gradient(act_rect, gr);
-                }
+            if !matches!(background, Brush::SolidColor(_)) {
+                self.draw_gradient_rounded_rectangle(
+                    geom,
+                    LogicalBorderRadius::default(),
+                    LogicalLength::new(0 as _),
+                    background,
+                    Brush::default(),
+                );
                 return;
             }
 
@@ -2067,18 +2585,38 @@ fn draw_rectangle(
     fn draw_border_rectangle(
         &mut self,
         rect: Pin<&dyn RenderBorderRectangle>,
-        _: &ItemRc,
+        self_rc: &ItemRc,
         size: LogicalSize,
-        _: &CachedRenderingData,
+        cache: &CachedRenderingData,
     ) {
+        if self.current_state.transform.is_some() {
+            self.draw_transformed(LogicalRect::from(size), |r| {
+                r.draw_border_rectangle(rect, self_rc, size, cache)
+            });
+            return;
+        }
         let geom = LogicalRect::from(size);
         if self.should_draw(&geom) {
             let mut border = rect.border_width();
             let radius = rect.border_radius();
-            // FIXME: gradients
-            let color = self.alpha_color(rect.background().color());
+            let background = rect.background();
+            let border_brush = rect.border_color();
+            if !matches!(background, Brush::SolidColor(_))
+                || (border.get() as f32 > 0.01 && !matches!(border_brush, Brush::SolidColor(_)))
+            {
+                self.draw_gradient_rounded_rectangle(
+                    geom,
+                    radius,
+                    border,
+                    background,
+                    border_brush,
+                );
+                return;
+            }
+
+            let color = self.alpha_color(background.color());
             let border_color = if border.get() as f32 > 0.01 {
-                self.alpha_color(rect.border_color().color())
+                self.alpha_color(border_brush.color())
             } else {
                 Color::default()
             };
@@ -2164,7 +2702,6 @@ fn draw_border_rectangle(
                 }
             }
 
-            // FIXME: gradients
             if border_color.alpha > 0 {
                 let mut add_border = |r: LogicalRect| {
                     if let Some(r) = r.intersection(&self.current_state.clip) {
@@ -2188,10 +2725,16 @@ fn draw_border_rectangle(
     fn draw_image(
         &mut self,
         image: Pin<&dyn RenderImage>,
-        _: &ItemRc,
+        self_rc: &ItemRc,
         size: LogicalSize,
-        _: &CachedRenderingData,
+        cache: &CachedRenderingData,
     ) {
+        if self.current_state.transform.is_some() {
+            self.draw_transformed(LogicalRect::from(size), |r| {
+                r.draw_image(image, self_rc, size, cache)
+            });
+            return;
+        }
         let geom = LogicalRect::from(size);
         if self.should_draw(&geom) {
             let source = image.source();
@@ -2234,7 +2777,11 @@ fn draw_image(
         }
     }
 
-    fn draw_text(&mut self, text: Pin<&crate::items::Text>, _: &ItemRc, size: LogicalSize) {
+    fn draw_text(&mut self, text: Pin<&crate::items::Text>, self_rc: &ItemRc, size: LogicalSize) {
+        if self.current_state.transform.is_some() {
+            self.draw_transformed(LogicalRect::from(size), |r| r.draw_text(text, self_rc, size));
+            return;
+        }
         let string = text.text();
         if string.trim().is_empty() {
             return;
@@ -2304,9 +2851,15 @@ fn draw_text(&mut self, text: Pin<&crate::items::Text>, _: &ItemRc, size: Logica
     fn draw_text_input(
         &mut self,
         text_input: Pin<&crate::items::TextInput>,
-        _: &ItemRc,
+        self_rc: &ItemRc,
         size: LogicalSize,
     ) {
+        if self.current_state.transform.is_some() {
+            self.draw_transformed(LogicalRect::from(size), |r| {
+                r.draw_text_input(text_input, self_rc, size)
+            });
+            return;
+        }
         let geom = LogicalRect::from(size);
         if !self.should_draw(&geom) {
             return;
@@ -2410,26 +2963,368 @@ fn draw_text_input(
         }
     }
 
-    #[cfg(feature = "std")]
+    #[cfg(feature = "software-renderer-path")]
+    fn draw_path(&mut self, path: Pin<&crate::items::Path>, item_rc: &ItemRc, size: LogicalSize) {
+        if self.current_state.transform.is_some() {
+            self.draw_transformed(LogicalRect::from(size), |r| r.draw_path(path, item_rc, size));
+            return;
+        }
+        let geom = LogicalRect::from(size);
+        if !self.should_draw(&geom) {
+            return;
+        }
+
+        let Some((offset, path_events)) = path.fitted_path_events(item_rc) else {
+            return;
+        };
+
+        let item_origin = self.current_state.offset.cast() * self.scale_factor;
+        let commands = path::to_zeno_commands(
+            path_events.iter(),
+            self.scale_factor.get(),
+            item_origin.to_vector() + offset.cast() * self.scale_factor,
+        );
+        if commands.is_empty() {
+            return;
+        }
+
+        let physical_clip =
+            (self.current_state.clip.translate(self.current_state.offset.to_vector()).cast()
+                * self.scale_factor)
+                .round()
+                .cast();
+        let brush_rect = euclid::Rect::new(item_origin, geom.size.cast() * self.scale_factor);
+
+        let fill_style = zeno::Style::Fill(match path.fill_rule() {
+            crate::items::FillRule::Nonzero => zeno::Fill::NonZero,
+            crate::items::FillRule::Evenodd => zeno::Fill::EvenOdd,
+        });
+        let stroke_width = (path.stroke_width().cast() * self.scale_factor).get();
+        let stroke_style = zeno::Style::Stroke(zeno::Stroke::new(stroke_width));
+
+        for (brush, style) in [(path.fill(), fill_style), (path.stroke(), stroke_style)] {
+            if brush.is_transparent() {
+                continue;
+            }
+            if matches!(style, zeno::Style::Stroke(_)) && stroke_width <= 0. {
+                continue;
+            }
+            let Some(target) = path::path_bounds(&commands, style, physical_clip) else {
+                continue;
+            };
+            let mask = path::render_mask(&commands, style, target);
+
+            let (buffer, colorize, alpha) = if let Brush::SolidColor(color) = brush {
+                let color = self.alpha_color(color);
+                if color.alpha() == 0 {
+                    continue;
+                }
+                (
+                    SharedBufferData::AlphaMap { data: mask.into(), width: target.width() as u16 },
+                    color,
+                    color.alpha(),
+                )
+            } else {
+                let buffer = path::brush_with_mask(
+                    &brush,
+                    &mask,
+                    target,
+                    brush_rect,
+                    self.current_state.alpha,
+                );
+                (
+                    SharedBufferData::SharedImage(SharedImageBuffer::RGBA8Premultiplied(buffer)),
+                    Color::default(),
+                    255,
+                )
+            };
+
+            self.processor.process_shared_image_buffer(
+                target.transformed(self.rotation),
+                SharedBufferCommand {
+                    buffer,
+                    source_rect: PhysicalRect::from_size(target.size),
+                    extra: SceneTextureExtra {
+                        colorize,
+                        alpha,
+                        rotation: self.rotation.orientation,
+                        dx: Fixed::from_integer(1),
+                        dy: Fixed::from_integer(1),
+                        off_x: Fixed::from_integer(0),
+                        off_y: Fixed::from_integer(0),
+                    },
+                },
+            );
+        }
+    }
+
+    #[cfg(all(feature = "std", not(feature = "software-renderer-path")))]
     fn draw_path(&mut self, _path: Pin<&crate::items::Path>, _: &ItemRc, _size: LogicalSize) {
-        // TODO
+        // Path rendering requires the `software-renderer-path` feature
     }
 
     fn draw_box_shadow(
         &mut self,
-        _box_shadow: Pin<&crate::items::BoxShadow>,
-        _: &ItemRc,
-        _size: LogicalSize,
+        box_shadow: Pin<&crate::items::BoxShadow>,
+        self_rc: &ItemRc,
+        size: LogicalSize,
     ) {
-        // TODO
+        if self.current_state.transform.is_some() {
+            let blur = box_shadow.blur().max(Default::default());
+            let offset = LogicalVector::from_lengths(box_shadow.offset_x(), box_shadow.offset_y());
+            let area = LogicalRect::from(size).translate(offset).inflate(blur.get(), blur.get());
+            self.draw_transformed(area, |r| r.draw_box_shadow(box_shadow, self_rc, size));
+            return;
+        }
+        let color = self.alpha_color(box_shadow.color());
+        if color.alpha() == 0 {
+            return;
+        }
+        let blur = box_shadow.blur().max(Default::default());
+        let offset = LogicalVector::from_lengths(box_shadow.offset_x(), box_shadow.offset_y());
+        let shadow_rect = LogicalRect::from(size).translate(offset).inflate(blur.get(), blur.get());
+        if !self.should_draw(&shadow_rect) {
+            return;
+        }
+
+        let key = BoxShadowKey {
+            width: (size.width_length().cast() * self.scale_factor).get().round() as i32,
+            height: (size.height_length().cast() * self.scale_factor).get().round() as i32,
+            radius: (box_shadow.border_radius().cast() * self.scale_factor).get().round() as i32,
+            blur: (blur.cast() * self.scale_factor).get().round() as i32,
+        };
+        if key.width <= 0 || key.height <= 0 {
+            return;
+        }
+        // The shadow must fit in the physical coordinates, which are i16
+        let (Ok(shadow_width), Ok(shadow_height)) = (
+            i16::try_from(key.width.saturating_add(key.blur.saturating_mul(2))),
+            i16::try_from(key.height.saturating_add(key.blur.saturating_mul(2))),
+        ) else {
+            return;
+        };
+        let shadow_size = PhysicalSize::new(shadow_width, shadow_height);
+        let alpha_map =
+            self.software_renderer.box_shadow_cache.borrow_mut().get_or_insert_with(key, || {
+                draw_functions::box_shadow_alpha_map(key.width, key.height, key.radius, key.blur)
+                    .into()
+            });
+
+        let physical_clip =
+            (self.current_state.clip.translate(self.current_state.offset.to_vector()).cast()
+                * self.scale_factor)
+                .round()
+                .cast();
+        let shadow_origin = ((self.current_state.offset + shadow_rect.origin.to_vector()).cast()
+            * self.scale_factor)
+            .round()
+            .cast();
+        let shadow_geometry = PhysicalRect::new(shadow_origin, shadow_size);
+        let Some(clipped) = shadow_geometry.intersection(&physical_clip) else {
+            return;
+        };
+
+        self.processor.process_shared_image_buffer(
+            clipped.transformed(self.rotation),
+            SharedBufferCommand {
+                buffer: SharedBufferData::AlphaMap {
+                    data: alpha_map,
+                    width: shadow_size.width as u16,
+                },
+                source_rect: clipped.translate(-shadow_origin.to_vector()),
+                extra: SceneTextureExtra {
+                    colorize: color,
+                    // color already is mixed with global alpha
+                    alpha: color.alpha(),
+                    rotation: self.rotation.orientation,
+                    dx: Fixed::from_integer(1),
+                    dy: Fixed::from_integer(1),
+                    off_x: Fixed::from_integer(0),
+                    off_y: Fixed::from_integer(0),
+                },
+            },
+        );
+    }
+
+    #[cfg(feature = "std")]
+    fn visit_opacity(
+        &mut self,
+        opacity_item: Pin<&crate::items::Opacity>,
+        item_rc: &ItemRc,
+        _size: LogicalSize,
+    ) -> crate::items::RenderingResult {
+        let opacity = opacity_item.opacity();
+        if crate::items::Opacity::need_layer(item_rc, opacity) {
+            self.render_and_blend_layer(opacity, item_rc)
+        } else {
+            self.apply_opacity(opacity);
+            self.software_renderer.layer_cache.release(item_rc);
+            crate::items::RenderingResult::ContinueRenderingChildren
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn visit_layer(
+        &mut self,
+        layer_item: Pin<&crate::items::Layer>,
+        self_rc: &ItemRc,
+        _size: LogicalSize,
+    ) -> crate::items::RenderingResult {
+        if layer_item.cache_rendering_hint() {
+            self.render_and_blend_layer(1.0, self_rc)
+        } else {
+            self.software_renderer.layer_cache.release(self_rc);
+            crate::items::RenderingResult::ContinueRenderingChildren
+        }
+    }
+
+    fn visit_clip(
+        &mut self,
+        clip_item: Pin<&crate::items::Clip>,
+        item_rc: &ItemRc,
+        size: LogicalSize,
+    ) -> crate::items::RenderingResult {
+        if !clip_item.clip() {
+            return crate::items::RenderingResult::ContinueRenderingChildren;
+        }
+        let geometry = LogicalRect::from(size);
+        let radius = clip_item.logical_border_radius();
+        let border_width = clip_item.border_width();
+
+        if radius.is_zero() {
+            self.software_renderer.layer_cache.release(item_rc);
+            return if self.combine_clip(geometry, radius, border_width) {
+                crate::items::RenderingResult::ContinueRenderingChildren
+            } else {
+                crate::items::RenderingResult::ContinueRenderingWithoutChildren
+            };
+        }
+
+        // If clipping is enabled but the clip element is outside the visible range, then we don't
+        // need to bother doing anything, not even rendering the children.
+        if !self.current_state.clip.intersects(&geometry) {
+            return crate::items::RenderingResult::ContinueRenderingWithoutChildren;
+        }
+
+        // The children are rendered in a layer, whose pixels outside of the rounded
+        // rectangle are then made transparent.
+        let scale_factor = self.scale_factor;
+        let window = self.window;
+        let software_renderer = self.software_renderer;
+        let render = || {
+            // Read the geometry again, so that the layer depends on it
+            let geometry = LogicalRect::from(item_rc.geometry().size);
+            let radius = clip_item.logical_border_radius();
+            let border_width = clip_item.border_width();
+            let layer_size: PhysicalSize = (geometry.size.cast() * scale_factor).ceil().cast();
+            if layer_size.is_empty() {
+                return None;
+            }
+            let mut buffer = Self::render_children_to_buffer(
+                item_rc,
+                PhysicalRect::from_size(layer_size),
+                scale_factor,
+                window,
+                software_renderer,
+            );
+            let border_width = border_width.cast() * scale_factor;
+            let clip_rect =
+                (geometry.cast() * scale_factor).inflate(-border_width.get(), -border_width.get());
+            draw_functions::clip_to_rounded_rectangle(
+                &mut buffer,
+                layer_size.width as usize,
+                clip_rect,
+                &(radius.cast() * scale_factor).inner(border_width),
+            );
+            Some(SharedImageBuffer::RGBA8Premultiplied(SharedPixelBuffer::clone_from_slice(
+                bytemuck::cast_slice::<_, u8>(&buffer),
+                layer_size.width as u32,
+                layer_size.height as u32,
+            )))
+        };
+        let layer = software_renderer.layer_cache.get_or_update_cache_entry(item_rc, render);
+
+        if let Some(layer) = layer {
+            if self.current_state.transform.is_some() {
+                self.draw_transformed(geometry, |r| r.draw_layer_image(layer, 1.));
+            } else {
+                self.draw_layer_image(layer, 1.);
+            }
+        }
+        crate::items::RenderingResult::ContinueRenderingWithoutChildren
+    }
+
+    fn visit_rotate(
+        &mut self,
+        rotate_item: Pin<&crate::items::Rotate>,
+        self_rc: &ItemRc,
+        _size: LogicalSize,
+    ) -> crate::items::RenderingResult {
+        let origin = LogicalVector::from_lengths(
+            rotate_item.rotation_origin_x(),
+            rotate_item.rotation_origin_y(),
+        );
+        self.translate(origin);
+        self.rotate(rotate_item.rotation_angle());
+        self.translate(-origin);
+        if self.current_state.transform.is_none() {
+            self.software_renderer.rotated_layer_cache.release(self_rc);
+            return crate::items::RenderingResult::ContinueRenderingChildren;
+        }
+
+        // The whole sub-tree is rendered once in a layer, without the rotation, and that layer
+        // is then transformed into the window.
+        let clip = self.current_state.clip;
+        let children_rect = crate::properties::evaluate_no_tracking(|| {
+            crate::item_rendering::item_children_bounding_rect(
+                self_rc.item_tree(),
+                self_rc.index() as isize,
+                &clip,
+            )
+        });
+        let area: PhysicalRect = (children_rect.cast() * self.scale_factor).round_out().cast();
+        if area.is_empty() {
+            return crate::items::RenderingResult::ContinueRenderingWithoutChildren;
+        }
+
+        let scale_factor = self.scale_factor;
+        let window = self.window;
+        let software_renderer = self.software_renderer;
+        let render = || RotatedLayer {
+            area,
+            buffer: Self::render_children_to_buffer(
+                self_rc,
+                area,
+                scale_factor,
+                window,
+                software_renderer,
+            )
+            .into(),
+        };
+        let layer = {
+            let cache = &software_renderer.rotated_layer_cache;
+            let layer = cache.get_or_update_cache_entry(self_rc, &render);
+            // The visible part of the children changes with the clip
+            if layer.area.contains_rect(&area) {
+                layer
+            } else {
+                cache.release(self_rc);
+                cache.get_or_update_cache_entry(self_rc, &render)
+            }
+        };
+
+        self.draw_transformed_buffer(&layer.buffer, layer.area);
+        crate::items::RenderingResult::ContinueRenderingWithoutChildren
     }
 
     fn combine_clip(
         &mut self,
         other: LogicalRect,
         _radius: LogicalBorderRadius,
-        _border_width: LogicalLength,
+        border_width: LogicalLength,
     ) -> bool {
+        // The clip is inside the border. The radius is handled by `visit_clip`
+        let other = other.inflate(-border_width.get(), -border_width.get());
         match self.current_state.clip.intersection(&other) {
             Some(r) => {
                 self.current_state.clip = r;
@@ -2440,7 +3335,6 @@ fn combine_clip(
                 false
             }
         }
-        // TODO: handle radius and border
     }
 
     fn get_current_clip(&self) -> LogicalRect {
@@ -2456,8 +3350,40 @@ fn translation(&self) -> LogicalVector {
         self.current_state.offset.to_vector()
     }
 
-    fn rotate(&mut self, _angle_in_degrees: f32) {
-        todo!()
+    fn map_to_window(&self, rect: LogicalRect) -> LogicalRect {
+        let rect = rect.translate(self.current_state.offset.to_vector());
+        match &self.current_state.transform {
+            Some(t) => t.transform.outer_transformed_rect(&rect.cast()).cast(),
+            None => rect,
+        }
+    }
+
+    fn rotate(&mut self, angle_in_degrees: f32) {
+        if angle_in_degrees % 360. == 0. {
+            return;
+        }
+        let offset = self.current_state.offset.cast::<f32>().to_vector();
+        let rotation = euclid::Transform2D::translation(-offset.x, -offset.y)
+            .then_rotate(euclid::Angle::degrees(angle_in_degrees))
+            .then_translate(offset);
+        let window_clip = self.map_to_window(self.current_state.clip);
+        let transform = match &self.current_state.transform {
+            Some(t) => ItemTransform {
+                transform: rotation.then(&t.transform),
+                window_clip: t.window_clip.intersection(&window_clip).unwrap_or_default(),
+            },
+            None => ItemTransform { transform: rotation, window_clip },
+        };
+        // The clip in the rotated coordinates is the bounding box of the window clip. What
+        // is outside of the actual clip is removed when drawing the transformed items.
+        self.current_state.clip =
+            transform.transform.inverse().map_or_else(LogicalRect::default, |inverse| {
+                inverse
+                    .outer_transformed_rect(&transform.window_clip.cast())
+                    .cast()
+                    .translate(-self.current_state.offset.to_vector())
+            });
+        self.current_state.transform = Some(transform);
     }
 
     fn apply_opacity(&mut self, opacity: f32) {
@@ -2478,43 +3404,28 @@ fn scale_factor(&self) -> f32 {
 
     fn draw_cached_pixmap(
         &mut self,
-        _item: &ItemRc,
+        item: &ItemRc,
         update_fn: &dyn Fn(&mut dyn FnMut(u32, u32, &[u8])),
     ) {
-        // FIXME: actually cache the pixmap
-        update_fn(&mut |width, height, data| {
-            let img = SharedImageBuffer::RGBA8Premultiplied(SharedPixelBuffer::clone_from_slice(
-                data, width, height,
-            ));
-
-            let physical_clip = (self.current_state.clip.cast() * self.scale_factor).cast();
-            let source_rect = euclid::rect(0, 0, width as _, height as _);
-
-            if let Some(clipped_src) = source_rect.intersection(&physical_clip) {
-                let geometry = clipped_src
-                    .translate(
-                        (self.current_state.offset.cast() * self.scale_factor).to_vector().cast(),
-                    )
-                    .round_in();
-
-                self.processor.process_shared_image_buffer(
-                    geometry.cast().transformed(self.rotation),
-                    SharedBufferCommand {
-                        buffer: SharedBufferData::SharedImage(img),
-                        source_rect,
-                        extra: SceneTextureExtra {
-                            colorize: Default::default(),
-                            alpha: (self.current_state.alpha * 255.) as u8,
-                            rotation: self.rotation.orientation,
-                            dx: Fixed::from_integer(1),
-                            dy: Fixed::from_integer(1),
-                            off_x: Fixed::from_integer(clipped_src.min_x() as _),
-                            off_y: Fixed::from_integer(clipped_src.min_y() as _),
-                        },
-                    },
-                );
-            }
-        });
+        if self.current_state.transform.is_some() {
+            self.draw_transformed(LogicalRect::from(item.geometry().size), |r| {
+                r.draw_cached_pixmap(item, update_fn)
+            });
+            return;
+        }
+        let render = || {
+            let mut image = None;
+            update_fn(&mut |width, height, data| {
+                image = Some(SharedImageBuffer::RGBA8Premultiplied(
+                    SharedPixelBuffer::clone_from_slice(data, width, height),
+                ));
+            });
+            image
+        };
+        let image = self.software_renderer.layer_cache.get_or_update_cache_entry(item, render);
+        if let Some(image) = image {
+            self.draw_layer_image(image, 1.);
+        }
     }
 
     fn draw_string(&mut self, string: &str, color: Color) {
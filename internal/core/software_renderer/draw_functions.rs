@@ -7,12 +7,20 @@
 //! on the line buffer
 
 use super::{PhysicalLength, PhysicalRect};
-use crate::graphics::{PixelFormat, Rgb8Pixel};
-use crate::lengths::{PointLengths, SizeLengths};
+use crate::graphics::{
+    BorderRadius, ConicGradientBrush, GradientStop, LinearGradientBrush, PixelFormat,
+    RadialGradientBrush, Rgb8Pixel, Rgba8Pixel, SharedPixelBuffer,
+};
+use crate::lengths::{PhysicalPx, PointLengths, SizeLengths};
+use crate::properties::InterpolatedPropertyValue;
 use crate::software_renderer::fixed::Fixed;
-use crate::Color;
+use crate::{Brush, Color};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
 use derive_more::{Add, Mul, Sub};
 use integer_sqrt::IntegerSquareRoot;
+#[allow(unused)]
+use num_traits::Float;
 
 /// Draw one line of the texture in the line buffer
 ///
@@ -504,111 +512,332 @@ fn interpolate_color(
     }
 }
 
-pub(super) fn draw_gradient_line(
-    rect: &PhysicalRect,
+/// Draw one line of a rounded rectangle whose background or border is filled with a gradient
+pub(super) fn draw_gradient_rounded_rectangle_line(
+    span: &PhysicalRect,
     line: PhysicalLength,
-    g: &super::GradientCommand,
-    mut buffer: &mut [impl TargetPixel],
+    rr: &super::GradientRoundedRectangle,
+    line_buffer: &mut [impl TargetPixel],
     extra_left_clip: i16,
 ) {
-    let fill_col1 = g.flags & 0b010 != 0;
-    let fill_col2 = g.flags & 0b100 != 0;
-    let invert_slope = g.flags & 0b1 != 0;
-
-    let y = (line.get() - rect.min_y() + g.top_clip.get()) as i32;
-    let size_y = (rect.height() + g.top_clip.get() + g.bottom_clip.get()) as i32;
-    let start = g.start as i32;
-
-    let (mut color1, mut color2) = (g.color1, g.color2);
+    let size = rr.rect.size;
+    let background = BrushSampler::new(&rr.background, size, rr.angle_offset);
+    let border = BrushSampler::new(&rr.border, size, rr.angle_offset);
+    let border_width = rr.border_width;
+    let inner_size = euclid::size2(size.width - 2. * border_width, size.height - 2. * border_width);
+    let inner_radius = rr.radius.inner(euclid::Length::new(border_width));
 
-    if g.start == 0 {
-        let p = if invert_slope {
-            (255 - start) * y / size_y
+    let y = line.get() as f32 + 0.5 - rr.rect.origin.y;
+    let x0 = (span.origin.x + extra_left_clip) as f32 + 0.5 - rr.rect.origin.x;
+    for (i, pix) in line_buffer.iter_mut().enumerate() {
+        let p = euclid::point2(x0 + i as f32, y);
+        let outer = (0.5 - rounded_rect_distance(p, size, &rr.radius)).clamp(0., 1.);
+        if outer <= 0. {
+            continue;
+        }
+        let inner = if border_width > 0. {
+            let inner_p = euclid::point2(p.x - border_width, p.y - border_width);
+            (0.5 - rounded_rect_distance(inner_p, inner_size, &inner_radius)).clamp(0., 1.)
         } else {
-            start + (255 - start) * y / size_y
+            outer
         };
-        if (fill_col1 || p >= 0) && (fill_col2 || p < 255) {
-            let col = interpolate_color(p.clamp(0, 255) as u32, color1, color2);
-            TargetPixel::blend_slice(buffer, col);
+
+        let mut color = PremultipliedRgbaColor::premultiply_with_coverage(
+            background.color_at(p),
+            outer * rr.alpha,
+        );
+        if outer > inner {
+            let b = PremultipliedRgbaColor::premultiply_with_coverage(
+                border.color_at(p),
+                (outer - inner) * rr.alpha,
+            );
+            let inv = 255 - b.alpha as u16;
+            color = PremultipliedRgbaColor {
+                red: (color.red as u16 * inv / 255) as u8 + b.red,
+                green: (color.green as u16 * inv / 255) as u8 + b.green,
+                blue: (color.blue as u16 * inv / 255) as u8 + b.blue,
+                alpha: (color.alpha as u16 * inv / 255) as u8 + b.alpha,
+            };
+        }
+        if color.alpha > 0 {
+            pix.blend(color);
         }
-        return;
     }
+}
 
-    let size_x = (rect.width() + g.left_clip.get() + g.right_clip.get()) as i32;
-
-    let mut x = if invert_slope {
-        (y * size_x * (255 - start)) / (size_y * start)
-    } else {
-        (size_y - y) * size_x * (255 - start) / (size_y * start)
-    } + g.left_clip.get() as i32
-        + extra_left_clip as i32;
+/// Signed distance between `p` and the edge of a rounded rectangle of the given `size`
+/// whose top left corner is at the origin. The distance is negative inside the rectangle.
+fn rounded_rect_distance(
+    p: euclid::Point2D<f32, PhysicalPx>,
+    size: euclid::Size2D<f32, PhysicalPx>,
+    radius: &BorderRadius<f32, PhysicalPx>,
+) -> f32 {
+    let half_width = size.width / 2.;
+    let half_height = size.height / 2.;
+    let radius = match (p.x < half_width, p.y < half_height) {
+        (true, true) => radius.top_left,
+        (false, true) => radius.top_right,
+        (false, false) => radius.bottom_right,
+        (true, false) => radius.bottom_left,
+    }
+    .min(half_width)
+    .min(half_height)
+    .max(0.);
+    let dx = (p.x - half_width).abs() - (half_width - radius);
+    let dy = (p.y - half_height).abs() - (half_height - radius);
+    (dx.max(0.) * dx.max(0.) + dy.max(0.) * dy.max(0.)).sqrt() + dx.max(dy).min(0.) - radius
+}
 
-    let len = ((255 * size_x) / start) as usize;
+/// Computes the color of a brush for any point of a rectangle
+pub(super) enum BrushSampler<'a> {
+    Color(Color),
+    Linear {
+        gradient: &'a LinearGradientBrush,
+        start: euclid::Point2D<f32, PhysicalPx>,
+        delta: euclid::Vector2D<f32, PhysicalPx>,
+        len_sq: f32,
+    },
+    Radial {
+        gradient: &'a RadialGradientBrush,
+        center: euclid::Point2D<f32, PhysicalPx>,
+        /// The horizontal and vertical radius of the ending shape
+        radii: euclid::Size2D<f32, PhysicalPx>,
+    },
+    Conic {
+        gradient: &'a ConicGradientBrush,
+        center: euclid::Point2D<f32, PhysicalPx>,
+        /// The angle of the first stop, in degree
+        angle: f32,
+    },
+}
 
-    if x < 0 {
-        let l = (-x as usize).min(buffer.len());
-        if invert_slope {
-            if fill_col1 {
-                TargetPixel::blend_slice(&mut buffer[..l], g.color1);
+impl<'a> BrushSampler<'a> {
+    /// Prepare the sampling of `brush` over a rectangle of the given `size`.
+    ///
+    /// `angle_offset` is an angle in degree added to the angle of linear and conic gradients,
+    /// to account for the rotation of the screen.
+    pub(super) fn new(
+        brush: &'a Brush,
+        size: euclid::Size2D<f32, PhysicalPx>,
+        angle_offset: f32,
+    ) -> Self {
+        match brush {
+            Brush::LinearGradient(gradient) => {
+                let (start, end) = crate::graphics::line_for_angle(
+                    gradient.angle() + angle_offset,
+                    euclid::size2(size.width, size.height),
+                );
+                let start = euclid::point2(start.x, start.y);
+                let delta = euclid::vec2(end.x, end.y) - start.to_vector();
+                Self::Linear { gradient, start, delta, len_sq: delta.square_length() }
             }
-        } else if fill_col2 {
-            TargetPixel::blend_slice(&mut buffer[..l], g.color2);
+            Brush::RadialGradient(gradient) => {
+                let rotated = (angle_offset / 90.).round() as i32 % 2 != 0;
+                let unrotated_size =
+                    if rotated { euclid::size2(size.height, size.width) } else { size.cast_unit() };
+                let radii = gradient.radii(unrotated_size);
+                let radii = if rotated { euclid::size2(radii.height, radii.width) } else { radii };
+                Self::Radial {
+                    gradient,
+                    center: rotated_center(gradient.center(), size, angle_offset),
+                    radii: radii.cast_unit(),
+                }
+            }
+            Brush::ConicGradient(gradient) => Self::Conic {
+                gradient,
+                center: rotated_center(gradient.center(), size, angle_offset),
+                angle: gradient.angle() + angle_offset,
+            },
+            _ => Self::Color(brush.color()),
         }
-        buffer = &mut buffer[l..];
-        x = 0;
     }
 
-    if buffer.len() + x as usize > len {
-        let l = len.saturating_sub(x as usize);
-        if invert_slope {
-            if fill_col2 {
-                TargetPixel::blend_slice(&mut buffer[l..], g.color2);
+    /// The color at position `p`, relative to the top left corner of the rectangle
+    pub(super) fn color_at(&self, p: euclid::Point2D<f32, PhysicalPx>) -> Color {
+        match self {
+            Self::Color(color) => *color,
+            Self::Linear { gradient, start, delta, len_sq } => {
+                let pos = if *len_sq > 0. { (p - *start).dot(*delta) / len_sq } else { 0. };
+                color_for_position(gradient.stops(), pos)
+            }
+            Self::Radial { gradient, center, radii } => {
+                let pos = if radii.width > 0. && radii.height > 0. {
+                    let d = p - *center;
+                    euclid::vec2::<f32, PhysicalPx>(d.x / radii.width, d.y / radii.height).length()
+                } else {
+                    0.
+                };
+                color_for_position(gradient.stops(), pos)
+            }
+            Self::Conic { gradient, center, angle } => {
+                let d = p - *center;
+                // 0 degree is at the top, and the angle grows clockwise
+                let pos = ((d.x.atan2(-d.y).to_degrees() - angle) / 360.).fract();
+                color_for_position(gradient.stops(), if pos < 0. { pos + 1. } else { pos })
             }
-        } else if fill_col1 {
-            TargetPixel::blend_slice(&mut buffer[l..], g.color1);
         }
-        buffer = &mut buffer[..l];
     }
+}
 
-    if buffer.is_empty() {
-        return;
+/// Returns the position within the rectangle of the given `size` of a gradient center expressed as a
+/// ratio of the unrotated rectangle, after rotating it with the screen by `angle_offset` degrees.
+fn rotated_center(
+    center: euclid::default::Point2D<f32>,
+    size: euclid::Size2D<f32, PhysicalPx>,
+    angle_offset: f32,
+) -> euclid::Point2D<f32, PhysicalPx> {
+    let (sin, cos) = angle_offset.to_radians().sin_cos();
+    let (width, height) = if (angle_offset / 90.).round() as i32 % 2 == 0 {
+        (size.width, size.height)
+    } else {
+        (size.height, size.width)
+    };
+    let dx = (center.x - 0.5) * width;
+    let dy = (center.y - 0.5) * height;
+    euclid::point2(size.width / 2. + dx * cos - dy * sin, size.height / 2. + dx * sin + dy * cos)
+}
+
+/// Returns the color of a gradient with the given stops at the `pos` position (between 0 and 1)
+fn color_for_position<'a>(mut stops: impl Iterator<Item = &'a GradientStop>, pos: f32) -> Color {
+    let Some(mut prev) = stops.next() else { return Color::default() };
+    if pos <= prev.position {
+        return prev.color;
+    }
+    for next in stops {
+        if pos <= next.position {
+            let range = next.position - prev.position;
+            return if range > 0. {
+                prev.color.interpolate(&next.color, (pos - prev.position) / range)
+            } else {
+                next.color
+            };
+        }
+        prev = next;
     }
+    prev.color
+}
+
+/// Compute the alpha map of a box shadow.
+///
+/// The returned buffer has a size of `(width + 2 * blur) * (height + 2 * blur)` and contains the
+/// coverage of a rounded rectangle of size `width`x`height` in its center, blurred by `blur` pixels.
+pub(super) fn box_shadow_alpha_map(width: i32, height: i32, radius: i32, blur: i32) -> Vec<u8> {
+    let buffer_width = (width + 2 * blur) as usize;
+    let buffer_height = (height + 2 * blur) as usize;
+    let size = euclid::size2(width as f32, height as f32);
+    let radius = BorderRadius::new_uniform(radius as f32);
+    // The blur is the same as the CSS one, where the standard deviation is half the blur radius
+    let sigma = blur as f32 / 2.;
 
-    if !invert_slope {
-        core::mem::swap(&mut color1, &mut color2);
+    let mut data = Vec::with_capacity(buffer_width * buffer_height);
+    for y in 0..buffer_height {
+        for x in 0..buffer_width {
+            let p = euclid::point2(x as f32 + 0.5 - blur as f32, y as f32 + 0.5 - blur as f32);
+            let distance = rounded_rect_distance(p, size, &radius);
+            let coverage = if sigma > 0. {
+                0.5 * erfc(distance / (sigma * core::f32::consts::SQRT_2))
+            } else {
+                (0.5 - distance).clamp(0., 1.)
+            };
+            data.push((coverage * 255.).round() as u8);
+        }
     }
+    data
+}
 
-    let dr = (((color2.red as i32 - color1.red as i32) * start) << 15) / (255 * size_x);
-    let dg = (((color2.green as i32 - color1.green as i32) * start) << 15) / (255 * size_x);
-    let db = (((color2.blue as i32 - color1.blue as i32) * start) << 15) / (255 * size_x);
-    let da = (((color2.alpha as i32 - color1.alpha as i32) * start) << 15) / (255 * size_x);
+/// Clip the content of `buffer`, whose lines are `stride` pixels long, to a rounded rectangle.
+///
+/// The pixels are multiplied line by line by their coverage of the rounded rectangle `rect`, so
+/// that everything outside of it becomes transparent and the corners are anti-aliased.
+pub(super) fn clip_to_rounded_rectangle(
+    buffer: &mut [PremultipliedRgbaColor],
+    stride: usize,
+    rect: euclid::Rect<f32, PhysicalPx>,
+    radius: &BorderRadius<f32, PhysicalPx>,
+) {
+    for (y, line) in buffer.chunks_mut(stride).enumerate() {
+        for (x, pix) in line.iter_mut().enumerate() {
+            let p = euclid::point2(x as f32 + 0.5 - rect.origin.x, y as f32 + 0.5 - rect.origin.y);
+            let coverage = (0.5 - rounded_rect_distance(p, rect.size, radius)).clamp(0., 1.);
+            if coverage >= 1. {
+                continue;
+            }
+            let coverage = (coverage * 255.).round() as u16;
+            *pix = PremultipliedRgbaColor {
+                red: (pix.red as u16 * coverage / 255) as u8,
+                green: (pix.green as u16 * coverage / 255) as u8,
+                blue: (pix.blue as u16 * coverage / 255) as u8,
+                alpha: (pix.alpha as u16 * coverage / 255) as u8,
+            };
+        }
+    }
+}
 
-    let mut r = ((color1.red as u32) << 15).wrapping_add((x * dr) as _);
-    let mut g = ((color1.green as u32) << 15).wrapping_add((x * dg) as _);
-    let mut b = ((color1.blue as u32) << 15).wrapping_add((x * db) as _);
-    let mut a = ((color1.alpha as u32) << 15).wrapping_add((x * da) as _);
+/// Resample `source`, a buffer of size `source_size`, into a buffer that covers the `target`
+/// rectangle.
+///
+/// `target_to_source` maps the coordinates of the target to the coordinates in the source.
+/// The pixels are interpolated bilinearly, and the pixels outside of the source are transparent.
+pub(super) fn transform_buffer(
+    source: &[PremultipliedRgbaColor],
+    source_size: super::PhysicalSize,
+    target: PhysicalRect,
+    target_to_source: &euclid::Transform2D<f32, PhysicalPx, PhysicalPx>,
+) -> SharedPixelBuffer<Rgba8Pixel> {
+    let width = source_size.width as i32;
+    let height = source_size.height as i32;
+    let pixel = |x: i32, y: i32| {
+        if x >= 0 && y >= 0 && x < width && y < height {
+            source[(y * width + x) as usize]
+        } else {
+            PremultipliedRgbaColor::default()
+        }
+    };
 
-    if color1.alpha == 255 && color2.alpha == 255 {
-        buffer.fill_with(|| {
-            let pix = TargetPixel::from_rgb((r >> 15) as u8, (g >> 15) as u8, (b >> 15) as u8);
-            r = r.wrapping_add(dr as _);
-            g = g.wrapping_add(dg as _);
-            b = b.wrapping_add(db as _);
-            pix
-        })
-    } else {
-        for pix in buffer {
-            pix.blend(PremultipliedRgbaColor {
-                red: (r >> 15) as u8,
-                green: (g >> 15) as u8,
-                blue: (b >> 15) as u8,
-                alpha: (a >> 15) as u8,
-            });
-            r = r.wrapping_add(dr as _);
-            g = g.wrapping_add(dg as _);
-            b = b.wrapping_add(db as _);
-            a = a.wrapping_add(da as _);
+    let mut buffer =
+        SharedPixelBuffer::<Rgba8Pixel>::new(target.width() as _, target.height() as _);
+    let target_width = target.width() as usize;
+    for (i, pix) in buffer.make_mut_slice().iter_mut().enumerate() {
+        let p = target_to_source.transform_point(euclid::point2(
+            (target.min_x() as usize + i % target_width) as f32 + 0.5,
+            (target.min_y() as usize + i / target_width) as f32 + 0.5,
+        ));
+        let (x, y) = (p.x - 0.5, p.y - 0.5);
+        let (x0, y0) = (x.floor(), y.floor());
+        if x0 < -1. || y0 < -1. || x0 >= width as f32 || y0 >= height as f32 {
+            continue;
         }
+        let (fx, fy) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as i32, y0 as i32);
+        let [c00, c10, c01, c11] =
+            [pixel(x0, y0), pixel(x0 + 1, y0), pixel(x0, y0 + 1), pixel(x0 + 1, y0 + 1)];
+        let mix = |a: u8, b: u8, c: u8, d: u8| {
+            let top = a as f32 + (b as f32 - a as f32) * fx;
+            let bottom = c as f32 + (d as f32 - c as f32) * fx;
+            (top + (bottom - top) * fy).round() as u8
+        };
+        *pix = Rgba8Pixel {
+            r: mix(c00.red, c10.red, c01.red, c11.red),
+            g: mix(c00.green, c10.green, c01.green, c11.green),
+            b: mix(c00.blue, c10.blue, c01.blue, c11.blue),
+            a: mix(c00.alpha, c10.alpha, c01.alpha, c11.alpha),
+        };
+    }
+    buffer
+}
+
+/// Approximation of the complementary error function (Abramowitz and Stegun 7.1.26)
+fn erfc(x: f32) -> f32 {
+    let z = x.abs();
+    let t = 1. / (1. + 0.3275911 * z);
+    let poly = t
+        * (0.254_829_6
+            + t * (-0.284_496_74 + t * (1.421_413_8 + t * (-1.453_152_1 + t * 1.061_405_4))));
+    let r = poly * (-z * z).exp();
+    if x >= 0. {
+        r
+    } else {
+        2. - r
     }
 }
 
@@ -638,6 +867,18 @@ fn from(col: Color) -> Self {
 }
 
 impl PremultipliedRgbaColor {
+    /// Convert a non premultiplied color to a premultiplied one, with its alpha multiplied by
+    /// `coverage` (between 0 and 1)
+    fn premultiply_with_coverage(col: Color, coverage: f32) -> Self {
+        let a = (col.alpha() as f32 * coverage) as u16;
+        Self {
+            alpha: a as u8,
+            red: (col.red() as u16 * a / 255) as u8,
+            green: (col.green() as u16 * a / 255) as u8,
+            blue: (col.blue() as u16 * a / 255) as u8,
+        }
+    }
+
     /// Convert a non premultiplied color to a premultiplied one
     fn premultiply(col: Color) -> Self {
         let a = col.alpha() as u16;
@@ -774,6 +1015,371 @@ fn from(p: Rgb565Pixel) -> Self {
     }
 }
 
+/// A 16bit pixel that has 5 blue bits, 6 green bits and 5 red bits
+///
+/// This is the same as [`Rgb565Pixel`], with the red and blue components swapped.
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Bgr565Pixel(pub u16);
+
+impl Bgr565Pixel {
+    /// Swap the first and last 5 bits, converting between RGB565 and BGR565
+    fn swap_red_blue(v: u16) -> u16 {
+        (v << 11) | (v & Rgb565Pixel::G_MASK) | (v >> 11)
+    }
+}
+
+impl TargetPixel for Bgr565Pixel {
+    fn blend(&mut self, color: PremultipliedRgbaColor) {
+        let mut rgb = Rgb565Pixel(Self::swap_red_blue(self.0));
+        rgb.blend(color);
+        self.0 = Self::swap_red_blue(rgb.0);
+    }
+
+    fn from_rgb(r: u8, g: u8, b: u8) -> Self {
+        Self(Self::swap_red_blue(Rgb565Pixel::from_rgb(r, g, b).0))
+    }
+}
+
+/// A [`Rgb565Pixel`] whose two bytes are swapped.
+///
+/// Many displays connected through SPI expect the pixels in big endian order: on a little
+/// endian CPU, this type can be used to render in a buffer that can be sent as is.
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Rgb565SwappedPixel(pub u16);
+
+impl TargetPixel for Rgb565SwappedPixel {
+    fn blend(&mut self, color: PremultipliedRgbaColor) {
+        let mut rgb = Rgb565Pixel(self.0.swap_bytes());
+        rgb.blend(color);
+        self.0 = rgb.0.swap_bytes();
+    }
+
+    fn from_rgb(r: u8, g: u8, b: u8) -> Self {
+        Self(Rgb565Pixel::from_rgb(r, g, b).0.swap_bytes())
+    }
+}
+
+/// A 8bit grayscale pixel, where 0 is black and 255 is white
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Gray8Pixel(pub u8);
+
+impl Gray8Pixel {
+    /// Compute the luma of the color, with the ITU-R BT.601 coefficients
+    fn luma(r: u8, g: u8, b: u8) -> u8 {
+        ((r as u32 * 77 + g as u32 * 150 + b as u32 * 29) >> 8) as u8
+    }
+}
+
+impl TargetPixel for Gray8Pixel {
+    fn blend(&mut self, color: PremultipliedRgbaColor) {
+        let a = (u8::MAX - color.alpha) as u16;
+        self.0 = (self.0 as u16 * a / 255) as u8 + Self::luma(color.red, color.green, color.blue);
+    }
+
+    fn from_rgb(r: u8, g: u8, b: u8) -> Self {
+        Self(Self::luma(r, g, b))
+    }
+}
+
+/// A 32bit pixel with 8 bits for the alpha, red, green and blue components, in that order from
+/// the most significant byte. The color components are not pre-multiplied by the alpha.
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Argb8888Pixel(pub u32);
+
+impl From<Argb8888Pixel> for PremultipliedRgbaColor {
+    fn from(p: Argb8888Pixel) -> Self {
+        let [alpha, red, green, blue] = p.0.to_be_bytes();
+        Self::premultiply(Color::from_argb_u8(alpha, red, green, blue))
+    }
+}
+
+impl From<PremultipliedRgbaColor> for Argb8888Pixel {
+    fn from(p: PremultipliedRgbaColor) -> Self {
+        if p.alpha == 0 {
+            return Self(0);
+        }
+        let unpremultiply = |c: u8| (c as u16 * 255 / p.alpha as u16).min(255) as u8;
+        Self(u32::from_be_bytes([
+            p.alpha,
+            unpremultiply(p.red),
+            unpremultiply(p.green),
+            unpremultiply(p.blue),
+        ]))
+    }
+}
+
+impl TargetPixel for Argb8888Pixel {
+    fn blend(&mut self, color: PremultipliedRgbaColor) {
+        let mut p = PremultipliedRgbaColor::from(*self);
+        p.blend(color);
+        *self = p.into();
+    }
+
+    fn from_rgb(r: u8, g: u8, b: u8) -> Self {
+        Self(u32::from_be_bytes([0xff, r, g, b]))
+    }
+
+    fn background() -> Self {
+        Self(0)
+    }
+}
+
+/// The thresholds of the ordered dithering, between 0 and 15
+const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// A pixel format into which lines rendered with 8 bits per color component are converted with
+/// ordered dithering, to avoid the banding of the gradients.
+///
+/// It is implemented for the 16 bits formats and for [`Gray8Pixel`], and is used by
+/// [`super::DitheredLineBuffer`] and [`super::SoftwareRenderer::render_dithered`].
+pub trait DitheredPixel: Copy {
+    /// Convert `line` into `output`, which has the same length.
+    ///
+    /// `x` and `y` are the position of the first pixel of `line` in the frame buffer. The
+    /// dithering pattern only depends on the position of the pixels, so lines can be converted
+    /// independently.
+    fn dither_line(line: &[Rgb8Pixel], x: usize, y: usize, output: &mut [Self]);
+}
+
+/// Convert a pixel to RGB565, adding the `threshold` of the dithering pattern before truncating
+fn dither_to_rgb565(p: Rgb8Pixel, threshold: u8) -> Rgb565Pixel {
+    // 5 bits for red and blue loose 3 bits, 6 bits for green loose 2 bits
+    let add = |c: u8, shift: u8| c.saturating_add(threshold >> (4 - shift));
+    Rgb565Pixel::from_rgb(add(p.r, 3), add(p.g, 2), add(p.b, 3))
+}
+
+impl DitheredPixel for Rgb565Pixel {
+    fn dither_line(line: &[Rgb8Pixel], x: usize, y: usize, output: &mut [Self]) {
+        let thresholds = &BAYER_4X4[y % 4];
+        for ((x, pix), out) in (x..).zip(line).zip(output) {
+            *out = dither_to_rgb565(*pix, thresholds[x % 4]);
+        }
+    }
+}
+
+impl DitheredPixel for Bgr565Pixel {
+    fn dither_line(line: &[Rgb8Pixel], x: usize, y: usize, output: &mut [Self]) {
+        let thresholds = &BAYER_4X4[y % 4];
+        for ((x, pix), out) in (x..).zip(line).zip(output) {
+            *out = Self(Self::swap_red_blue(dither_to_rgb565(*pix, thresholds[x % 4]).0));
+        }
+    }
+}
+
+impl DitheredPixel for Rgb565SwappedPixel {
+    fn dither_line(line: &[Rgb8Pixel], x: usize, y: usize, output: &mut [Self]) {
+        let thresholds = &BAYER_4X4[y % 4];
+        for ((x, pix), out) in (x..).zip(line).zip(output) {
+            *out = Self(dither_to_rgb565(*pix, thresholds[x % 4]).0.swap_bytes());
+        }
+    }
+}
+
+impl DitheredPixel for Gray8Pixel {
+    fn dither_line(line: &[Rgb8Pixel], x: usize, y: usize, output: &mut [Self]) {
+        let thresholds = &BAYER_4X4[y % 4];
+        for ((x, pix), out) in (x..).zip(line).zip(output) {
+            // The fractional part of the luma decides whether it is rounded up
+            let luma = pix.r as u32 * 77 + pix.g as u32 * 150 + pix.b as u32 * 29;
+            *out = Self(((luma + thresholds[x % 4] as u32 * 16) >> 8).min(255) as u8);
+        }
+    }
+}
+
+/// Convert a line of gray pixels to a 1bit per pixel line, using ordered dithering.
+///
+/// `output` is the whole line of the monochrome buffer, where the most significant bit of each byte
+/// is the left most pixel, and a bit set means white. `x` and `y` are the position of the first
+/// pixel of `line` in that buffer. The dithering pattern only depends on the position of the
+/// pixels, so lines can be converted independently, as with [`super::SoftwareRenderer::render_by_line`].
+pub fn dither_to_monochrome(line: &[Gray8Pixel], x: usize, y: usize, output: &mut [u8]) {
+    let thresholds = &BAYER_4X4[y % 4];
+    for (x, pix) in (x..).zip(line) {
+        let mask = 0x80 >> (x % 8);
+        if pix.0 > thresholds[x % 4] * 16 + 7 {
+            output[x / 8] |= mask;
+        } else {
+            output[x / 8] &= !mask;
+        }
+    }
+}
+
+#[test]
+fn gradient_color_for_position() {
+    let stops = [
+        GradientStop { position: 0.25, color: Color::from_rgb_u8(255, 0, 0) },
+        GradientStop { position: 0.75, color: Color::from_rgb_u8(0, 0, 255) },
+    ];
+    assert_eq!(color_for_position(stops.iter(), 0.), Color::from_rgb_u8(255, 0, 0));
+    assert_eq!(color_for_position(stops.iter(), 1.), Color::from_rgb_u8(0, 0, 255));
+    let middle = color_for_position(stops.iter(), 0.5);
+    assert!(middle.red() > 100 && middle.red() < 155);
+    assert!(middle.blue() > 100 && middle.blue() < 155);
+    assert_eq!(color_for_position([].iter(), 0.5), Color::default());
+}
+
+#[test]
+fn gradient_rounded_rectangle_line() {
+    let brush = Brush::LinearGradient(LinearGradientBrush::new(
+        90.,
+        [
+            GradientStop { position: 0., color: Color::from_rgb_u8(255, 0, 0) },
+            GradientStop { position: 1., color: Color::from_rgb_u8(0, 0, 255) },
+        ],
+    ));
+    let rr = super::GradientRoundedRectangle {
+        rect: euclid::rect(0., 0., 10., 4.),
+        radius: BorderRadius::default(),
+        border_width: 0.,
+        background: brush,
+        border: Brush::default(),
+        angle_offset: 0.,
+        alpha: 1.,
+    };
+    let mut buffer = [PremultipliedRgbaColor::default(); 10];
+    draw_gradient_rounded_rectangle_line(
+        &euclid::rect(0, 0, 10, 4),
+        PhysicalLength::new(1),
+        &rr,
+        &mut buffer,
+        0,
+    );
+    assert!(buffer.iter().all(|p| p.alpha == 255));
+    assert!(buffer[0].red > 200 && buffer[0].blue < 50);
+    assert!(buffer[9].blue > 200 && buffer[9].red < 50);
+}
+
+#[test]
+fn conic_gradient_sampler() {
+    let red = Color::from_rgb_u8(255, 0, 0);
+    let blue = Color::from_rgb_u8(0, 0, 255);
+    // Red on the first half turn, blue on the second one
+    let stops = [
+        GradientStop { position: 0., color: red },
+        GradientStop { position: 0.5, color: red },
+        GradientStop { position: 0.5, color: blue },
+        GradientStop { position: 1., color: blue },
+    ];
+    let brush = Brush::ConicGradient(ConicGradientBrush::new(0., euclid::point2(0.5, 0.5), stops));
+    let sampler = BrushSampler::new(&brush, euclid::size2(10., 10.), 0.);
+    assert_eq!(sampler.color_at(euclid::point2(9., 5.)), red);
+    assert_eq!(sampler.color_at(euclid::point2(1., 5.)), blue);
+
+    // Starting at the bottom swaps the sides
+    let brush =
+        Brush::ConicGradient(ConicGradientBrush::new(180., euclid::point2(0.5, 0.5), stops));
+    let sampler = BrushSampler::new(&brush, euclid::size2(10., 10.), 0.);
+    assert_eq!(sampler.color_at(euclid::point2(9., 5.)), blue);
+    assert_eq!(sampler.color_at(euclid::point2(1., 5.)), red);
+
+    // With the center in the top right corner, the whole rectangle is on the second half
+    let brush = Brush::ConicGradient(ConicGradientBrush::new(0., euclid::point2(1., 0.), stops));
+    let sampler = BrushSampler::new(&brush, euclid::size2(10., 10.), 0.);
+    assert_eq!(sampler.color_at(euclid::point2(5., 5.)), blue);
+    // The center follows the rotation of the screen: the top right corner moves to the bottom right
+    let sampler = BrushSampler::new(&brush, euclid::size2(10., 10.), 90.);
+    assert_eq!(sampler.color_at(euclid::point2(5., 5.)), blue);
+}
+
+#[test]
+fn radial_gradient_sampler() {
+    let red = Color::from_rgb_u8(255, 0, 0);
+    let blue = Color::from_rgb_u8(0, 0, 255);
+    let stops = [
+        GradientStop { position: 0., color: red },
+        GradientStop { position: 0.5, color: red },
+        GradientStop { position: 0.5, color: blue },
+        GradientStop { position: 1., color: blue },
+    ];
+    // An ellipse touching the sides of a 20x10 rectangle
+    let brush = Brush::RadialGradient(RadialGradientBrush::new(
+        crate::graphics::RadialGradientShape::Ellipse,
+        crate::graphics::RadialGradientExtent::ClosestSide,
+        euclid::point2(0.5, 0.5),
+        stops,
+    ));
+    let sampler = BrushSampler::new(&brush, euclid::size2(20., 10.), 0.);
+    assert_eq!(sampler.color_at(euclid::point2(14., 5.)), red);
+    assert_eq!(sampler.color_at(euclid::point2(16., 5.)), blue);
+    assert_eq!(sampler.color_at(euclid::point2(10., 7.)), red);
+    assert_eq!(sampler.color_at(euclid::point2(10., 8.)), blue);
+
+    // A circle centered in the top left corner
+    let brush = Brush::RadialGradient(RadialGradientBrush::new(
+        crate::graphics::RadialGradientShape::Circle,
+        crate::graphics::RadialGradientExtent::FarthestSide,
+        euclid::point2(0., 0.),
+        stops,
+    ));
+    let sampler = BrushSampler::new(&brush, euclid::size2(20., 10.), 0.);
+    assert_eq!(sampler.color_at(euclid::point2(9., 0.)), red);
+    assert_eq!(sampler.color_at(euclid::point2(0., 11.)), blue);
+    // Rotating the screen moves the top left corner to the top right
+    let sampler = BrushSampler::new(&brush, euclid::size2(10., 20.), 90.);
+    assert_eq!(sampler.color_at(euclid::point2(10., 9.)), red);
+    assert_eq!(sampler.color_at(euclid::point2(0., 5.)), blue);
+}
+
+#[test]
+fn transform_buffer_translation() {
+    let red = PremultipliedRgbaColor { red: 255, green: 0, blue: 0, alpha: 255 };
+    let source = [red; 4];
+    // The 2x2 source is moved to (10, 10) and is drawn in a 4x4 target at (9, 9)
+    let buffer = transform_buffer(
+        &source,
+        euclid::size2(2, 2),
+        euclid::rect(9, 9, 4, 4),
+        &euclid::Transform2D::translation(-10., -10.),
+    );
+    let pixels = buffer.as_slice();
+    assert_eq!(pixels[0].a, 0);
+    assert_eq!(pixels[5], Rgba8Pixel { r: 255, g: 0, b: 0, a: 255 });
+    assert_eq!(pixels[10], Rgba8Pixel { r: 255, g: 0, b: 0, a: 255 });
+    assert_eq!(pixels[15].a, 0);
+}
+
+#[test]
+fn box_shadow_alpha_map_coverage() {
+    let data = box_shadow_alpha_map(10, 6, 0, 4);
+    assert_eq!(data.len(), 18 * 14);
+    // mostly covered in the center, and almost transparent in the corners
+    assert!(data[7 * 18 + 9] > 200);
+    assert!(data[0] < 10);
+    // symmetric
+    assert_eq!(data[3 * 18 + 2], data[3 * 18 + 15]);
+
+    let data = box_shadow_alpha_map(4, 4, 0, 0);
+    assert!(data.iter().all(|a| *a == 255));
+}
+
+#[test]
+fn rounded_clip_coverage() {
+    let red = PremultipliedRgbaColor { red: 255, green: 0, blue: 0, alpha: 255 };
+    let mut buffer = [red; 12 * 12];
+    clip_to_rounded_rectangle(
+        &mut buffer,
+        12,
+        euclid::rect(1., 1., 10., 10.),
+        &BorderRadius::new_uniform(4.),
+    );
+    // outside of the rectangle
+    assert_eq!(buffer[0].alpha, 0);
+    assert_eq!(buffer[5].alpha, 0);
+    // the corner is cut, but not the middle of the edges
+    assert_eq!(buffer[12 + 1].alpha, 0);
+    assert_eq!(buffer[12 + 5].alpha, 255);
+    assert_eq!(buffer[5 * 12 + 1].alpha, 255);
+    assert_eq!((buffer[6 * 12 + 6].red, buffer[6 * 12 + 6].alpha), (255, 255));
+    // the curve is anti-aliased
+    let edge = buffer[2 * 12 + 2];
+    assert!(edge.alpha > 0 && edge.alpha < 255);
+    assert_eq!(edge.red, edge.alpha);
+}
+
 #[test]
 fn rgb565() {
     let pix565 = Rgb565Pixel::from_rgb(0xff, 0x25, 0);
@@ -784,3 +1390,105 @@ fn rgb565() {
     let pix888: Rgb8Pixel = pix565.into();
     assert_eq!(pix565, pix888.into());
 }
+
+#[test]
+fn additional_pixel_formats() {
+    let half_red = PremultipliedRgbaColor { red: 128, green: 0, blue: 0, alpha: 128 };
+
+    let mut bgr = Bgr565Pixel::from_rgb(0, 0, 255);
+    assert_eq!(bgr, Bgr565Pixel(0b11111_000000_00000));
+    bgr.blend(half_red);
+    let mut rgb = Rgb565Pixel::from_rgb(0, 0, 255);
+    rgb.blend(half_red);
+    assert_eq!(bgr.0, Bgr565Pixel::swap_red_blue(rgb.0));
+
+    let mut swapped = Rgb565SwappedPixel::from_rgb(0, 0, 255);
+    assert_eq!(swapped.0, Rgb565Pixel::from_rgb(0, 0, 255).0.swap_bytes());
+    swapped.blend(half_red);
+    assert_eq!(swapped.0, rgb.0.swap_bytes());
+
+    assert_eq!(Gray8Pixel::from_rgb(255, 255, 255), Gray8Pixel(255));
+    let mut gray = Gray8Pixel(255);
+    gray.blend(PremultipliedRgbaColor { red: 0, green: 0, blue: 0, alpha: 128 });
+    assert_eq!(gray, Gray8Pixel(127));
+
+    let mut argb = Argb8888Pixel::background();
+    argb.blend(half_red);
+    assert_eq!(argb, Argb8888Pixel(0x80ff0000));
+    argb.blend(PremultipliedRgbaColor { red: 0, green: 0, blue: 255, alpha: 255 });
+    assert_eq!(argb, Argb8888Pixel(0xff0000ff));
+}
+
+#[test]
+fn monochrome_dithering() {
+    let mut output = [0xaa_u8; 2];
+    let line = [Gray8Pixel(255), Gray8Pixel(0), Gray8Pixel(255), Gray8Pixel(255)];
+    dither_to_monochrome(&line, 6, 0, &mut output);
+    // bits outside of the line are kept
+    assert_eq!(output, [0b1010_1010, 0b1110_1010]);
+
+    // a mid gray is rendered as a pattern with as many white as black pixels
+    let line = [Gray8Pixel(128); 16];
+    let mut white = 0;
+    for y in 0..4 {
+        let mut output = [0_u8; 2];
+        dither_to_monochrome(&line, 0, y, &mut output);
+        white += output[0].count_ones() + output[1].count_ones();
+    }
+    assert_eq!(white, 32);
+}
+
+#[test]
+fn rgb565_dithering() {
+    // A color between two RGB565 values is rendered as a mix of both
+    let line = [Rgb8Pixel { r: 12, g: 2, b: 0 }; 16];
+    let mut red = 0;
+    let mut green = 0;
+    for y in 0..4 {
+        let mut output = [Rgb565Pixel(0); 16];
+        Rgb565Pixel::dither_line(&line, 0, y, &mut output);
+        for p in output {
+            red += p.red() as u32;
+            green += p.green() as u32;
+            assert_eq!(p.blue(), 0);
+        }
+    }
+    assert_eq!(red, 12 * 64);
+    assert_eq!(green, 2 * 64);
+
+    // Exact colors are not changed
+    let line = [Rgb8Pixel { r: 248, g: 252, b: 8 }; 4];
+    let mut output = [Rgb565Pixel(0); 4];
+    Rgb565Pixel::dither_line(&line, 3, 1, &mut output);
+    assert!(output.iter().all(|p| *p == Rgb565Pixel::from_rgb(248, 252, 8)));
+    let mut bgr = [Bgr565Pixel(0); 4];
+    Bgr565Pixel::dither_line(&line, 3, 1, &mut bgr);
+    assert!(bgr.iter().all(|p| *p == Bgr565Pixel::from_rgb(248, 252, 8)));
+    let mut swapped = [Rgb565SwappedPixel(0); 4];
+    Rgb565SwappedPixel::dither_line(&line, 3, 1, &mut swapped);
+    assert!(swapped.iter().all(|p| *p == Rgb565SwappedPixel::from_rgb(248, 252, 8)));
+
+    // White stays white
+    let line = [Rgb8Pixel { r: 255, g: 255, b: 255 }; 4];
+    Rgb565Pixel::dither_line(&line, 0, 3, &mut output);
+    assert!(output.iter().all(|p| p.0 == 0xffff));
+}
+
+#[test]
+fn gray8_dithering() {
+    assert_eq!(Gray8Pixel::from_rgb(255, 255, 255), Gray8Pixel(255));
+    let line = [Rgb8Pixel { r: 255, g: 255, b: 255 }; 4];
+    let mut output = [Gray8Pixel(0); 4];
+    Gray8Pixel::dither_line(&line, 0, 0, &mut output);
+    assert_eq!(output, [Gray8Pixel(255); 4]);
+
+    // The luma of this color is 100.2, 3 pixels out of 16 are rounded up
+    let line = [Rgb8Pixel { r: 0, g: 171, b: 0 }; 16];
+    let mut total = 0;
+    for y in 0..4 {
+        let mut output = [Gray8Pixel(0); 16];
+        Gray8Pixel::dither_line(&line, 0, y, &mut output);
+        total += output.iter().map(|p| p.0 as u32).sum::<u32>();
+    }
+    assert_eq!(total, 100 * 64 + 12);
+}
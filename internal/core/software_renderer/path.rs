@@ -0,0 +1,113 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.2 OR LicenseRef-Slint-commercial
+
+//! This module contains the code to rasterize paths for the software renderer.
+//!
+//! The path is converted to [`zeno`] commands and rendered into a coverage mask.
+//! That mask is then either used as an alpha map (for solid colors), or combined
+//! with a gradient into a pre-multiplied RGBA buffer.
+
+use super::PhysicalRect;
+use crate::graphics::{Rgba8Pixel, SharedPixelBuffer};
+use crate::lengths::PhysicalPx;
+use crate::Brush;
+use alloc::vec::Vec;
+use zeno::{Command, Point};
+
+/// Convert the lyon path events into zeno commands.
+///
+/// The coordinates are multiplied by `scale` and then translated by `offset`
+pub(super) fn to_zeno_commands(
+    events: impl Iterator<Item = lyon_path::Event<lyon_path::math::Point, lyon_path::math::Point>>,
+    scale: f32,
+    offset: euclid::Vector2D<f32, PhysicalPx>,
+) -> Vec<Command> {
+    let map =
+        |p: lyon_path::math::Point| Point::new(p.x * scale + offset.x, p.y * scale + offset.y);
+    events
+        .filter_map(|event| match event {
+            lyon_path::Event::Begin { at } => Some(Command::MoveTo(map(at))),
+            lyon_path::Event::Line { from: _, to } => Some(Command::LineTo(map(to))),
+            lyon_path::Event::Quadratic { from: _, ctrl, to } => {
+                Some(Command::QuadTo(map(ctrl), map(to)))
+            }
+            lyon_path::Event::Cubic { from: _, ctrl1, ctrl2, to } => {
+                Some(Command::CurveTo(map(ctrl1), map(ctrl2), map(to)))
+            }
+            lyon_path::Event::End { close: true, .. } => Some(Command::Close),
+            lyon_path::Event::End { close: false, .. } => None,
+        })
+        .collect()
+}
+
+/// Compute the physical rectangle that is covered by the path with the given style,
+/// intersected with the `clip`.
+pub(super) fn path_bounds<'a>(
+    commands: &[Command],
+    style: impl Into<zeno::Style<'a>>,
+    clip: PhysicalRect,
+) -> Option<PhysicalRect> {
+    let bounds = zeno::bounds(commands, style, None);
+    if bounds.is_empty() {
+        return None;
+    }
+    let rect = euclid::Box2D::<f32, PhysicalPx>::new(
+        euclid::point2(bounds.min.x, bounds.min.y),
+        euclid::point2(bounds.max.x, bounds.max.y),
+    )
+    .round_out()
+    .to_rect()
+    .cast();
+    rect.intersection(&clip)
+}
+
+/// Render the path into an anti-aliased coverage mask of the size of `target`.
+///
+/// The commands must be in the same (physical) coordinate space as `target`.
+pub(super) fn render_mask<'a>(
+    commands: &[Command],
+    style: impl Into<zeno::Style<'a>>,
+    target: PhysicalRect,
+) -> Vec<u8> {
+    let (data, _) = zeno::Mask::new(commands)
+        .style(style)
+        .size(target.width() as u32, target.height() as u32)
+        .offset(zeno::Vector::new(-target.origin.x as f32, -target.origin.y as f32))
+        .render();
+    data
+}
+
+/// Create a pre-multiplied buffer of the size of `target`, filled with the `brush`
+/// and whose alpha is the coverage given by the `mask`.
+///
+/// `brush_rect` is the rectangle, in the same coordinate as target, that the gradient
+/// should span.
+pub(super) fn brush_with_mask(
+    brush: &Brush,
+    mask: &[u8],
+    target: PhysicalRect,
+    brush_rect: euclid::Rect<f32, PhysicalPx>,
+    global_alpha: f32,
+) -> SharedPixelBuffer<Rgba8Pixel> {
+    let width = target.width() as u32;
+    let height = target.height() as u32;
+    let mut buffer = SharedPixelBuffer::<Rgba8Pixel>::new(width, height);
+    let sampler = super::draw_functions::BrushSampler::new(brush, brush_rect.size, 0.);
+
+    for (i, (pix, coverage)) in buffer.make_mut_slice().iter_mut().zip(mask).enumerate() {
+        if *coverage == 0 {
+            continue;
+        }
+        let x = target.min_x() as f32 + (i % width as usize) as f32 + 0.5;
+        let y = target.min_y() as f32 + (i / width as usize) as f32 + 0.5;
+        let color = sampler.color_at(euclid::point2(x, y) - brush_rect.origin.to_vector());
+        let alpha = (color.alpha() as f32 * global_alpha) as u16 * *coverage as u16 / 255;
+        *pix = Rgba8Pixel {
+            r: (color.red() as u16 * alpha / 255) as u8,
+            g: (color.green() as u16 * alpha / 255) as u8,
+            b: (color.blue() as u16 * alpha / 255) as u8,
+            a: alpha as u8,
+        };
+    }
+    buffer
+}
@@ -272,6 +272,163 @@ pub fn format(args: core::fmt::Arguments<'_>) -> SharedString {
     output
 }
 
+/// Implementation of the member functions of the `string` type in the .slint language.
+///
+/// Lengths and indices are expressed in grapheme clusters, and matches are only
+/// considered when they start and end on a grapheme cluster boundary.
+#[doc(hidden)]
+pub mod functions {
+    use super::SharedString;
+    use crate::SharedVector;
+    #[cfg(not(feature = "std"))]
+    use alloc::{string::String, vec::Vec};
+    use unicode_segmentation::UnicodeSegmentation;
+
+    /// The byte offsets of all grapheme cluster boundaries, including the end of the string
+    fn boundaries(s: &str) -> Vec<usize> {
+        s.grapheme_indices(true).map(|(i, _)| i).chain(core::iter::once(s.len())).collect()
+    }
+
+    /// The byte offsets of the non-overlapping occurrences of `needle` in `s` that
+    /// are aligned on grapheme cluster boundaries. `needle` must not be empty.
+    fn find_all(s: &str, needle: &str) -> Vec<usize> {
+        let boundaries = boundaries(s);
+        let mut result = Vec::new();
+        let mut next = 0;
+        for &start in &boundaries {
+            if start < next || !s[start..].starts_with(needle) {
+                continue;
+            }
+            let end = start + needle.len();
+            if boundaries.binary_search(&end).is_ok() {
+                result.push(start);
+                next = end;
+            }
+        }
+        result
+    }
+
+    /// Returns the number of grapheme clusters in the string
+    pub fn length(s: &str) -> i32 {
+        s.graphemes(true).count() as i32
+    }
+
+    /// Returns the string converted to upper case
+    pub fn to_uppercase(s: &str) -> SharedString {
+        s.to_uppercase().into()
+    }
+
+    /// Returns the string converted to lower case
+    pub fn to_lowercase(s: &str) -> SharedString {
+        s.to_lowercase().into()
+    }
+
+    /// Returns the string without leading and trailing whitespace
+    pub fn trim(s: &str) -> SharedString {
+        s.trim().into()
+    }
+
+    /// Returns true if `needle` occurs in `s`
+    pub fn contains(s: &str, needle: &str) -> bool {
+        needle.is_empty() || !find_all(s, needle).is_empty()
+    }
+
+    /// Returns true if `s` starts with `prefix`
+    pub fn starts_with(s: &str, prefix: &str) -> bool {
+        s.starts_with(prefix) && boundaries(s).binary_search(&prefix.len()).is_ok()
+    }
+
+    /// Returns true if `s` ends with `suffix`
+    pub fn ends_with(s: &str, suffix: &str) -> bool {
+        s.ends_with(suffix) && boundaries(s).binary_search(&(s.len() - suffix.len())).is_ok()
+    }
+
+    /// Returns `s` with all occurrences of `from` replaced by `to`
+    pub fn replace(s: &str, from: &str, to: &str) -> SharedString {
+        if from.is_empty() {
+            return s.into();
+        }
+        let mut result = String::with_capacity(s.len());
+        let mut last = 0;
+        for start in find_all(s, from) {
+            result.push_str(&s[last..start]);
+            result.push_str(to);
+            last = start + from.len();
+        }
+        result.push_str(&s[last..]);
+        result.into()
+    }
+
+    /// Splits `s` at each occurrence of `separator`.
+    /// An empty separator splits the string into its grapheme clusters.
+    pub fn split(s: &str, separator: &str) -> SharedVector<SharedString> {
+        if s.is_empty() {
+            return SharedVector::default();
+        }
+        if separator.is_empty() {
+            return s.graphemes(true).map(SharedString::from).collect();
+        }
+        let mut result = SharedVector::default();
+        let mut last = 0;
+        for start in find_all(s, separator) {
+            result.push(SharedString::from(&s[last..start]));
+            last = start + separator.len();
+        }
+        result.push(SharedString::from(&s[last..]));
+        result
+    }
+
+    /// Returns at most `length` grapheme clusters of `s`, starting at the grapheme cluster `start`
+    pub fn substring(s: &str, start: i32, length: i32) -> SharedString {
+        let boundaries = boundaries(s);
+        let count = boundaries.len() - 1;
+        let start = (start.max(0) as usize).min(count);
+        let end = start.saturating_add(length.max(0) as usize).min(count);
+        s[boundaries[start]..boundaries[end]].into()
+    }
+
+    /// Returns the grapheme cluster at `index`, or an empty string if it is out of bounds
+    pub fn char_at(s: &str, index: i32) -> SharedString {
+        if index < 0 {
+            return SharedString::default();
+        }
+        substring(s, index, 1)
+    }
+
+    #[test]
+    fn test_string_functions() {
+        // "e" followed by a combining acute accent is a single grapheme cluster
+        let s = "cafe\u{301} au lait";
+        assert_eq!(length(s), 12);
+        assert_eq!(length(""), 0);
+        assert_eq!(to_uppercase(s), "CAFE\u{301} AU LAIT");
+        assert_eq!(to_lowercase("HeLLo"), "hello");
+        assert_eq!(trim("  \thello world \n"), "hello world");
+        assert!(contains(s, "au"));
+        assert!(contains(s, ""));
+        assert!(!contains(s, "cafe"));
+        assert!(contains(s, "cafe\u{301}"));
+        assert!(starts_with(s, "caf"));
+        assert!(!starts_with(s, "cafe"));
+        assert!(ends_with(s, "lait"));
+        assert!(!ends_with(s, "\u{301} au lait"));
+        assert_eq!(replace(s, "a", "o"), "cofe\u{301} ou loit");
+        assert_eq!(replace(s, "e", "i"), s);
+        assert_eq!(replace(s, "", "x"), s);
+        assert_eq!(split("a,b,,c", ",").as_slice(), ["a", "b", "", "c"]);
+        assert_eq!(split("", ",").as_slice(), [] as [SharedString; 0]);
+        assert_eq!(split("abc", ".").as_slice(), ["abc"]);
+        assert_eq!(split(s, "").len(), 12);
+        assert_eq!(substring(s, 2, 2), "fe\u{301}");
+        assert_eq!(substring(s, 9, 100), "ait");
+        assert_eq!(substring(s, -5, 2), "ca");
+        assert_eq!(substring(s, 100, 2), "");
+        assert_eq!(char_at(s, 3), "e\u{301}");
+        assert_eq!(char_at(s, 12), "");
+        assert_eq!(char_at(s, -1), "");
+    }
+}
+
 #[test]
 fn simple_test() {
     let x = SharedString::from("hello world!");
@@ -432,6 +589,99 @@ fn test_slint_shared_string_append() {
         append("!");
         assert_eq!(s.as_str(), "Hello, world!");
     }
+
+    /// Returns the number of grapheme clusters in the string
+    #[no_mangle]
+    pub extern "C" fn slint_shared_string_length(ss: &SharedString) -> i32 {
+        functions::length(ss)
+    }
+
+    /// Converts the string to upper case
+    #[no_mangle]
+    pub extern "C" fn slint_shared_string_to_uppercase(ss: &SharedString, out: &mut SharedString) {
+        *out = functions::to_uppercase(ss);
+    }
+
+    /// Converts the string to lower case
+    #[no_mangle]
+    pub extern "C" fn slint_shared_string_to_lowercase(ss: &SharedString, out: &mut SharedString) {
+        *out = functions::to_lowercase(ss);
+    }
+
+    /// Removes the leading and trailing whitespace of the string
+    #[no_mangle]
+    pub extern "C" fn slint_shared_string_trim(ss: &SharedString, out: &mut SharedString) {
+        *out = functions::trim(ss);
+    }
+
+    /// Returns true if `needle` occurs in the string
+    #[no_mangle]
+    pub extern "C" fn slint_shared_string_contains(
+        ss: &SharedString,
+        needle: &SharedString,
+    ) -> bool {
+        functions::contains(ss, needle)
+    }
+
+    /// Returns true if the string starts with `prefix`
+    #[no_mangle]
+    pub extern "C" fn slint_shared_string_starts_with(
+        ss: &SharedString,
+        prefix: &SharedString,
+    ) -> bool {
+        functions::starts_with(ss, prefix)
+    }
+
+    /// Returns true if the string ends with `suffix`
+    #[no_mangle]
+    pub extern "C" fn slint_shared_string_ends_with(
+        ss: &SharedString,
+        suffix: &SharedString,
+    ) -> bool {
+        functions::ends_with(ss, suffix)
+    }
+
+    /// Replaces all occurrences of `from` with `to`
+    #[no_mangle]
+    pub extern "C" fn slint_shared_string_replace(
+        ss: &SharedString,
+        from: &SharedString,
+        to: &SharedString,
+        out: &mut SharedString,
+    ) {
+        *out = functions::replace(ss, from, to);
+    }
+
+    /// Splits the string at each occurrence of `separator`
+    #[no_mangle]
+    pub extern "C" fn slint_shared_string_split(
+        ss: &SharedString,
+        separator: &SharedString,
+        out: &mut SharedVector<SharedString>,
+    ) {
+        *out = functions::split(ss, separator);
+    }
+
+    /// Extracts `length` grapheme clusters starting at the grapheme cluster `start`
+    #[no_mangle]
+    pub extern "C" fn slint_shared_string_substring(
+        ss: &SharedString,
+        start: i32,
+        length: i32,
+        out: &mut SharedString,
+    ) {
+        *out = functions::substring(ss, start, length);
+    }
+
+    /// Returns the grapheme cluster at `index`
+    #[no_mangle]
+    pub extern "C" fn slint_shared_string_char_at(
+        ss: &SharedString,
+        index: i32,
+        out: &mut SharedString,
+    ) {
+        *out = functions::char_at(ss, index);
+    }
 }
 
 #[cfg(feature = "serde")]
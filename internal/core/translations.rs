@@ -21,14 +21,20 @@ fn from_name(&self, _name: &str) -> Option<Self::Output<'_>> {
     }
 
     impl<T: Display> FormatArgs for [T] {
-        type Output<'a> = &'a T where T: 'a;
+        type Output<'a>
+            = &'a T
+        where
+            T: 'a;
         fn from_index(&self, index: usize) -> Option<&T> {
             self.get(index)
         }
     }
 
     impl<const N: usize, T: Display> FormatArgs for [T; N] {
-        type Output<'a> = &'a T where T: 'a;
+        type Output<'a>
+            = &'a T
+        where
+            T: 'a;
         fn from_index(&self, index: usize) -> Option<&T> {
             self.get(index)
         }
@@ -154,7 +160,8 @@ fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
 }
 
 impl<'a, T: FormatArgs + ?Sized> FormatArgs for WithPlural<'a, T> {
-    type Output<'b> = DisplayOrInt<T::Output<'b>>
+    type Output<'b>
+        = DisplayOrInt<T::Output<'b>>
     where
         Self: 'b;
 
@@ -225,12 +232,160 @@ fn demangle_context(r: String) -> String {
 }
 
 pub fn mark_all_translations_dirty() {
+    CURRENT_NUMBER_SEPARATORS.with(|current| current.set(None));
     crate::context::GLOBAL_CONTEXT.with(|ctx| {
         let Some(ctx) = ctx.get() else { return };
         ctx.0.translations_dirty.mark_dirty();
     })
 }
 
+crate::thread_local! {
+    /// The separators of the current locale, read from the environment once and reset by
+    /// [`mark_all_translations_dirty`]
+    static CURRENT_NUMBER_SEPARATORS: core::cell::Cell<Option<NumberSeparators>> = Default::default();
+}
+
+/// The characters used to separate the decimals and group the thousands when formatting numbers
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct NumberSeparators {
+    decimal: char,
+    group: char,
+}
+
+impl Default for NumberSeparators {
+    fn default() -> Self {
+        Self { decimal: '.', group: ',' }
+    }
+}
+
+impl NumberSeparators {
+    /// Returns the separators of the current locale.
+    ///
+    /// They are taken from the same place as the translations: with gettext, this is the
+    /// `LC_NUMERIC` category of the C locale, set up from the environment when the translations
+    /// are initialized. They are only read once; bindings that use them are re-evaluated when
+    /// the translations are marked dirty, which also causes them to be read again.
+    fn current() -> Self {
+        crate::context::GLOBAL_CONTEXT.with(|ctx| {
+            let Some(ctx) = ctx.get() else { return };
+            ctx.0.translations_dirty.as_ref().get();
+        });
+        CURRENT_NUMBER_SEPARATORS.with(|current| {
+            if let Some(separators) = current.get() {
+                return separators;
+            }
+            let separators = Self::from_locale();
+            current.set(Some(separators));
+            separators
+        })
+    }
+
+    #[cfg(all(target_family = "unix", feature = "gettext-rs"))]
+    fn from_locale() -> Self {
+        #![allow(unsafe_code)]
+        let first_char = |s: *const core::ffi::c_char| {
+            if s.is_null() {
+                return None;
+            }
+            // Safety: localeconv returns pointers to nul-terminated strings
+            let s = unsafe { core::ffi::CStr::from_ptr(s) };
+            s.to_str().ok()?.chars().next()
+        };
+        // Safety: localeconv returns a pointer to a static struct which stays valid until the next
+        // call to localeconv or setlocale. The strings are copied before returning.
+        let (decimal, group) = unsafe {
+            let conv = libc::localeconv();
+            if conv.is_null() {
+                return Self::default();
+            }
+            (first_char((*conv).decimal_point), first_char((*conv).thousands_sep))
+        };
+        let default = Self::default();
+        // The "C" locale has no thousands separator
+        Self { decimal: decimal.unwrap_or(default.decimal), group: group.unwrap_or(default.group) }
+    }
+
+    #[cfg(not(all(target_family = "unix", feature = "gettext-rs")))]
+    fn from_locale() -> Self {
+        Self::default()
+    }
+}
+
+fn format_number_with_separators(
+    value: f64,
+    decimals: i32,
+    grouping: bool,
+    separators: NumberSeparators,
+) -> SharedString {
+    use core::fmt::Write;
+    if !value.is_finite() {
+        return crate::format!("{}", value);
+    }
+    let decimals = decimals.clamp(0, 15);
+    // Round half away from zero, unlike the formatter which rounds half to even
+    let scale = 10f64.powi(decimals);
+    let scaled = (value.abs() * scale).round();
+    let rounded = if scaled.is_finite() { scaled / scale } else { value.abs() };
+    let formatted = crate::format!("{:.*}", decimals as usize, rounded);
+    let (integer, fraction) = formatted.split_once('.').unwrap_or((&formatted, ""));
+    let mut output = SharedString::default();
+    // Don't output "-0.00"
+    if value < 0. && formatted.bytes().any(|b| (b'1'..=b'9').contains(&b)) {
+        output.push_str("-");
+    }
+    for (i, digit) in integer.chars().enumerate() {
+        if grouping && i > 0 && (integer.len() - i) % 3 == 0 {
+            output.write_char(separators.group).unwrap();
+        }
+        output.write_char(digit).unwrap();
+    }
+    if !fraction.is_empty() {
+        output.write_char(separators.decimal).unwrap();
+        output.push_str(fraction);
+    }
+    output
+}
+
+/// Format a number with exactly `decimals` decimals, using the decimal separator of the current
+/// locale and, if `grouping` is true, its thousands separator.
+pub fn format_number(value: f64, decimals: i32, grouping: bool) -> SharedString {
+    format_number_with_separators(value, decimals, grouping, NumberSeparators::current())
+}
+
+/// Format the integer part of a number, padded with leading zeros to have at least `digits` digits
+pub fn format_padded_number(value: f64, digits: i32) -> SharedString {
+    let value = value as i64;
+    let digits = digits.clamp(0, 20) as usize;
+    if value < 0 {
+        crate::format!("-{:0digits$}", value.unsigned_abs())
+    } else {
+        crate::format!("{:0digits$}", value)
+    }
+}
+
+#[test]
+fn test_format_number() {
+    let en = NumberSeparators::default();
+    let de = NumberSeparators { decimal: ',', group: '.' };
+    let fr = NumberSeparators { decimal: ',', group: '\u{a0}' };
+    assert_eq!(format_number_with_separators(1234.5, 2, false, en), "1234.50");
+    assert_eq!(format_number_with_separators(1234.5, 2, false, de), "1234,50");
+    assert_eq!(format_number_with_separators(1234567.891, 1, true, en), "1,234,567.9");
+    assert_eq!(format_number_with_separators(-1234567.891, 2, true, de), "-1.234.567,89");
+    assert_eq!(format_number_with_separators(123456., 0, true, fr), "123\u{a0}456");
+    assert_eq!(format_number_with_separators(1234.5, 0, true, de), "1.235");
+    assert_eq!(format_number_with_separators(999., 0, true, en), "999");
+    assert_eq!(format_number_with_separators(-0.001, 2, false, en), "0.00");
+    assert_eq!(format_number_with_separators(0.5, -3, false, en), "1");
+    assert_eq!(format_number_with_separators(2.5, 0, false, en), "3");
+    assert_eq!(format_number_with_separators(-2.5, 0, false, en), "-3");
+    assert_eq!(format_number_with_separators(0.125, 2, false, en), "0.13");
+    assert_eq!(format_number_with_separators(f64::MAX, 2, false, en).len(), 312);
+    assert_eq!(format_padded_number(5., 2), "05");
+    assert_eq!(format_padded_number(-5.7, 3), "-005");
+    assert_eq!(format_padded_number(1234., 2), "1234");
+}
+
 #[cfg(feature = "gettext-rs")]
 /// Initialize the translation by calling the [`bindtextdomain`](https://man7.org/linux/man-pages/man3/bindtextdomain.3.html) function from gettext
 pub fn gettext_bindtextdomain(_domain: &str, _dirname: std::path::PathBuf) -> std::io::Result<()> {
@@ -266,6 +421,23 @@ pub extern "C" fn slint_translate(
             translate(to_translate.as_str(), &context, &domain, arguments.as_slice(), n, &plural)
     }
 
+    /// Format a number with the separators of the current locale.
+    #[no_mangle]
+    pub extern "C" fn slint_format_number(
+        value: f64,
+        decimals: i32,
+        grouping: bool,
+        out: &mut SharedString,
+    ) {
+        *out = format_number(value, decimals, grouping);
+    }
+
+    /// Format the integer part of a number, padded with leading zeros.
+    #[no_mangle]
+    pub extern "C" fn slint_format_padded_number(value: f64, digits: i32, out: &mut SharedString) {
+        *out = format_padded_number(value, digits);
+    }
+
     /// Mark all translated string as dirty to perform re-translation in case the language change
     #[no_mangle]
     pub extern "C" fn slint_translations_mark_dirty() {
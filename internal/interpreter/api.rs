@@ -23,6 +23,7 @@
 pub use i_slint_core::graphics::{
     Brush, Color, Image, LoadImageError, Rgb8Pixel, Rgba8Pixel, RgbaColor, SharedPixelBuffer,
 };
+pub use i_slint_core::items::ImageLoadingStatus;
 use i_slint_core::items::*;
 
 use crate::dynamic_item_tree::ErasedItemTreeBox;
@@ -75,6 +76,7 @@ fn from(ty: LangType) -> Self {
             LangType::Struct { .. } => Self::Struct,
             LangType::Void => Self::Void,
             LangType::Image => Self::Image,
+            LangType::Optional(inner) => Self::from(*inner),
             _ => Self::Other,
         }
     }
@@ -637,6 +639,48 @@ pub async fn build_from_path<P: AsRef<Path>>(
         c.ok().map(|inner| ComponentDefinition { inner: inner.into() })
     }
 
+    /// Compile a .slint file and all the components it exports
+    ///
+    /// Unlike [`Self::build_from_path`], which only compiles the last exported component, the
+    /// returned [`CompilationResult`] gives access to all the exported components, structs and
+    /// enums of the file by name.
+    ///
+    /// The diagnostics are available from the returned [`CompilationResult`], as well as from
+    /// [`Self::diagnostics()`].
+    ///
+    /// This function is `async` but in practice, this is only asynchronous if
+    /// [`Self::set_file_loader`] was called and its future is actually asynchronous.
+    /// If that is not used, then it is fine to use a very simple executor, such as the one
+    /// provided by the `spin_on` crate
+    pub async fn build_all_from_path<P: AsRef<Path>>(&mut self, path: P) -> CompilationResult {
+        let path = path.as_ref();
+        let source = match i_slint_compiler::diagnostics::load_from_path(path) {
+            Ok(s) => s,
+            Err(d) => {
+                self.diagnostics = vec![d];
+                return CompilationResult {
+                    diagnostics: self.diagnostics.clone(),
+                    ..Default::default()
+                };
+            }
+        };
+        self.build_all_impl(source, path.into()).await
+    }
+
+    /// Compile some .slint code and all the components it exports
+    ///
+    /// The `path` argument will be used for diagnostics and to compute relative
+    /// paths while importing.
+    ///
+    /// See [`Self::build_all_from_path`] for details.
+    pub async fn build_all_from_source(
+        &mut self,
+        source_code: String,
+        path: PathBuf,
+    ) -> CompilationResult {
+        self.build_all_impl(source_code, path).await
+    }
+
     /// Compile some .slint code into a ComponentDefinition
     ///
     /// The `path` argument will be used for diagnostics and to compute relative
@@ -702,6 +746,104 @@ async fn build_from_versioned_source_impl(
         self.diagnostics = diag.into_iter().collect();
         c.ok().map(|inner| ComponentDefinition { inner: inner.into() })
     }
+
+    async fn build_all_impl(&mut self, source_code: String, path: PathBuf) -> CompilationResult {
+        let (result, diag) =
+            crate::dynamic_item_tree::load_all(source_code, path, None, self.config.clone()).await;
+        self.diagnostics = diag.into_iter().collect();
+        let Ok((item_trees, exported)) = result else {
+            return CompilationResult {
+                diagnostics: self.diagnostics.clone(),
+                ..Default::default()
+            };
+        };
+        let mut definitions =
+            item_trees.into_iter().map(|inner| ComponentDefinition { inner }).collect::<Vec<_>>();
+        let components = exported
+            .components
+            .into_iter()
+            .filter_map(|name| {
+                let index = definitions.iter().position(|d| d.name() == name)?;
+                Some((name, definitions.swap_remove(index)))
+            })
+            .collect();
+        CompilationResult {
+            components,
+            structs: exported.structs,
+            enums: exported.enums,
+            diagnostics: self.diagnostics.clone(),
+        }
+    }
+}
+
+/// The result of compiling a .slint file with [`ComponentCompiler::build_all_from_path`] or
+/// [`ComponentCompiler::build_all_from_source`].
+///
+/// It gives access to the exported components, structs and enums by name. The component
+/// definitions can be instantiated as many times as needed without compiling again.
+#[derive(Clone, Default)]
+pub struct CompilationResult {
+    components: Vec<(String, ComponentDefinition)>,
+    structs: Vec<(String, Vec<(String, ValueType)>)>,
+    enums: Vec<(String, Vec<String>)>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl CompilationResult {
+    /// Returns true if the compilation failed.
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics.iter().any(|d| d.level() == DiagnosticLevel::Error)
+    }
+
+    /// Returns the diagnostics that were produced during the compilation, such as warnings
+    /// or errors. The [`print_diagnostics`] function can be used to display them to the users.
+    pub fn diagnostics(&self) -> impl Iterator<Item = Diagnostic> + '_ {
+        self.diagnostics.iter().cloned()
+    }
+
+    /// Returns the names of the exported components.
+    pub fn component_names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.components.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Returns the definition of the exported component with the given name, if any.
+    pub fn component(&self, name: &str) -> Option<ComponentDefinition> {
+        self.components.iter().find(|(n, _)| n == name).map(|(_, definition)| definition.clone())
+    }
+
+    /// Returns the definitions of all the exported components.
+    pub fn components(&self) -> impl Iterator<Item = ComponentDefinition> + '_ {
+        self.components.iter().map(|(_, definition)| definition.clone())
+    }
+
+    /// Returns the names of the exported structs.
+    pub fn struct_names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.structs.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Returns the fields of the exported struct with the given name, and their type.
+    pub fn struct_fields(
+        &self,
+        name: &str,
+    ) -> Option<impl Iterator<Item = (&str, ValueType)> + '_> {
+        self.structs
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, fields)| fields.iter().map(|(field, ty)| (field.as_str(), *ty)))
+    }
+
+    /// Returns the names of the exported enums.
+    pub fn enum_names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.enums.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Returns the values of the exported enum with the given name.
+    pub fn enum_values(&self, name: &str) -> Option<impl Iterator<Item = &str> + '_> {
+        self.enums
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, values)| values.iter().map(|value| value.as_str()))
+    }
 }
 
 /// ComponentDefinition is a representation of a compiled component from .slint markup.
@@ -726,6 +868,9 @@ pub fn create(&self) -> Result<ComponentInstance, PlatformError> {
     }
 
     /// Creates a new instance of the component and returns a shared handle to it.
+    ///
+    /// Returns an error if the `component-factory` of the ComponentContainer requires an
+    /// interface that this component doesn't implement.
     #[doc(hidden)]
     #[cfg(feature = "internal")]
     pub fn create_embedded(&self, ctx: FactoryContext) -> Result<ComponentInstance, PlatformError> {
@@ -881,6 +1026,18 @@ pub fn name(&self) -> &str {
         self.inner.unerase(guard).id()
     }
 
+    /// Returns the names of the interfaces that this component implements, as listed after
+    /// `implements` in the .slint file.
+    ///
+    /// This can be used to check that a component loaded at run-time can be used in a
+    /// `component-factory<Interface>` property before creating a factory for it.
+    pub fn interfaces(&self) -> impl Iterator<Item = &str> + '_ {
+        // We create here a 'static guard, because unfortunately the returned type would be restricted to the guard lifetime
+        // which is not required, but this is safe because there is only one instance of the unerased type
+        let guard = unsafe { generativity::Guard::new(generativity::Id::new()) };
+        self.inner.unerase(guard).original.implements.iter().map(|i| i.name.as_str())
+    }
+
     /// This gives access to the tree of Elements.
     #[cfg(feature = "internal")]
     #[doc(hidden)]
@@ -1433,6 +1590,74 @@ fn component_definition_properties() {
     assert_eq!(instance.get_property("underscores-and-dashes-preserved"), Ok(Value::Number(88.)));
 }
 
+#[test]
+fn compilation_result_all_exports() {
+    i_slint_backend_testing::init_no_event_loop();
+    let mut compiler = ComponentCompiler::default();
+    compiler.set_style("fluent".into());
+    let result = spin_on::spin_on(
+        compiler.build_all_from_source(
+            r#"
+    export struct Person { name: string, age: int }
+    export enum Mode { light, dark }
+    export global Settings { in-out property <Mode> mode; }
+    component Base { in property <int> base-value: 3; }
+    export component Dialog inherits Base { in-out property <string> title: "Dialog"; }
+    component Hidden {}
+    export component Main {
+        in-out property <int> count: 42;
+        Dialog { title: "Inner"; }
+    }"#
+            .into(),
+            "".into(),
+        ),
+    );
+    assert!(!result.has_errors());
+    assert_eq!(result.component_names().collect::<Vec<_>>(), ["Dialog", "Main"]);
+    assert_eq!(result.struct_names().collect::<Vec<_>>(), ["Person"]);
+    assert_eq!(
+        result.struct_fields("Person").unwrap().collect::<Vec<_>>(),
+        [("age", ValueType::Number), ("name", ValueType::String)]
+    );
+    assert_eq!(result.enum_values("Mode").unwrap().collect::<Vec<_>>(), ["light", "dark"]);
+    assert!(result.component("Hidden").is_none());
+    assert!(result.component("Settings").is_none());
+
+    let dialog = result.component("Dialog").unwrap().create().unwrap();
+    assert_eq!(dialog.get_property("title"), Ok(Value::String("Dialog".into())));
+    assert_eq!(dialog.get_property("base-value"), Err(GetPropertyError::NoSuchProperty));
+    let main = result.component("Main").unwrap();
+    let (first, second) = (main.create().unwrap(), main.create().unwrap());
+    first.set_property("count", Value::Number(1.)).unwrap();
+    assert_eq!(second.get_property("count"), Ok(Value::Number(42.)));
+}
+
+#[test]
+fn component_definition_interfaces() {
+    i_slint_backend_testing::init_no_event_loop();
+    let mut compiler = ComponentCompiler::default();
+    compiler.set_style("fluent".into());
+    let result = spin_on::spin_on(
+        compiler.build_all_from_source(
+            r#"
+    export interface Plugin { in property <string> title; callback activated(); }
+    interface Resizable { in-out property <length> preferred-width; }
+    export component MyPlugin implements Plugin, Resizable {
+        in property <string> title;
+        in-out property <length> preferred-width;
+        callback activated();
+    }
+    export component NotAPlugin {}"#
+                .into(),
+            "".into(),
+        ),
+    );
+    assert!(!result.has_errors());
+    let plugin = result.component("MyPlugin").unwrap();
+    assert_eq!(plugin.interfaces().collect::<Vec<_>>(), ["Plugin", "Resizable"]);
+    assert_eq!(result.component("NotAPlugin").unwrap().interfaces().count(), 0);
+}
+
 #[test]
 fn component_definition_properties2() {
     i_slint_backend_testing::init_no_event_loop();
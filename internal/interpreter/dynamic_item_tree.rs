@@ -480,6 +480,16 @@ pub fn create(
             Ok(())
         })?;
 
+        if let WindowOptions::Embed { parent_item_tree, parent_item_tree_index } = &options {
+            if let Some(interface) = required_interface(parent_item_tree, *parent_item_tree_index) {
+                let errors =
+                    object_tree::interface_implementation_errors(&self.original, &interface);
+                if let Some(error) = errors.into_iter().next() {
+                    return Err(PlatformError::Other(error));
+                }
+            }
+        }
+
         let instance = instantiate(self, None, None, Some(&options), Default::default());
         if let WindowOptions::UseExistingWindow(existing_adapter) = options {
             WindowInner::from_pub(existing_adapter.window())
@@ -785,6 +795,35 @@ fn rtti_for<T: 'static + Default + rtti::BuiltinItem + vtable::HasStaticVTable<I
     (T::name(), Rc::new(rtti))
 }
 
+/// The names of the components, structs and enums exported by a compiled file
+#[derive(Default, Clone)]
+pub(crate) struct ExportedTypes {
+    pub components: Vec<String>,
+    pub structs: Vec<(String, Vec<(String, crate::api::ValueType)>)>,
+    pub enums: Vec<(String, Vec<String>)>,
+}
+
+impl ExportedTypes {
+    fn from_document(doc: &object_tree::Document) -> Self {
+        let mut result = Self::default();
+        for (name, component_or_type) in doc.exports.iter() {
+            if let Some(c) = component_or_type.as_ref().left() {
+                if !c.is_global() {
+                    result.components.push(name.name.clone());
+                }
+            } else if let Some(Type::Struct { fields, .. }) = component_or_type.as_ref().right() {
+                result.structs.push((
+                    name.name.clone(),
+                    fields.iter().map(|(f, ty)| (f.clone(), ty.clone().into())).collect(),
+                ));
+            } else if let Some(Type::Enumeration(e)) = component_or_type.as_ref().right() {
+                result.enums.push((name.name.clone(), e.values.clone()));
+            }
+        }
+        result
+    }
+}
+
 /// Create a ItemTreeDescription from a source.
 /// The path corresponding to the source need to be passed as well (path is used for diagnostics
 /// and loading relative assets)
@@ -792,25 +831,10 @@ pub async fn load(
     source: String,
     path: std::path::PathBuf,
     version: SourceFileVersion,
-    #[allow(unused_mut)] mut compiler_config: CompilerConfiguration,
+    compiler_config: CompilerConfiguration,
     guard: generativity::Guard<'_>,
 ) -> (Result<Rc<ItemTreeDescription<'_>>, ()>, i_slint_compiler::diagnostics::BuildDiagnostics) {
-    // If the native style should be Qt, resolve it here as we have the knowledge of the native style
-    #[cfg(all(unix, not(target_os = "macos")))]
-    if i_slint_backend_selector::HAS_NATIVE_STYLE {
-        let is_native = match &compiler_config.style {
-            Some(s) => s == "native",
-            None => std::env::var("SLINT_STYLE").map_or(true, |s| s == "native"),
-        };
-        if is_native {
-            compiler_config.style = Some("qt".into());
-        }
-    }
-
-    let diag = BuildDiagnostics::default();
-    let (path, mut diag, loader) =
-        i_slint_compiler::load_root_file(&path, version, &path, source, diag, compiler_config)
-            .await;
+    let (path, mut diag, loader) = compile(source, path, version, compiler_config).await;
     if diag.has_error() {
         return (Err(()), diag);
     }
@@ -840,6 +864,76 @@ pub async fn load(
     (Ok(item_tree), diag)
 }
 
+/// Create the ItemTreeDescription of every component exported by a source, compiling it once.
+///
+/// Each ItemTreeDescription is named after the export.
+pub(crate) async fn load_all(
+    source: String,
+    path: std::path::PathBuf,
+    version: SourceFileVersion,
+    mut compiler_config: CompilerConfiguration,
+) -> (
+    Result<(Vec<ErasedItemTreeDescription>, ExportedTypes), ()>,
+    i_slint_compiler::diagnostics::BuildDiagnostics,
+) {
+    compiler_config.compile_all_exported_components = true;
+    let (path, diag, loader) = compile(source, path, version, compiler_config).await;
+    if diag.has_error() {
+        return (Err(()), diag);
+    }
+
+    let (item_trees, exported) = {
+        let doc = loader.get_document(&path).unwrap();
+        let item_trees = doc
+            .root_components()
+            .filter(|c| {
+                !matches!(
+                    c.root_element.borrow().base_type,
+                    ElementType::Global | ElementType::Error
+                )
+            })
+            .map(|c| {
+                generativity::make_guard!(guard);
+                ErasedItemTreeDescription::from(generate_item_tree(c, guard))
+            })
+            .collect::<Vec<_>>();
+        (item_trees, ExportedTypes::from_document(doc))
+    };
+
+    #[cfg(feature = "highlight")]
+    {
+        let loader = Rc::new(loader);
+        for it in &item_trees {
+            let _ = it.0.type_loader.set(loader.clone());
+        }
+    }
+
+    (Ok((item_trees, exported)), diag)
+}
+
+/// Compile the source and run the passes
+async fn compile(
+    source: String,
+    path: std::path::PathBuf,
+    version: SourceFileVersion,
+    #[allow(unused_mut)] mut compiler_config: CompilerConfiguration,
+) -> (std::path::PathBuf, BuildDiagnostics, i_slint_compiler::typeloader::TypeLoader) {
+    // If the native style should be Qt, resolve it here as we have the knowledge of the native style
+    #[cfg(all(unix, not(target_os = "macos")))]
+    if i_slint_backend_selector::HAS_NATIVE_STYLE {
+        let is_native = match &compiler_config.style {
+            Some(s) => s == "native",
+            None => std::env::var("SLINT_STYLE").map_or(true, |s| s == "native"),
+        };
+        if is_native {
+            compiler_config.style = Some("qt".into());
+        }
+    }
+
+    let diag = BuildDiagnostics::default();
+    i_slint_compiler::load_root_file(&path, version, &path, source, diag, compiler_config).await
+}
+
 pub(crate) fn generate_item_tree<'id>(
     component: &Rc<object_tree::Component>,
     guard: generativity::Guard<'id>,
@@ -1071,12 +1165,12 @@ fn animated_property_info<T>(
                     .insert(name.clone(), builder.type_builder.add_field_type::<Callback>());
                 continue;
             }
-            Type::ComponentFactory => property_info::<ComponentFactory>(),
+            Type::ComponentFactory(_) => property_info::<ComponentFactory>(),
             Type::Struct { name: Some(name), .. } if name.ends_with("::StateInfo") => {
                 property_info::<i_slint_core::properties::StateInfo>()
             }
             Type::Struct { .. } => property_info::<Value>(),
-            Type::Array(_) => property_info::<Value>(),
+            Type::Array(_) | Type::Optional(_) => property_info::<Value>(),
             Type::Easing => property_info::<i_slint_core::animations::EasingCurve>(),
             Type::Percent => property_info::<f32>(),
             Type::Enumeration(e) => {
@@ -1107,6 +1201,8 @@ macro_rules! match_enum_type {
             | Type::Model
             | Type::PathData
             | Type::UnitProduct(_)
+            | Type::Interface(_)
+            | Type::TypeParameter(_)
             | Type::ElementReference => panic!("bad type {:?}", &decl.property_type),
         };
         custom_properties.insert(
@@ -1856,6 +1952,39 @@ extern "C" fn subtree_index(component: ItemTreeRefPin) -> usize {
     }
 }
 
+/// Returns the interface that the components embedded at `parent_item_tree_index` must implement,
+/// when the parent was created by the interpreter and the `component-factory` of the
+/// ComponentContainer is declared as `component-factory<Interface>`
+fn required_interface(
+    parent_item_tree: &ItemTreeWeak,
+    parent_item_tree_index: u32,
+) -> Option<Rc<i_slint_compiler::langtype::Interface>> {
+    let parent = parent_item_tree.upgrade()?;
+    let parent = vtable::VRc::borrow_pin(&parent);
+    // Item trees generated by the Rust or C++ code generators don't have an ItemTreeDescription
+    if parent.get_vtable().get_item_tree as usize != get_item_tree as usize {
+        return None;
+    }
+    generativity::make_guard!(guard);
+    let instance_ref = unsafe { InstanceRef::from_pin_ref(parent, guard) };
+    let Some(ItemTreeNode::DynamicTree { parent_index, .. }) =
+        instance_ref.description.item_tree.get(parent_item_tree_index as usize)
+    else {
+        return None;
+    };
+    let container = instance_ref.description.original_elements.get(*parent_index as usize)?;
+    let container = container.borrow();
+    let binding = container.bindings.get("component-factory")?.borrow();
+    let ty = match binding.two_way_bindings.first() {
+        Some(nr) if matches!(binding.expression, Expression::Invalid) => nr.ty(),
+        _ => binding.expression.ty(),
+    };
+    match ty {
+        Type::ComponentFactory(interface) => interface,
+        _ => None,
+    }
+}
+
 unsafe extern "C" fn embed_component(
     component: ItemTreeRefPin,
     parent_component: &ItemTreeWeak,
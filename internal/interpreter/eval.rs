@@ -4,14 +4,16 @@
 use crate::api::{SetPropertyError, Struct, Value};
 use crate::dynamic_item_tree::InstanceRef;
 use core::pin::Pin;
-use corelib::graphics::{GradientStop, LinearGradientBrush, PathElement, RadialGradientBrush};
+use corelib::graphics::{
+    ConicGradientBrush, GradientStop, LinearGradientBrush, PathElement, RadialGradientBrush,
+};
 use corelib::items::{ColorScheme, ItemRef, PropertyAnimation};
 use corelib::model::{Model, ModelRc};
 use corelib::rtti::AnimatedBindingKind;
 use corelib::{Brush, Color, PathData, SharedString, SharedVector};
 use i_slint_compiler::expression_tree::{
-    BuiltinFunction, EasingCurve, Expression, MinMaxOp, Path as ExprPath,
-    PathElement as ExprPathElement,
+    BuiltinFunction, EasingCurve, Expression, MinMaxOp, ModelOperation, Path as ExprPath,
+    PathElement as ExprPathElement, RadialGradientExtent, RadialGradientShape,
 };
 use i_slint_compiler::langtype::Type;
 use i_slint_compiler::object_tree::ElementRc;
@@ -140,6 +142,7 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
         Expression::StringLiteral(s) => Value::String(s.into()),
         Expression::NumberLiteral(n, unit) => Value::Number(unit.normalize(*n)),
         Expression::BoolLiteral(b) => Value::Bool(*b),
+        Expression::NullLiteral(_) => Value::Void,
         Expression::CallbackReference { .. } => panic!("callback in expression"),
         Expression::FunctionReference { .. } => panic!("function in expression"),
         Expression::BuiltinFunctionReference(..) => panic!(
@@ -302,6 +305,28 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
 
                     }
                 }
+                i_slint_compiler::expression_tree::ImageReference::EmbeddedAnimation { resource_id } => {
+                    generativity::make_guard!(guard);
+                    let toplevel_instance = match &local_context.component_instance {
+                        ComponentInstance::InstanceRef(instance) => instance.toplevel_instance(guard),
+                        ComponentInstance::GlobalComponent(_) => unimplemented!(),
+                    };
+                    let extra_data = toplevel_instance.description.extra_data_offset.apply(toplevel_instance.as_ref());
+                    let path = extra_data.embedded_file_resources.get().unwrap().get(resource_id).expect("internal error: invalid resource id");
+
+                    // The frames are decoded by the run-time from the original file
+                    let virtual_file = i_slint_compiler::fileaccess::load_file(std::path::Path::new(path)).unwrap();  // embedding pass ensured that the file exists
+                    match (virtual_file.canon_path, virtual_file.builtin_contents) {
+                        (static_path, Some(static_data)) => {
+                            let extension = static_path.extension().and_then(|e| e.to_str()).unwrap_or_default();
+                            Ok(corelib::graphics::load_image_from_embedded_data(
+                                corelib::slice::Slice::from_slice(static_data),
+                                corelib::slice::Slice::from_slice(extension.as_bytes())
+                            ))
+                        }
+                        (path, None) => corelib::graphics::Image::load_from_path(&path),
+                    }
+                }
                 i_slint_compiler::expression_tree::ImageReference::EmbeddedTexture { .. } => {
                     todo!()
                 }
@@ -323,6 +348,21 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                 _ => local_context.return_value.clone().expect("conditional expression did not evaluate to boolean"),
             }
         }
+        Expression::NullCoalescing { lhs, rhs } => {
+            match eval_expression(lhs, local_context) {
+                Value::Void => eval_expression(rhs, local_context),
+                value => value,
+            }
+        }
+        Expression::OptionalChaining { optional, variable, body } => {
+            match eval_expression(optional, local_context) {
+                Value::Void => Value::Void,
+                value => {
+                    local_context.local_variables.insert(variable.clone(), value);
+                    eval_expression(body, local_context)
+                }
+            }
+        }
         Expression::Array { values, .. } => Value::Model(
             ModelRc::new(corelib::model::SharedVectorModel::from(
                 values.iter().map(|e| eval_expression(e, local_context)).collect::<SharedVector<_>>()
@@ -365,8 +405,31 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                 GradientStop{ color, position }
             }))))
         }
-        Expression::RadialGradient{stops} => {
-            Value::Brush(Brush::RadialGradient(RadialGradientBrush::new_circle(stops.iter().map(|(color, stop)| {
+        Expression::RadialGradient{shape, extent, center_x, center_y, stops} => {
+            let shape = match shape {
+                RadialGradientShape::Circle => corelib::graphics::RadialGradientShape::Circle,
+                RadialGradientShape::Ellipse => corelib::graphics::RadialGradientShape::Ellipse,
+            };
+            let extent = match extent {
+                RadialGradientExtent::ClosestSide => corelib::graphics::RadialGradientExtent::ClosestSide,
+                RadialGradientExtent::FarthestSide => corelib::graphics::RadialGradientExtent::FarthestSide,
+                RadialGradientExtent::ClosestCorner => corelib::graphics::RadialGradientExtent::ClosestCorner,
+                RadialGradientExtent::FarthestCorner => corelib::graphics::RadialGradientExtent::FarthestCorner,
+                RadialGradientExtent::AverageSide => corelib::graphics::RadialGradientExtent::AverageSide,
+            };
+            let center_x: f32 = eval_expression(center_x, local_context).try_into().unwrap();
+            let center_y: f32 = eval_expression(center_y, local_context).try_into().unwrap();
+            Value::Brush(Brush::RadialGradient(RadialGradientBrush::new(shape, extent, corelib::graphics::euclid::point2(center_x, center_y), stops.iter().map(|(color, stop)| {
+                let color = eval_expression(color, local_context).try_into().unwrap();
+                let position = eval_expression(stop, local_context).try_into().unwrap();
+                GradientStop{ color, position }
+            }))))
+        }
+        Expression::ConicGradient{from_angle, center_x, center_y, stops} => {
+            let from_angle = eval_expression(from_angle, local_context).try_into().unwrap();
+            let center_x: f32 = eval_expression(center_x, local_context).try_into().unwrap();
+            let center_y: f32 = eval_expression(center_y, local_context).try_into().unwrap();
+            Value::Brush(Brush::ConicGradient(ConicGradientBrush::new(from_angle, corelib::graphics::euclid::point2(center_x, center_y), stops.iter().map(|(color, stop)| {
                 let color = eval_expression(color, local_context).try_into().unwrap();
                 let position = eval_expression(stop, local_context).try_into().unwrap();
                 GradientStop{ color, position }
@@ -409,6 +472,94 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                 MinMaxOp::Max => Value::Number(lhs.max(rhs)),
             }
         }
+        Expression::ForLoop { variable, model, body } => {
+            // When the body is a struct, the loop evaluates to the struct that stopped it
+            let mut result = None;
+            let mut run_body = |row, local_context: &mut EvalLocalContext| {
+                local_context.local_variables.insert(variable.clone(), row);
+                match eval_expression(body, local_context) {
+                    Value::Struct(s) => {
+                        let proceed = s.get_field("condition") == Some(&Value::Bool(true));
+                        if !proceed {
+                            result = Some(Value::Struct(s));
+                        }
+                        proceed
+                    }
+                    value => value == Value::Bool(true),
+                }
+            };
+            let row_ty = match model.ty() {
+                Type::Array(ty) => *ty,
+                _ => Type::Int32,
+            };
+            match eval_expression(model, local_context) {
+                Value::Model(model) => {
+                    model.model_tracker().track_row_count_changes();
+                    for index in 0..model.row_count() {
+                        model.model_tracker().track_row_data_changes(index);
+                        let row = model.row_data(index).unwrap_or_else(|| default_value_for_type(&row_ty));
+                        if !run_body(row, local_context) {
+                            break;
+                        }
+                    }
+                }
+                Value::Number(count) => {
+                    for index in 0..(count as i32) {
+                        if !run_body(Value::Number(index as f64), local_context) {
+                            break;
+                        }
+                    }
+                }
+                _ => panic!("for loop over something that is not a model"),
+            }
+            result.unwrap_or_else(|| match body.ty() {
+                ty @ Type::Struct { .. } => default_value_for_type(&ty),
+                _ => Value::Void,
+            })
+        }
+        Expression::Break | Expression::Continue => panic!("break and continue should have been lowered"),
+        Expression::ModelOperation { model, operation } => {
+            let Value::Model(model) = eval_expression(model, local_context) else {
+                panic!("model operation on something that is not a model")
+            };
+            match operation {
+                ModelOperation::Filter { variable, body } => {
+                    let lambda = make_lambda(variable, body, local_context);
+                    let filter = move |row: &Value| lambda(row.clone()) == Value::Bool(true);
+                    Value::Model(ModelRc::new(corelib::model::FilterModel::new(model, filter)))
+                }
+                ModelOperation::Map { variable, body } => {
+                    let lambda = make_lambda(variable, body, local_context);
+                    Value::Model(ModelRc::new(corelib::model::MapModel::new(model, lambda)))
+                }
+                ModelOperation::SortBy { variable, body } => {
+                    let key = make_lambda(variable, body, local_context);
+                    let compare = move |a: &Value, b: &Value| match (key(a.clone()), key(b.clone())) {
+                        (Value::Number(a), Value::Number(b)) => {
+                            a.partial_cmp(&b).unwrap_or(core::cmp::Ordering::Equal)
+                        }
+                        (Value::String(a), Value::String(b)) => a.cmp(&b),
+                        _ => core::cmp::Ordering::Equal,
+                    };
+                    Value::Model(ModelRc::new(corelib::model::SortModel::new(model, compare)))
+                }
+                ModelOperation::Slice { start, end } => {
+                    let start: i32 = eval_expression(start, local_context).try_into().unwrap();
+                    let end: i32 = eval_expression(end, local_context).try_into().unwrap();
+                    model.model_tracker().track_row_count_changes();
+                    let count = model.row_count() as i32;
+                    let start = start.clamp(0, count);
+                    let end = end.clamp(start, count);
+                    let rows = (start..end).map(|i| {
+                        model.model_tracker().track_row_data_changes(i as usize);
+                        model.row_data(i as usize).unwrap_or_default()
+                    });
+                    Value::Model(ModelRc::new(corelib::model::VecModel::from(
+                        rows.collect::<Vec<_>>(),
+                    )))
+                }
+            }
+        }
     }
 }
 
@@ -779,6 +930,203 @@ fn call_builtin_function(
                 panic!("Argument not a string");
             }
         }
+        BuiltinFunction::StringToInt => {
+            if arguments.len() != 1 {
+                panic!("internal error: incorrect argument count to StringToInt")
+            }
+            if let Value::String(s) = eval_expression(&arguments[0], local_context) {
+                Value::Number(s.as_str().parse::<i32>().unwrap_or_default() as f64)
+            } else {
+                panic!("Argument not a string");
+            }
+        }
+        BuiltinFunction::StringLength => {
+            if arguments.len() != 1 {
+                panic!("internal error: incorrect argument count to StringLength")
+            }
+            if let Value::String(s) = eval_expression(&arguments[0], local_context) {
+                Value::Number(corelib::string::functions::length(&s) as f64)
+            } else {
+                panic!("Argument not a string");
+            }
+        }
+        BuiltinFunction::StringIsEmpty => {
+            if arguments.len() != 1 {
+                panic!("internal error: incorrect argument count to StringIsEmpty")
+            }
+            if let Value::String(s) = eval_expression(&arguments[0], local_context) {
+                Value::Bool(s.is_empty())
+            } else {
+                panic!("Argument not a string");
+            }
+        }
+        BuiltinFunction::StringToUppercase => {
+            if arguments.len() != 1 {
+                panic!("internal error: incorrect argument count to StringToUppercase")
+            }
+            if let Value::String(s) = eval_expression(&arguments[0], local_context) {
+                Value::String(corelib::string::functions::to_uppercase(&s))
+            } else {
+                panic!("Argument not a string");
+            }
+        }
+        BuiltinFunction::StringToLowercase => {
+            if arguments.len() != 1 {
+                panic!("internal error: incorrect argument count to StringToLowercase")
+            }
+            if let Value::String(s) = eval_expression(&arguments[0], local_context) {
+                Value::String(corelib::string::functions::to_lowercase(&s))
+            } else {
+                panic!("Argument not a string");
+            }
+        }
+        BuiltinFunction::StringTrim => {
+            if arguments.len() != 1 {
+                panic!("internal error: incorrect argument count to StringTrim")
+            }
+            if let Value::String(s) = eval_expression(&arguments[0], local_context) {
+                Value::String(corelib::string::functions::trim(&s))
+            } else {
+                panic!("Argument not a string");
+            }
+        }
+        BuiltinFunction::StringContains => {
+            if arguments.len() != 2 {
+                panic!("internal error: incorrect argument count to StringContains")
+            }
+            match (
+                eval_expression(&arguments[0], local_context),
+                eval_expression(&arguments[1], local_context),
+            ) {
+                (Value::String(s), Value::String(other)) => {
+                    Value::Bool(corelib::string::functions::contains(&s, &other))
+                }
+                _ => panic!("Arguments not strings"),
+            }
+        }
+        BuiltinFunction::StringStartsWith => {
+            if arguments.len() != 2 {
+                panic!("internal error: incorrect argument count to StringStartsWith")
+            }
+            match (
+                eval_expression(&arguments[0], local_context),
+                eval_expression(&arguments[1], local_context),
+            ) {
+                (Value::String(s), Value::String(other)) => {
+                    Value::Bool(corelib::string::functions::starts_with(&s, &other))
+                }
+                _ => panic!("Arguments not strings"),
+            }
+        }
+        BuiltinFunction::StringEndsWith => {
+            if arguments.len() != 2 {
+                panic!("internal error: incorrect argument count to StringEndsWith")
+            }
+            match (
+                eval_expression(&arguments[0], local_context),
+                eval_expression(&arguments[1], local_context),
+            ) {
+                (Value::String(s), Value::String(other)) => {
+                    Value::Bool(corelib::string::functions::ends_with(&s, &other))
+                }
+                _ => panic!("Arguments not strings"),
+            }
+        }
+        BuiltinFunction::StringReplace => {
+            if arguments.len() != 3 {
+                panic!("internal error: incorrect argument count to StringReplace")
+            }
+            match (
+                eval_expression(&arguments[0], local_context),
+                eval_expression(&arguments[1], local_context),
+                eval_expression(&arguments[2], local_context),
+            ) {
+                (Value::String(s), Value::String(from), Value::String(to)) => {
+                    Value::String(corelib::string::functions::replace(&s, &from, &to))
+                }
+                _ => panic!("Arguments not strings"),
+            }
+        }
+        BuiltinFunction::StringSplit => {
+            if arguments.len() != 2 {
+                panic!("internal error: incorrect argument count to StringSplit")
+            }
+            match (
+                eval_expression(&arguments[0], local_context),
+                eval_expression(&arguments[1], local_context),
+            ) {
+                (Value::String(s), Value::String(separator)) => {
+                    Value::Model(ModelRc::new(corelib::model::SharedVectorModel::from(
+                        corelib::string::functions::split(&s, &separator)
+                            .into_iter()
+                            .map(Value::String)
+                            .collect::<SharedVector<Value>>(),
+                    )))
+                }
+                _ => panic!("Arguments not strings"),
+            }
+        }
+        BuiltinFunction::StringSubstring => {
+            if arguments.len() != 3 {
+                panic!("internal error: incorrect argument count to StringSubstring")
+            }
+            match (
+                eval_expression(&arguments[0], local_context),
+                eval_expression(&arguments[1], local_context),
+                eval_expression(&arguments[2], local_context),
+            ) {
+                (Value::String(s), Value::Number(start), Value::Number(length)) => Value::String(
+                    corelib::string::functions::substring(&s, start as i32, length as i32),
+                ),
+                _ => panic!("Invalid arguments to StringSubstring"),
+            }
+        }
+        BuiltinFunction::StringCharAt => {
+            if arguments.len() != 2 {
+                panic!("internal error: incorrect argument count to StringCharAt")
+            }
+            match (
+                eval_expression(&arguments[0], local_context),
+                eval_expression(&arguments[1], local_context),
+            ) {
+                (Value::String(s), Value::Number(index)) => {
+                    Value::String(corelib::string::functions::char_at(&s, index as i32))
+                }
+                _ => panic!("Invalid arguments to StringCharAt"),
+            }
+        }
+        BuiltinFunction::NumberToFixed | BuiltinFunction::NumberFormat => {
+            if arguments.len() != 2 {
+                panic!("internal error: incorrect argument count to {f:?}")
+            }
+            match (
+                eval_expression(&arguments[0], local_context),
+                eval_expression(&arguments[1], local_context),
+            ) {
+                (Value::Number(value), Value::Number(decimals)) => {
+                    Value::String(corelib::translations::format_number(
+                        value,
+                        decimals as i32,
+                        f == BuiltinFunction::NumberFormat,
+                    ))
+                }
+                _ => panic!("Arguments not numbers"),
+            }
+        }
+        BuiltinFunction::NumberToPadded => {
+            if arguments.len() != 2 {
+                panic!("internal error: incorrect argument count to NumberToPadded")
+            }
+            match (
+                eval_expression(&arguments[0], local_context),
+                eval_expression(&arguments[1], local_context),
+            ) {
+                (Value::Number(value), Value::Number(digits)) => {
+                    Value::String(corelib::translations::format_padded_number(value, digits as i32))
+                }
+                _ => panic!("Arguments not numbers"),
+            }
+        }
         BuiltinFunction::ColorRgbaStruct => {
             if arguments.len() != 1 {
                 panic!("internal error: incorrect argument count to ColorRGBAComponents")
@@ -931,6 +1279,42 @@ fn call_builtin_function(
                 }
             }
         }
+        BuiltinFunction::ArrayIndexOf | BuiltinFunction::ArrayContains => {
+            if arguments.len() != 2 {
+                panic!("internal error: incorrect argument count to {f:?}")
+            }
+            let Value::Model(model) = eval_expression(&arguments[0], local_context) else {
+                panic!("First argument not an array");
+            };
+            let value = eval_expression(&arguments[1], local_context);
+            model.model_tracker().track_row_count_changes();
+            let index = (0..model.row_count()).find(|i| {
+                model.model_tracker().track_row_data_changes(*i);
+                model.row_data(*i).as_ref() == Some(&value)
+            });
+            match f {
+                BuiltinFunction::ArrayIndexOf => Value::Number(index.map_or(-1., |i| i as f64)),
+                _ => Value::Bool(index.is_some()),
+            }
+        }
+        BuiltinFunction::ArraySum | BuiltinFunction::ArrayMin | BuiltinFunction::ArrayMax => {
+            if arguments.len() != 1 {
+                panic!("internal error: incorrect argument count to {f:?}")
+            }
+            let Value::Model(model) = eval_expression(&arguments[0], local_context) else {
+                panic!("First argument not an array");
+            };
+            model.model_tracker().track_row_count_changes();
+            let mut rows = (0..model.row_count()).map(|i| {
+                model.model_tracker().track_row_data_changes(i);
+                model.row_data(i).and_then(|v| f64::try_from(v).ok()).unwrap_or_default()
+            });
+            Value::Number(match f {
+                BuiltinFunction::ArraySum => rows.sum(),
+                BuiltinFunction::ArrayMin => rows.reduce(f64::min).unwrap_or_default(),
+                _ => rows.reduce(f64::max).unwrap_or_default(),
+            })
+        }
         BuiltinFunction::Rgb => {
             let r: i32 = eval_expression(&arguments[0], local_context).try_into().unwrap();
             let g: i32 = eval_expression(&arguments[1], local_context).try_into().unwrap();
@@ -1336,6 +1720,8 @@ fn check_value_type(value: &Value, ty: &Type) -> bool {
         | Type::InferredCallback
         | Type::Callback { .. }
         | Type::Function { .. }
+        | Type::Interface(_)
+        | Type::TypeParameter(_)
         | Type::ElementReference => panic!("not valid property type"),
         Type::Float32 => matches!(value, Value::Number(_)),
         Type::Int32 => matches!(value, Value::Number(_)),
@@ -1359,6 +1745,7 @@ fn check_value_type(value: &Value, ty: &Type) -> bool {
         Type::Array(inner) => {
             matches!(value, Value::Model(m) if m.iter().all(|v| check_value_type(&v, inner)))
         }
+        Type::Optional(inner) => matches!(value, Value::Void) || check_value_type(value, inner),
         Type::Struct { fields, .. } => {
             matches!(value, Value::Struct(str) if str.iter().all(|(k, v)| fields.get(k).map_or(false, |ty| check_value_type(v, ty))))
         }
@@ -1366,7 +1753,7 @@ fn check_value_type(value: &Value, ty: &Type) -> bool {
             matches!(value, Value::EnumerationValue(name, _) if name == en.name.as_str())
         }
         Type::LayoutCache => matches!(value, Value::LayoutCache(_)),
-        Type::ComponentFactory => matches!(value, Value::ComponentFactory(_)),
+        Type::ComponentFactory(_) => matches!(value, Value::ComponentFactory(_)),
     }
 }
 
@@ -1590,6 +1977,55 @@ fn convert_path_element(
     }
 }
 
+/// Return a closure that evaluates the body of a [`ModelOperation`]'s lambda for a row.
+///
+/// The body only depends on the local variables, so they are copied in the closure.
+/// The component is still needed to create the local context.
+fn make_lambda(
+    variable: &str,
+    body: &Expression,
+    local_context: &EvalLocalContext,
+) -> impl Fn(Value) -> Value + 'static {
+    let local_variables = local_context.local_variables.clone();
+    let variable = variable.to_owned();
+    let body = body.clone();
+    let component = match &local_context.component_instance {
+        ComponentInstance::InstanceRef(c) => Ok(c.self_weak().get().unwrap().clone()),
+        ComponentInstance::GlobalComponent(g) => Err((*g).clone()),
+    };
+    move |row| {
+        let mut local_variables = local_variables.clone();
+        local_variables.insert(variable.clone(), row);
+        match &component {
+            Ok(self_weak) => {
+                let Some(self_rc) = self_weak.upgrade() else { return Value::Void };
+                generativity::make_guard!(guard);
+                let self_ = self_rc.unerase(guard);
+                let component_instance = ComponentInstance::InstanceRef(self_.borrow_instance());
+                eval_lambda_body(&body, local_variables, component_instance)
+            }
+            Err(global) => {
+                let component_instance = ComponentInstance::GlobalComponent(global);
+                eval_lambda_body(&body, local_variables, component_instance)
+            }
+        }
+    }
+}
+
+fn eval_lambda_body(
+    body: &Expression,
+    local_variables: HashMap<String, Value>,
+    component_instance: ComponentInstance,
+) -> Value {
+    let mut local_context = EvalLocalContext {
+        local_variables,
+        function_arguments: Vec::new(),
+        component_instance,
+        return_value: None,
+    };
+    eval_expression(body, &mut local_context)
+}
+
 /// Create a value suitable as the default value of a given type
 pub fn default_value_for_type(ty: &Type) -> Value {
     match ty {
@@ -1611,14 +2047,16 @@ pub fn default_value_for_type(ty: &Type) -> Value {
             Value::EnumerationValue(e.name.clone(), e.values.get(e.default_value).unwrap().clone())
         }
         Type::Easing => Value::EasingCurve(Default::default()),
-        Type::Void | Type::Invalid => Value::Void,
+        Type::Void | Type::Invalid | Type::Optional(_) => Value::Void,
         Type::UnitProduct(_) => Value::Number(0.),
         Type::PathData => Value::PathData(Default::default()),
         Type::LayoutCache => Value::LayoutCache(Default::default()),
-        Type::ComponentFactory => Value::ComponentFactory(Default::default()),
+        Type::ComponentFactory(_) => Value::ComponentFactory(Default::default()),
         Type::InferredProperty
         | Type::InferredCallback
         | Type::ElementReference
+        | Type::Interface(_)
+        | Type::TypeParameter(_)
         | Type::Function { .. } => {
             panic!("There can't be such property")
         }
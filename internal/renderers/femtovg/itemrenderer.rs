@@ -10,7 +10,7 @@
 use i_slint_core::graphics::euclid::num::Zero;
 use i_slint_core::graphics::euclid::{self};
 use i_slint_core::graphics::rendering_metrics_collector::RenderingMetrics;
-use i_slint_core::graphics::{IntRect, Point, Size};
+use i_slint_core::graphics::{GradientStop, IntRect, Point, Size};
 use i_slint_core::item_rendering::{
     CachedRenderingData, ItemCache, ItemRenderer, RenderBorderRectangle, RenderImage,
 };
@@ -43,6 +43,9 @@ pub enum ItemGraphicsCacheEntry {
         _original_image: Rc<Texture>,
         colorized_image: Rc<Texture>,
     },
+    /// Gradients that femtovg can't render natively, rendered into textures. Only used for items
+    /// that aren't images, with one texture per brush of the item, such as its fill and stroke.
+    GradientTextures(Vec<(GradientTextureKey, Rc<Texture>)>),
 }
 
 impl ItemGraphicsCacheEntry {
@@ -50,6 +53,7 @@ fn as_texture(&self) -> &Rc<Texture> {
         match self {
             ItemGraphicsCacheEntry::Texture(image) => image,
             ItemGraphicsCacheEntry::ColorizedImage { colorized_image, .. } => colorized_image,
+            ItemGraphicsCacheEntry::GradientTextures(..) => unreachable!(),
         }
     }
     fn is_colorized_image(&self) -> bool {
@@ -59,6 +63,16 @@ fn is_colorized_image(&self) -> bool {
 
 pub(super) type ItemGraphicsCache = ItemCache<Option<ItemGraphicsCacheEntry>>;
 
+/// Identifies the content of a gradient texture: the brush and the size of the path it fills.
+#[derive(Clone, PartialEq)]
+pub struct GradientTextureKey {
+    brush: Brush,
+    size: euclid::default::Size2D<f32>,
+}
+
+/// The maximum number of gradient textures cached per item, one per brush that an item can use.
+const MAX_GRADIENT_TEXTURES_PER_ITEM: usize = 2;
+
 const KAPPA90: f32 = 0.55228;
 
 #[derive(Clone)]
@@ -178,7 +192,9 @@ pub fn global_alpha_transparent(&self) -> bool {
     }
 
     /// Draws a `Rectangle` using the `GLItemRenderer`.
-    pub fn draw_rect(&mut self, size: LogicalSize, brush: Brush) {
+    ///
+    /// Gradients that need to be rendered into a texture are cached with the `item_rc`, if any.
+    pub fn draw_rect(&mut self, size: LogicalSize, brush: Brush, item_rc: Option<&ItemRc>) {
         let geometry = PhysicalRect::from(size * self.scale_factor);
         if geometry.is_empty() {
             return;
@@ -188,7 +204,7 @@ pub fn draw_rect(&mut self, size: LogicalSize, brush: Brush) {
         }
         // TODO: cache path in item to avoid re-tesselation
         let path = rect_to_path(geometry);
-        let paint = match self.brush_to_paint(brush, &path) {
+        let paint = match self.brush_to_paint(brush, &path, item_rc) {
             Some(paint) => paint,
             None => return,
         }
@@ -200,14 +216,19 @@ pub fn draw_rect(&mut self, size: LogicalSize, brush: Brush) {
 }
 
 impl<'a> ItemRenderer for GLItemRenderer<'a> {
-    fn draw_rectangle(&mut self, rect: Pin<&items::Rectangle>, _: &ItemRc, size: LogicalSize) {
-        self.draw_rect(size, rect.background());
+    fn draw_rectangle(
+        &mut self,
+        rect: Pin<&items::Rectangle>,
+        item_rc: &ItemRc,
+        size: LogicalSize,
+    ) {
+        self.draw_rect(size, rect.background(), Some(item_rc));
     }
 
     fn draw_border_rectangle(
         &mut self,
         rect: Pin<&dyn RenderBorderRectangle>,
-        _: &ItemRc,
+        item_rc: &ItemRc,
         size: LogicalSize,
         _: &CachedRenderingData,
     ) {
@@ -260,12 +281,13 @@ fn draw_border_rectangle(
             (background_path, Some(border_path))
         };
 
-        let fill_paint = self.brush_to_paint(rect.background(), &background_path);
+        let fill_paint = self.brush_to_paint(rect.background(), &background_path, Some(item_rc));
 
         let border_paint = self
             .brush_to_paint(
                 rect.border_color(),
                 maybe_border_path.as_ref().unwrap_or(&background_path),
+                Some(item_rc),
             )
             .map(|mut paint| {
                 paint.set_line_width(border_width.get());
@@ -294,7 +316,7 @@ fn draw_image(
         self.draw_image_impl(item_rc, image, size);
     }
 
-    fn draw_text(&mut self, text: Pin<&items::Text>, _: &ItemRc, size: LogicalSize) {
+    fn draw_text(&mut self, text: Pin<&items::Text>, item_rc: &ItemRc, size: LogicalSize) {
         let max_width = size.width_length() * self.scale_factor;
         let max_height = size.height_length() * self.scale_factor;
 
@@ -317,7 +339,7 @@ fn draw_text(&mut self, text: Pin<&items::Text>, _: &ItemRc, size: LogicalSize)
         });
 
         let text_path = rect_to_path((size * self.scale_factor).into());
-        let paint = match self.brush_to_paint(text.color(), &text_path) {
+        let paint = match self.brush_to_paint(text.color(), &text_path, Some(item_rc)) {
             Some(paint) => font.init_paint(text.letter_spacing() * self.scale_factor, paint),
             None => return,
         };
@@ -333,7 +355,7 @@ fn draw_text(&mut self, text: Pin<&items::Text>, _: &ItemRc, size: LogicalSize)
             TextStrokeStyle::Outside => stroke_width * 2.0,
             TextStrokeStyle::Center => stroke_width,
         };
-        let stroke_paint = match self.brush_to_paint(text.stroke(), &text_path) {
+        let stroke_paint = match self.brush_to_paint(text.stroke(), &text_path, Some(item_rc)) {
             Some(mut paint) => {
                 if text.stroke().is_transparent() {
                     None
@@ -376,7 +398,7 @@ fn draw_text(&mut self, text: Pin<&items::Text>, _: &ItemRc, size: LogicalSize)
     fn draw_text_input(
         &mut self,
         text_input: Pin<&items::TextInput>,
-        _: &ItemRc,
+        item_rc: &ItemRc,
         size: LogicalSize,
     ) {
         let width = size.width_length() * self.scale_factor;
@@ -402,6 +424,7 @@ fn draw_text_input(
         let paint = match self.brush_to_paint(
             visual_representation.text_color,
             &rect_to_path((size * self.scale_factor).into()),
+            Some(item_rc),
         ) {
             Some(paint) => font.init_paint(text_input.letter_spacing() * self.scale_factor, paint),
             None => return,
@@ -651,18 +674,20 @@ fn add_point(&mut self, p: Point) {
             }
         }
 
-        let fill_paint = self.brush_to_paint(path.fill(), &femtovg_path).map(|mut fill_paint| {
-            fill_paint.set_fill_rule(match path.fill_rule() {
-                FillRule::Nonzero => femtovg::FillRule::NonZero,
-                FillRule::Evenodd => femtovg::FillRule::EvenOdd,
+        let fill_paint =
+            self.brush_to_paint(path.fill(), &femtovg_path, Some(item_rc)).map(|mut fill_paint| {
+                fill_paint.set_fill_rule(match path.fill_rule() {
+                    FillRule::Nonzero => femtovg::FillRule::NonZero,
+                    FillRule::Evenodd => femtovg::FillRule::EvenOdd,
+                });
+                fill_paint
             });
-            fill_paint
-        });
 
-        let border_paint = self.brush_to_paint(path.stroke(), &femtovg_path).map(|mut paint| {
-            paint.set_line_width((path.stroke_width() * self.scale_factor).get());
-            paint
-        });
+        let border_paint =
+            self.brush_to_paint(path.stroke(), &femtovg_path, Some(item_rc)).map(|mut paint| {
+                paint.set_line_width((path.stroke_width() * self.scale_factor).get());
+                paint
+            });
 
         self.canvas.borrow_mut().save_with(|canvas| {
             canvas.translate(offset.x, offset.y);
@@ -1273,8 +1298,9 @@ fn colorize_image(
         let mut image_rect = femtovg::Path::new();
         image_rect.rect(0., 0., image_size.width, image_size.height);
 
+        // The colorized image is cached with the image item, so the gradient texture is not cached.
         // We fill the entire image, there is no need to apply anti-aliasing around the edges
-        let brush_paint = match self.brush_to_paint(colorize_brush, &image_rect) {
+        let brush_paint = match self.brush_to_paint(colorize_brush, &image_rect, None) {
             Some(paint) => paint.with_anti_alias(false),
             None => return original_cache_entry,
         };
@@ -1514,12 +1540,21 @@ fn draw_image_impl(
         }
     }
 
-    fn brush_to_paint(&self, brush: Brush, path: &femtovg::Path) -> Option<femtovg::Paint> {
+    /// Returns the paint to fill the `path` with the `brush`.
+    ///
+    /// Gradients that femtovg can't render natively are rendered into a texture, which is cached
+    /// with the `item_rc` if any.
+    fn brush_to_paint(
+        &self,
+        brush: Brush,
+        path: &femtovg::Path,
+        item_rc: Option<&ItemRc>,
+    ) -> Option<femtovg::Paint> {
         if brush.is_transparent() {
             return None;
         }
-        Some(match brush {
-            Brush::SolidColor(color) => femtovg::Paint::color(to_femtovg_color(&color)),
+        Some(match &brush {
+            Brush::SolidColor(color) => femtovg::Paint::color(to_femtovg_color(color)),
             Brush::LinearGradient(gradient) => {
                 let path_bounds = path_bounding_box(&self.canvas, path);
 
@@ -1541,20 +1576,145 @@ fn brush_to_paint(&self, brush: Brush, path: &femtovg::Path) -> Option<femtovg::
                 let path_width = path_bounds.width();
                 let path_height = path_bounds.height();
 
-                let stops =
-                    gradient.stops().map(|stop| (stop.position, to_femtovg_color(&stop.color)));
-                femtovg::Paint::radial_gradient_stops(
-                    path_width / 2.,
-                    path_height / 2.,
-                    0.,
-                    (path_width + path_height) / 4.,
-                    stops,
-                )
+                let center_x = gradient.center().x * path_width;
+                let center_y = gradient.center().y * path_height;
+                let radii = gradient.radii([path_width, path_height].into());
+                if radii.width == radii.height {
+                    let stops =
+                        gradient.stops().map(|stop| (stop.position, to_femtovg_color(&stop.color)));
+                    femtovg::Paint::radial_gradient_stops(
+                        center_x,
+                        center_y,
+                        0.,
+                        radii.width,
+                        stops,
+                    )
+                } else {
+                    // femtovg only supports circular gradients, so ellipses are rendered into an
+                    // image that is used to fill the path
+                    self.gradient_image_paint(
+                        &brush,
+                        item_rc,
+                        path_bounds,
+                        gradient.stops(),
+                        |x, y| {
+                            let dx = (x - center_x) / radii.width;
+                            let dy = (y - center_y) / radii.height;
+                            (dx * dx + dy * dy).sqrt()
+                        },
+                    )?
+                }
+            }
+            Brush::ConicGradient(gradient) => {
+                // femtovg doesn't support conic gradients, so the gradient is rendered into an
+                // image that is used to fill the path
+                let path_bounds = path_bounding_box(&self.canvas, path);
+
+                let center_x = gradient.center().x * path_bounds.width().ceil();
+                let center_y = gradient.center().y * path_bounds.height().ceil();
+                self.gradient_image_paint(
+                    &brush,
+                    item_rc,
+                    path_bounds,
+                    gradient.stops(),
+                    |x, y| {
+                        // 0 degree is at the top, and the angle grows clockwise
+                        let angle = (x - center_x).atan2(center_y - y).to_degrees();
+                        ((angle - gradient.angle()) / 360.).rem_euclid(1.)
+                    },
+                )?
             }
             _ => return None,
         })
     }
 
+    /// Renders a gradient into an image covering `path_bounds`, and returns a paint filling with
+    /// that image. `position` returns the position in the gradient of the pixel at `x`, `y`.
+    ///
+    /// The image is cached with the `item_rc`, keyed by the `brush` and the size of the path.
+    fn gradient_image_paint<'b>(
+        &self,
+        brush: &Brush,
+        item_rc: Option<&ItemRc>,
+        path_bounds: euclid::default::Box2D<f32>,
+        stops: impl Iterator<Item = &'b GradientStop>,
+        position: impl Fn(f32, f32) -> f32,
+    ) -> Option<femtovg::Paint> {
+        let width = path_bounds.width().ceil() as usize;
+        let height = path_bounds.height().ceil() as usize;
+        if width == 0 || height == 0 {
+            return None;
+        }
+        let key = GradientTextureKey { brush: brush.clone(), size: path_bounds.size() };
+        let cached_texture = item_rc.and_then(|item_rc| {
+            self.graphics_cache.with_entry(item_rc, |cache_entry| match cache_entry {
+                Some(ItemGraphicsCacheEntry::GradientTextures(textures)) => {
+                    textures.iter().find(|(k, _)| *k == key).map(|(_, texture)| texture.clone())
+                }
+                _ => None,
+            })
+        });
+        let texture = match cached_texture {
+            Some(texture) => texture,
+            None => {
+                let pixels = gradient_pixels(stops, width, height, position);
+                let image_id = self
+                    .canvas
+                    .borrow_mut()
+                    .create_image(
+                        imgref::Img::new(pixels.as_slice(), width, height),
+                        femtovg::ImageFlags::PREMULTIPLIED,
+                    )
+                    .ok()?;
+                let texture = Texture::adopt(&self.canvas, image_id);
+                match item_rc {
+                    Some(item_rc) => self.cache_gradient_texture(item_rc, key, texture.clone()),
+                    // The image can only be deleted once the path was drawn
+                    None => self.textures_to_delete_after_flush.borrow_mut().push(texture.clone()),
+                }
+                texture
+            }
+        };
+        Some(femtovg::Paint::image(
+            texture.id,
+            path_bounds.min.x,
+            path_bounds.min.y,
+            width as f32,
+            height as f32,
+            0.,
+            1.,
+        ))
+    }
+
+    /// Adds the texture of a gradient to the cache entry of the item. The oldest textures of
+    /// the item are released, since they are for brushes that the item doesn't use anymore.
+    fn cache_gradient_texture(
+        &self,
+        item_rc: &ItemRc,
+        key: GradientTextureKey,
+        texture: Rc<Texture>,
+    ) {
+        let mut textures = self
+            .graphics_cache
+            .with_entry(item_rc, |cache_entry| match cache_entry {
+                Some(ItemGraphicsCacheEntry::GradientTextures(textures)) => Some(textures.clone()),
+                _ => None,
+            })
+            .unwrap_or_default();
+        if textures.len() >= MAX_GRADIENT_TEXTURES_PER_ITEM {
+            let stale = textures.len() + 1 - MAX_GRADIENT_TEXTURES_PER_ITEM;
+            // The images might have been used for paths drawn in this frame already
+            self.textures_to_delete_after_flush
+                .borrow_mut()
+                .extend(textures.drain(..stale).map(|(_, texture)| texture));
+        }
+        textures.push((key, texture));
+        self.graphics_cache.release(item_rc);
+        self.graphics_cache.get_or_update_cache_entry(item_rc, || {
+            Some(ItemGraphicsCacheEntry::GradientTextures(textures))
+        });
+    }
+
     fn current_render_target(&self) -> femtovg::RenderTarget {
         self.state.last().unwrap().current_render_target
     }
@@ -1563,3 +1723,48 @@ fn current_render_target(&self) -> femtovg::RenderTarget {
 pub fn to_femtovg_color(col: &Color) -> femtovg::Color {
     femtovg::Color::rgba(col.red(), col.green(), col.blue(), col.alpha())
 }
+
+/// Computes the premultiplied pixels of a gradient covering a `width`x`height` image, where
+/// `position` returns the position in the gradient of the pixel at `x`, `y`.
+fn gradient_pixels<'a>(
+    stops: impl Iterator<Item = &'a GradientStop>,
+    width: usize,
+    height: usize,
+    position: impl Fn(f32, f32) -> f32,
+) -> Vec<rgb::RGBA8> {
+    use i_slint_core::properties::InterpolatedPropertyValue;
+    let stops = stops.collect::<Vec<_>>();
+    let color_at = |pos: f32| {
+        let Some(first) = stops.first() else { return Color::default() };
+        let mut prev = first;
+        if pos <= prev.position {
+            return prev.color;
+        }
+        for next in &stops[1..] {
+            if pos <= next.position {
+                let range = next.position - prev.position;
+                return if range > 0. {
+                    prev.color.interpolate(&next.color, (pos - prev.position) / range)
+                } else {
+                    next.color
+                };
+            }
+            prev = next;
+        }
+        prev.color
+    };
+    let mut pixels = Vec::with_capacity(width * height);
+    for y in 0..height {
+        for x in 0..width {
+            let color = color_at(position(x as f32 + 0.5, y as f32 + 0.5));
+            let alpha = color.alpha() as u16;
+            pixels.push(rgb::RGBA8 {
+                r: (color.red() as u16 * alpha / 255) as u8,
+                g: (color.green() as u16 * alpha / 255) as u8,
+                b: (color.blue() as u16 * alpha / 255) as u8,
+                a: color.alpha(),
+            });
+        }
+    }
+    pixels
+}
@@ -282,6 +282,7 @@ fn internal_render_with_post_callback(
                                 window.size().to_logical(window_inner.scale_factor()),
                             ),
                             brush,
+                            None,
                         );
                     }
                 }
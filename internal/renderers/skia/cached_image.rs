@@ -132,6 +132,16 @@ pub(crate) fn as_skia_image(
         ImageInner::NineSlice(n) => {
             as_skia_image(n.image(), target_size_fn, ImageFit::Preserve, scale_factor, canvas)
         }
+        ImageInner::Animated(animated) => as_skia_image(
+            animated.frame_at(0, false),
+            target_size_fn,
+            image_fit,
+            scale_factor,
+            canvas,
+        ),
+        ImageInner::Pending(pending) => {
+            as_skia_image(pending.image(), target_size_fn, image_fit, scale_factor, canvas)
+        }
     }
 }
 
@@ -102,7 +102,7 @@ fn brush_to_shader(
             Brush::RadialGradient(g) => {
                 let (colors, pos): (Vec<_>, Vec<_>) =
                     g.stops().map(|s| (to_skia_color(&s.color), s.position)).unzip();
-                let circle_scale = width.max(height) / 2.;
+                let radii = g.radii([width.get(), height.get()].into());
                 skia_safe::gradient_shader::radial(
                     skia_safe::Point::new(0., 0.),
                     1.,
@@ -110,11 +110,28 @@ fn brush_to_shader(
                     Some(&*pos),
                     TileMode::Clamp,
                     skia_safe::gradient_shader::Flags::INTERPOLATE_COLORS_IN_PREMUL,
-                    skia_safe::Matrix::scale((circle_scale.get(), circle_scale.get()))
-                        .post_translate((width.get() / 2., height.get() / 2.))
+                    skia_safe::Matrix::scale((radii.width, radii.height))
+                        .post_translate((g.center().x * width.get(), g.center().y * height.get()))
                         as &skia_safe::Matrix,
                 )
             }
+            Brush::ConicGradient(g) => {
+                let (colors, pos): (Vec<_>, Vec<_>) =
+                    g.stops().map(|s| (to_skia_color(&s.color), s.position)).unzip();
+                let center =
+                    skia_safe::Point::new(g.center().x * width.get(), g.center().y * height.get());
+                // Skia's sweep gradients start at 3 o'clock, while the angle of conic gradients
+                // starts at the top
+                skia_safe::gradient_shader::sweep(
+                    center,
+                    skia_safe::gradient_shader::GradientShaderColors::Colors(&colors),
+                    Some(&*pos),
+                    TileMode::Clamp,
+                    (0., 360.),
+                    skia_safe::gradient_shader::Flags::INTERPOLATE_COLORS_IN_PREMUL,
+                    &skia_safe::Matrix::rotate_deg_pivot(g.angle() - 90., center),
+                )
+            }
             _ => None,
         }
     }
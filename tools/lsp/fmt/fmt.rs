@@ -105,7 +105,7 @@ fn format_node(
         SyntaxKind::ConditionalExpression => {
             return format_conditional_expression(node, writer, state);
         }
-        SyntaxKind::Expression => {
+        SyntaxKind::Expression | SyntaxKind::ForRangeEnd => {
             return format_expression(node, writer, state);
         }
         SyntaxKind::CodeBlock => {
@@ -114,6 +114,18 @@ fn format_node(
         SyntaxKind::ReturnStatement => {
             return format_return_statement(node, writer, state);
         }
+        SyntaxKind::LetStatement => {
+            return format_let_statement(node, writer, state);
+        }
+        SyntaxKind::ForStatement => {
+            return format_for_statement(node, writer, state);
+        }
+        SyntaxKind::BreakStatement | SyntaxKind::ContinueStatement => {
+            return format_loop_control_statement(node, writer, state);
+        }
+        SyntaxKind::Lambda => {
+            return format_lambda(node, writer, state);
+        }
         SyntaxKind::AtGradient => {
             return format_at_gradient(node, writer, state);
         }
@@ -640,6 +652,7 @@ fn format_binary_expression(
                 SyntaxKind::MinusEqual,
                 SyntaxKind::StarEqual,
                 SyntaxKind::DivEqual,
+                SyntaxKind::QuestionQuestion,
             ],
             writer,
             state,
@@ -766,6 +779,75 @@ fn format_return_statement(
     Ok(())
 }
 
+fn format_let_statement(
+    node: &SyntaxNode,
+    writer: &mut impl TokenWriter,
+    state: &mut FormatState,
+) -> Result<(), std::io::Error> {
+    let mut sub = node.children_with_tokens();
+    whitespace_to(&mut sub, SyntaxKind::Identifier, writer, state, "")?;
+    whitespace_to(&mut sub, SyntaxKind::DeclaredIdentifier, writer, state, " ")?;
+    if node.child_node(SyntaxKind::Type).is_some() {
+        whitespace_to(&mut sub, SyntaxKind::Colon, writer, state, "")?;
+        whitespace_to(&mut sub, SyntaxKind::Type, writer, state, " ")?;
+    }
+    whitespace_to(&mut sub, SyntaxKind::Equal, writer, state, " ")?;
+    whitespace_to(&mut sub, SyntaxKind::Expression, writer, state, " ")?;
+    whitespace_to(&mut sub, SyntaxKind::Semicolon, writer, state, "")?;
+    state.new_line();
+    finish_node(sub, writer, state)?;
+    Ok(())
+}
+
+fn format_for_statement(
+    node: &SyntaxNode,
+    writer: &mut impl TokenWriter,
+    state: &mut FormatState,
+) -> Result<(), std::io::Error> {
+    let mut sub = node.children_with_tokens();
+    let ok = whitespace_to(&mut sub, SyntaxKind::Identifier, writer, state, "")?
+        && whitespace_to(&mut sub, SyntaxKind::DeclaredIdentifier, writer, state, " ")?
+        && whitespace_to(&mut sub, SyntaxKind::Identifier, writer, state, " ")?
+        && whitespace_to(&mut sub, SyntaxKind::Expression, writer, state, " ")?
+        && (node.child_node(SyntaxKind::ForRangeEnd).is_none()
+            || whitespace_to(&mut sub, SyntaxKind::ForRangeEnd, writer, state, "")?)
+        && whitespace_to(&mut sub, SyntaxKind::CodeBlock, writer, state, " ")?;
+    if ok {
+        state.whitespace_to_add = None;
+        state.new_line();
+    }
+    finish_node(sub, writer, state)?;
+    Ok(())
+}
+
+fn format_loop_control_statement(
+    node: &SyntaxNode,
+    writer: &mut impl TokenWriter,
+    state: &mut FormatState,
+) -> Result<(), std::io::Error> {
+    let mut sub = node.children_with_tokens();
+    whitespace_to(&mut sub, SyntaxKind::Identifier, writer, state, "")?;
+    if node.child_token(SyntaxKind::Semicolon).is_some() {
+        whitespace_to(&mut sub, SyntaxKind::Semicolon, writer, state, "")?;
+    }
+    state.new_line();
+    finish_node(sub, writer, state)?;
+    Ok(())
+}
+
+fn format_lambda(
+    node: &SyntaxNode,
+    writer: &mut impl TokenWriter,
+    state: &mut FormatState,
+) -> Result<(), std::io::Error> {
+    let mut sub = node.children_with_tokens();
+    let _ok = whitespace_to(&mut sub, SyntaxKind::DeclaredIdentifier, writer, state, "")?
+        && whitespace_to(&mut sub, SyntaxKind::FatArrow, writer, state, " ")?
+        && whitespace_to(&mut sub, SyntaxKind::Expression, writer, state, " ")?;
+    finish_node(sub, writer, state)?;
+    Ok(())
+}
+
 fn format_at_gradient(
     node: &SyntaxNode,
     writer: &mut impl TokenWriter,
@@ -1757,6 +1839,93 @@ fn code_block() {
         );
     }
 
+    #[test]
+    fn let_statement() {
+        assert_formatting(
+            r#"
+component ABC {
+    function compute() -> int {
+        let   a=42;  let b : int
+            = a*2;
+        if (b > a) { let c:string="x"; debug(c); }
+        b
+    }
+}
+"#,
+            r#"
+component ABC {
+    function compute() -> int {
+        let a = 42;
+        let b: int = a * 2;
+        if (b > a) {
+            let c: string = "x";
+            debug(c);
+        }
+        b
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn for_statement() {
+        assert_formatting(
+            r#"
+component ABC {
+    in-out property <[int]> values;
+    in-out property <int> total;
+    function compute() {
+        for   x   in values{ total += x; }
+        for i in 0 .. 10 {
+        if (i == 5) { break; } if (i == 2) { continue; }
+            total += i; }
+    }
+}
+"#,
+            r#"
+component ABC {
+    in-out property <[int]> values;
+    in-out property <int> total;
+    function compute() {
+        for x in values {
+            total += x;
+        }
+        for i in 0..10 {
+            if (i == 5) {
+                break;
+            }
+            if (i == 2) {
+                continue;
+            }
+            total += i;
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn lambda() {
+        assert_formatting(
+            r#"
+component ABC {
+    in-out property <[int]> values;
+    out property <[int]> big: values.filter(x=>x>2);
+    out property <[int]> doubled: values.map(x   =>   x*2).sort-by(x=>-x);
+}
+"#,
+            r#"
+component ABC {
+    in-out property <[int]> values;
+    out property <[int]> big: values.filter(x => x > 2);
+    out property <[int]> doubled: values.map(x => x * 2).sort-by(x => -x);
+}
+"#,
+        );
+    }
+
     #[test]
     fn trailing_comma_array() {
         assert_formatting(
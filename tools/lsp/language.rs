@@ -5,10 +5,15 @@
 
 pub mod completion;
 mod component_catalog;
+mod folding_range;
 mod formatting;
 mod goto;
+mod inlay_hints;
 pub mod properties;
+mod quick_fixes;
+mod references;
 mod semantic_tokens;
+mod signature_help;
 #[cfg(test)]
 pub mod test;
 
@@ -26,18 +31,22 @@
     langtype::Type,
 };
 use i_slint_compiler::{typeloader::TypeLoader, typeregister::TypeRegister};
+use itertools::Either;
 use lsp_types::request::{
     CodeActionRequest, CodeLensRequest, ColorPresentationRequest, Completion, DocumentColor,
-    DocumentHighlightRequest, DocumentSymbolRequest, ExecuteCommand, Formatting, GotoDefinition,
-    HoverRequest, PrepareRenameRequest, Rename, SemanticTokensFullRequest,
+    DocumentHighlightRequest, DocumentSymbolRequest, ExecuteCommand, FoldingRangeRequest,
+    Formatting, GotoDefinition, HoverRequest, InlayHintRequest, PrepareRenameRequest, References,
+    Rename, SemanticTokensFullRequest, SignatureHelpRequest, WorkspaceSymbolRequest,
 };
 use lsp_types::{
     ClientCapabilities, CodeActionOrCommand, CodeActionProviderCapability, CodeLens,
     CodeLensOptions, Color, ColorInformation, ColorPresentation, Command, CompletionOptions,
-    DocumentSymbol, DocumentSymbolResponse, Hover, InitializeParams, InitializeResult, OneOf,
-    Position, PrepareRenameResponse, PublishDiagnosticsParams, RenameOptions,
-    SemanticTokensFullOptions, SemanticTokensLegend, SemanticTokensOptions, ServerCapabilities,
-    ServerInfo, TextDocumentSyncCapability, TextEdit, Url, WorkDoneProgressOptions,
+    DocumentSymbol, DocumentSymbolResponse, FoldingRangeProviderCapability, Hover,
+    InitializeParams, InitializeResult, OneOf, Position, PrepareRenameResponse,
+    PublishDiagnosticsParams, RenameOptions, SemanticTokensFullOptions, SemanticTokensLegend,
+    SemanticTokensOptions, ServerCapabilities, ServerInfo, SignatureHelpOptions,
+    TextDocumentSyncCapability, TextEdit, Url, WorkDoneProgressOptions, WorkspaceSymbol,
+    WorkspaceSymbolResponse,
 };
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -182,6 +191,7 @@ pub fn server_initialize_result(client_cap: &ClientCapabilities) -> InitializeRe
                 completion_item: None,
             }),
             definition_provider: Some(OneOf::Left(true)),
+            references_provider: Some(OneOf::Left(true)),
             text_document_sync: Some(TextDocumentSyncCapability::Kind(
                 lsp_types::TextDocumentSyncKind::FULL,
             )),
@@ -191,6 +201,8 @@ pub fn server_initialize_result(client_cap: &ClientCapabilities) -> InitializeRe
                 ..Default::default()
             }),
             document_symbol_provider: Some(OneOf::Left(true)),
+            workspace_symbol_provider: Some(OneOf::Left(true)),
+            folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
             color_provider: Some(true.into()),
             code_lens_provider: Some(CodeLensOptions { resolve_provider: Some(true) }),
             semantic_tokens_provider: Some(
@@ -222,6 +234,12 @@ pub fn server_initialize_result(client_cap: &ClientCapabilities) -> InitializeRe
                 },
             ),
             document_formatting_provider: Some(OneOf::Left(true)),
+            inlay_hint_provider: Some(OneOf::Left(true)),
+            signature_help_provider: Some(SignatureHelpOptions {
+                trigger_characters: Some(vec!["(".to_owned(), ",".to_owned()]),
+                retrigger_characters: None,
+                work_done_progress_options: WorkDoneProgressOptions::default(),
+            }),
             ..ServerCapabilities::default()
         },
         server_info: Some(ServerInfo {
@@ -243,6 +261,28 @@ pub fn register_request_handlers(rh: &mut RequestHandler) {
         .and_then(|token| goto::goto_definition(document_cache, token.0));
         Ok(result)
     });
+    rh.register::<SignatureHelpRequest, _>(|params, ctx| async move {
+        let document_cache = &mut ctx.document_cache.borrow_mut();
+        let result = token_descr(
+            document_cache,
+            &params.text_document_position_params.text_document.uri,
+            &params.text_document_position_params.position,
+        )
+        .and_then(|token| signature_help::get_signature_help(document_cache, token.0, token.1));
+        Ok(result)
+    });
+    rh.register::<References, _>(|params, ctx| async move {
+        let document_cache = &mut ctx.document_cache.borrow_mut();
+        let result = token_descr(
+            document_cache,
+            &params.text_document_position.text_document.uri,
+            &params.text_document_position.position,
+        )
+        .and_then(|token| {
+            references::find_references(document_cache, token.0, params.context.include_declaration)
+        });
+        Ok(result)
+    });
     rh.register::<Completion, _>(|params, ctx| async move {
         let document_cache = &mut ctx.document_cache.borrow_mut();
 
@@ -282,11 +322,19 @@ pub fn register_request_handlers(rh: &mut RequestHandler) {
     rh.register::<CodeActionRequest, _>(|params, ctx| async move {
         let document_cache = &mut ctx.document_cache.borrow_mut();
 
-        let result = token_descr(document_cache, &params.text_document.uri, &params.range.start)
-            .and_then(|(token, _)| {
-                get_code_actions(document_cache, token, &ctx.init_param.capabilities)
-            });
-        Ok(result)
+        let mut result = quick_fixes::get_quick_fixes(
+            document_cache,
+            &params.text_document.uri,
+            &params.context.diagnostics,
+        );
+        result.extend(
+            token_descr(document_cache, &params.text_document.uri, &params.range.start)
+                .and_then(|(token, _)| {
+                    get_code_actions(document_cache, token, &ctx.init_param.capabilities)
+                })
+                .unwrap_or_default(),
+        );
+        Ok((!result.is_empty()).then_some(result))
     });
     rh.register::<ExecuteCommand, _>(|params, ctx| async move {
         if params.command.as_str() == SHOW_PREVIEW_COMMAND {
@@ -337,6 +385,14 @@ pub fn register_request_handlers(rh: &mut RequestHandler) {
         let document_cache = &mut ctx.document_cache.borrow_mut();
         Ok(get_document_symbols(document_cache, &params.text_document))
     });
+    rh.register::<WorkspaceSymbolRequest, _>(|params, ctx| async move {
+        let document_cache = &mut ctx.document_cache.borrow_mut();
+        Ok(get_workspace_symbols(document_cache, &params.query))
+    });
+    rh.register::<FoldingRangeRequest, _>(|params, ctx| async move {
+        let document_cache = &mut ctx.document_cache.borrow_mut();
+        Ok(folding_range::get_folding_ranges(document_cache, &params.text_document))
+    });
     rh.register::<CodeLensRequest, _>(|params, ctx| async move {
         let document_cache = &mut ctx.document_cache.borrow_mut();
         Ok(get_code_lenses(document_cache, &params.text_document))
@@ -345,6 +401,10 @@ pub fn register_request_handlers(rh: &mut RequestHandler) {
         let document_cache = &mut ctx.document_cache.borrow_mut();
         Ok(semantic_tokens::get_semantic_tokens(document_cache, &params.text_document))
     });
+    rh.register::<InlayHintRequest, _>(|params, ctx| async move {
+        let document_cache = &mut ctx.document_cache.borrow_mut();
+        Ok(inlay_hints::get_inlay_hints(document_cache, &params.text_document, &params.range))
+    });
     rh.register::<DocumentHighlightRequest, _>(|_params, ctx| async move {
         let document_cache = &mut ctx.document_cache.borrow_mut();
         let uri = _params.text_document_position_params.text_document.uri;
@@ -865,6 +925,8 @@ fn get_code_actions(
     let uri = Url::from_file_path(token.source_file.path()).ok()?;
     let mut result = vec![];
 
+    result.extend(quick_fixes::add_missing_struct_fields(document_cache, &token));
+
     let component = syntax_nodes::Component::new(node.clone())
         .or_else(|| {
             syntax_nodes::DeclaredIdentifier::new(node.clone())
@@ -1201,6 +1263,75 @@ fn gen_children(elem: &ElementRc, ds: &DocumentSymbol) -> Option<Vec<DocumentSym
     Some(r.into())
 }
 
+fn get_workspace_symbols(
+    document_cache: &mut DocumentCache,
+    query: &str,
+) -> Option<WorkspaceSymbolResponse> {
+    let query = query.to_lowercase();
+    // All the characters of the query must appear in the name, in order
+    let matches_query = |name: &str| {
+        let mut name = name.chars().flat_map(char::to_lowercase);
+        query.chars().all(|q| name.any(|c| c == q))
+    };
+
+    let mut r = vec![];
+    for (path, doc) in document_cache.documents.all_file_documents() {
+        if path.starts_with("builtin:/") {
+            continue;
+        }
+        for (exported_name, item) in doc.exports.iter() {
+            if !matches_query(&exported_name.name) {
+                continue;
+            }
+            let (node, kind) = match item {
+                Either::Left(c) => {
+                    let Some(node) =
+                        c.root_element.borrow().debug.first().and_then(|d| d.0.parent())
+                    else {
+                        continue;
+                    };
+                    let kind = if c.is_global() {
+                        lsp_types::SymbolKind::OBJECT
+                    } else {
+                        lsp_types::SymbolKind::CLASS
+                    };
+                    (node, kind)
+                }
+                Either::Right(Type::Struct { node: Some(node), .. }) => {
+                    let Some(node) = node.parent() else { continue };
+                    (node, lsp_types::SymbolKind::STRUCT)
+                }
+                Either::Right(Type::Enumeration(enumeration)) => {
+                    let Some(node) = enumeration.node.clone() else { continue };
+                    (node.into(), lsp_types::SymbolKind::ENUM)
+                }
+                Either::Right(Type::Interface(interface)) => {
+                    let Some(node) = interface.node.clone() else { continue };
+                    (node.into(), lsp_types::SymbolKind::INTERFACE)
+                }
+                _ => continue,
+            };
+            // Re-exports are reported by the document that declares the item
+            if node.source_file.path() != path {
+                continue;
+            }
+            let Some((uri, range)) = util::map_node_and_url(&node) else { continue };
+            r.push(WorkspaceSymbol {
+                name: exported_name.name.clone(),
+                kind,
+                tags: None,
+                container_name: None,
+                location: OneOf::Left(lsp_types::Location { uri, range }),
+                data: None,
+            });
+        }
+    }
+
+    r.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Some(WorkspaceSymbolResponse::Nested(r))
+}
+
 fn get_code_lenses(
     document_cache: &mut DocumentCache,
     text_document: &lsp_types::TextDocumentIdentifier,
@@ -1681,6 +1812,47 @@ macro_rules! tree {
         check_start_with(tree!(1 0 1 0).range.start, "ta := TouchArea");
     }
 
+    #[test]
+    fn test_workspace_symbols() {
+        let source = r#"import { Button } from "std-widgets.slint";
+        enum TheEnum { Abc, Def }
+        component FooBar { }
+        export struct Str { abc: string }
+        export { TheEnum }
+        export global SomeGlobal {
+            in-out property<Str> prop;
+        }
+        export component TestWindow inherits Window {
+            FooBar {}
+        }
+        "#;
+
+        let (mut dc, uri, _) = test::loaded_document_cache(source.into());
+
+        let mut names = |query: &str| {
+            let Some(WorkspaceSymbolResponse::Nested(result)) =
+                get_workspace_symbols(&mut dc, query)
+            else {
+                panic!("not nested")
+            };
+            assert!(result.iter().all(|s| matches!(&s.location, OneOf::Left(l) if l.uri == uri)));
+            result.into_iter().map(|s| (s.name, s.kind)).collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            names(""),
+            [
+                ("SomeGlobal".to_string(), lsp_types::SymbolKind::OBJECT),
+                ("Str".to_string(), lsp_types::SymbolKind::STRUCT),
+                ("TestWindow".to_string(), lsp_types::SymbolKind::CLASS),
+                ("TheEnum".to_string(), lsp_types::SymbolKind::ENUM),
+            ]
+        );
+        assert_eq!(names("tw"), [("TestWindow".to_string(), lsp_types::SymbolKind::CLASS)]);
+        assert_eq!(names("glob"), [("SomeGlobal".to_string(), lsp_types::SymbolKind::OBJECT)]);
+        assert!(names("FooBar").is_empty());
+    }
+
     #[test]
     fn test_code_actions() {
         let (mut dc, url, _) = loaded_document_cache(
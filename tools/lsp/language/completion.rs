@@ -197,6 +197,10 @@ pub(crate) fn completion_at(
         SyntaxKind::BindingExpression
             | SyntaxKind::CodeBlock
             | SyntaxKind::ReturnStatement
+            | SyntaxKind::LetStatement
+            | SyntaxKind::ForStatement
+            | SyntaxKind::Lambda
+            | SyntaxKind::ForRangeEnd
             | SyntaxKind::Expression
             | SyntaxKind::FunctionCallExpression
             | SyntaxKind::SelfAssignment
@@ -218,6 +222,7 @@ pub(crate) fn completion_at(
                     ("image-url", "image-url(\"$1\")"),
                     ("linear-gradient", "linear-gradient($1)"),
                     ("radial-gradient", "radial-gradient(circle, $1)"),
+                    ("conic-gradient", "conic-gradient($1)"),
                 ]
                 .into_iter()
                 .map(|(label, insert)| {
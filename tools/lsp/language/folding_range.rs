@@ -0,0 +1,139 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.2 OR LicenseRef-Slint-commercial
+
+use super::DocumentCache;
+use crate::util::map_position;
+
+use i_slint_compiler::parser::{SyntaxKind, SyntaxNode, TextRange};
+
+use lsp_types::{FoldingRange, FoldingRangeKind};
+
+/// Return the folding ranges of the document: elements, states, transitions, code blocks,
+/// declarations, multi-line comments, and the list of imports.
+pub fn get_folding_ranges(
+    document_cache: &mut DocumentCache,
+    text_document: &lsp_types::TextDocumentIdentifier,
+) -> Option<Vec<FoldingRange>> {
+    let filepath = super::uri_to_file(&text_document.uri)?;
+    let doc = document_cache.documents.get_document(&filepath)?;
+    let doc_node = doc.node.as_ref()?;
+
+    let mut result = vec![];
+    let mut push = |range: TextRange, kind: Option<FoldingRangeKind>| {
+        let start_line = map_position(&doc_node.source_file, range.start()).line;
+        let end_line = map_position(&doc_node.source_file, range.end()).line;
+        if end_line > start_line {
+            result.push(FoldingRange { start_line, end_line, kind, ..Default::default() });
+        }
+    };
+
+    // Consecutive imports are folded together
+    let mut imports: Option<TextRange> = None;
+    for child in doc_node.children() {
+        if child.kind() == SyntaxKind::ImportSpecifier {
+            let range = trimmed_range(&child);
+            imports = Some(imports.map_or(range, |r| r.cover(range)));
+        } else if let Some(range) = imports.take() {
+            push(range, Some(FoldingRangeKind::Imports));
+        }
+    }
+    if let Some(range) = imports {
+        push(range, Some(FoldingRangeKind::Imports));
+    }
+
+    for element in doc_node.node.descendants_with_tokens() {
+        match element {
+            rowan::NodeOrToken::Node(node) => {
+                if matches!(
+                    node.kind(),
+                    SyntaxKind::Element
+                        | SyntaxKind::States
+                        | SyntaxKind::State
+                        | SyntaxKind::Transitions
+                        | SyntaxKind::Transition
+                        | SyntaxKind::PropertyAnimation
+                        | SyntaxKind::CodeBlock
+                        | SyntaxKind::StructDeclaration
+                        | SyntaxKind::EnumDeclaration
+                        | SyntaxKind::InterfaceDeclaration
+                ) {
+                    let node = SyntaxNode { node, source_file: doc_node.source_file.clone() };
+                    push(trimmed_range(&node), None);
+                }
+            }
+            rowan::NodeOrToken::Token(token) => {
+                if token.kind() == SyntaxKind::Comment {
+                    push(token.text_range(), Some(FoldingRangeKind::Comment));
+                }
+            }
+        }
+    }
+
+    result.sort_by_key(|r| (r.start_line, r.end_line));
+    // An element and its code block may span the same lines
+    result.dedup_by_key(|r| (r.start_line, r.end_line));
+    Some(result)
+}
+
+/// The range of the node without the leading and trailing whitespace and comments
+fn trimmed_range(node: &SyntaxNode) -> TextRange {
+    let mut tokens = node
+        .node
+        .descendants_with_tokens()
+        .filter_map(|t| t.into_token())
+        .filter(|t| !matches!(t.kind(), SyntaxKind::Whitespace | SyntaxKind::Comment));
+    match tokens.next() {
+        Some(first) => {
+            let last = tokens.last().unwrap_or_else(|| first.clone());
+            first.text_range().cover(last.text_range())
+        }
+        None => node.text_range(),
+    }
+}
+
+#[test]
+fn test_folding_ranges() {
+    let source = r#"import { Button } from "std-widgets.slint";
+import {
+    LineEdit
+} from "std-widgets.slint";
+
+/* A multi-line
+   comment */
+enum E { A, B }
+export component Test {
+    property <bool> checked;
+    states [
+        checked when checked: {
+            r.color: red;
+        }
+    ]
+    r := Rectangle {
+        TouchArea {
+            clicked => {
+                checked = !checked;
+            }
+        }
+    }
+}"#;
+
+    let (mut dc, uri, _) = crate::language::test::loaded_document_cache(source.into());
+    let ranges = get_folding_ranges(&mut dc, &lsp_types::TextDocumentIdentifier { uri })
+        .unwrap()
+        .into_iter()
+        .map(|r| (r.start_line, r.end_line, r.kind))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ranges,
+        [
+            (0, 3, Some(FoldingRangeKind::Imports)),
+            (5, 6, Some(FoldingRangeKind::Comment)),
+            (8, 22, None),
+            (10, 14, None),
+            (11, 13, None),
+            (15, 21, None),
+            (16, 20, None),
+            (17, 19, None),
+        ]
+    );
+}
@@ -23,12 +23,13 @@ pub fn goto_definition(
         if let Some(n) = syntax_nodes::QualifiedName::new(node.clone()) {
             let parent = n.parent()?;
             return match parent.kind() {
-                SyntaxKind::Type => {
+                SyntaxKind::Type | SyntaxKind::ImplementsSpecifier => {
                     let qual = i_slint_compiler::object_tree::QualifiedTypeName::from_node(n);
                     let doc = document_cache.documents.get_document(node.source_file.path())?;
                     match doc.local_registry.lookup_qualified(&qual.members) {
                         Type::Struct { node: Some(node), .. } => goto_node(node.parent().as_ref()?),
                         Type::Enumeration(e) => goto_node(e.node.as_ref()?),
+                        Type::Interface(i) => goto_node(i.node.as_ref()?),
                         _ => None,
                     }
                 }
@@ -0,0 +1,254 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.2 OR LicenseRef-Slint-commercial
+
+use super::DocumentCache;
+use crate::util::{declaration_node, lookup_qualified_name, map_node, map_position};
+
+use i_slint_compiler::expression_tree::Expression;
+use i_slint_compiler::langtype::Type;
+use i_slint_compiler::lookup::LookupResult;
+use i_slint_compiler::namedreference::NamedReference;
+use i_slint_compiler::object_tree::{recurse_elem, PropertyVisibility};
+use i_slint_compiler::parser::{
+    identifier_text, normalize_identifier, syntax_nodes, SyntaxKind, SyntaxNode,
+};
+
+use lsp_types::{InlayHint, InlayHintKind, InlayHintLabel, Position, Range};
+
+/// Return the inlay hints for the document within the given range:
+///  * The type of properties declared without a type (two way bindings)
+///  * The name of the parameters in function and callback calls
+///  * The value of global properties that are bound to a literal
+pub fn get_inlay_hints(
+    document_cache: &mut DocumentCache,
+    text_document: &lsp_types::TextDocumentIdentifier,
+    range: &Range,
+) -> Option<Vec<InlayHint>> {
+    let filepath = super::uri_to_file(&text_document.uri)?;
+    let doc = document_cache.documents.get_document(&filepath)?;
+    let doc_node = doc.node.clone()?;
+
+    let mut result = vec![];
+
+    for component in &doc.inner_components {
+        recurse_elem(&component.root_element, &(), &mut |elem, _| {
+            for decl in elem.borrow().property_declarations.values() {
+                let Some(node) = decl.node.clone().and_then(syntax_nodes::PropertyDeclaration::new)
+                else {
+                    continue;
+                };
+                if node.Type().is_some() || !decl.property_type.is_property_type() {
+                    continue;
+                }
+                let Some(range) = map_node(&node.DeclaredIdentifier()) else { continue };
+                result.push(InlayHint {
+                    padding_right: Some(true),
+                    ..hint(
+                        range.start,
+                        format!("<{}>", decl.property_type),
+                        Some(InlayHintKind::TYPE),
+                    )
+                });
+            }
+        });
+    }
+
+    for node in doc_node.node.descendants() {
+        let node = SyntaxNode { node, source_file: doc_node.source_file.clone() };
+        if let Some(call) = syntax_nodes::FunctionCallExpression::new(node.clone()) {
+            parameter_hints(document_cache, call, &mut result);
+        } else if let Some(qn) = syntax_nodes::QualifiedName::new(node) {
+            if qn.parent().map_or(false, |p| p.kind() == SyntaxKind::Expression) {
+                if let Some(value) = global_constant_hint(document_cache, qn) {
+                    result.push(value);
+                }
+            }
+        }
+    }
+
+    result.retain(|h| h.position >= range.start && h.position <= range.end);
+    result.sort_by_key(|h| h.position);
+    Some(result)
+}
+
+fn hint(position: Position, label: String, kind: Option<InlayHintKind>) -> InlayHint {
+    InlayHint {
+        position,
+        label: InlayHintLabel::String(label),
+        kind,
+        text_edits: None,
+        tooltip: None,
+        padding_left: None,
+        padding_right: None,
+        data: None,
+    }
+}
+
+/// Add the name of the parameters in front of the arguments of a call to a function or a callback
+fn parameter_hints(
+    document_cache: &DocumentCache,
+    call: syntax_nodes::FunctionCallExpression,
+    result: &mut Vec<InlayHint>,
+) {
+    let mut args = call.Expression();
+    let Some(callee) = args.next().and_then(|e| e.QualifiedName()) else { return };
+    let Some(LookupResult::Expression { expression, .. }) =
+        lookup_qualified_name(&document_cache.documents, &callee)
+    else {
+        return;
+    };
+    let params = match expression {
+        Expression::FunctionReference(nr, _) => {
+            let Some(function) = declaration_node(&nr).and_then(syntax_nodes::Function::new) else {
+                return;
+            };
+            function
+                .ArgumentDeclaration()
+                .map(|param| identifier_text(&param.DeclaredIdentifier()))
+                .collect::<Vec<_>>()
+        }
+        Expression::CallbackReference(nr, _) => callback_handler_arguments(&nr),
+        _ => return,
+    };
+    for (arg, param) in args.zip(params) {
+        let Some(param) = param else { continue };
+        // Don't repeat the name if the argument is just a reference to something with the same name
+        if arg
+            .QualifiedName()
+            .map_or(false, |qn| normalize_identifier(&qn.text().to_string()) == param)
+        {
+            continue;
+        }
+        // The expression node may start with whitespace
+        let Some(first) = arg
+            .node
+            .descendants_with_tokens()
+            .filter_map(|t| t.into_token())
+            .find(|t| !matches!(t.kind(), SyntaxKind::Whitespace | SyntaxKind::Comment))
+        else {
+            continue;
+        };
+        let position = map_position(&arg.source_file, first.text_range().start());
+        result.push(InlayHint {
+            padding_right: Some(true),
+            ..hint(position, format!("{param}:"), Some(InlayHintKind::PARAMETER))
+        });
+    }
+}
+
+/// The arguments of a callback don't have names in its declaration, so use the names of the
+/// arguments of its handler, such as `a` and `b` in `clicked(a, b) => { ... }`, if there is one.
+fn callback_handler_arguments(nr: &NamedReference) -> Vec<Option<String>> {
+    let element = nr.element();
+    let element = element.borrow();
+    element
+        .debug
+        .iter()
+        .flat_map(|(node, _)| node.CallbackConnection())
+        .find(|handler| identifier_text(handler).map_or(false, |name| name == nr.name()))
+        .map(|handler| handler.DeclaredIdentifier().map(|arg| identifier_text(&arg)).collect())
+        .unwrap_or_default()
+}
+
+/// If the qualified name refers to an `out` property of a global which is bound to a literal,
+/// return a hint with that value
+fn global_constant_hint(
+    document_cache: &DocumentCache,
+    qn: syntax_nodes::QualifiedName,
+) -> Option<InlayHint> {
+    let LookupResult::Expression { expression: Expression::PropertyReference(nr), .. } =
+        lookup_qualified_name(&document_cache.documents, &qn)?
+    else {
+        return None;
+    };
+    let element = nr.element();
+    let element = element.borrow();
+    if !element.enclosing_component.upgrade().map_or(false, |c| c.is_global())
+        || element.property_declarations.get(nr.name())?.visibility != PropertyVisibility::Output
+    {
+        return None;
+    }
+    let binding = element.bindings.get(nr.name())?.borrow();
+    if !binding.two_way_bindings.is_empty() {
+        return None;
+    }
+    let value = literal_text(&binding.expression)?;
+    let range = map_node(&qn)?;
+    Some(InlayHint { padding_left: Some(true), ..hint(range.end, format!("= {value}"), None) })
+}
+
+fn literal_text(expression: &Expression) -> Option<String> {
+    match expression {
+        Expression::NumberLiteral(value, unit) => Some(format!("{value}{unit}")),
+        Expression::BoolLiteral(value) => Some(value.to_string()),
+        Expression::StringLiteral(value) => Some(format!("{value:?}")),
+        Expression::EnumerationValue(value) => {
+            Some(format!("{}.{}", value.enumeration.name, value))
+        }
+        Expression::Cast { from, to: Type::Color } => match from.as_ref() {
+            Expression::NumberLiteral(value, _) => {
+                let argb = *value as u32;
+                Some(format!("#{:06x}{:02x}", argb & 0xffffff, argb >> 24))
+            }
+            _ => None,
+        },
+        Expression::Cast { from, .. } => literal_text(from),
+        Expression::CodeBlock(sub) if sub.len() == 1 => literal_text(&sub[0]),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_inlay_hints() {
+    let source = r#"import { StyleMetrics } from "std-widgets.slint";
+component Abc {
+    in property <length> size;
+}
+export component Test {
+    function add(first: int, second: int) -> int { return first + second; }
+    property <length> spacing: StyleMetrics.layout-spacing;
+    abc := Abc {}
+    property abc-size <=> abc.size;
+    property <int> sum: add(1, 2);
+    property <int> first: 3;
+    property <int> other: add(first, 2);
+    callback notify(int, string);
+    notify(count, text) => { }
+    init => { notify(sum, "hello"); }
+}"#;
+
+    let (mut dc, uri, diag) = crate::language::test::loaded_document_cache(source.into());
+    assert!(diag.values().all(|d| d.is_empty()), "{diag:?}");
+
+    let text_document = lsp_types::TextDocumentIdentifier { uri };
+    let range = Range::new(Position::new(0, 0), Position::new(100, 0));
+    let hints = get_inlay_hints(&mut dc, &text_document, &range)
+        .unwrap()
+        .into_iter()
+        .map(|h| {
+            let InlayHintLabel::String(label) = h.label else { panic!("unexpected label") };
+            (h.position.line, h.position.character, label, h.kind)
+        })
+        .collect::<Vec<_>>();
+    let parameter = Some(InlayHintKind::PARAMETER);
+    assert_eq!(
+        hints,
+        [
+            (6, 58, "= 8px".to_string(), None),
+            (8, 13, "<length>".to_string(), Some(InlayHintKind::TYPE)),
+            (9, 28, "first:".to_string(), parameter),
+            (9, 31, "second:".to_string(), parameter),
+            (11, 37, "second:".to_string(), parameter),
+            (14, 21, "count:".to_string(), parameter),
+            (14, 26, "text:".to_string(), parameter),
+        ]
+    );
+
+    // Only the hints in the range are returned
+    let range = Range::new(Position::new(9, 0), Position::new(10, 0));
+    let hints = get_inlay_hints(&mut dc, &text_document, &range).unwrap();
+    assert_eq!(
+        hints.iter().map(|h| (h.position.line, h.position.character)).collect::<Vec<_>>(),
+        [(9, 28), (9, 31)]
+    );
+}
@@ -0,0 +1,643 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.2 OR LicenseRef-Slint-commercial
+
+// cSpell: ignore colr coutn widht
+
+use super::{get_document_and_offset, token_at_offset, DocumentCache};
+use crate::common;
+use crate::util::{
+    declaration_node, lookup_current_element_type, lookup_qualified_name, map_range, map_token,
+    property_declaration_node, with_lookup_ctx,
+};
+
+use i_slint_compiler::deprecated_syntax;
+use i_slint_compiler::diagnostics::{SourceFile, Spanned};
+use i_slint_compiler::expression_tree::Expression;
+use i_slint_compiler::langtype::{ElementType, Type};
+use i_slint_compiler::lookup::{LookupObject, LookupResult};
+use i_slint_compiler::parser::{
+    identifier_text, normalize_identifier, syntax_nodes, SyntaxKind, SyntaxNode, SyntaxToken,
+    TextRange,
+};
+
+use lsp_types::{CodeAction, CodeActionKind, CodeActionOrCommand, Diagnostic, TextEdit, Url};
+
+struct Fix {
+    title: String,
+    source_file: SourceFile,
+    edits: Vec<TextEdit>,
+}
+
+impl Fix {
+    fn new(title: impl Into<String>, source_file: &SourceFile, edits: Vec<TextEdit>) -> Self {
+        Self { title: title.into(), source_file: source_file.clone(), edits }
+    }
+}
+
+/// Return the quick fixes for the diagnostics that the client sent along with the code action request
+pub fn get_quick_fixes(
+    document_cache: &mut DocumentCache,
+    uri: &Url,
+    diagnostics: &[Diagnostic],
+) -> Vec<CodeActionOrCommand> {
+    let mut result = vec![];
+    for diagnostic in diagnostics {
+        let Some(token) =
+            get_document_and_offset(&document_cache.documents, uri, &diagnostic.range.start)
+                .and_then(|(doc, offset)| token_at_offset(doc.node.as_ref()?, offset))
+        else {
+            continue;
+        };
+        let fixes = fixes_for_diagnostic(document_cache, &diagnostic.message, &token);
+        let is_preferred = fixes.len() == 1;
+        result.extend(fixes.into_iter().map(|fix| {
+            CodeActionOrCommand::CodeAction(CodeAction {
+                title: fix.title,
+                kind: Some(CodeActionKind::QUICKFIX),
+                diagnostics: Some(vec![diagnostic.clone()]),
+                edit: common::create_workspace_edit_from_source_file(&fix.source_file, fix.edits),
+                is_preferred: is_preferred.then_some(true),
+                ..Default::default()
+            })
+        }));
+    }
+    result
+}
+
+fn fixes_for_diagnostic(
+    document_cache: &DocumentCache,
+    message: &str,
+    token: &SyntaxToken,
+) -> Vec<Fix> {
+    if let Some(rest) = message.strip_prefix("Cannot assign to ") {
+        // A binding from outside the component that declares the property
+        let qualifier = if rest.starts_with("private ") {
+            "in"
+        } else if rest.starts_with("output ") {
+            "in-out"
+        } else {
+            return vec![];
+        };
+        binding_property_declaration(document_cache, token)
+            .and_then(|decl| set_qualifier(&decl, qualifier))
+            .into_iter()
+            .collect()
+    } else if [" on a input property", " on a output property", " on a private property"]
+        .iter()
+        .any(|suffix| message.ends_with(suffix))
+    {
+        // An assignment in a callback or function
+        expression_property_declaration(document_cache, token)
+            .and_then(|decl| set_qualifier(&decl, "in-out"))
+            .into_iter()
+            .collect()
+    } else if message.starts_with("Unknown property ") {
+        let mut fixes = typo_fixes(token, binding_property_candidates(document_cache, token));
+        fixes.extend(declare_property(document_cache, token));
+        fixes
+    } else if let Some(rest) = message.strip_prefix("Unknown unqualified identifier '") {
+        if let Some((_, suggestion)) = rest.split_once("Did you mean '") {
+            let suggestion = suggestion.trim_end_matches("'?");
+            replace_token(token, format!("Change to '{suggestion}'"), suggestion)
+                .into_iter()
+                .collect()
+        } else if rest.ends_with('\'') {
+            typo_fixes(token, identifier_candidates(document_cache, token))
+        } else {
+            vec![]
+        }
+    } else if message.starts_with("Call of impure function '")
+        || message.starts_with("Call of impure callback '")
+    {
+        mark_pure(document_cache, token).into_iter().collect()
+    } else if message.starts_with("':=' to declare a struct is deprecated")
+        || message.starts_with("':=' to declare a global is deprecated")
+    {
+        remove_colon_equal(token).into_iter().collect()
+    } else if message.starts_with("':=' to declare a component is deprecated") {
+        new_component_declaration(token).into_iter().collect()
+    } else if let Some(rest) = message.strip_prefix("The property '") {
+        deprecated_property(token, rest).into_iter().collect()
+    } else if message.contains(" is implicitly marked for export.") {
+        add_export(token).into_iter().collect()
+    } else {
+        vec![]
+    }
+}
+
+/// Replace the text of the token
+fn replace_token(token: &SyntaxToken, title: String, text: &str) -> Option<Fix> {
+    let range = map_token(token)?;
+    Some(Fix::new(title, &token.source_file, vec![TextEdit::new(range, text.into())]))
+}
+
+/// The declaration of the property set by the binding `token` is the name of
+fn binding_property_declaration(
+    document_cache: &DocumentCache,
+    token: &SyntaxToken,
+) -> Option<syntax_nodes::PropertyDeclaration> {
+    let binding = syntax_nodes::Binding::new(token.parent())?;
+    let ElementType::Component(c) = element_type(document_cache, binding.parent()?)? else {
+        return None;
+    };
+    property_declaration_node(c.root_element.clone(), &normalize_identifier(token.text()))
+        .and_then(syntax_nodes::PropertyDeclaration::new)
+}
+
+/// The declaration of the property referenced by the qualified name `token` is part of
+fn expression_property_declaration(
+    document_cache: &DocumentCache,
+    token: &SyntaxToken,
+) -> Option<syntax_nodes::PropertyDeclaration> {
+    let qn = syntax_nodes::QualifiedName::new(token.parent())?;
+    match lookup_qualified_name(&document_cache.documents, &qn)? {
+        LookupResult::Expression { expression: Expression::PropertyReference(nr), .. } => {
+            declaration_node(&nr).and_then(syntax_nodes::PropertyDeclaration::new)
+        }
+        _ => None,
+    }
+}
+
+fn element_type(document_cache: &DocumentCache, element: SyntaxNode) -> Option<ElementType> {
+    let global_tr = document_cache.documents.global_type_registry.borrow();
+    let tr = element
+        .source_file()
+        .and_then(|sf| document_cache.documents.get_document(sf.path()))
+        .map(|doc| &doc.local_registry)
+        .unwrap_or(&global_tr);
+    lookup_current_element_type(element, tr)
+}
+
+/// Replace the `in`, `out`, `in-out`, or `private` qualifier of a property declaration
+fn set_qualifier(decl: &syntax_nodes::PropertyDeclaration, qualifier: &str) -> Option<Fix> {
+    let name = identifier_text(&decl.DeclaredIdentifier())?;
+    let keywords = decl
+        .children_with_tokens()
+        .filter_map(|t| t.into_token())
+        .filter(|t| t.kind() == SyntaxKind::Identifier)
+        .collect::<Vec<_>>();
+    let property = keywords.iter().find(|t| t.text() == "property")?;
+    let start = keywords.first()?.text_range().start();
+    let range = map_range(&decl.source_file, TextRange::new(start, property.text_range().start()));
+    Some(Fix::new(
+        format!("Declare '{name}' as '{qualifier}'"),
+        &decl.source_file,
+        vec![TextEdit::new(range, format!("{qualifier} "))],
+    ))
+}
+
+/// Turn the binding into the declaration of a property, with the type of its expression
+fn declare_property(document_cache: &DocumentCache, token: &SyntaxToken) -> Option<Fix> {
+    let binding = syntax_nodes::Binding::new(token.parent())?;
+    let ty = with_lookup_ctx(&document_cache.documents, binding.clone().into(), |ctx| {
+        Expression::from_binding_expression_node(binding.BindingExpression().into(), ctx).ty()
+    })?;
+    if !ty.is_property_type() {
+        return None;
+    }
+    let position = map_token(token)?.start;
+    Some(Fix::new(
+        format!("Declare property '{}'", token.text()),
+        &token.source_file,
+        vec![TextEdit::new(lsp_types::Range::new(position, position), format!("property <{ty}> "))],
+    ))
+}
+
+/// The properties of the element in which `token` is the name of a binding
+fn binding_property_candidates(document_cache: &DocumentCache, token: &SyntaxToken) -> Vec<String> {
+    token
+        .parent()
+        .parent()
+        .and_then(|element| element_type(document_cache, element))
+        .map(|ty| {
+            ty.property_list()
+                .into_iter()
+                .filter(|(_, ty)| ty.is_property_type())
+                .map(|(name, _)| name)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Everything that can be looked up unqualified where `token` is
+fn identifier_candidates(document_cache: &DocumentCache, token: &SyntaxToken) -> Vec<String> {
+    with_lookup_ctx(&document_cache.documents, token.parent(), |ctx| {
+        let mut names = vec![];
+        i_slint_compiler::lookup::global_lookup().for_each_entry(ctx, &mut |name, _| {
+            names.push(name.to_string());
+            None::<()>
+        });
+        names
+    })
+    .unwrap_or_default()
+}
+
+/// Suggest the candidates that are the closest to the text of the token
+fn typo_fixes(token: &SyntaxToken, candidates: Vec<String>) -> Vec<Fix> {
+    let name = normalize_identifier(token.text());
+    let max_distance = (name.chars().count() / 3).max(1);
+    let mut candidates = candidates
+        .into_iter()
+        .filter(|c| *c != name)
+        .map(|c| (edit_distance(&name, &c), c))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect::<Vec<_>>();
+    candidates.sort();
+    candidates.dedup();
+    candidates
+        .into_iter()
+        .take(3)
+        .filter_map(|(_, c)| replace_token(token, format!("Change to '{c}'"), &c))
+        .collect()
+}
+
+/// The number of insertions, deletions, substitutions, or transpositions of two adjacent
+/// characters to go from `a` to `b`
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1).min(d[i][j - 1] + 1).min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+/// Add `pure` to the declaration of the function or callback that is called
+fn mark_pure(document_cache: &DocumentCache, token: &SyntaxToken) -> Option<Fix> {
+    let qn = syntax_nodes::QualifiedName::new(token.parent())?;
+    let nr = match lookup_qualified_name(&document_cache.documents, &qn)? {
+        LookupResult::Expression {
+            expression: Expression::FunctionReference(nr, _) | Expression::CallbackReference(nr, _),
+            ..
+        } => nr,
+        _ => return None,
+    };
+    let decl = declaration_node(&nr)?;
+    let keyword = decl
+        .children_with_tokens()
+        .filter_map(|t| t.into_token())
+        .find(|t| matches!(t.text(), "function" | "callback"))?;
+    let position = map_token(&keyword)?.start;
+    Some(Fix::new(
+        format!("Mark '{}' as pure", nr.name()),
+        &decl.source_file,
+        vec![TextEdit::new(lsp_types::Range::new(position, position), "pure ".into())],
+    ))
+}
+
+/// The struct or component declared with the deprecated `:=` syntax
+fn colon_equal_declaration(token: &SyntaxToken) -> Option<SyntaxNode> {
+    let mut node = token.parent();
+    loop {
+        if matches!(node.kind(), SyntaxKind::Component | SyntaxKind::StructDeclaration) {
+            return Some(node);
+        }
+        node = node.parent()?;
+    }
+}
+
+fn to_lsp_edit(source_file: &SourceFile, edit: deprecated_syntax::TextEdit) -> TextEdit {
+    TextEdit::new(map_range(source_file, edit.range), edit.text)
+}
+
+fn remove_colon_equal(token: &SyntaxToken) -> Option<Fix> {
+    let declaration = colon_equal_declaration(token)?;
+    let edit = deprecated_syntax::remove_colon_equal(&declaration)?;
+    Some(Fix::new(
+        "Remove ':='",
+        &declaration.source_file,
+        vec![to_lsp_edit(&declaration.source_file, edit)],
+    ))
+}
+
+/// `Foo := Base {}` becomes `component Foo inherits Base {}`, like the `slint-updater` does
+fn new_component_declaration(token: &SyntaxToken) -> Option<Fix> {
+    let component = colon_equal_declaration(token)?;
+    let edits = deprecated_syntax::new_component_declaration(&component)?;
+    Some(Fix::new(
+        "Use the 'component' syntax",
+        &component.source_file,
+        edits.into_iter().map(|edit| to_lsp_edit(&component.source_file, edit)).collect(),
+    ))
+}
+
+/// `rest` is what follows `The property '` in the message:
+/// `old' has been deprecated. Please use 'new' instead`
+fn deprecated_property(token: &SyntaxToken, rest: &str) -> Option<Fix> {
+    let (old, rest) = rest.split_once("' has been deprecated. Please use '")?;
+    let new = rest.strip_suffix("' instead")?;
+    // The diagnostic may cover the whole qualified name
+    let node = token.parent();
+    let edit = deprecated_syntax::rename_identifier(&node, old, new)?;
+    Some(Fix::new(
+        format!("Change to '{new}'"),
+        &node.source_file,
+        vec![to_lsp_edit(&node.source_file, edit)],
+    ))
+}
+
+fn add_export(token: &SyntaxToken) -> Option<Fix> {
+    let mut node = token.parent();
+    while node.kind() != SyntaxKind::Component {
+        node = node.parent()?;
+    }
+    let start = map_range(&node.source_file, node.text_range()).start;
+    Some(Fix::new(
+        "Add 'export'",
+        &node.source_file,
+        vec![TextEdit::new(lsp_types::Range::new(start, start), "export ".into())],
+    ))
+}
+
+/// Code action to explicitly set the fields of a struct that are not set in an object literal
+/// assigned to a property of that struct type
+pub fn add_missing_struct_fields(
+    document_cache: &mut DocumentCache,
+    token: &SyntaxToken,
+) -> Option<CodeActionOrCommand> {
+    let mut literal = token.parent();
+    while literal.kind() != SyntaxKind::ObjectLiteral {
+        if matches!(literal.kind(), SyntaxKind::BindingExpression | SyntaxKind::Element) {
+            return None;
+        }
+        literal = literal.parent()?;
+    }
+    // Only when the object literal is the whole binding, so that we know its type
+    let expression = literal.parent().filter(|n| n.kind() == SyntaxKind::Expression)?;
+    if expression.parent()?.kind() != SyntaxKind::BindingExpression {
+        return None;
+    }
+    let ty = with_lookup_ctx(&document_cache.documents, literal.clone(), |ctx| {
+        ctx.property_type.clone()
+    })?;
+    let Type::Struct { fields, .. } = ty else { return None };
+
+    let existing = syntax_nodes::ObjectLiteral::new(literal.clone())?
+        .ObjectMember()
+        .filter_map(|m| m.child_text(SyntaxKind::Identifier))
+        .map(|name| normalize_identifier(&name))
+        .collect::<Vec<_>>();
+    let missing = fields
+        .iter()
+        .filter(|(name, _)| !existing.contains(name))
+        .filter_map(|(name, ty)| Some(format!("{name}: {}", default_value_text(ty)?)))
+        .collect::<Vec<_>>();
+    if missing.is_empty() {
+        return None;
+    }
+    let missing = missing.join(", ");
+
+    // The comma separating the members is part of the member that precedes it
+    let tokens = literal
+        .node
+        .descendants_with_tokens()
+        .filter_map(|t| t.into_token())
+        .filter(|t| !matches!(t.kind(), SyntaxKind::Whitespace | SyntaxKind::Comment))
+        .collect::<Vec<_>>();
+    let [.., last, r_brace] = tokens.as_slice() else { return None };
+    if r_brace.kind() != SyntaxKind::RBrace {
+        return None;
+    }
+    let end = last.text_range().end();
+    let (range, text) = match last.kind() {
+        SyntaxKind::LBrace => {
+            (TextRange::new(end, r_brace.text_range().start()), format!(" {missing} "))
+        }
+        SyntaxKind::Comma => (TextRange::empty(end), format!(" {missing}")),
+        _ => (TextRange::empty(end), format!(", {missing}")),
+    };
+
+    Some(CodeActionOrCommand::CodeAction(CodeAction {
+        title: "Add missing fields".into(),
+        kind: Some(CodeActionKind::QUICKFIX),
+        edit: common::create_workspace_edit_from_source_file(
+            &literal.source_file,
+            vec![TextEdit::new(map_range(&literal.source_file, range), text)],
+        ),
+        ..Default::default()
+    }))
+}
+
+/// The text of the default value of a type, to be used in a struct literal
+fn default_value_text(ty: &Type) -> Option<String> {
+    Some(match ty {
+        Type::Int32 | Type::Float32 => "0".into(),
+        Type::String => "\"\"".into(),
+        Type::Bool => "false".into(),
+        Type::LogicalLength | Type::PhysicalLength => "0px".into(),
+        Type::Rem => "0rem".into(),
+        Type::Duration => "0ms".into(),
+        Type::Angle => "0deg".into(),
+        Type::Percent => "0%".into(),
+        Type::Color | Type::Brush => "#00000000".into(),
+        Type::Image => "@image-url(\"\")".into(),
+        Type::Array(_) => "[]".into(),
+        Type::Struct { .. } => "{}".into(),
+        Type::Optional(_) => "null".into(),
+        Type::Enumeration(e) => format!("{}.{}", e.name, e.clone().default_value()),
+        _ => return None,
+    })
+}
+
+/// The title of the quick fixes and the text of their first edit, for all the diagnostics
+/// of the source
+#[cfg(test)]
+fn quick_fixes(source: &str) -> Vec<(String, String)> {
+    let (mut dc, uri, diag) = crate::language::test::loaded_document_cache(source.into());
+    let diagnostics = diag.get(&uri).cloned().unwrap_or_default();
+    get_quick_fixes(&mut dc, &uri, &diagnostics)
+        .into_iter()
+        .map(|action| {
+            let CodeActionOrCommand::CodeAction(action) = action else { panic!("not an action") };
+            let Some(lsp_types::DocumentChanges::Edits(edits)) =
+                action.edit.and_then(|e| e.document_changes)
+            else {
+                panic!("no edits")
+            };
+            let lsp_types::OneOf::Left(edit) = &edits[0].edits[0] else { panic!("annotated") };
+            (action.title, edit.new_text.clone())
+        })
+        .collect()
+}
+
+#[test]
+fn test_edit_distance() {
+    assert_eq!(edit_distance("width", "width"), 0);
+    assert_eq!(edit_distance("widht", "width"), 1);
+    assert_eq!(edit_distance("colr", "color"), 1);
+    assert_eq!(edit_distance("x", "height"), 6);
+}
+
+#[test]
+fn test_qualifier_fixes() {
+    let fixes = quick_fixes(
+        r#"
+component Foo { property <int> value; }
+export component Test { Foo { value: 42; } }"#,
+    );
+    assert_eq!(fixes, [("Declare 'value' as 'in'".to_string(), "in ".to_string())]);
+
+    let fixes = quick_fixes(
+        r#"
+component Foo { in property <int> value; }
+export component Test {
+    foo := Foo { }
+    TouchArea { clicked => { foo.value = 42; } }
+}"#,
+    );
+    assert_eq!(fixes, [("Declare 'value' as 'in-out'".to_string(), "in-out ".to_string())]);
+}
+
+#[test]
+fn test_unknown_property_fixes() {
+    let fixes = quick_fixes("export component Test { Rectangle { widht: 10px; } }");
+    assert_eq!(
+        fixes,
+        [
+            ("Change to 'width'".to_string(), "width".to_string()),
+            ("Declare property 'widht'".to_string(), "property <length> ".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_unknown_identifier_fixes() {
+    let fixes = quick_fixes(
+        r#"
+export component Test {
+    property <int> count;
+    property <int> x: coutn;
+}"#,
+    );
+    assert_eq!(fixes, [("Change to 'count'".to_string(), "count".to_string())]);
+}
+
+#[test]
+fn test_pure_fixes() {
+    let fixes = quick_fixes(
+        r#"
+export component Test {
+    public function foo() -> int { return 42; }
+    property <int> x: foo();
+}"#,
+    );
+    assert_eq!(fixes, [("Mark 'foo' as pure".to_string(), "pure ".to_string())]);
+}
+
+#[test]
+fn test_deprecated_syntax_fixes() {
+    let fixes = quick_fixes(
+        r#"
+struct Foo := { a: int }
+Test := Rectangle { }"#,
+    );
+    assert!(fixes.contains(&("Remove ':='".to_string(), String::new())));
+    assert!(fixes.contains(&("Use the 'component' syntax".to_string(), "component ".to_string())));
+    assert!(fixes.contains(&("Add 'export'".to_string(), "export ".to_string())));
+}
+
+/// The fixes are chosen from the text of the diagnostics: make sure that each message that
+/// `fixes_for_diagnostic` recognizes is still produced by the compiler
+#[test]
+fn test_recognized_diagnostics() {
+    let cases = [
+        (
+            "component Foo { property <int> value; } export component Test { Foo { value: 42; } }",
+            "Declare 'value' as 'in'",
+        ),
+        (
+            "component Foo { out property <int> value; } export component Test { Foo { value: 42; } }",
+            "Declare 'value' as 'in-out'",
+        ),
+        (
+            r#"component Foo { in property <int> value; }
+export component Test { foo := Foo { } TouchArea { clicked => { foo.value = 42; } } }"#,
+            "Declare 'value' as 'in-out'",
+        ),
+        (
+            r#"component Foo { out property <int> value; }
+export component Test { foo := Foo { } TouchArea { clicked => { foo.value = 42; } } }"#,
+            "Declare 'value' as 'in-out'",
+        ),
+        ("export component Test { Rectangle { widht: 10px; } }", "Change to 'width'"),
+        (
+            "export component Test { property <int> count; property <int> x: coutn; }",
+            "Change to 'count'",
+        ),
+        (
+            "export component Test inherits Text { property <string> label: text; }",
+            "Change to 'self.text'",
+        ),
+        (
+            "export component Test { public function foo() -> int { return 42; } property <int> x: foo(); }",
+            "Mark 'foo' as pure",
+        ),
+        (
+            "export component Test { callback foo() -> int; property <int> x: foo(); }",
+            "Mark 'foo' as pure",
+        ),
+        ("struct Foo := { a: int }", "Remove ':='"),
+        ("global Settings := { property <int> value; }", "Remove ':='"),
+        ("Test := Rectangle { }", "Use the 'component' syntax"),
+        ("Test := Rectangle { }", "Add 'export'"),
+        ("export component Test { Rectangle { color: white; } }", "Change to 'background'"),
+    ];
+    for (source, title) in cases {
+        let fixes = quick_fixes(source);
+        assert!(
+            fixes.iter().any(|(t, _)| t == title),
+            "no fix {title:?} for {source:?}, got {fixes:?}"
+        );
+    }
+}
+
+#[test]
+fn test_add_missing_struct_fields() {
+    let source = r#"
+struct Foo { a: int, b: string, c: length }
+export component Test {
+    property <Foo> foo: { a: 42 };
+    property <Foo> bar: { b: "x", };
+}"#;
+    let (mut dc, uri, _) = crate::language::test::loaded_document_cache(source.into());
+    let doc = dc
+        .documents
+        .get_document(&crate::language::uri_to_file(&uri).unwrap())
+        .unwrap()
+        .node
+        .clone()
+        .unwrap();
+
+    let mut new_text = |needle: &str| {
+        let offset = source.find(needle).unwrap() as u32;
+        let token = token_at_offset(&doc, offset).unwrap();
+        let CodeActionOrCommand::CodeAction(action) = add_missing_struct_fields(&mut dc, &token)?
+        else {
+            panic!("not an action")
+        };
+        let Some(lsp_types::DocumentChanges::Edits(edits)) =
+            action.edit.and_then(|e| e.document_changes)
+        else {
+            panic!("no edits")
+        };
+        let lsp_types::OneOf::Left(edit) = &edits[0].edits[0] else { panic!("annotated") };
+        Some(edit.new_text.clone())
+    };
+
+    assert_eq!(new_text("a: 42"), Some(", b: \"\", c: 0px".into()));
+    assert_eq!(new_text("b: \"x\""), Some(" a: 0, c: 0px".into()));
+    assert_eq!(new_text("property <Foo> foo"), None);
+}
@@ -0,0 +1,177 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.2 OR LicenseRef-Slint-commercial
+
+use super::goto::goto_definition;
+use super::DocumentCache;
+use crate::util::{map_node_and_url, map_token};
+
+#[cfg(target_arch = "wasm32")]
+use crate::wasm_prelude::*;
+use i_slint_compiler::parser::{normalize_identifier, SyntaxKind, SyntaxNode, SyntaxToken};
+
+use lsp_types::{GotoDefinitionResponse, Location, Position, Url};
+
+/// Find all the uses of the property, callback, function, element, component, or type
+/// that `token` refers to, in all the documents loaded in the `document_cache`.
+///
+/// The token can either be a use, or the name in the declaration.
+pub fn find_references(
+    document_cache: &mut DocumentCache,
+    token: SyntaxToken,
+    include_declaration: bool,
+) -> Option<Vec<Location>> {
+    if token.kind() != SyntaxKind::Identifier {
+        return None;
+    }
+    let target = definition_location(document_cache, token.clone())?;
+    let name = normalize_identifier(token.text());
+
+    // Collect the candidates first, since resolving them needs the document cache
+    let candidates = document_cache
+        .documents
+        .all_file_documents()
+        .filter(|(path, _)| !path.starts_with("builtin:/"))
+        .filter_map(|(_, doc)| doc.node.as_ref())
+        .flat_map(|doc_node| {
+            let source_file = doc_node.source_file.clone();
+            doc_node
+                .node
+                .descendants_with_tokens()
+                .filter_map(|t| t.into_token())
+                .filter(|t| {
+                    t.kind() == SyntaxKind::Identifier && normalize_identifier(t.text()) == name
+                })
+                .map(move |token| SyntaxToken { token, source_file: source_file.clone() })
+        })
+        .collect::<Vec<_>>();
+
+    let mut result = vec![];
+    for candidate in candidates {
+        if !include_declaration && declaration_node(&candidate).is_some() {
+            continue;
+        }
+        if definition_location(document_cache, candidate.clone()).as_ref() != Some(&target) {
+            continue;
+        }
+        let Ok(uri) = Url::from_file_path(candidate.source_file.path()) else { continue };
+        let Some(range) = map_token(&candidate) else { continue };
+        result.push(Location { uri, range });
+    }
+    Some(result)
+}
+
+/// Return the location of the declaration `token` refers to, the same way goto definition does.
+/// If the token is the name of a declaration, this is the location of that declaration.
+fn definition_location(
+    document_cache: &mut DocumentCache,
+    token: SyntaxToken,
+) -> Option<(Url, Position)> {
+    if let Some(node) = declaration_node(&token) {
+        let (uri, range) = map_node_and_url(&node)?;
+        return Some((uri, range.start));
+    }
+    match goto_definition(document_cache, token)? {
+        GotoDefinitionResponse::Link(links) => {
+            let link = links.first()?;
+            Some((link.target_uri.clone(), link.target_range.start))
+        }
+        _ => None,
+    }
+}
+
+/// If `token` is the name in a declaration, return the node goto definition would point to
+/// for a use of that name.
+fn declaration_node(token: &SyntaxToken) -> Option<SyntaxNode> {
+    let parent = token.parent();
+    match parent.kind() {
+        // `id := Element { ... }`
+        SyntaxKind::SubElement => parent.child_node(SyntaxKind::Element),
+        SyntaxKind::DeclaredIdentifier => {
+            let declaration = parent.parent()?;
+            match declaration.kind() {
+                SyntaxKind::Component => declaration.child_node(SyntaxKind::Element),
+                SyntaxKind::PropertyDeclaration
+                | SyntaxKind::CallbackDeclaration
+                | SyntaxKind::Function
+                | SyntaxKind::StructDeclaration
+                | SyntaxKind::EnumDeclaration
+                | SyntaxKind::InterfaceDeclaration => Some(declaration),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+#[test]
+fn test_find_references() {
+    let source = r#"
+component Abc {
+    in property <string> hello;
+    callback clicked();
+}
+export component Test {
+    property <string> hello: "unrelated";
+    abc := Abc {
+        hello: "foo";
+        clicked => { abc.hello = root.hello; }
+    }
+    Text {
+        text: abc.hello;
+    }
+    Abc { }
+}"#;
+
+    let (mut dc, uri, _) = crate::language::test::loaded_document_cache(source.into());
+    let doc = dc
+        .documents
+        .get_document(&crate::language::uri_to_file(&uri).unwrap())
+        .unwrap()
+        .node
+        .clone()
+        .unwrap();
+
+    let lines = |locations: Vec<Location>| {
+        assert!(locations.iter().all(|l| l.uri == uri));
+        let mut lines = locations.iter().map(|l| l.range.start.line).collect::<Vec<_>>();
+        lines.sort();
+        lines
+    };
+
+    // The `hello` property of Abc, from its declaration
+    let offset = source.find("in property <string> hello").unwrap() as u32;
+    let token = crate::language::token_at_offset(&doc, offset + 22).unwrap();
+    assert_eq!(token.text(), "hello");
+    let refs = find_references(&mut dc, token.clone(), true).unwrap();
+    assert_eq!(lines(refs), [2, 8, 9, 12]);
+    let refs = find_references(&mut dc, token, false).unwrap();
+    assert_eq!(lines(refs), [8, 9, 12]);
+
+    // The same, from a use
+    let offset = source.find("text: abc.hello").unwrap() as u32;
+    let token = crate::language::token_at_offset(&doc, offset + 11).unwrap();
+    assert_eq!(token.text(), "hello");
+    let refs = find_references(&mut dc, token, true).unwrap();
+    assert_eq!(lines(refs), [2, 8, 9, 12]);
+
+    // The `hello` property of Test is a different property
+    let offset = source.find("root.hello").unwrap() as u32;
+    let token = crate::language::token_at_offset(&doc, offset + 6).unwrap();
+    assert_eq!(token.text(), "hello");
+    let refs = find_references(&mut dc, token, true).unwrap();
+    assert_eq!(lines(refs), [6, 9]);
+
+    // The element id
+    let offset = source.find("abc := ").unwrap() as u32;
+    let token = crate::language::token_at_offset(&doc, offset).unwrap();
+    assert_eq!(token.text(), "abc");
+    let refs = find_references(&mut dc, token, true).unwrap();
+    assert_eq!(lines(refs), [7, 9, 12]);
+
+    // The component
+    let offset = source.find("component Abc").unwrap() as u32;
+    let token = crate::language::token_at_offset(&doc, offset + 10).unwrap();
+    assert_eq!(token.text(), "Abc");
+    let refs = find_references(&mut dc, token, false).unwrap();
+    assert_eq!(lines(refs), [7, 14]);
+}
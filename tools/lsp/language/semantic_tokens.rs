@@ -60,6 +60,7 @@ pub fn get_semantic_tokens(
                     SyntaxKind::Type => Some((self::TYPE, 0)),
                     // the base type
                     SyntaxKind::Element => Some((self::TYPE, 0)),
+                    SyntaxKind::ImplementsSpecifier => Some((self::TYPE, 0)),
                     // FIXME: we should do actual lookup
                     SyntaxKind::Expression => None,
                     SyntaxKind::StatePropertyChange => Some((self::PROPERTY, 0)),
@@ -81,18 +82,29 @@ pub fn get_semantic_tokens(
                         SyntaxKind::PropertyDeclaration => {
                             Some((self::PROPERTY, 1 << self::DEFINITION))
                         }
+                        SyntaxKind::LetStatement
+                        | SyntaxKind::ForStatement
+                        | SyntaxKind::Lambda => Some((self::VARIABLE, 1 << self::DEFINITION)),
                         SyntaxKind::State | SyntaxKind::Transition => {
                             // This is the state name, but what semantic type is that?
                             None
                         }
                         SyntaxKind::StructDeclaration => Some((self::TYPE, 1 << self::DEFINITION)),
                         SyntaxKind::EnumDeclaration => Some((self::ENUM, 1 << self::DEFINITION)),
+                        SyntaxKind::InterfaceDeclaration => {
+                            Some((self::TYPE, 1 << self::DEFINITION))
+                        }
                         _ => None,
                     }
                 }
                 SyntaxKind::ChildrenPlaceholder => Some((self::MACRO, 0)),
                 SyntaxKind::Binding | SyntaxKind::TwoWayBinding => Some((self::PROPERTY, 0)),
                 SyntaxKind::ReturnStatement => Some((self::KEYWORD, 0)),
+                SyntaxKind::LetStatement => Some((self::KEYWORD, 0)),
+                SyntaxKind::ForStatement => Some((self::KEYWORD, 0)),
+                SyntaxKind::BreakStatement | SyntaxKind::ContinueStatement => {
+                    Some((self::KEYWORD, 0))
+                }
                 SyntaxKind::AtImageUrl => Some((self::MACRO, 0)),
                 SyntaxKind::AtGradient => Some((self::MACRO, 0)),
                 SyntaxKind::AtTr => Some((self::MACRO, 0)),
@@ -133,6 +145,8 @@ pub fn get_semantic_tokens(
                 SyntaxKind::ObjectTypeMember => Some((self::PROPERTY, 1 << self::DEFINITION)),
                 SyntaxKind::StructDeclaration => Some((self::KEYWORD, 0)),
                 SyntaxKind::EnumDeclaration => Some((self::KEYWORD, 0)),
+                SyntaxKind::InterfaceDeclaration => Some((self::KEYWORD, 0)),
+                SyntaxKind::ImplementsSpecifier => Some((self::KEYWORD, 0)),
                 _ => None,
             },
             SyntaxKind::PlusEqual
@@ -153,7 +167,7 @@ pub fn get_semantic_tokens(
             | SyntaxKind::Star
             | SyntaxKind::Div
             | SyntaxKind::Equal => Some((self::OPERATOR, 0)),
-            SyntaxKind::Question => Some((self::OPERATOR, 0)),
+            SyntaxKind::Question | SyntaxKind::QuestionQuestion => Some((self::OPERATOR, 0)),
             SyntaxKind::At => Some((self::MACRO, 0)),
             _ => None,
         };
@@ -0,0 +1,211 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.2 OR LicenseRef-Slint-commercial
+
+use super::DocumentCache;
+use crate::util::{declaration_node, lookup_qualified_name};
+
+use i_slint_compiler::expression_tree::{BuiltinMacroFunction, Expression};
+use i_slint_compiler::langtype::Type;
+use i_slint_compiler::lookup::LookupResult;
+use i_slint_compiler::parser::{
+    identifier_text, syntax_nodes, SyntaxKind, SyntaxNode, SyntaxToken, TextSize,
+};
+
+use lsp_types::{ParameterInformation, ParameterLabel, SignatureHelp, SignatureInformation};
+
+/// Return the signature of the function, callback, or builtin being called at `offset`,
+/// with the argument under the cursor as active parameter.
+pub fn get_signature_help(
+    document_cache: &mut DocumentCache,
+    token: SyntaxToken,
+    offset: u32,
+) -> Option<SignatureHelp> {
+    let mut node = token.parent();
+    loop {
+        if matches!(node.kind(), SyntaxKind::FunctionCallExpression | SyntaxKind::AtTr) {
+            let mut in_parentheses = false;
+            let mut active_parameter = 0;
+            for t in node.children_with_tokens().filter_map(|t| t.into_token()) {
+                if t.text_range().start() >= TextSize::from(offset) {
+                    break;
+                }
+                match t.kind() {
+                    SyntaxKind::LParent => in_parentheses = true,
+                    SyntaxKind::RParent => in_parentheses = false,
+                    SyntaxKind::Comma => active_parameter += 1,
+                    _ => (),
+                }
+            }
+            if in_parentheses {
+                break signature_for_call(document_cache, node, active_parameter);
+            }
+        }
+        node = node.parent()?;
+    }
+}
+
+fn signature_for_call(
+    document_cache: &DocumentCache,
+    node: SyntaxNode,
+    active_parameter: u32,
+) -> Option<SignatureHelp> {
+    let signature = if node.kind() == SyntaxKind::AtTr {
+        signature("@tr", ["text".to_string(), "...".to_string()], None)
+    } else {
+        let callee = syntax_nodes::FunctionCallExpression::new(node)?
+            .Expression()
+            .next()?
+            .QualifiedName()?;
+        let name = callee.text().to_string().split_whitespace().collect::<String>();
+        let LookupResult::Expression { expression, .. } =
+            lookup_qualified_name(&document_cache.documents, &callee)?
+        else {
+            return None;
+        };
+        // Member functions get the object they are called on as first argument
+        let (expression, skip) = match expression {
+            Expression::MemberFunction { member, .. } => (*member, 1),
+            expression => (expression, 0),
+        };
+        match &expression {
+            Expression::BuiltinMacroReference(m, _) => {
+                signature(&name, macro_parameters(m).iter().map(|p| p.to_string()), None)
+            }
+            Expression::FunctionReference(nr, _) => {
+                let Type::Function { return_type, args } = nr.ty() else { return None };
+                let names = declaration_node(nr)
+                    .and_then(syntax_nodes::Function::new)
+                    .map(|f| {
+                        f.ArgumentDeclaration()
+                            .map(|a| identifier_text(&a.DeclaredIdentifier()))
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default();
+                let params = args.iter().enumerate().map(|(i, ty)| match names.get(i) {
+                    Some(Some(name)) => format!("{name}: {ty}"),
+                    _ => ty.to_string(),
+                });
+                signature(&name, params, Some(&*return_type))
+            }
+            Expression::CallbackReference(nr, _) => {
+                let Type::Callback { return_type, args } = nr.ty() else { return None };
+                signature(&name, args.iter().map(|ty| ty.to_string()), return_type.as_deref())
+            }
+            Expression::BuiltinFunctionReference(f, _) => {
+                let Type::Function { return_type, args } = f.ty() else { return None };
+                signature(
+                    &name,
+                    args.iter().skip(skip).map(|ty| ty.to_string()),
+                    Some(&*return_type),
+                )
+            }
+            _ => return None,
+        }
+    };
+
+    // Variadic functions end with a `...` parameter that matches all the remaining arguments
+    let parameters = signature.parameters.as_deref().unwrap_or_default();
+    let active_parameter = match parameters.last() {
+        Some(ParameterInformation {
+            label: ParameterLabel::LabelOffsets([start, end]), ..
+        }) if &signature.label[*start as usize..*end as usize] == "..." => {
+            active_parameter.min(parameters.len() as u32 - 1)
+        }
+        _ => active_parameter,
+    };
+
+    Some(SignatureHelp {
+        signatures: vec![signature],
+        active_signature: Some(0),
+        active_parameter: Some(active_parameter),
+    })
+}
+
+/// Build the signature `name(param1, param2) -> return_type`
+fn signature(
+    name: &str,
+    params: impl IntoIterator<Item = String>,
+    return_type: Option<&Type>,
+) -> SignatureInformation {
+    let mut label = format!("{name}(");
+    let mut parameters = vec![];
+    for (i, param) in params.into_iter().enumerate() {
+        if i > 0 {
+            label += ", ";
+        }
+        let start = label.len() as u32;
+        label += &param;
+        parameters.push(ParameterInformation {
+            label: ParameterLabel::LabelOffsets([start, label.len() as u32]),
+            documentation: None,
+        });
+    }
+    label += ")";
+    if let Some(return_type) = return_type.filter(|ty| **ty != Type::Void) {
+        label += &format!(" -> {return_type}");
+    }
+    SignatureInformation {
+        label,
+        documentation: None,
+        parameters: Some(parameters),
+        active_parameter: None,
+    }
+}
+
+/// The macros don't have a fixed type, so describe their parameters by hand
+fn macro_parameters(m: &BuiltinMacroFunction) -> &'static [&'static str] {
+    match m {
+        BuiltinMacroFunction::Min | BuiltinMacroFunction::Max => &["a", "b", "..."],
+        BuiltinMacroFunction::Clamp => &["value", "minimum", "maximum"],
+        BuiltinMacroFunction::Mod => &["value", "divisor"],
+        BuiltinMacroFunction::CubicBezier => &["x1", "y1", "x2", "y2"],
+        BuiltinMacroFunction::Rgb => &["red", "green", "blue", "alpha"],
+        BuiltinMacroFunction::Hsv => &["hue", "saturation", "value", "alpha"],
+        BuiltinMacroFunction::Debug => &["..."],
+        BuiltinMacroFunction::ArrayFilter => &["predicate"],
+        BuiltinMacroFunction::ArrayMap => &["transform"],
+        BuiltinMacroFunction::ArraySortBy => &["key"],
+        BuiltinMacroFunction::ArraySlice => &["start", "end"],
+    }
+}
+
+#[test]
+fn test_signature_help() {
+    let source = r#"
+export component Test {
+    pure callback clicked(int, string) -> bool;
+    function add(first: int, second: int) -> int { return first + second; }
+    property <int> a: add(1, 2);
+    property <float> b: Math.sqrt(4);
+    property <color> c: rgb(1, 2, 3);
+    property <int> d: max(1, 2, 3, 4);
+    property <string> e: @tr("Hello {}", "world");
+    property <bool> f: clicked(1, "x");
+}"#;
+
+    let (mut dc, uri, _) = crate::language::test::loaded_document_cache(source.into());
+    let doc = dc
+        .documents
+        .get_document(&crate::language::uri_to_file(&uri).unwrap())
+        .unwrap()
+        .node
+        .clone()
+        .unwrap();
+
+    let mut help_at = |needle: &str| {
+        let offset = source.find(needle).unwrap() as u32 + needle.len() as u32;
+        let token = crate::language::token_at_offset(&doc, offset).unwrap();
+        let help = get_signature_help(&mut dc, token, offset)?;
+        Some((help.signatures[0].label.clone(), help.active_parameter.unwrap()))
+    };
+
+    assert_eq!(help_at("a: add("), Some(("add(first: int, second: int) -> int".into(), 0)));
+    assert_eq!(help_at("a: add(1, "), Some(("add(first: int, second: int) -> int".into(), 1)));
+    assert_eq!(help_at("Math.sqrt("), Some(("Math.sqrt(float) -> float".into(), 0)));
+    assert_eq!(help_at("rgb(1, 2"), Some(("rgb(red, green, blue, alpha)".into(), 1)));
+    assert_eq!(help_at("max(1, 2, 3, "), Some(("max(a, b, ...)".into(), 2)));
+    assert_eq!(help_at("@tr(\"Hello {}\", "), Some(("@tr(text, ...)".into(), 1)));
+    assert_eq!(help_at("clicked(1, "), Some(("clicked(int, string) -> bool".into(), 1)));
+    // Not within the parentheses
+    assert_eq!(help_at("property <int> a: ad"), None);
+}
@@ -3,9 +3,12 @@
 
 use i_slint_compiler::diagnostics::{DiagnosticLevel, SourceFile, Spanned};
 use i_slint_compiler::langtype::{ElementType, Type};
-use i_slint_compiler::lookup::LookupCtx;
+use i_slint_compiler::lookup::{LookupCtx, LookupObject, LookupResult};
+use i_slint_compiler::namedreference::NamedReference;
 use i_slint_compiler::object_tree;
-use i_slint_compiler::parser::{syntax_nodes, SyntaxKind, SyntaxNode, SyntaxToken};
+use i_slint_compiler::parser::{
+    normalize_identifier, syntax_nodes, SyntaxKind, SyntaxNode, SyntaxToken,
+};
 use i_slint_compiler::parser::{TextRange, TextSize};
 use i_slint_compiler::typeloader::TypeLoader;
 use i_slint_compiler::typeregister::TypeRegister;
@@ -268,6 +271,46 @@ fn lookup_expression_context(mut n: SyntaxNode) -> Option<ExpressionContextInfo>
     Some(ExpressionContextInfo::new(element, prop_name, is_animate))
 }
 
+/// Lookup all the identifiers of a qualified name used in an expression, like `foo.bar.baz`
+pub fn lookup_qualified_name(
+    type_loader: &TypeLoader,
+    qn: &syntax_nodes::QualifiedName,
+) -> Option<LookupResult> {
+    with_lookup_ctx(type_loader, qn.clone().into(), |ctx| {
+        let mut it = qn
+            .children_with_tokens()
+            .filter_map(|t| t.into_token())
+            .filter(|t| t.kind() == SyntaxKind::Identifier);
+        let global = i_slint_compiler::lookup::global_lookup();
+        let mut expr_it = global.lookup(ctx, &normalize_identifier(it.next()?.text()))?;
+        for tok in it {
+            expr_it = expr_it.lookup(ctx, &normalize_identifier(tok.text()))?;
+        }
+        Some(expr_it)
+    })?
+}
+
+/// The node of the declaration of the property, callback, or function referenced by `nr`
+pub fn declaration_node(nr: &NamedReference) -> Option<SyntaxNode> {
+    property_declaration_node(nr.element(), nr.name())
+}
+
+/// The node of the declaration of the property, callback, or function `name` of the element,
+/// looking into the base components
+pub fn property_declaration_node(mut el: object_tree::ElementRc, name: &str) -> Option<SyntaxNode> {
+    loop {
+        if let Some(x) = el.borrow().property_declarations.get(name) {
+            return x.node.clone();
+        }
+        let base = el.borrow().base_type.clone();
+        if let ElementType::Component(c) = base {
+            el = c.root_element.clone();
+        } else {
+            return None;
+        }
+    }
+}
+
 pub fn to_lsp_diag(d: &i_slint_compiler::diagnostics::Diagnostic) -> lsp_types::Diagnostic {
     lsp_types::Diagnostic::new(
         to_range(d.line_column()),
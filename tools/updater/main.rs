@@ -178,6 +178,9 @@ struct State {
     current_elem: Option<ElementRc>,
 
     lookup_change: LookupChangeState,
+
+    /// The rewrites to apply to the tokens of the node being visited
+    edits: Vec<i_slint_compiler::deprecated_syntax::TextEdit>,
 }
 
 fn visit_node(
@@ -291,8 +294,15 @@ fn fold_node(
 fn fold_token(
     node: i_slint_compiler::parser::SyntaxToken,
     file: &mut impl Write,
-    #[allow(unused)] state: &mut State,
+    state: &mut State,
 ) -> std::io::Result<()> {
+    let range = node.text_range();
+    for edit in state.edits.iter().filter(|e| e.range.start() == range.start()) {
+        file.write_all(edit.text.as_bytes())?;
+    }
+    if state.edits.iter().any(|e| !e.range.is_empty() && e.range.contains_range(range)) {
+        return Ok(());
+    }
     /* Example: this adds the "ms" prefix to the number within a "duration" binding
     if state.property_name == Some("duration".into()) && node.kind() == SyntaxKind::NumberLiteral {
         if !node.text().ends_with("s") {
@@ -2,13 +2,14 @@
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.2 OR LicenseRef-Slint-commercial
 
 use crate::Cli;
-use i_slint_compiler::parser::{SyntaxKind, SyntaxNode};
+use i_slint_compiler::deprecated_syntax::rename_identifier;
+use i_slint_compiler::parser::{normalize_identifier, SyntaxKind, SyntaxNode};
 use std::io::Write;
 
 pub(crate) fn fold_node(
     node: &SyntaxNode,
-    file: &mut impl Write,
-    _state: &mut crate::State,
+    _file: &mut impl Write,
+    state: &mut crate::State,
     _args: &Cli,
 ) -> std::io::Result<bool> {
     let kind = node.kind();
@@ -18,17 +19,22 @@ pub(crate) fn fold_node(
         let q = i_slint_compiler::object_tree::QualifiedTypeName::from_node(node.clone().into())
             .to_string();
         if q == "PointerEventButton.none" {
-            for t in node.children_with_tokens() {
-                let text = t.into_token().unwrap().to_string();
-                write!(file, "{}", if text == "none" { "other" } else { &text })?;
-            }
-            return Ok(true);
+            state.edits.extend(rename_identifier(node, "none", "other"));
         } else if q.starts_with("Keys.") {
-            for t in node.children_with_tokens() {
-                let text = t.into_token().unwrap().to_string();
-                write!(file, "{}", if text == "Keys" { "Key" } else { &text })?;
+            state.edits.extend(rename_identifier(node, "Keys", "Key"));
+        }
+    } else if kind == SyntaxKind::Binding
+        && node.parent().map_or(false, |n| n.kind() == SyntaxKind::Element)
+    {
+        // Bindings to deprecated aliases, such as `color` in a `Rectangle`
+        if let (Some(elem), Some(name)) =
+            (&state.current_elem, node.child_text(SyntaxKind::Identifier))
+        {
+            let name = normalize_identifier(&name);
+            let resolved_name = elem.borrow().lookup_property(&name).resolved_name.to_string();
+            if resolved_name != name {
+                state.edits.extend(rename_identifier(node, &name, &resolved_name));
             }
-            return Ok(true);
         }
     }
 